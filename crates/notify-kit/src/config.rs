@@ -0,0 +1,185 @@
+//! Building a [`Hub`] from a TOML or JSON config file.
+//!
+//! ```toml
+//! enabled_kinds = ["turn_completed", "approval_requested"]
+//! per_sink_timeout_ms = 3000
+//!
+//! [[sinks]]
+//! type = "slack"
+//! webhook_url = "https://hooks.slack.com/services/T000/B000/XXX"
+//!
+//! [[sinks]]
+//! type = "telegram"
+//! bot_token = "123456:ABC"
+//! chat_id = "-1001234567890"
+//! ```
+//!
+//! Each entry in `sinks` is tagged by `type` and maps to the matching `*Config`; the sink
+//! timeout follows `per_sink_timeout_ms`. The environment variables of
+//! [`crate::build_hub_from_standard_env`] build the same [`SinkFileConfig`]s.
+
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::time::Duration;
+
+pub use crate::sink_spec::SinkFileConfig;
+use crate::{Hub, HubConfig, Severity};
+
+/// The top-level config document.
+#[non_exhaustive]
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HubFileConfig {
+    /// See [`HubConfig::enabled_kinds`]; omitted means all kinds.
+    #[serde(default)]
+    pub enabled_kinds: Option<BTreeSet<String>>,
+    /// See [`HubConfig::min_severity`].
+    #[serde(default)]
+    pub min_severity: Option<Severity>,
+    /// Hub per-sink timeout and each sink's own request timeout; defaults to 5000.
+    #[serde(default)]
+    pub per_sink_timeout_ms: Option<u64>,
+    #[serde(default)]
+    pub sinks: Vec<SinkFileConfig>,
+}
+
+impl HubFileConfig {
+    pub fn from_toml_str(input: &str) -> crate::Result<Self> {
+        toml::from_str(input).map_err(|err| {
+            crate::Error::invalid_config(anyhow::anyhow!("parse notify config (toml): {err}"))
+        })
+    }
+
+    pub fn from_json_str(input: &str) -> crate::Result<Self> {
+        serde_json::from_str(input).map_err(|err| {
+            crate::Error::invalid_config(anyhow::anyhow!("parse notify config (json): {err}"))
+        })
+    }
+
+    /// Builds every sink and the hub; fails on the first sink whose config is rejected.
+    pub fn build(&self) -> crate::Result<Hub> {
+        let timeout = Duration::from_millis(self.per_sink_timeout_ms.unwrap_or(5000).max(1));
+        let mut builder = Hub::builder().config(HubConfig {
+            enabled_kinds: self.enabled_kinds.clone(),
+            min_severity: self.min_severity,
+            per_sink_timeout: timeout,
+            ..HubConfig::default()
+        });
+        for (idx, sink) in self.sinks.iter().enumerate() {
+            let built = sink.build(timeout).map_err(|err| {
+                crate::Error::invalid_config(anyhow::anyhow!(
+                    "sinks[{idx}] ({}): {err}",
+                    sink.type_name()
+                ))
+            })?;
+            builder = builder.add_sink(built);
+        }
+        Ok(builder.build())
+    }
+}
+
+/// Parses a TOML document and builds the hub it describes.
+pub fn hub_from_toml_str(input: &str) -> crate::Result<Hub> {
+    HubFileConfig::from_toml_str(input)?.build()
+}
+
+/// Parses a JSON document and builds the hub it describes.
+pub fn hub_from_json_str(input: &str) -> crate::Result<Hub> {
+    HubFileConfig::from_json_str(input)?.build()
+}
+
+/// Reads `path` and builds the hub it describes; `.json` files are parsed as JSON, anything
+/// else as TOML.
+pub fn load_hub(path: impl AsRef<Path>) -> crate::Result<Hub> {
+    let path = path.as_ref();
+    let input = std::fs::read_to_string(path).map_err(|err| {
+        crate::Error::invalid_config(anyhow::anyhow!(
+            "read notify config {}: {err}",
+            path.display()
+        ))
+    })?;
+    let is_json = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+    if is_json {
+        hub_from_json_str(&input)
+    } else {
+        hub_from_toml_str(&input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"
+enabled_kinds = ["turn_completed"]
+min_severity = "warning"
+per_sink_timeout_ms = 3000
+
+[[sinks]]
+type = "slack"
+webhook_url = "https://hooks.slack.com/services/T000/B000/XXX"
+
+[[sinks]]
+type = "dingtalk"
+webhook_url = "https://oapi.dingtalk.com/robot/send?access_token=x"
+secret = "SECxyz"
+"#;
+
+    #[test]
+    fn builds_hub_from_toml_sample() {
+        let config = HubFileConfig::from_toml_str(SAMPLE).expect("parse sample");
+        assert_eq!(config.sinks.len(), 2);
+        let dbg = format!("{config:?}");
+        assert!(!dbg.contains("SECxyz"), "{dbg}");
+        assert!(!dbg.contains("XXX"), "{dbg}");
+
+        let report = config.build().expect("build hub").diagnostic_report();
+        assert!(report.contains("sinks: 2"), "{report}");
+        assert!(report.contains("[0] slack"), "{report}");
+        assert!(report.contains("[1] dingtalk"), "{report}");
+        assert!(report.contains("enabled_kinds: turn_completed"), "{report}");
+        assert!(report.contains("min_severity: warning"), "{report}");
+        assert!(report.contains("per_sink_timeout: 3s"), "{report}");
+    }
+
+    #[test]
+    fn builds_hub_from_json() {
+        let hub = hub_from_json_str(
+            r#"{"sinks": [
+                {"type": "discord", "webhook_url": "https://discord.com/api/webhooks/1/token"},
+                {"type": "bark", "device_key": "device"}
+            ]}"#,
+        )
+        .expect("build hub");
+        let report = hub.diagnostic_report();
+        assert!(report.contains("[0] discord"), "{report}");
+        assert!(report.contains("[1] bark"), "{report}");
+    }
+
+    #[test]
+    fn unknown_sink_type_is_a_clear_error() {
+        let err = HubFileConfig::from_toml_str("[[sinks]]\ntype = \"pager\"\n")
+            .expect_err("expected unknown type");
+        let msg = err.to_string();
+        assert!(msg.contains("unknown variant `pager`"), "{msg}");
+        assert!(msg.contains("slack"), "{msg}");
+
+        let err = HubFileConfig::from_toml_str(
+            "[[sinks]]\ntype = \"slack\"\nwebhook_url = \"https://hooks.slack.com/x\"\ntoken = \"t\"\n",
+        )
+        .expect_err("expected unknown field");
+        assert!(err.to_string().contains("unknown field `token`"), "{err}");
+    }
+
+    #[test]
+    fn invalid_sink_config_names_the_entry() {
+        let Err(err) = hub_from_toml_str(
+            "[[sinks]]\ntype = \"sound\"\n\n[[sinks]]\ntype = \"slack\"\nwebhook_url = \"http://example.com\"\n",
+        ) else {
+            panic!("expected invalid slack url");
+        };
+        assert!(err.to_string().contains("sinks[1] (slack)"), "{err}");
+    }
+}
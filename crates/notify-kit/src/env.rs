@@ -1,29 +1,20 @@
 use std::collections::BTreeSet;
-use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::Context;
 
-use crate::{
-    FeishuWebhookConfig, FeishuWebhookSink, GenericWebhookConfig, GenericWebhookSink, Hub,
-    HubConfig, Sink, SlackWebhookConfig, SlackWebhookSink, SoundConfig, SoundSink,
-};
+use crate::sink_spec::SinkFileConfig;
+use crate::{Hub, HubConfig, Severity};
 
-#[derive(Debug, Clone, Copy)]
+/// Looks up one environment variable; `std::env::var` outside of tests.
+type EnvVar<'a> = &'a dyn Fn(&str) -> Option<String>;
+
+#[derive(Debug, Clone, Copy, Default)]
 pub struct StandardEnvHubOptions {
     pub default_sound_enabled: bool,
     pub require_sink: bool,
 }
 
-impl Default for StandardEnvHubOptions {
-    fn default() -> Self {
-        Self {
-            default_sound_enabled: false,
-            require_sink: false,
-        }
-    }
-}
-
 fn parse_bool_env_value(raw: &str) -> Option<bool> {
     match raw.trim().to_ascii_lowercase().as_str() {
         "1" | "true" | "yes" | "on" => Some(true),
@@ -32,21 +23,18 @@ fn parse_bool_env_value(raw: &str) -> Option<bool> {
     }
 }
 
-fn env_bool(key: &str) -> Option<bool> {
-    std::env::var(key)
-        .ok()
-        .and_then(|value| parse_bool_env_value(&value))
+fn env_bool(var: EnvVar<'_>, key: &str) -> Option<bool> {
+    var(key).and_then(|value| parse_bool_env_value(&value))
 }
 
-fn env_nonempty(key: &str) -> Option<String> {
-    std::env::var(key)
-        .ok()
+fn env_nonempty(var: EnvVar<'_>, key: &str) -> Option<String> {
+    var(key)
         .map(|value| value.trim().to_string())
         .filter(|value| !value.is_empty())
 }
 
-fn parse_timeout_ms_env(key: &str) -> anyhow::Result<Duration> {
-    let timeout = env_nonempty(key)
+fn parse_timeout_ms_env(var: EnvVar<'_>, key: &str) -> anyhow::Result<Duration> {
+    let timeout = env_nonempty(var, key)
         .map(|value| value.parse::<u64>())
         .transpose()?
         .unwrap_or(5000);
@@ -54,57 +42,110 @@ fn parse_timeout_ms_env(key: &str) -> anyhow::Result<Duration> {
 }
 
 pub fn build_hub_from_standard_env(options: StandardEnvHubOptions) -> anyhow::Result<Option<Hub>> {
+    build_hub_from_env(options, &|key| std::env::var(key).ok())
+}
+
+fn build_hub_from_env(
+    options: StandardEnvHubOptions,
+    var: EnvVar<'_>,
+) -> anyhow::Result<Option<Hub>> {
     const OMNE_NOTIFY_SOUND_ENV: &str = "OMNE_NOTIFY_SOUND";
     const OMNE_NOTIFY_WEBHOOK_URL_ENV: &str = "OMNE_NOTIFY_WEBHOOK_URL";
     const OMNE_NOTIFY_WEBHOOK_FIELD_ENV: &str = "OMNE_NOTIFY_WEBHOOK_FIELD";
     const OMNE_NOTIFY_FEISHU_WEBHOOK_URL_ENV: &str = "OMNE_NOTIFY_FEISHU_WEBHOOK_URL";
     const OMNE_NOTIFY_SLACK_WEBHOOK_URL_ENV: &str = "OMNE_NOTIFY_SLACK_WEBHOOK_URL";
+    const OMNE_NOTIFY_DISCORD_WEBHOOK_URL_ENV: &str = "OMNE_NOTIFY_DISCORD_WEBHOOK_URL";
+    const OMNE_NOTIFY_TELEGRAM_BOT_TOKEN_ENV: &str = "OMNE_NOTIFY_TELEGRAM_BOT_TOKEN";
+    const OMNE_NOTIFY_TELEGRAM_CHAT_ID_ENV: &str = "OMNE_NOTIFY_TELEGRAM_CHAT_ID";
+    const OMNE_NOTIFY_DINGTALK_WEBHOOK_URL_ENV: &str = "OMNE_NOTIFY_DINGTALK_WEBHOOK_URL";
+    const OMNE_NOTIFY_DINGTALK_SECRET_ENV: &str = "OMNE_NOTIFY_DINGTALK_SECRET";
+    const OMNE_NOTIFY_WECOM_WEBHOOK_URL_ENV: &str = "OMNE_NOTIFY_WECOM_WEBHOOK_URL";
+    const OMNE_NOTIFY_BARK_DEVICE_KEY_ENV: &str = "OMNE_NOTIFY_BARK_DEVICE_KEY";
+    const OMNE_NOTIFY_PUSHPLUS_TOKEN_ENV: &str = "OMNE_NOTIFY_PUSHPLUS_TOKEN";
+    const OMNE_NOTIFY_SERVERCHAN_SEND_KEY_ENV: &str = "OMNE_NOTIFY_SERVERCHAN_SEND_KEY";
     const OMNE_NOTIFY_TIMEOUT_MS_ENV: &str = "OMNE_NOTIFY_TIMEOUT_MS";
     const OMNE_NOTIFY_EVENTS_ENV: &str = "OMNE_NOTIFY_EVENTS";
+    const OMNE_NOTIFY_MIN_SEVERITY_ENV: &str = "OMNE_NOTIFY_MIN_SEVERITY";
 
-    let sound_enabled = env_bool(OMNE_NOTIFY_SOUND_ENV).unwrap_or(options.default_sound_enabled);
-    let timeout = parse_timeout_ms_env(OMNE_NOTIFY_TIMEOUT_MS_ENV)
+    let sound_enabled =
+        env_bool(var, OMNE_NOTIFY_SOUND_ENV).unwrap_or(options.default_sound_enabled);
+    let timeout = parse_timeout_ms_env(var, OMNE_NOTIFY_TIMEOUT_MS_ENV)
         .with_context(|| format!("invalid {OMNE_NOTIFY_TIMEOUT_MS_ENV}"))?;
+    let min_severity = env_nonempty(var, OMNE_NOTIFY_MIN_SEVERITY_ENV)
+        .map(|value| value.parse::<Severity>())
+        .transpose()
+        .with_context(|| format!("invalid {OMNE_NOTIFY_MIN_SEVERITY_ENV}"))?;
 
-    let mut sinks: Vec<Arc<dyn Sink>> = Vec::new();
+    let mut specs = Vec::new();
     if sound_enabled {
-        sinks.push(Arc::new(SoundSink::new(SoundConfig { command_argv: None })));
+        specs.push(SinkFileConfig::Sound);
     }
-
-    if let Some(url) = env_nonempty(OMNE_NOTIFY_WEBHOOK_URL_ENV) {
-        let mut cfg = GenericWebhookConfig::new(url).with_timeout(timeout);
-        if let Some(field) = env_nonempty(OMNE_NOTIFY_WEBHOOK_FIELD_ENV) {
-            cfg = cfg.with_payload_field(field);
-        }
-        sinks.push(Arc::new(
-            GenericWebhookSink::new(cfg).context("build generic webhook sink")?,
-        ));
+    if let Some(url) = env_nonempty(var, OMNE_NOTIFY_WEBHOOK_URL_ENV) {
+        specs.push(SinkFileConfig::Webhook {
+            url,
+            payload_field: env_nonempty(var, OMNE_NOTIFY_WEBHOOK_FIELD_ENV),
+        });
     }
-
-    if let Some(url) = env_nonempty(OMNE_NOTIFY_FEISHU_WEBHOOK_URL_ENV) {
-        let cfg = FeishuWebhookConfig::new(url).with_timeout(timeout);
-        sinks.push(Arc::new(
-            FeishuWebhookSink::new(cfg).context("build feishu sink")?,
-        ));
+    if let Some(webhook_url) = env_nonempty(var, OMNE_NOTIFY_FEISHU_WEBHOOK_URL_ENV) {
+        specs.push(SinkFileConfig::Feishu { webhook_url });
     }
-
-    if let Some(url) = env_nonempty(OMNE_NOTIFY_SLACK_WEBHOOK_URL_ENV) {
-        let cfg = SlackWebhookConfig::new(url).with_timeout(timeout);
-        sinks.push(Arc::new(
-            SlackWebhookSink::new(cfg).context("build slack sink")?,
-        ));
+    if let Some(webhook_url) = env_nonempty(var, OMNE_NOTIFY_SLACK_WEBHOOK_URL_ENV) {
+        specs.push(SinkFileConfig::Slack { webhook_url });
+    }
+    if let Some(webhook_url) = env_nonempty(var, OMNE_NOTIFY_DISCORD_WEBHOOK_URL_ENV) {
+        specs.push(SinkFileConfig::Discord { webhook_url });
+    }
+    match (
+        env_nonempty(var, OMNE_NOTIFY_TELEGRAM_BOT_TOKEN_ENV),
+        env_nonempty(var, OMNE_NOTIFY_TELEGRAM_CHAT_ID_ENV),
+    ) {
+        (Some(bot_token), Some(chat_id)) => specs.push(SinkFileConfig::Telegram {
+            bot_token,
+            chat_id,
+            api_base: None,
+        }),
+        (None, None) => {}
+        _ => anyhow::bail!(
+            "{OMNE_NOTIFY_TELEGRAM_BOT_TOKEN_ENV} and {OMNE_NOTIFY_TELEGRAM_CHAT_ID_ENV} must be set together"
+        ),
+    }
+    if let Some(webhook_url) = env_nonempty(var, OMNE_NOTIFY_DINGTALK_WEBHOOK_URL_ENV) {
+        specs.push(SinkFileConfig::DingTalk {
+            webhook_url,
+            secret: env_nonempty(var, OMNE_NOTIFY_DINGTALK_SECRET_ENV),
+        });
+    }
+    if let Some(webhook_url) = env_nonempty(var, OMNE_NOTIFY_WECOM_WEBHOOK_URL_ENV) {
+        specs.push(SinkFileConfig::WeCom { webhook_url });
+    }
+    if let Some(device_key) = env_nonempty(var, OMNE_NOTIFY_BARK_DEVICE_KEY_ENV) {
+        specs.push(SinkFileConfig::Bark { device_key });
+    }
+    if let Some(token) = env_nonempty(var, OMNE_NOTIFY_PUSHPLUS_TOKEN_ENV) {
+        specs.push(SinkFileConfig::PushPlus { token });
+    }
+    if let Some(send_key) = env_nonempty(var, OMNE_NOTIFY_SERVERCHAN_SEND_KEY_ENV) {
+        specs.push(SinkFileConfig::ServerChan { send_key });
     }
 
+    let sinks = specs
+        .iter()
+        .map(|spec| {
+            spec.build(timeout)
+                .with_context(|| format!("build {} sink", spec.type_name()))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
     if sinks.is_empty() {
         if options.require_sink {
             anyhow::bail!(
-                "no notification sinks configured (enable {OMNE_NOTIFY_SOUND_ENV}=1 or provide webhook envs)"
+                "no notification sinks configured (enable {OMNE_NOTIFY_SOUND_ENV}=1 or provide sink envs)"
             );
         }
         return Ok(None);
     }
 
-    let enabled_kinds = std::env::var(OMNE_NOTIFY_EVENTS_ENV).ok().and_then(|raw| {
+    let enabled_kinds = var(OMNE_NOTIFY_EVENTS_ENV).and_then(|raw| {
         let set = raw
             .split(',')
             .map(str::trim)
@@ -117,8 +158,91 @@ pub fn build_hub_from_standard_env(options: StandardEnvHubOptions) -> anyhow::Re
     Ok(Some(Hub::new(
         HubConfig {
             enabled_kinds,
+            min_severity,
             per_sink_timeout: timeout,
+            ..HubConfig::default()
         },
         sinks,
     )))
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    /// Scoped stand-in for the process environment, so tests neither race on nor leak real
+    /// variables.
+    struct TestEnv(HashMap<&'static str, &'static str>);
+
+    impl TestEnv {
+        fn new(vars: &[(&'static str, &'static str)]) -> Self {
+            Self(vars.iter().copied().collect())
+        }
+
+        fn build(&self, options: StandardEnvHubOptions) -> anyhow::Result<Option<Hub>> {
+            build_hub_from_env(options, &|key| self.0.get(key).map(ToString::to_string))
+        }
+    }
+
+    #[test]
+    fn builds_new_sinks_from_env() {
+        let hub = TestEnv::new(&[
+            (
+                "OMNE_NOTIFY_DISCORD_WEBHOOK_URL",
+                "https://discord.com/api/webhooks/1/token",
+            ),
+            ("OMNE_NOTIFY_TELEGRAM_BOT_TOKEN", "123:secret"),
+            ("OMNE_NOTIFY_TELEGRAM_CHAT_ID", " -100200 "),
+            (
+                "OMNE_NOTIFY_DINGTALK_WEBHOOK_URL",
+                "https://oapi.dingtalk.com/robot/send?access_token=x",
+            ),
+            ("OMNE_NOTIFY_DINGTALK_SECRET", "SECxyz"),
+            ("OMNE_NOTIFY_BARK_DEVICE_KEY", "device"),
+            ("OMNE_NOTIFY_PUSHPLUS_TOKEN", ""),
+        ])
+        .build(StandardEnvHubOptions::default())
+        .expect("build hub")
+        .expect("hub with sinks");
+
+        let report = hub.diagnostic_report();
+        assert!(report.contains("sinks: 4"), "{report}");
+        for name in ["discord", "telegram", "dingtalk", "bark"] {
+            assert!(report.contains(&format!("] {name}")), "{name}: {report}");
+        }
+        assert!(!report.contains("pushplus"), "{report}");
+        assert!(!report.contains("secret"), "{report}");
+    }
+
+    #[test]
+    fn telegram_requires_token_and_chat_id() {
+        let Err(err) = TestEnv::new(&[("OMNE_NOTIFY_TELEGRAM_BOT_TOKEN", "123:secret")])
+            .build(StandardEnvHubOptions::default())
+        else {
+            panic!("expected missing chat id");
+        };
+        assert!(
+            err.to_string().contains("OMNE_NOTIFY_TELEGRAM_CHAT_ID"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn no_sinks_is_none_unless_required() {
+        let env = TestEnv::new(&[("OMNE_NOTIFY_SOUND", "0")]);
+        assert!(
+            env.build(StandardEnvHubOptions::default())
+                .expect("no sinks is ok")
+                .is_none()
+        );
+        let Err(err) = env.build(StandardEnvHubOptions {
+            require_sink: true,
+            ..StandardEnvHubOptions::default()
+        }) else {
+            panic!("expected required sink");
+        };
+        assert!(err.to_string().contains("no notification sinks"), "{err}");
+    }
+}
@@ -1,30 +1,105 @@
+/// Broad category of an [`Error`], for callers that react differently to e.g. a timeout, a
+/// rejected request or a misconfigured sink.
+///
+/// Read it with [`Error::kind`]. Errors that don't fit a category (or come from a custom sink
+/// that didn't set one) are [`ErrorKind::Other`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// A request, DNS lookup or per-sink send ran out of time.
+    Timeout,
+    /// The connection to the remote endpoint could not be established.
+    Connect,
+    /// The remote endpoint answered with a non-success HTTP status.
+    HttpStatus(u16),
+    /// A sink or hub option is invalid (bad url, empty token, out-of-range limit, ...).
+    InvalidConfig,
+    /// A url or resolved address was rejected by the SSRF protections.
+    Ssrf,
+    /// A payload could not be encoded or a response could not be decoded.
+    Serialization,
+    /// Anything else.
+    Other,
+}
+
 #[derive(Debug)]
-pub struct Error(anyhow::Error);
+pub struct Error {
+    inner: anyhow::Error,
+    kind: ErrorKind,
+}
 
 impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if f.alternate() {
-            write!(f, "{:#}", self.0)
+            write!(f, "{:#}", self.inner)
         } else {
-            write!(f, "{}", self.0)
+            write!(f, "{}", self.inner)
         }
     }
 }
 
+impl Error {
+    pub(crate) fn with_kind(kind: ErrorKind, err: impl Into<anyhow::Error>) -> Self {
+        Self {
+            inner: err.into(),
+            kind,
+        }
+    }
+
+    pub(crate) fn timeout(err: impl Into<anyhow::Error>) -> Self {
+        Self::with_kind(ErrorKind::Timeout, err)
+    }
+
+    pub(crate) fn invalid_config(err: impl Into<anyhow::Error>) -> Self {
+        Self::with_kind(ErrorKind::InvalidConfig, err)
+    }
+
+    pub(crate) fn ssrf(err: impl Into<anyhow::Error>) -> Self {
+        Self::with_kind(ErrorKind::Ssrf, err)
+    }
+
+    pub(crate) fn serialization(err: impl Into<anyhow::Error>) -> Self {
+        Self::with_kind(ErrorKind::Serialization, err)
+    }
+
+    pub(crate) fn http_status(status: reqwest::StatusCode, err: impl Into<anyhow::Error>) -> Self {
+        Self::with_kind(ErrorKind::HttpStatus(status.as_u16()), err)
+    }
+
+    /// The category of this error.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Returns the underlying error if it is of type `E` (for example
+    /// [`UnknownSinkError`](crate::UnknownSinkError) or [`TryNotifyError`](crate::TryNotifyError)).
+    pub fn downcast_ref<E>(&self) -> Option<&E>
+    where
+        E: std::fmt::Display + std::fmt::Debug + Send + Sync + 'static,
+    {
+        self.inner.downcast_ref::<E>()
+    }
+}
+
 impl std::error::Error for Error {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-        self.0.source()
+        self.inner.source()
     }
 }
 
 impl From<anyhow::Error> for Error {
     fn from(err: anyhow::Error) -> Self {
-        Self(err)
+        Self::with_kind(ErrorKind::Other, err)
     }
 }
 
 impl From<std::io::Error> for Error {
     fn from(err: std::io::Error) -> Self {
-        Self(anyhow::Error::from(err))
+        let kind = if err.kind() == std::io::ErrorKind::TimedOut {
+            ErrorKind::Timeout
+        } else {
+            ErrorKind::Other
+        };
+        Self::with_kind(kind, err)
     }
 }
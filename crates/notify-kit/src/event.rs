@@ -1,6 +1,6 @@
 use std::collections::BTreeMap;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Severity {
     Info,
     Success,
@@ -8,13 +8,317 @@ pub enum Severity {
     Error,
 }
 
+impl Severity {
+    /// Lowercase name: `"info"`, `"success"`, `"warning"` or `"error"`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Info => "info",
+            Self::Success => "success",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        }
+    }
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Returned by `Severity::from_str` for anything other than the four names (case-insensitive).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseSeverityError {
+    pub input: String,
+}
+
+impl std::fmt::Display for ParseSeverityError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid severity `{}`: expected one of info, success, warning, error",
+            self.input
+        )
+    }
+}
+
+impl std::error::Error for ParseSeverityError {}
+
+impl std::str::FromStr for Severity {
+    type Err = ParseSeverityError;
+
+    /// Parses the lowercase names (see [`Severity::as_str`]) case-insensitively, ignoring
+    /// surrounding whitespace.
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        [Self::Info, Self::Success, Self::Warning, Self::Error]
+            .into_iter()
+            .find(|severity| severity.as_str().eq_ignore_ascii_case(input.trim()))
+            .ok_or_else(|| ParseSeverityError {
+                input: input.to_string(),
+            })
+    }
+}
+
+/// Serializes as the lowercase name ([`Severity::as_str`]); parsing is case-insensitive.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Severity {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Severity {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        name.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Reserved tag for a per-event push priority (`low`, `normal`, `high` or `critical`).
+///
+/// Push sinks with a priority/level parameter (currently [`crate::BarkSink`]) use it instead of the
+/// severity-derived one and strip it from the rendered tags; other sinks show it as a plain tag.
+pub const PRIORITY_TAG: &str = "notify.priority";
+
+/// At most this many [`Event::attachments`] per event (see [`Event::validate_attachments`]).
+pub const MAX_ATTACHMENTS: usize = 10;
+
+/// Total size cap for one event's [`Event::attachments`], in bytes (20 MiB).
+pub const MAX_ATTACHMENT_TOTAL_BYTES: usize = 20 * 1024 * 1024;
+
+/// Where an [`Attachment`]'s content comes from.
+#[derive(Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AttachmentSource {
+    /// Bytes carried by the event itself.
+    Inline(Vec<u8>),
+    /// An `https` URL, downloaded at send time.
+    Url(String),
+    /// A local file path, read at send time.
+    Path(String),
+}
+
+impl std::fmt::Debug for AttachmentSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Inline(bytes) => f.debug_struct("Inline").field("len", &bytes.len()).finish(),
+            Self::Url(url) => f.debug_tuple("Url").field(url).finish(),
+            Self::Path(path) => f.debug_tuple("Path").field(path).finish(),
+        }
+    }
+}
+
+/// A file carried by an [`Event`], e.g. a log snippet or a screenshot.
+///
+/// Inline attachments hold their bytes; [`Attachment::from_url`] and [`Attachment::from_path`]
+/// reference a file that is only loaded at send time, by the sinks that can (multipart
+/// [`crate::GenericWebhookSink`]). For those, an empty `filename` / `content_type` is derived
+/// from the URL or path when loading.
+///
+/// With the `serde` feature it serializes as `{"filename", "content_type"}` plus exactly one of
+/// `data` (inline bytes, base64 with the standard alphabet, padded), `url` or `path`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "AttachmentRepr", try_from = "AttachmentRepr")
+)]
+#[non_exhaustive]
+pub struct Attachment {
+    pub filename: String,
+    /// MIME type, e.g. `text/plain` or `image/png`.
+    pub content_type: String,
+    pub source: AttachmentSource,
+}
+
+impl Attachment {
+    /// An inline attachment.
+    pub fn new(
+        filename: impl Into<String>,
+        content_type: impl Into<String>,
+        bytes: impl Into<Vec<u8>>,
+    ) -> Self {
+        Self {
+            filename: filename.into(),
+            content_type: content_type.into(),
+            source: AttachmentSource::Inline(bytes.into()),
+        }
+    }
+
+    /// A file downloaded from an `https` URL at send time.
+    pub fn from_url(url: impl Into<String>) -> Self {
+        Self::from_source(AttachmentSource::Url(url.into()))
+    }
+
+    /// A local file read at send time.
+    pub fn from_path(path: impl Into<String>) -> Self {
+        Self::from_source(AttachmentSource::Path(path.into()))
+    }
+
+    fn from_source(source: AttachmentSource) -> Self {
+        Self {
+            filename: String::new(),
+            content_type: String::new(),
+            source,
+        }
+    }
+
+    #[must_use]
+    pub fn with_filename(mut self, filename: impl Into<String>) -> Self {
+        self.filename = filename.into();
+        self
+    }
+
+    #[must_use]
+    pub fn with_content_type(mut self, content_type: impl Into<String>) -> Self {
+        self.content_type = content_type.into();
+        self
+    }
+
+    /// The bytes of an inline attachment; `None` for URL and path sources.
+    pub fn bytes(&self) -> Option<&[u8]> {
+        match &self.source {
+            AttachmentSource::Inline(bytes) => Some(bytes),
+            AttachmentSource::Url(_) | AttachmentSource::Path(_) => None,
+        }
+    }
+
+    /// `filename`, or the last segment of the URL / path when it is blank.
+    pub(crate) fn display_name(&self) -> &str {
+        let filename = self.filename.trim();
+        if !filename.is_empty() {
+            return filename;
+        }
+        match &self.source {
+            AttachmentSource::Inline(_) => filename,
+            AttachmentSource::Url(src) | AttachmentSource::Path(src) => {
+                let src = src.trim();
+                let src = src.split(['?', '#']).next().unwrap_or(src);
+                src.rsplit(['/', '\\']).next().unwrap_or(src)
+            }
+        }
+    }
+
+    pub(crate) fn is_image(&self) -> bool {
+        self.content_type
+            .trim()
+            .to_ascii_lowercase()
+            .starts_with("image/")
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct AttachmentRepr {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    filename: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    content_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    data: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    path: Option<String>,
+}
+
+#[cfg(feature = "serde")]
+impl From<Attachment> for AttachmentRepr {
+    fn from(attachment: Attachment) -> Self {
+        use base64::Engine as _;
+
+        let mut repr = Self {
+            filename: attachment.filename,
+            content_type: attachment.content_type,
+            data: None,
+            url: None,
+            path: None,
+        };
+        match attachment.source {
+            AttachmentSource::Inline(bytes) => {
+                repr.data = Some(base64::engine::general_purpose::STANDARD.encode(bytes));
+            }
+            AttachmentSource::Url(url) => repr.url = Some(url),
+            AttachmentSource::Path(path) => repr.path = Some(path),
+        }
+        repr
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<AttachmentRepr> for Attachment {
+    type Error = String;
+
+    fn try_from(repr: AttachmentRepr) -> Result<Self, Self::Error> {
+        use base64::Engine as _;
+
+        let source = match (repr.data, repr.url, repr.path) {
+            (Some(data), None, None) => AttachmentSource::Inline(
+                base64::engine::general_purpose::STANDARD
+                    .decode(data.as_bytes())
+                    .map_err(|err| format!("invalid attachment data: {err}"))?,
+            ),
+            (None, Some(url), None) => AttachmentSource::Url(url),
+            (None, None, Some(path)) => AttachmentSource::Path(path),
+            _ => return Err("attachment needs exactly one of data, url or path".to_string()),
+        };
+        Ok(Self {
+            filename: repr.filename,
+            content_type: repr.content_type,
+            source,
+        })
+    }
+}
+
+/// With the `serde` feature, `body` and `url` are omitted when `None` (`tag_order` and
+/// `attachments` when empty), and everything but `kind`, `severity` and `title` may be missing
+/// when deserializing.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Event {
     pub kind: String,
     pub severity: Severity,
     pub title: String,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
     pub body: Option<String>,
+    /// Primary link (e.g. "view details"); sinks with native link support surface it as a link or
+    /// button, text sinks print it on its own line after the body.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub url: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
     pub tags: BTreeMap<String, String>,
+    /// Render order for `tags` (see [`Event::with_ordered_tags`]): listed keys come first, in
+    /// this order, followed by the remaining tags sorted by key. Empty renders all tags sorted.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub tag_order: Vec<String>,
+    /// Files to send along (see [`Event::validate_attachments`] for the limits). Sinks that can
+    /// upload files send them (Telegram documents, Feishu images, multipart generic webhooks);
+    /// text sinks list their filenames instead. Only multipart generic webhooks load URL and
+    /// path sources; other uploading sinks list those by name.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub attachments: Vec<Attachment>,
+    /// `false` for [`Event::without_severity`] until [`Event::with_severity`]; deserialized events
+    /// always count as explicit.
+    #[cfg_attr(feature = "serde", serde(skip, default = "explicit_severity"))]
+    severity_explicit: bool,
+}
+
+#[cfg(feature = "serde")]
+fn explicit_severity() -> bool {
+    true
 }
 
 impl Event {
@@ -24,19 +328,359 @@ impl Event {
             severity,
             title: title.into(),
             body: None,
+            url: None,
             tags: BTreeMap::new(),
+            tag_order: Vec::new(),
+            attachments: Vec::new(),
+            severity_explicit: true,
+        }
+    }
+
+    /// An event whose producer leaves the severity open: `Info` unless
+    /// [`crate::HubConfig::kind_severity_defaults`] maps its kind.
+    pub fn without_severity(kind: impl Into<String>, title: impl Into<String>) -> Self {
+        Self {
+            severity_explicit: false,
+            ..Self::new(kind, Severity::Info, title)
         }
     }
 
+    #[must_use]
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self.severity_explicit = true;
+        self
+    }
+
+    /// Whether the producer chose `severity` (see [`Event::without_severity`]).
+    pub fn has_explicit_severity(&self) -> bool {
+        self.severity_explicit
+    }
+
     #[must_use]
     pub fn with_body(mut self, body: impl Into<String>) -> Self {
         self.body = Some(body.into());
         self
     }
 
+    #[must_use]
+    pub fn with_url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
     #[must_use]
     pub fn with_tag(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
         self.tags.insert(key.into(), value.into());
         self
     }
+
+    /// Adds tags that render in the given order, ahead of tags added with [`Event::with_tag`]
+    /// (which keep sorting by key). Re-adding a key updates its value but keeps its position.
+    #[must_use]
+    pub fn with_ordered_tags<K, V>(mut self, tags: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        for (key, value) in tags {
+            let key = key.into();
+            if !self.tag_order.contains(&key) {
+                self.tag_order.push(key.clone());
+            }
+            self.tags.insert(key, value.into());
+        }
+        self
+    }
+
+    /// `tags` in render order: keys from `tag_order` that are present (each once), then the
+    /// rest sorted by key.
+    pub fn tags_in_order(&self) -> impl Iterator<Item = (&String, &String)> + '_ {
+        let ordered = self
+            .tag_order
+            .iter()
+            .enumerate()
+            .filter(|(idx, key)| !self.tag_order[..*idx].contains(key))
+            .filter_map(|(_, key)| self.tags.get_key_value(key));
+        let rest = self
+            .tags
+            .iter()
+            .filter(|(key, _)| !self.tag_order.contains(key));
+        ordered.chain(rest)
+    }
+
+    #[must_use]
+    pub fn with_attachment(mut self, attachment: Attachment) -> Self {
+        self.attachments.push(attachment);
+        self
+    }
+
+    /// Checks [`Event::attachments`] against [`MAX_ATTACHMENTS`] and
+    /// [`MAX_ATTACHMENT_TOTAL_BYTES`] (inline bytes only) and rejects inline attachments without
+    /// a filename; uploading sinks call this before sending anything.
+    pub fn validate_attachments(&self) -> crate::Result<()> {
+        if self.attachments.len() > MAX_ATTACHMENTS {
+            return Err(anyhow::anyhow!(
+                "too many attachments: {} (max {MAX_ATTACHMENTS})",
+                self.attachments.len()
+            )
+            .into());
+        }
+        let mut total = 0usize;
+        for (idx, attachment) in self.attachments.iter().enumerate() {
+            let Some(bytes) = attachment.bytes() else {
+                continue;
+            };
+            if attachment.filename.trim().is_empty() {
+                return Err(anyhow::anyhow!("attachment {idx}: filename must not be empty").into());
+            }
+            total = total.saturating_add(bytes.len());
+        }
+        if total > MAX_ATTACHMENT_TOTAL_BYTES {
+            return Err(anyhow::anyhow!(
+                "attachments too large: {total} bytes (max {MAX_ATTACHMENT_TOTAL_BYTES})"
+            )
+            .into());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [Severity; 4] = [
+        Severity::Info,
+        Severity::Success,
+        Severity::Warning,
+        Severity::Error,
+    ];
+
+    #[test]
+    fn severity_display_round_trips_through_from_str() {
+        for severity in ALL {
+            let name = severity.to_string();
+            assert_eq!(name, severity.as_str());
+            assert_eq!(name.parse::<Severity>(), Ok(severity));
+            assert_eq!(name.to_ascii_uppercase().parse::<Severity>(), Ok(severity));
+        }
+        assert_eq!(" Warning\n".parse::<Severity>(), Ok(Severity::Warning));
+    }
+
+    #[test]
+    fn invalid_severity_lists_valid_values() {
+        for input in ["", "warn", "critical", "info!"] {
+            let err = input.parse::<Severity>().expect_err("invalid severity");
+            assert_eq!(err.input, input);
+            assert_eq!(
+                err.to_string(),
+                format!(
+                    "invalid severity `{input}`: expected one of info, success, warning, error"
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn ordered_tags_iterate_in_insertion_order() {
+        let event = Event::new("k", Severity::Info, "t")
+            .with_tag("zone", "eu")
+            .with_ordered_tags([("status", "failed"), ("duration", "12s")])
+            .with_tag("branch", "main")
+            .with_ordered_tags([("attempt", "3"), ("status", "ok")]);
+
+        let keys: Vec<&str> = event.tags_in_order().map(|(k, _)| k.as_str()).collect();
+        assert_eq!(keys, ["status", "duration", "attempt", "branch", "zone"]);
+        assert_eq!(
+            event.tags["status"], "ok",
+            "re-adding updates the value in place"
+        );
+
+        // Keys removed from the map are skipped; plain events stay sorted.
+        let mut event = event;
+        event.tags.remove("duration");
+        let keys: Vec<&str> = event.tags_in_order().map(|(k, _)| k.as_str()).collect();
+        assert_eq!(keys, ["status", "attempt", "branch", "zone"]);
+        let sorted = Event::new("k", Severity::Info, "t")
+            .with_tag("status", "ok")
+            .with_tag("duration", "1s");
+        let keys: Vec<&str> = sorted.tags_in_order().map(|(k, _)| k.as_str()).collect();
+        assert_eq!(keys, ["duration", "status"]);
+    }
+
+    #[test]
+    fn severity_is_explicit_unless_left_open() {
+        assert!(Event::new("k", Severity::Info, "t").has_explicit_severity());
+
+        let open = Event::without_severity("k", "t");
+        assert!(!open.has_explicit_severity());
+        assert_eq!(open.severity, Severity::Info);
+
+        let chosen = open.with_severity(Severity::Info);
+        assert!(chosen.has_explicit_severity());
+        assert_eq!(chosen, Event::new("k", Severity::Info, "t"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn event_round_trips_through_json() {
+        let event = Event::new("build", Severity::Warning, "flaky test")
+            .with_body("retried 3 times")
+            .with_url("https://ci.example.com/runs/42")
+            .with_tag("branch", "main")
+            .with_tag("attempt", "3");
+
+        let json = serde_json::to_value(&event).expect("serialize");
+        assert_eq!(json["severity"], "warning");
+        assert_eq!(json["tags"]["branch"], "main");
+        let back: Event = serde_json::from_value(json).expect("deserialize");
+        assert_eq!(back, event);
+
+        let ordered = event.clone().with_ordered_tags([("status", "ok")]);
+        let json = serde_json::to_value(&ordered).expect("serialize");
+        assert_eq!(json["tag_order"], serde_json::json!(["status"]));
+        let back: Event = serde_json::from_value(json).expect("deserialize");
+        assert_eq!(back, ordered);
+
+        let minimal = Event::new("k", Severity::Info, "t");
+        let json = serde_json::to_string(&minimal).expect("serialize");
+        assert_eq!(
+            json,
+            r#"{"kind":"k","severity":"info","title":"t","tags":{}}"#
+        );
+        let back: Event =
+            serde_json::from_str(r#"{"kind":"k","severity":"info","title":"t"}"#).expect("parse");
+        assert_eq!(back, minimal);
+    }
+
+    #[test]
+    fn validates_attachment_count_total_size_and_filenames() {
+        let event = Event::new("k", Severity::Info, "t");
+        assert!(event.validate_attachments().is_ok());
+
+        let mut event = event;
+        for idx in 0..MAX_ATTACHMENTS {
+            event = event.with_attachment(Attachment::new(
+                format!("{idx}.log"),
+                "text/plain",
+                b"x".to_vec(),
+            ));
+        }
+        assert!(event.validate_attachments().is_ok());
+        let err = event
+            .clone()
+            .with_attachment(Attachment::new("extra.log", "text/plain", b"x".to_vec()))
+            .validate_attachments()
+            .expect_err("too many");
+        assert!(err.to_string().contains("too many attachments"), "{err}");
+
+        let half = MAX_ATTACHMENT_TOTAL_BYTES / 2;
+        let event = Event::new("k", Severity::Info, "t")
+            .with_attachment(Attachment::new(
+                "a.bin",
+                "application/octet-stream",
+                vec![0; half],
+            ))
+            .with_attachment(Attachment::new(
+                "b.bin",
+                "application/octet-stream",
+                vec![0; half],
+            ));
+        assert!(event.validate_attachments().is_ok());
+        let err = event
+            .with_attachment(Attachment::new(
+                "c.bin",
+                "application/octet-stream",
+                vec![0; 1],
+            ))
+            .validate_attachments()
+            .expect_err("too large");
+        assert!(err.to_string().contains("attachments too large"), "{err}");
+
+        let err = Event::new("k", Severity::Info, "t")
+            .with_attachment(Attachment::new(" ", "text/plain", b"x".to_vec()))
+            .validate_attachments()
+            .expect_err("empty filename");
+        assert!(err.to_string().contains("attachment 0"), "{err}");
+
+        // URL and path sources are sized at load time and may leave the filename to be derived.
+        let event = Event::new("k", Severity::Info, "t")
+            .with_attachment(Attachment::from_url(
+                "https://files.example.com/r/nightly.pdf?sig=abc",
+            ))
+            .with_attachment(Attachment::from_path("/var/log/app.log"));
+        assert!(event.validate_attachments().is_ok());
+        assert_eq!(event.attachments[0].display_name(), "nightly.pdf");
+        assert_eq!(event.attachments[1].display_name(), "app.log");
+        assert_eq!(event.attachments[1].bytes(), None);
+    }
+
+    #[test]
+    fn attachment_debug_omits_bytes() {
+        let attachment = Attachment::new("secret.log", "text/plain", b"password=hunter2".to_vec());
+        let dbg = format!("{attachment:?}");
+        assert!(dbg.contains("secret.log"), "{dbg}");
+        assert!(dbg.contains("len: 16"), "{dbg}");
+        assert!(!dbg.contains("hunter2"), "{dbg}");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn attachments_round_trip_as_base64() {
+        let event = Event::new("k", Severity::Info, "t")
+            .with_attachment(Attachment::new(
+                "hello.txt",
+                "text/plain",
+                b"hello".to_vec(),
+            ))
+            .with_attachment(Attachment::from_url("https://files.example.com/a.pdf"))
+            .with_attachment(Attachment::from_path("/tmp/report.txt").with_filename("r.txt"));
+        let json = serde_json::to_value(&event).expect("serialize");
+        assert_eq!(json["attachments"][0]["filename"], "hello.txt");
+        assert_eq!(json["attachments"][0]["content_type"], "text/plain");
+        assert_eq!(json["attachments"][0]["data"], "aGVsbG8=");
+        assert_eq!(
+            json["attachments"][1],
+            serde_json::json!({"url": "https://files.example.com/a.pdf"})
+        );
+        assert_eq!(
+            json["attachments"][2],
+            serde_json::json!({"filename": "r.txt", "path": "/tmp/report.txt"})
+        );
+        let back: Event = serde_json::from_value(json).expect("deserialize");
+        assert_eq!(back, event);
+
+        let err = serde_json::from_str::<Attachment>(r#"{"url":"https://a/b","path":"/c"}"#)
+            .expect_err("two sources");
+        assert!(err.to_string().contains("exactly one"), "{err}");
+
+        let err = serde_json::from_str::<Attachment>(
+            r#"{"filename":"a","content_type":"text/plain","data":"!!"}"#,
+        )
+        .expect_err("bad base64");
+        assert!(err.to_string().contains("invalid attachment data"), "{err}");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn severity_serializes_lowercase_and_parses_case_insensitively() {
+        for severity in ALL {
+            let json = serde_json::to_string(&severity).expect("serialize");
+            assert_eq!(json, format!("\"{}\"", severity.as_str()));
+            let upper = json.to_ascii_uppercase();
+            let parsed: Severity = serde_json::from_str(&upper).expect("parse");
+            assert_eq!(parsed, severity);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn unknown_severity_is_a_clear_error() {
+        let err = serde_json::from_str::<Severity>(r#""critical""#).expect_err("unknown severity");
+        let msg = err.to_string();
+        assert!(msg.contains("critical"), "{msg}");
+        assert!(msg.contains("info"), "{msg}");
+        assert!(msg.contains("error"), "{msg}");
+    }
 }
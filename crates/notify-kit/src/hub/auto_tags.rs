@@ -0,0 +1,98 @@
+use std::sync::OnceLock;
+
+use crate::event::Event;
+
+/// Tags the hub adds to every event before it reaches the sinks.
+///
+/// Useful in multi-host deployments to tell which machine/process emitted a notification:
+/// - `hostname` adds `host=<hostname>` (resolved once per process);
+/// - `pid` adds `pid=<process id>`;
+/// - `app_name` adds `app=<name>`.
+///
+/// Tags already set on the event win; auto tags never override them. All disabled by default.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AutoTags {
+    pub hostname: bool,
+    pub pid: bool,
+    pub app_name: Option<String>,
+}
+
+impl AutoTags {
+    pub(super) fn resolve(&self) -> Vec<(&'static str, String)> {
+        let mut tags = Vec::new();
+        if self.hostname {
+            if let Some(hostname) = cached_hostname() {
+                tags.push(("host", hostname.to_string()));
+            }
+        }
+        if self.pid {
+            tags.push(("pid", std::process::id().to_string()));
+        }
+        if let Some(app_name) = self.app_name.as_deref() {
+            tags.push(("app", app_name.to_string()));
+        }
+        tags
+    }
+}
+
+fn cached_hostname() -> Option<&'static str> {
+    static HOSTNAME: OnceLock<Option<String>> = OnceLock::new();
+    HOSTNAME
+        .get_or_init(|| {
+            gethostname::gethostname()
+                .into_string()
+                .ok()
+                .filter(|hostname| !hostname.trim().is_empty())
+        })
+        .as_deref()
+}
+
+pub(super) fn apply(tags: &[(&'static str, String)], event: &mut Event) {
+    for (key, value) in tags {
+        if !event.tags.contains_key(*key) {
+            event.tags.insert((*key).to_string(), value.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Severity;
+
+    #[test]
+    fn resolves_enabled_tags_only() {
+        assert!(AutoTags::default().resolve().is_empty());
+
+        let tags = AutoTags {
+            hostname: false,
+            pid: true,
+            app_name: Some("svc".to_string()),
+        }
+        .resolve();
+        assert_eq!(
+            tags,
+            vec![
+                ("pid", std::process::id().to_string()),
+                ("app", "svc".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn apply_does_not_override_user_tags() {
+        let tags = vec![
+            ("host", "auto-host".to_string()),
+            ("app", "auto-app".to_string()),
+        ];
+        let mut event = Event::new("k", Severity::Info, "t").with_tag("app", "mine");
+
+        apply(&tags, &mut event);
+
+        assert_eq!(
+            event.tags.get("host").map(String::as_str),
+            Some("auto-host")
+        );
+        assert_eq!(event.tags.get("app").map(String::as_str), Some("mine"));
+    }
+}
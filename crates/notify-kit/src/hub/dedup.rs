@@ -0,0 +1,173 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::event::Event;
+use crate::lru::BoundedLru;
+
+/// Computes the dedup key of an event (see [`DedupConfig::with_key`]).
+pub type DedupKeyFn = Arc<dyn Fn(&Event) -> String + Send + Sync>;
+
+/// Suppresses repeated events, e.g. from a flaky job that retries and re-emits the same failure.
+///
+/// An event is dropped before reaching any sink when an event with the same key was dispatched
+/// less than `window` ago. The window starts at the first dispatch and is not extended by
+/// suppressed duplicates, so a condition that keeps firing is re-notified once per `window`.
+/// A dispatch that no sink delivers does not count, so retrying a failed send is not
+/// suppressed. The default key is `kind` + `title`.
+///
+/// At most `max_keys` keys are remembered (least recently used are forgotten first), which caps
+/// memory for high-cardinality keys.
+#[non_exhaustive]
+#[derive(Clone)]
+pub struct DedupConfig {
+    pub window: Duration,
+    pub key: DedupKeyFn,
+    pub max_keys: usize,
+}
+
+impl std::fmt::Debug for DedupConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DedupConfig")
+            .field("window", &self.window)
+            .field("max_keys", &self.max_keys)
+            .finish_non_exhaustive()
+    }
+}
+
+impl DedupConfig {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            key: Arc::new(default_key),
+            max_keys: 1024,
+        }
+    }
+
+    #[must_use]
+    pub fn with_key<F>(mut self, key: F) -> Self
+    where
+        F: Fn(&Event) -> String + Send + Sync + 'static,
+    {
+        self.key = Arc::new(key);
+        self
+    }
+
+    #[must_use]
+    pub fn with_max_keys(mut self, max_keys: usize) -> Self {
+        self.max_keys = max_keys.max(1);
+        self
+    }
+}
+
+fn default_key(event: &Event) -> String {
+    format!("{}\n{}", event.kind, event.title)
+}
+
+pub(super) struct Deduper {
+    key: DedupKeyFn,
+    seen: Mutex<BoundedLru<String, ()>>,
+}
+
+impl Deduper {
+    pub(super) fn new(config: DedupConfig) -> Self {
+        Self {
+            seen: Mutex::new(BoundedLru::new(config.max_keys, Some(config.window))),
+            key: config.key,
+        }
+    }
+
+    /// Returns `None` if `event` should be suppressed; otherwise records it as dispatched and
+    /// returns its key, to pass to [`Deduper::release`] if no sink delivers it.
+    pub(super) fn claim(&self, event: &Event) -> Option<String> {
+        self.claim_at(event, Instant::now())
+    }
+
+    fn claim_at(&self, event: &Event, now: Instant) -> Option<String> {
+        let key = (self.key)(event);
+        let mut seen = self.lock();
+        if seen.get(&key, now).is_some() {
+            return None;
+        }
+        seen.insert(key.clone(), (), now);
+        Some(key)
+    }
+
+    /// Forgets a claimed key so the next event with it is dispatched again.
+    pub(super) fn release(&self, key: String) {
+        self.lock().remove(&key);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BoundedLru<String, ()>> {
+        self.seen
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Severity;
+
+    impl Deduper {
+        fn is_new_at(&self, event: &Event, now: Instant) -> bool {
+            self.claim_at(event, now).is_some()
+        }
+    }
+
+    #[test]
+    fn suppresses_within_window_only() {
+        let deduper = Deduper::new(DedupConfig::new(Duration::from_secs(60)));
+        let event = Event::new("k", Severity::Error, "job failed");
+        let start = Instant::now();
+
+        assert!(deduper.is_new_at(&event, start));
+        assert!(!deduper.is_new_at(&event, start + Duration::from_secs(30)));
+        // Suppressed duplicates do not extend the window.
+        assert!(deduper.is_new_at(&event, start + Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn default_key_is_kind_and_title() {
+        let deduper = Deduper::new(DedupConfig::new(Duration::from_secs(60)));
+        let now = Instant::now();
+
+        assert!(deduper.is_new_at(&Event::new("a", Severity::Info, "t"), now));
+        assert!(deduper.is_new_at(&Event::new("b", Severity::Info, "t"), now));
+        assert!(deduper.is_new_at(&Event::new("a", Severity::Info, "u"), now));
+        assert!(!deduper.is_new_at(
+            &Event::new("a", Severity::Error, "t").with_body("different body"),
+            now
+        ));
+    }
+
+    #[test]
+    fn custom_key() {
+        let deduper = Deduper::new(
+            DedupConfig::new(Duration::from_secs(60))
+                .with_key(|event: &Event| event.tags.get("job").cloned().unwrap_or_default()),
+        );
+        let now = Instant::now();
+
+        assert!(deduper.is_new_at(
+            &Event::new("a", Severity::Info, "x").with_tag("job", "1"),
+            now
+        ));
+        assert!(!deduper.is_new_at(
+            &Event::new("b", Severity::Info, "y").with_tag("job", "1"),
+            now
+        ));
+    }
+
+    #[test]
+    fn released_key_is_dispatched_again() {
+        let deduper = Deduper::new(DedupConfig::new(Duration::from_secs(60)));
+        let event = Event::new("k", Severity::Error, "job failed");
+        let now = Instant::now();
+
+        let key = deduper.claim_at(&event, now).expect("first dispatch");
+        assert!(!deduper.is_new_at(&event, now));
+        deduper.release(key);
+        assert!(deduper.is_new_at(&event, now));
+    }
+}
@@ -0,0 +1,193 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Sampling for the warnings `Hub::notify` logs when a sink fails.
+///
+/// Per sink, the first failure is always logged; after that only every `every_nth` failure is,
+/// plus one whenever `summary_interval` has passed since the last logged failure. Each logged
+/// line carries the number of failures suppressed since the previous one, and a sink that
+/// recovers logs how many failures it had. This keeps logs readable during an outage where a sink
+/// fails for every event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FailureLogSampling {
+    pub every_nth: usize,
+    pub summary_interval: Duration,
+}
+
+impl Default for FailureLogSampling {
+    fn default() -> Self {
+        Self {
+            every_nth: 100,
+            summary_interval: Duration::from_secs(60),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum FailureLogDecision {
+    Log { suppressed: u64 },
+    Suppress,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct SinkFailureLogState {
+    consecutive_failures: u64,
+    suppressed: u64,
+    last_logged: Option<Instant>,
+}
+
+pub(super) struct FailureLogSampler {
+    sampling: Option<FailureLogSampling>,
+    sinks: Mutex<Vec<SinkFailureLogState>>,
+}
+
+impl FailureLogSampler {
+    pub(super) fn new(sampling: Option<FailureLogSampling>, sink_count: usize) -> Self {
+        Self {
+            sampling,
+            sinks: Mutex::new(vec![SinkFailureLogState::default(); sink_count]),
+        }
+    }
+
+    fn lock_sinks(&self) -> std::sync::MutexGuard<'_, Vec<SinkFailureLogState>> {
+        self.sinks
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    pub(super) fn record_failure(&self, sink_idx: usize) -> FailureLogDecision {
+        self.record_failure_at(sink_idx, Instant::now())
+    }
+
+    fn record_failure_at(&self, sink_idx: usize, now: Instant) -> FailureLogDecision {
+        let Some(sampling) = self.sampling else {
+            return FailureLogDecision::Log { suppressed: 0 };
+        };
+        let mut sinks = self.lock_sinks();
+        let Some(state) = sinks.get_mut(sink_idx) else {
+            return FailureLogDecision::Log { suppressed: 0 };
+        };
+
+        state.consecutive_failures = state.consecutive_failures.saturating_add(1);
+        let every_nth = u64::try_from(sampling.every_nth.max(1)).unwrap_or(u64::MAX);
+        let nth_hit = (state.consecutive_failures - 1) % every_nth == 0;
+        let summary_due = state
+            .last_logged
+            .is_some_and(|last| now.duration_since(last) >= sampling.summary_interval);
+        if !nth_hit && !summary_due {
+            state.suppressed = state.suppressed.saturating_add(1);
+            return FailureLogDecision::Suppress;
+        }
+
+        state.last_logged = Some(now);
+        FailureLogDecision::Log {
+            suppressed: std::mem::take(&mut state.suppressed),
+        }
+    }
+
+    /// Resets the sink's counters; returns how many consecutive failures it had, if any.
+    pub(super) fn record_success(&self, sink_idx: usize) -> Option<u64> {
+        self.sampling?;
+        let mut sinks = self.lock_sinks();
+        let state = sinks.get_mut(sink_idx)?;
+        if state.consecutive_failures == 0 {
+            return None;
+        }
+        let failures = state.consecutive_failures;
+        *state = SinkFailureLogState::default();
+        Some(failures)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sampler(every_nth: usize, summary_interval: Duration) -> FailureLogSampler {
+        FailureLogSampler::new(
+            Some(FailureLogSampling {
+                every_nth,
+                summary_interval,
+            }),
+            2,
+        )
+    }
+
+    #[test]
+    fn logs_first_failure_then_one_in_n_with_suppressed_count() {
+        let sampler = sampler(10, Duration::from_secs(3600));
+        let now = Instant::now();
+
+        let decisions: Vec<FailureLogDecision> =
+            (0..25).map(|_| sampler.record_failure_at(0, now)).collect();
+
+        let logged: Vec<(usize, u64)> = decisions
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, decision)| match decision {
+                FailureLogDecision::Log { suppressed } => Some((idx, *suppressed)),
+                FailureLogDecision::Suppress => None,
+            })
+            .collect();
+        assert_eq!(logged, vec![(0, 0), (10, 9), (20, 9)]);
+    }
+
+    #[test]
+    fn logs_summary_after_interval() {
+        let sampler = sampler(1000, Duration::from_secs(60));
+        let start = Instant::now();
+
+        assert_eq!(
+            sampler.record_failure_at(0, start),
+            FailureLogDecision::Log { suppressed: 0 }
+        );
+        for secs in 1..5 {
+            assert_eq!(
+                sampler.record_failure_at(0, start + Duration::from_secs(secs)),
+                FailureLogDecision::Suppress
+            );
+        }
+        assert_eq!(
+            sampler.record_failure_at(0, start + Duration::from_secs(61)),
+            FailureLogDecision::Log { suppressed: 4 }
+        );
+    }
+
+    #[test]
+    fn sampling_is_keyed_per_sink_and_reset_on_success() {
+        let sampler = sampler(10, Duration::from_secs(3600));
+        let now = Instant::now();
+
+        assert!(matches!(
+            sampler.record_failure_at(0, now),
+            FailureLogDecision::Log { .. }
+        ));
+        assert_eq!(
+            sampler.record_failure_at(0, now),
+            FailureLogDecision::Suppress
+        );
+        assert!(matches!(
+            sampler.record_failure_at(1, now),
+            FailureLogDecision::Log { .. }
+        ));
+
+        assert_eq!(sampler.record_success(0), Some(2));
+        assert_eq!(sampler.record_success(0), None);
+        assert_eq!(
+            sampler.record_failure_at(0, now),
+            FailureLogDecision::Log { suppressed: 0 }
+        );
+    }
+
+    #[test]
+    fn without_sampling_every_failure_is_logged() {
+        let sampler = FailureLogSampler::new(None, 1);
+        for _ in 0..5 {
+            assert_eq!(
+                sampler.record_failure(0),
+                FailureLogDecision::Log { suppressed: 0 }
+            );
+        }
+        assert_eq!(sampler.record_success(0), None);
+    }
+}
@@ -0,0 +1,3929 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt::Write as _;
+use std::panic::AssertUnwindSafe;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+mod auto_tags;
+mod dedup;
+mod failure_log;
+
+use arc_swap::ArcSwap;
+use futures_util::FutureExt;
+use futures_util::stream::{FuturesUnordered, StreamExt};
+
+use crate::ErrorKind;
+use crate::event::{Event, Severity};
+use crate::jitter::JitterRng;
+use crate::sinks::Sink;
+
+pub use self::auto_tags::AutoTags;
+use self::dedup::Deduper;
+pub use self::dedup::{DedupConfig, DedupKeyFn};
+pub use self::failure_log::FailureLogSampling;
+use self::failure_log::{FailureLogDecision, FailureLogSampler};
+
+const DEFAULT_MAX_INFLIGHT_EVENTS: usize = 128;
+const DEFAULT_MAX_SINK_SENDS_IN_PARALLEL: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TryNotifyError {
+    NoTokioRuntime,
+    Overloaded,
+}
+
+impl std::fmt::Display for TryNotifyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoTokioRuntime => write!(f, "no tokio runtime"),
+            Self::Overloaded => write!(f, "hub is overloaded"),
+        }
+    }
+}
+
+impl std::error::Error for TryNotifyError {}
+
+/// Returned (inside [`crate::Error`]) by [`Hub::send_to`] when no sink was registered under the
+/// requested name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownSinkError {
+    pub name: String,
+}
+
+impl std::fmt::Display for UnknownSinkError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown sink name: {}", self.name)
+    }
+}
+
+impl std::error::Error for UnknownSinkError {}
+
+#[derive(Clone)]
+pub struct HubConfig {
+    /// Optional allow-list for event kinds.
+    ///
+    /// - `None`: allow all event kinds.
+    /// - `Some(set)`: only allow event kinds present in the set.
+    pub enabled_kinds: Option<BTreeSet<String>>,
+    /// Optional severity threshold: events below it are dropped before dispatch.
+    ///
+    /// Compared against the effective severity, i.e. after [`HubConfig::kind_severity_defaults`]
+    /// is applied. `None` allows all severities.
+    pub min_severity: Option<Severity>,
+    /// Per-sink timeout to ensure notifications never block the caller.
+    ///
+    /// This is a **hard upper bound** enforced by `Hub` (via `tokio::time::timeout`) around each
+    /// `Sink::send`. If a sink has its own internal timeout (e.g. an HTTP request timeout), keep
+    /// `per_sink_timeout` >= that value (and ideally leave some slack for preflight work like DNS
+    /// checks), otherwise `Hub` may time out first.
+    pub per_sink_timeout: Duration,
+    /// Optional random extra time added to `per_sink_timeout` for each sink send.
+    ///
+    /// The effective timeout is uniformly distributed in `[per_sink_timeout, per_sink_timeout +
+    /// per_sink_timeout_jitter]`, so fleets running identical configs do not time out (and retry)
+    /// in lockstep. `Duration::ZERO` (the default) disables jitter.
+    pub per_sink_timeout_jitter: Duration,
+    /// Maximum number of sinks called concurrently for one event (default 16).
+    ///
+    /// `1` sends strictly sequentially, in registration order; `0` is treated as `1`.
+    pub max_sink_sends_in_parallel: usize,
+    /// Opt-in self-notifications (see [`SelfNotifyConfig`]); `None` disables them.
+    pub self_notify: Option<SelfNotifyConfig>,
+    /// Per-sink sampling of the failure warnings logged by `notify` (see
+    /// [`FailureLogSampling`]); `None` logs every failure.
+    pub failure_log_sampling: Option<FailureLogSampling>,
+    /// Host/process tags added to every event (see [`AutoTags`]); disabled by default.
+    pub auto_tags: AutoTags,
+    /// Suppression of repeated events (see [`DedupConfig`]); `None` dispatches every event.
+    pub dedup: Option<DedupConfig>,
+    /// Severity for events of a kind created without one ([`Event::without_severity`]), e.g.
+    /// `approval_requested` → `Warning`. Applied before dedup and sink routing; events with an
+    /// explicit severity, including `Info`, keep it.
+    pub kind_severity_defaults: BTreeMap<String, Severity>,
+    /// When [`Hub::send`] reports sink failures as `Err` (see [`SendErrorPolicy`]).
+    pub error_when: SendErrorPolicy,
+    /// Sink that receives a compact summary event after each send (broadcasts, [`Hub::send_any`]
+    /// and [`Hub::send_to`]), e.g. a metrics webhook; `None` disables it.
+    ///
+    /// The summary keeps the original `kind` and `severity`, uses the kind as title, and carries
+    /// only the tags `timestamp_ms`, `delivered_count` and `failed_count`. It is sent before the
+    /// send returns, under the same inflight permit and `per_sink_timeout` as the event itself;
+    /// failures are only logged.
+    pub mirror_summary_to: Option<Arc<dyn Sink>>,
+}
+
+impl std::fmt::Debug for HubConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mirror = self
+            .mirror_summary_to
+            .as_ref()
+            .map(|sink| std::panic::catch_unwind(AssertUnwindSafe(|| sink.name())).ok());
+        f.debug_struct("HubConfig")
+            .field("enabled_kinds", &self.enabled_kinds)
+            .field("min_severity", &self.min_severity)
+            .field("per_sink_timeout", &self.per_sink_timeout)
+            .field("per_sink_timeout_jitter", &self.per_sink_timeout_jitter)
+            .field(
+                "max_sink_sends_in_parallel",
+                &self.max_sink_sends_in_parallel,
+            )
+            .field("self_notify", &self.self_notify)
+            .field("failure_log_sampling", &self.failure_log_sampling)
+            .field("auto_tags", &self.auto_tags)
+            .field("dedup", &self.dedup)
+            .field("kind_severity_defaults", &self.kind_severity_defaults)
+            .field("error_when", &self.error_when)
+            .field("mirror_summary_to", &mirror)
+            .finish()
+    }
+}
+
+impl Default for HubConfig {
+    fn default() -> Self {
+        Self {
+            enabled_kinds: None,
+            min_severity: None,
+            per_sink_timeout: Duration::from_secs(5),
+            per_sink_timeout_jitter: Duration::ZERO,
+            max_sink_sends_in_parallel: DEFAULT_MAX_SINK_SENDS_IN_PARALLEL,
+            self_notify: None,
+            failure_log_sampling: None,
+            auto_tags: AutoTags::default(),
+            dedup: None,
+            kind_severity_defaults: BTreeMap::new(),
+            error_when: SendErrorPolicy::default(),
+            mirror_summary_to: None,
+        }
+    }
+}
+
+/// When [`Hub::send`] turns sink failures into an `Err`.
+///
+/// The error always lists every failed sink; the policy only decides whether it is returned.
+/// [`Hub::send_detailed`] is unaffected.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SendErrorPolicy {
+    /// Any failing sink is an error.
+    #[default]
+    AnyFailure,
+    /// Only an error when no sink succeeded (and at least one failed).
+    AllFailures,
+    /// Only an error when more than this many sinks failed.
+    ThresholdExceeded(usize),
+}
+
+impl SendErrorPolicy {
+    fn is_error(self, failed: usize, succeeded: usize) -> bool {
+        match self {
+            Self::AnyFailure => failed > 0,
+            Self::AllFailures => failed > 0 && succeeded == 0,
+            Self::ThresholdExceeded(threshold) => failed > threshold,
+        }
+    }
+}
+
+/// How [`HubConfig::merge_with`] combines two `enabled_kinds` allow-lists that are both set.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EnabledKindsMerge {
+    /// The override's list replaces the base list.
+    #[default]
+    Replace,
+    /// Kinds from both lists are allowed.
+    Union,
+}
+
+impl HubConfig {
+    /// Layers `other` on top of `self` (e.g. defaults, then a config file, then CLI flags).
+    ///
+    /// Fields `other` sets win; a field counts as set when it is `Some` or differs from
+    /// [`HubConfig::default`], so an override cannot reset a field back to its default value.
+    /// `auto_tags` is merged per flag (enabled in either) and `app_name` (override wins).
+    /// `kind_severity_defaults` are merged per kind (override wins). `enabled_kinds` lists are
+    /// replaced; see [`HubConfig::merge_with`] for a union.
+    #[must_use]
+    pub fn merge(self, other: HubConfig) -> HubConfig {
+        self.merge_with(other, EnabledKindsMerge::Replace)
+    }
+
+    /// Like [`HubConfig::merge`], with explicit `enabled_kinds` semantics when both sides set it.
+    #[must_use]
+    pub fn merge_with(self, other: HubConfig, kinds: EnabledKindsMerge) -> HubConfig {
+        let defaults = HubConfig::default();
+        let enabled_kinds = match (self.enabled_kinds, other.enabled_kinds) {
+            (Some(mut base), Some(over)) if kinds == EnabledKindsMerge::Union => {
+                base.extend(over);
+                Some(base)
+            }
+            (base, over) => over.or(base),
+        };
+        HubConfig {
+            enabled_kinds,
+            min_severity: other.min_severity.or(self.min_severity),
+            per_sink_timeout: pick(
+                self.per_sink_timeout,
+                other.per_sink_timeout,
+                defaults.per_sink_timeout,
+            ),
+            per_sink_timeout_jitter: pick(
+                self.per_sink_timeout_jitter,
+                other.per_sink_timeout_jitter,
+                defaults.per_sink_timeout_jitter,
+            ),
+            max_sink_sends_in_parallel: pick(
+                self.max_sink_sends_in_parallel,
+                other.max_sink_sends_in_parallel,
+                defaults.max_sink_sends_in_parallel,
+            ),
+            self_notify: other.self_notify.or(self.self_notify),
+            failure_log_sampling: other.failure_log_sampling.or(self.failure_log_sampling),
+            auto_tags: AutoTags {
+                hostname: self.auto_tags.hostname || other.auto_tags.hostname,
+                pid: self.auto_tags.pid || other.auto_tags.pid,
+                app_name: other.auto_tags.app_name.or(self.auto_tags.app_name),
+            },
+            dedup: other.dedup.or(self.dedup),
+            kind_severity_defaults: {
+                let mut merged = self.kind_severity_defaults;
+                merged.extend(other.kind_severity_defaults);
+                merged
+            },
+            error_when: pick(self.error_when, other.error_when, defaults.error_when),
+            mirror_summary_to: other.mirror_summary_to.or(self.mirror_summary_to),
+        }
+    }
+}
+
+/// `over` if it was changed from `default`, otherwise `base`.
+fn pick<T: PartialEq>(base: T, over: T, default: T) -> T {
+    if over != default { over } else { base }
+}
+
+/// Lets the hub report its own trouble as a synthetic [`Severity::Error`] event.
+///
+/// A meta event is emitted when either
+/// - `overload_threshold` events were dropped (hub overloaded) within `window`, or
+/// - `failure_threshold` consecutive events had at least one failing sink.
+///
+/// At most one meta event is emitted per `window`, so a broken meta sink cannot cause a
+/// notification loop. Meta events bypass `enabled_kinds`, per-sink [`SinkFilter`]s and the
+/// inflight limit, and their own delivery failures are only logged.
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub struct SelfNotifyConfig {
+    /// Event kind of meta events.
+    pub kind: String,
+    /// Deliver meta events only to the sink registered under this [`HubBuilder::add_named`]
+    /// label, or, if no label matches, to the sinks whose `name()` matches; `None` means all
+    /// sinks.
+    pub meta_sink: Option<String>,
+    pub overload_threshold: usize,
+    pub failure_threshold: usize,
+    pub window: Duration,
+}
+
+impl Default for SelfNotifyConfig {
+    fn default() -> Self {
+        Self {
+            kind: "notify_kit.hub".to_string(),
+            meta_sink: None,
+            overload_threshold: 10,
+            failure_threshold: 5,
+            window: Duration::from_secs(300),
+        }
+    }
+}
+
+impl SelfNotifyConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn with_kind(mut self, kind: impl Into<String>) -> Self {
+        self.kind = kind.into();
+        self
+    }
+
+    #[must_use]
+    pub fn with_meta_sink(mut self, sink_name: impl Into<String>) -> Self {
+        self.meta_sink = Some(sink_name.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_overload_threshold(mut self, overload_threshold: usize) -> Self {
+        self.overload_threshold = overload_threshold.max(1);
+        self
+    }
+
+    #[must_use]
+    pub fn with_failure_threshold(mut self, failure_threshold: usize) -> Self {
+        self.failure_threshold = failure_threshold.max(1);
+        self
+    }
+
+    #[must_use]
+    pub fn with_window(mut self, window: Duration) -> Self {
+        self.window = window;
+        self
+    }
+}
+
+/// Per-sink routing filter used by [`Hub::new_with_routes`].
+///
+/// A sink only receives events with `severity >= min_severity` and, when `kinds` is set, whose
+/// kind is in the set. The hub-wide `HubConfig::enabled_kinds` is applied first.
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub struct SinkFilter {
+    pub min_severity: Severity,
+    pub kinds: Option<BTreeSet<String>>,
+}
+
+impl Default for SinkFilter {
+    fn default() -> Self {
+        Self {
+            min_severity: Severity::Info,
+            kinds: None,
+        }
+    }
+}
+
+impl SinkFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn with_min_severity(mut self, min_severity: Severity) -> Self {
+        self.min_severity = min_severity;
+        self
+    }
+
+    #[must_use]
+    pub fn with_kinds<I, S>(mut self, kinds: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.kinds = Some(kinds.into_iter().map(Into::into).collect());
+        self
+    }
+
+    fn accepts(&self, event: &Event) -> bool {
+        if event.severity < self.min_severity {
+            return false;
+        }
+        let Some(kinds) = &self.kinds else {
+            return true;
+        };
+        kinds.contains(event.kind.as_str())
+    }
+}
+
+/// Result of delivering one event to one sink, see [`Hub::send_detailed`].
+#[non_exhaustive]
+#[derive(Debug)]
+pub struct SinkOutcome {
+    /// `Sink::name()` (or `"<unknown>"` if it panicked).
+    pub name: &'static str,
+    /// Position of the sink in the list the hub was built with.
+    pub index: usize,
+    pub result: crate::Result<()>,
+}
+
+/// What [`Hub::send_batch`] does after an event fails to deliver.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BatchFailureMode {
+    /// Send every event; the result list has one entry per event.
+    #[default]
+    ContinueAll,
+    /// Stop at the first failed event; later events are not sent and have no result entry.
+    StopOnFirst,
+}
+
+#[derive(Clone)]
+pub struct Hub {
+    inner: Arc<HubInner>,
+}
+
+/// Builder for [`Hub`], see [`Hub::builder`].
+pub struct HubBuilder {
+    config: HubConfig,
+    sinks: Vec<HubSink>,
+    names: HashMap<String, usize>,
+    max_inflight_events: usize,
+}
+
+impl std::fmt::Debug for HubBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HubBuilder")
+            .field("config", &self.config)
+            .field("sinks", &self.sinks.len())
+            .field("names", &self.names)
+            .field("max_inflight_events", &self.max_inflight_events)
+            .finish()
+    }
+}
+
+impl Default for HubBuilder {
+    fn default() -> Self {
+        Self {
+            config: HubConfig::default(),
+            sinks: Vec::new(),
+            names: HashMap::new(),
+            max_inflight_events: DEFAULT_MAX_INFLIGHT_EVENTS,
+        }
+    }
+}
+
+impl HubBuilder {
+    /// Replaces the whole [`HubConfig`]; combine with the individual setters below as needed.
+    #[must_use]
+    pub fn config(mut self, config: HubConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Only allow these event kinds (see [`HubConfig::enabled_kinds`]).
+    #[must_use]
+    pub fn enabled_kinds<I, S>(mut self, kinds: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.config.enabled_kinds = Some(kinds.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Drop events below `min_severity` (see [`HubConfig::min_severity`]).
+    #[must_use]
+    pub fn min_severity(mut self, min_severity: Severity) -> Self {
+        self.config.min_severity = Some(min_severity);
+        self
+    }
+
+    #[must_use]
+    pub fn per_sink_timeout(mut self, per_sink_timeout: Duration) -> Self {
+        self.config.per_sink_timeout = per_sink_timeout;
+        self
+    }
+
+    /// Adds host/process tags to every event (see [`HubConfig::auto_tags`]).
+    #[must_use]
+    pub fn auto_tags(mut self, auto_tags: AutoTags) -> Self {
+        self.config.auto_tags = auto_tags;
+        self
+    }
+
+    /// Severity for `kind` events created without one (see
+    /// [`HubConfig::kind_severity_defaults`]).
+    #[must_use]
+    pub fn kind_severity_default(mut self, kind: impl Into<String>, severity: Severity) -> Self {
+        self.config
+            .kind_severity_defaults
+            .insert(kind.into(), severity);
+        self
+    }
+
+    /// When [`Hub::send`] returns `Err` for sink failures (see [`HubConfig::error_when`]).
+    #[must_use]
+    pub fn error_when(mut self, error_when: SendErrorPolicy) -> Self {
+        self.config.error_when = error_when;
+        self
+    }
+
+    /// Sends a compact summary of each broadcast to `sink` (see
+    /// [`HubConfig::mirror_summary_to`]).
+    #[must_use]
+    pub fn mirror_summary_to(mut self, sink: Arc<dyn Sink>) -> Self {
+        self.config.mirror_summary_to = Some(sink);
+        self
+    }
+
+    /// Bound on events being delivered concurrently (see [`Hub::new_with_inflight_limit`]).
+    #[must_use]
+    pub fn max_inflight_events(mut self, max_inflight_events: usize) -> Self {
+        self.max_inflight_events = max_inflight_events;
+        self
+    }
+
+    /// Bound on sinks called concurrently for one event (see
+    /// [`HubConfig::max_sink_sends_in_parallel`]).
+    #[must_use]
+    pub fn max_sink_sends_in_parallel(mut self, max_sink_sends_in_parallel: usize) -> Self {
+        self.config.max_sink_sends_in_parallel = max_sink_sends_in_parallel;
+        self
+    }
+
+    /// Registers a sink that only receives events accepted by `filter` (see [`SinkFilter`]).
+    #[must_use]
+    pub fn add_routed(mut self, sink: Arc<dyn Sink>, filter: SinkFilter) -> Self {
+        self.sinks.push(HubSink::new(sink, Some(filter)));
+        self
+    }
+
+    #[must_use]
+    pub fn add_sink(mut self, sink: Arc<dyn Sink>) -> Self {
+        self.sinks.push(HubSink::new(sink, None));
+        self
+    }
+
+    /// Registers a sink under `name` so it can be targeted with [`Hub::send_to`].
+    ///
+    /// Named sinks still receive broadcasts. If `name` is already taken, the sink is registered
+    /// but the name keeps pointing at the first sink (a warning is logged).
+    #[must_use]
+    pub fn add_named(mut self, name: impl Into<String>, sink: Arc<dyn Sink>) -> Self {
+        let name = name.into();
+        let idx = self.sinks.len();
+        self.sinks.push(HubSink::new(sink, None));
+        match self.names.entry(name) {
+            std::collections::hash_map::Entry::Vacant(entry) => {
+                entry.insert(idx);
+            }
+            std::collections::hash_map::Entry::Occupied(entry) => {
+                tracing::warn!(
+                    sink = "hub",
+                    name = %entry.key(),
+                    "duplicate sink name; send_to keeps the first registration"
+                );
+            }
+        }
+        self
+    }
+
+    pub fn build(self) -> Hub {
+        Hub::from_hub_sinks(
+            self.config,
+            self.sinks,
+            self.names,
+            self.max_inflight_events,
+        )
+    }
+}
+
+struct HubInner {
+    sinks: Vec<HubSink>,
+    names: HashMap<String, usize>,
+    jitter_rng: JitterRng,
+    inflight: Arc<tokio::sync::Semaphore>,
+    max_inflight_events: usize,
+    /// Replaced as a whole by [`Hub::replace_config`]; each send works on one loaded snapshot.
+    settings: ArcSwap<HubSettings>,
+}
+
+/// Everything derived from a [`HubConfig`], including the state of stateful features (dedup
+/// cache, self-notify windows, failure-log sampling).
+struct HubSettings {
+    enabled_kinds: Option<HashSet<String>>,
+    min_severity: Option<Severity>,
+    per_sink_timeout: Duration,
+    per_sink_timeout_jitter: Duration,
+    max_sink_sends_in_parallel: usize,
+    self_notify: Option<SelfNotifier>,
+    failure_log: FailureLogSampler,
+    auto_tags: Vec<(&'static str, String)>,
+    dedup: Option<Deduper>,
+    kind_severity_defaults: HashMap<String, Severity>,
+    error_when: SendErrorPolicy,
+    mirror_summary_to: Option<HubSink>,
+}
+
+struct SelfNotifier {
+    config: SelfNotifyConfig,
+    state: Mutex<SelfNotifyState>,
+}
+
+struct SelfNotifyState {
+    window_start: Instant,
+    dropped_in_window: usize,
+    consecutive_failures: usize,
+    last_emitted: Option<Instant>,
+}
+
+struct HubSink {
+    sink: Arc<dyn Sink>,
+    name: Option<&'static str>,
+    filter: Option<SinkFilter>,
+}
+
+impl HubSink {
+    fn new(sink: Arc<dyn Sink>, filter: Option<SinkFilter>) -> Self {
+        Self {
+            name: std::panic::catch_unwind(AssertUnwindSafe(|| sink.name())).ok(),
+            sink,
+            filter,
+        }
+    }
+
+    fn accepts(&self, event: &Event) -> bool {
+        self.filter
+            .as_ref()
+            .is_none_or(|filter| filter.accepts(event))
+    }
+}
+
+impl Hub {
+    pub fn builder() -> HubBuilder {
+        HubBuilder::default()
+    }
+
+    pub fn new(config: HubConfig, sinks: Vec<Arc<dyn Sink>>) -> Self {
+        Self::new_with_inflight_limit(config, sinks, DEFAULT_MAX_INFLIGHT_EVENTS)
+    }
+
+    pub fn new_with_inflight_limit(
+        config: HubConfig,
+        sinks: Vec<Arc<dyn Sink>>,
+        max_inflight_events: usize,
+    ) -> Self {
+        let sinks = sinks
+            .into_iter()
+            .map(|sink| HubSink::new(sink, None))
+            .collect();
+        Self::from_hub_sinks(config, sinks, HashMap::new(), max_inflight_events)
+    }
+
+    /// Like [`Hub::new`], but each sink only receives the events its [`SinkFilter`] accepts.
+    ///
+    /// For example, route `Severity::Error` to a pager while keeping `Info` in chat:
+    /// `(pager, SinkFilter::new().with_min_severity(Severity::Error))`.
+    pub fn new_with_routes(config: HubConfig, routes: Vec<(Arc<dyn Sink>, SinkFilter)>) -> Self {
+        let sinks = routes
+            .into_iter()
+            .map(|(sink, filter)| HubSink::new(sink, Some(filter)))
+            .collect();
+        Self::from_hub_sinks(config, sinks, HashMap::new(), DEFAULT_MAX_INFLIGHT_EVENTS)
+    }
+
+    fn from_hub_sinks(
+        config: HubConfig,
+        sinks: Vec<HubSink>,
+        names: HashMap<String, usize>,
+        max_inflight_events: usize,
+    ) -> Self {
+        let max_inflight_events = max_inflight_events.max(1);
+        let settings = HubSettings::new(config, sinks.len());
+        let inner = HubInner {
+            sinks,
+            names,
+            jitter_rng: crate::jitter::random_fraction,
+            inflight: Arc::new(tokio::sync::Semaphore::new(max_inflight_events)),
+            max_inflight_events,
+            settings: ArcSwap::from_pointee(settings),
+        };
+        Self {
+            inner: Arc::new(inner),
+        }
+    }
+
+    /// Atomically swaps in a new config (e.g. on `SIGHUP`), for this hub and all its clones.
+    ///
+    /// Sinks, sink names and the inflight limit are kept. Every send started before the swap
+    /// finishes with the old config and every later one uses the new config; no send sees a mix
+    /// of both. Stateful features restart with the new config: the [`HubConfig::dedup`] cache,
+    /// [`HubConfig::self_notify`] windows and [`HubConfig::failure_log_sampling`] counters are
+    /// reset.
+    pub fn replace_config(&self, config: HubConfig) {
+        let settings = HubSettings::new(config, self.inner.sinks.len());
+        self.inner.settings.store(Arc::new(settings));
+    }
+
+    /// Fire-and-forget notification.
+    ///
+    /// - Requires a Tokio runtime; if none is present, the notification is dropped and a warning is
+    ///   logged.
+    /// - Concurrency is bounded; if overloaded, notifications are dropped (with a warning).
+    pub fn notify(&self, event: Event) {
+        if self.inner.sinks.is_empty() {
+            return;
+        }
+        let settings = self.inner.settings.load_full();
+        if !settings.is_enabled(&event) {
+            return;
+        }
+
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            tracing::warn!(
+                sink = "hub",
+                kind = %event.kind,
+                "notify dropped: no tokio runtime"
+            );
+            return;
+        };
+
+        if let Err(event) = self.try_notify_spawn(handle, settings, event) {
+            tracing::warn!(sink = "hub", kind = %event.kind, "notify dropped: overloaded");
+        }
+    }
+
+    /// Attempt to enqueue a fire-and-forget notification.
+    ///
+    /// Returns:
+    /// - `Err(TryNotifyError::NoTokioRuntime)` if called outside a Tokio runtime.
+    /// - `Err(TryNotifyError::Overloaded)` when Hub inflight capacity is full.
+    pub fn try_notify(&self, event: Event) -> Result<(), TryNotifyError> {
+        if self.inner.sinks.is_empty() {
+            return Ok(());
+        }
+        let settings = self.inner.settings.load_full();
+        if !settings.is_enabled(&event) {
+            return Ok(());
+        }
+
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            return Err(TryNotifyError::NoTokioRuntime);
+        };
+
+        match self.try_notify_spawn(handle, settings, event) {
+            Ok(()) => Ok(()),
+            Err(_) => Err(TryNotifyError::Overloaded),
+        }
+    }
+
+    pub async fn send(&self, event: Event) -> crate::Result<()> {
+        let settings = self.inner.settings.load_full();
+        let outcomes = self.send_detailed_with(&settings, event).await?;
+        HubInner::aggregate_outcomes(outcomes, settings.error_when)
+    }
+
+    /// Blocking [`Hub::send`] for code without a Tokio runtime (e.g. sync CLIs): the event is
+    /// sent on a temporary current-thread runtime.
+    ///
+    /// Returns an error when called inside a Tokio runtime, where blocking the thread could
+    /// stall or deadlock it; use [`Hub::send`] there.
+    pub fn send_blocking(&self, event: Event) -> crate::Result<()> {
+        if tokio::runtime::Handle::try_current().is_ok() {
+            return Err(anyhow::anyhow!(
+                "hub send_blocking cannot run inside a tokio runtime; use send().await"
+            )
+            .into());
+        }
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|err| anyhow::anyhow!("build tokio runtime: {err}"))?;
+        rt.block_on(self.send(event))
+    }
+
+    /// Like [`Hub::send`], but returns one [`SinkOutcome`] per sink instead of an aggregated
+    /// error, in sink registration order.
+    ///
+    /// Sinks skipped by their [`SinkFilter`] have no outcome. The outer `Err` is reserved for
+    /// hub-level failures (e.g. no Tokio runtime); an event whose kind is disabled, that is below
+    /// [`HubConfig::min_severity`], or that is suppressed by [`HubConfig::dedup`], yields an
+    /// empty vector.
+    pub async fn send_detailed(&self, event: Event) -> crate::Result<Vec<SinkOutcome>> {
+        let settings = self.inner.settings.load_full();
+        self.send_detailed_with(&settings, event).await
+    }
+
+    async fn send_detailed_with(
+        &self,
+        settings: &Arc<HubSettings>,
+        mut event: Event,
+    ) -> crate::Result<Vec<SinkOutcome>> {
+        if self.inner.sinks.is_empty() {
+            return Ok(Vec::new());
+        }
+        if !settings.is_enabled(&event) {
+            return Ok(Vec::new());
+        }
+
+        tokio::runtime::Handle::try_current()
+            .map_err(|_| anyhow::Error::from(TryNotifyError::NoTokioRuntime))?;
+        let _permit = self
+            .inner
+            .inflight
+            .acquire()
+            .await
+            .map_err(|_| anyhow::anyhow!("hub inflight semaphore closed"))?;
+        settings.apply_kind_severity_default(&mut event);
+        let DedupClaim::Dispatch(dedup_key) = settings.claim_dedup(&event) else {
+            return Ok(Vec::new());
+        };
+        auto_tags::apply(&settings.auto_tags, &mut event);
+        let outcomes = self.inner.send_detailed(settings, &event).await;
+        settings.release_dedup_if_undelivered(dedup_key, &outcomes);
+        HubInner::record_outcome(
+            &self.inner,
+            settings,
+            outcomes.iter().all(|outcome| outcome.result.is_ok()),
+        );
+        let (delivered, failed) = outcome_counts(&outcomes);
+        self.inner
+            .send_mirror_summary(settings, &event, delivered, failed)
+            .await;
+        Ok(outcomes)
+    }
+
+    /// Races all sinks that accept `event` and returns the name of the first one to deliver it;
+    /// fails only if every sink fails (the error lists each failure, as with [`Hub::send`]).
+    ///
+    /// Meant for failover-style delivery of critical alerts: sends still in flight are cancelled
+    /// once one sink succeeds. An event that no sink would receive (disabled kind, below
+    /// `min_severity`, or rejected by every [`SinkFilter`]) is an error, and
+    /// [`HubConfig::dedup`] does not apply, since the caller asks for confirmation.
+    pub async fn send_any(&self, mut event: Event) -> crate::Result<&'static str> {
+        let settings = self.inner.settings.load_full();
+        if !settings.is_kind_enabled(event.kind.as_str()) {
+            return Err(anyhow::anyhow!("event kind `{}` is not enabled", event.kind).into());
+        }
+        if !settings.meets_min_severity(&event) {
+            return Err(anyhow::anyhow!(
+                "event severity `{}` is below min_severity",
+                event.severity
+            )
+            .into());
+        }
+
+        tokio::runtime::Handle::try_current()
+            .map_err(|_| anyhow::Error::from(TryNotifyError::NoTokioRuntime))?;
+        let _permit = self
+            .inner
+            .inflight
+            .acquire()
+            .await
+            .map_err(|_| anyhow::anyhow!("hub inflight semaphore closed"))?;
+        settings.apply_kind_severity_default(&mut event);
+        auto_tags::apply(&settings.auto_tags, &mut event);
+        let (result, failed) = self.inner.send_any(&settings, &event).await;
+        HubInner::record_outcome(&self.inner, &settings, result.is_ok());
+        self.inner
+            .send_mirror_summary(&settings, &event, usize::from(result.is_ok()), failed)
+            .await;
+        result
+    }
+
+    /// Sends `event` only to the sink registered under `name` (see [`HubBuilder::add_named`]).
+    ///
+    /// Returns an error wrapping [`UnknownSinkError`] if no sink has that name. `enabled_kinds`
+    /// and `min_severity` still apply; per-sink [`SinkFilter`]s do not, since the target is
+    /// explicit.
+    pub async fn send_to(&self, name: &str, mut event: Event) -> crate::Result<()> {
+        let Some(&idx) = self.inner.names.get(name) else {
+            return Err(anyhow::Error::from(UnknownSinkError {
+                name: name.to_string(),
+            })
+            .into());
+        };
+        let settings = self.inner.settings.load_full();
+        if !settings.is_enabled(&event) {
+            return Ok(());
+        }
+
+        tokio::runtime::Handle::try_current()
+            .map_err(|_| anyhow::Error::from(TryNotifyError::NoTokioRuntime))?;
+        let _permit = self
+            .inner
+            .inflight
+            .acquire()
+            .await
+            .map_err(|_| anyhow::anyhow!("hub inflight semaphore closed"))?;
+        settings.apply_kind_severity_default(&mut event);
+        auto_tags::apply(&settings.auto_tags, &mut event);
+        let (index, sink_name, result) = HubInner::send_one_sink(
+            self.inner.effective_timeout(&settings),
+            idx,
+            &self.inner.sinks[idx],
+            &event,
+        )
+        .await;
+        HubInner::record_outcome(&self.inner, &settings, result.is_ok());
+        let delivered = usize::from(result.is_ok());
+        self.inner
+            .send_mirror_summary(&settings, &event, delivered, 1 - delivered)
+            .await;
+        result.map_err(|err| HubInner::build_failures_error(vec![(index, sink_name, err)]))
+    }
+
+    /// Sends `events` one after another (each like [`Hub::send`]) and returns their results in
+    /// order.
+    ///
+    /// With [`BatchFailureMode::ContinueAll`] every event is sent; with
+    /// [`BatchFailureMode::StopOnFirst`] the list ends with the first failure and the remaining
+    /// events are dropped unsent.
+    pub async fn send_batch<I>(&self, events: I, mode: BatchFailureMode) -> Vec<crate::Result<()>>
+    where
+        I: IntoIterator<Item = Event>,
+    {
+        let events = events.into_iter();
+        let mut results = Vec::with_capacity(events.size_hint().0);
+        for event in events {
+            let result = self.send(event).await;
+            let failed = result.is_err();
+            results.push(result);
+            if failed && mode == BatchFailureMode::StopOnFirst {
+                break;
+            }
+        }
+        results
+    }
+
+    /// Returns, in registration order, the payload each sink would send for `event`, without
+    /// sending anything.
+    ///
+    /// Applies the same routing as [`Hub::send`] (enabled kinds, min severity, [`SinkFilter`]s,
+    /// kind severity defaults and auto tags) but no dedup. Only sinks implementing
+    /// [`Sink::preview`] (the built-in HTTP sinks) appear; sinks whose `name()` or `preview()`
+    /// panics are skipped.
+    pub fn dry_run(&self, mut event: Event) -> Vec<(&'static str, serde_json::Value)> {
+        let settings = self.inner.settings.load_full();
+        if !settings.is_enabled(&event) {
+            return Vec::new();
+        }
+        settings.apply_kind_severity_default(&mut event);
+        auto_tags::apply(&settings.auto_tags, &mut event);
+
+        self.inner
+            .sinks
+            .iter()
+            .filter(|hub_sink| hub_sink.accepts(&event))
+            .filter_map(|hub_sink| {
+                let name = hub_sink.name?;
+                let preview =
+                    std::panic::catch_unwind(AssertUnwindSafe(|| hub_sink.sink.preview(&event)))
+                        .ok()??;
+                Some((name, preview))
+            })
+            .collect()
+    }
+
+    /// Redacted, human-readable summary of this hub's setup for support bundles and bug reports.
+    ///
+    /// Lists each sink (name, [`HubBuilder::add_named`] label, [`Sink::diagnostic_target`],
+    /// routing filter), timeouts, enabled kinds and inflight capacity. Webhook paths, tokens and
+    /// secrets never appear: targets use the same redaction as the sinks' `Debug` output.
+    pub fn diagnostic_report(&self) -> String {
+        DiagnosticReport(&self.inner).to_string()
+    }
+
+    fn try_notify_spawn(
+        &self,
+        handle: tokio::runtime::Handle,
+        settings: Arc<HubSettings>,
+        mut event: Event,
+    ) -> std::result::Result<(), Box<Event>> {
+        let inner = self.inner.clone();
+
+        let permit = match inner.inflight.clone().try_acquire_owned() {
+            Ok(permit) => permit,
+            Err(_) => {
+                HubInner::record_dropped(&inner, &settings);
+                return Err(Box::new(event));
+            }
+        };
+        settings.apply_kind_severity_default(&mut event);
+        let DedupClaim::Dispatch(dedup_key) = settings.claim_dedup(&event) else {
+            return Ok(());
+        };
+
+        auto_tags::apply(&settings.auto_tags, &mut event);
+        handle.spawn(async move {
+            let _permit = permit;
+            let outcomes = inner.send_detailed(&settings, &event).await;
+            settings.release_dedup_if_undelivered(dedup_key, &outcomes);
+            HubInner::record_outcome(
+                &inner,
+                &settings,
+                outcomes.iter().all(|outcome| outcome.result.is_ok()),
+            );
+            settings.log_notify_outcomes(&event, &outcomes);
+            let (delivered, failed) = outcome_counts(&outcomes);
+            inner
+                .send_mirror_summary(&settings, &event, delivered, failed)
+                .await;
+        });
+        Ok(())
+    }
+}
+
+impl SelfNotifier {
+    fn new(config: SelfNotifyConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(SelfNotifyState {
+                window_start: Instant::now(),
+                dropped_in_window: 0,
+                consecutive_failures: 0,
+                last_emitted: None,
+            }),
+        }
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, SelfNotifyState> {
+        self.state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    fn record_dropped(&self) -> Option<Event> {
+        let now = Instant::now();
+        let mut state = self.lock_state();
+        if now.duration_since(state.window_start) >= self.config.window {
+            state.window_start = now;
+            state.dropped_in_window = 0;
+        }
+        state.dropped_in_window = state.dropped_in_window.saturating_add(1);
+        if state.dropped_in_window < self.config.overload_threshold
+            || !self.try_claim_emit(&mut state, now)
+        {
+            return None;
+        }
+        let dropped = std::mem::take(&mut state.dropped_in_window);
+        drop(state);
+
+        Some(
+            Event::new(
+                self.config.kind.clone(),
+                Severity::Error,
+                "notify hub overloaded",
+            )
+            .with_body(format!(
+                "dropped {dropped} notifications within {:?} because the hub is overloaded",
+                self.config.window
+            ))
+            .with_tag("reason", "overload")
+            .with_tag("dropped", dropped.to_string()),
+        )
+    }
+
+    fn record_outcome(&self, ok: bool) -> Option<Event> {
+        let now = Instant::now();
+        let mut state = self.lock_state();
+        if ok {
+            state.consecutive_failures = 0;
+            return None;
+        }
+        state.consecutive_failures = state.consecutive_failures.saturating_add(1);
+        if state.consecutive_failures < self.config.failure_threshold
+            || !self.try_claim_emit(&mut state, now)
+        {
+            return None;
+        }
+        let failures = std::mem::take(&mut state.consecutive_failures);
+        drop(state);
+
+        Some(
+            Event::new(
+                self.config.kind.clone(),
+                Severity::Error,
+                "notify sinks failing",
+            )
+            .with_body(format!(
+                "{failures} consecutive notifications failed on one or more sinks"
+            ))
+            .with_tag("reason", "sink_failures")
+            .with_tag("failures", failures.to_string()),
+        )
+    }
+
+    fn try_claim_emit(&self, state: &mut SelfNotifyState, now: Instant) -> bool {
+        if let Some(last) = state.last_emitted {
+            if now.duration_since(last) < self.config.window {
+                return false;
+            }
+        }
+        state.last_emitted = Some(now);
+        true
+    }
+}
+
+struct DiagnosticReport<'a>(&'a HubInner);
+
+impl std::fmt::Display for DiagnosticReport<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let inner = self.0;
+        let settings = inner.settings.load();
+        let mut labels: Vec<(&usize, &String)> =
+            inner.names.iter().map(|(name, idx)| (idx, name)).collect();
+        labels.sort();
+
+        writeln!(f, "notify-kit hub")?;
+        writeln!(f, "sinks: {}", inner.sinks.len())?;
+        for (idx, hub_sink) in inner.sinks.iter().enumerate() {
+            write!(f, "  [{idx}] {}", hub_sink.name.unwrap_or("<unknown>"))?;
+            for (_, label) in labels.iter().filter(|(label_idx, _)| **label_idx == idx) {
+                write!(f, " label={label}")?;
+            }
+            let target =
+                std::panic::catch_unwind(AssertUnwindSafe(|| hub_sink.sink.diagnostic_target()))
+                    .ok()
+                    .flatten();
+            if let Some(target) = target {
+                write!(f, " target={target}")?;
+            }
+            if let Some(filter) = &hub_sink.filter {
+                write!(f, " min_severity={}", filter.min_severity)?;
+                if let Some(kinds) = &filter.kinds {
+                    write!(f, " kinds={}", join_sorted(kinds.iter()))?;
+                }
+            }
+            writeln!(f)?;
+        }
+        writeln!(
+            f,
+            "per_sink_timeout: {:?} (jitter {:?})",
+            settings.per_sink_timeout, settings.per_sink_timeout_jitter
+        )?;
+        writeln!(
+            f,
+            "max_sink_sends_in_parallel: {}",
+            settings.max_sink_sends_in_parallel
+        )?;
+        match &settings.enabled_kinds {
+            None => writeln!(f, "enabled_kinds: all")?,
+            Some(kinds) => writeln!(f, "enabled_kinds: {}", join_sorted(kinds.iter()))?,
+        }
+        if let Some(min_severity) = settings.min_severity {
+            writeln!(f, "min_severity: {min_severity}")?;
+        }
+        writeln!(
+            f,
+            "inflight: {} available of {}",
+            inner.inflight.available_permits(),
+            inner.max_inflight_events
+        )?;
+        if !settings.kind_severity_defaults.is_empty() {
+            let defaults: BTreeMap<_, _> = settings.kind_severity_defaults.iter().collect();
+            write!(f, "kind_severity_defaults:")?;
+            for (kind, severity) in defaults {
+                write!(f, " {kind}={severity}")?;
+            }
+            writeln!(f)?;
+        }
+        if !settings.auto_tags.is_empty() {
+            let keys: Vec<&str> = settings.auto_tags.iter().map(|(key, _)| *key).collect();
+            writeln!(f, "auto_tags: {}", keys.join(","))?;
+        }
+        if let Some(mirror) = &settings.mirror_summary_to {
+            writeln!(
+                f,
+                "mirror_summary_to: {}",
+                mirror.name.unwrap_or("<unknown>")
+            )?;
+        }
+        writeln!(
+            f,
+            "dedup: {}",
+            if settings.dedup.is_some() {
+                "on"
+            } else {
+                "off"
+            }
+        )?;
+        write!(
+            f,
+            "self_notify: {}",
+            if settings.self_notify.is_some() {
+                "on"
+            } else {
+                "off"
+            }
+        )
+    }
+}
+
+/// `(delivered, failed)` sink counts of a broadcast.
+fn outcome_counts(outcomes: &[SinkOutcome]) -> (usize, usize) {
+    let delivered = outcomes
+        .iter()
+        .filter(|outcome| outcome.result.is_ok())
+        .count();
+    (delivered, outcomes.len() - delivered)
+}
+
+/// The reduced event sent to [`HubConfig::mirror_summary_to`].
+fn summary_event(event: &Event, delivered: usize, failed: usize) -> Event {
+    let timestamp_ms = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis())
+        .unwrap_or_default();
+    Event::new(event.kind.clone(), event.severity, event.kind.clone())
+        .with_tag("timestamp_ms", timestamp_ms.to_string())
+        .with_tag("delivered_count", delivered.to_string())
+        .with_tag("failed_count", failed.to_string())
+}
+
+fn join_sorted<'a>(items: impl Iterator<Item = &'a String>) -> String {
+    let mut items: Vec<&str> = items.map(String::as_str).collect();
+    items.sort_unstable();
+    items.join(",")
+}
+
+impl HubSettings {
+    fn new(config: HubConfig, sink_count: usize) -> Self {
+        Self {
+            enabled_kinds: config
+                .enabled_kinds
+                .map(|enabled_kinds| enabled_kinds.into_iter().collect()),
+            min_severity: config.min_severity,
+            per_sink_timeout: config.per_sink_timeout,
+            per_sink_timeout_jitter: config.per_sink_timeout_jitter,
+            max_sink_sends_in_parallel: config.max_sink_sends_in_parallel.max(1),
+            self_notify: config.self_notify.map(SelfNotifier::new),
+            failure_log: FailureLogSampler::new(config.failure_log_sampling, sink_count),
+            auto_tags: config.auto_tags.resolve(),
+            dedup: config.dedup.map(Deduper::new),
+            kind_severity_defaults: config.kind_severity_defaults.into_iter().collect(),
+            error_when: config.error_when,
+            mirror_summary_to: config
+                .mirror_summary_to
+                .map(|sink| HubSink::new(sink, None)),
+        }
+    }
+
+    fn is_kind_enabled(&self, kind: &str) -> bool {
+        let Some(enabled) = &self.enabled_kinds else {
+            return true;
+        };
+        enabled.contains(kind)
+    }
+
+    /// Whether `event` passes [`HubConfig::min_severity`], judged on its effective severity.
+    fn meets_min_severity(&self, event: &Event) -> bool {
+        let Some(min_severity) = self.min_severity else {
+            return true;
+        };
+        let mut severity = event.severity;
+        if !event.has_explicit_severity() {
+            if let Some(&default) = self.kind_severity_defaults.get(&event.kind) {
+                severity = default;
+            }
+        }
+        severity >= min_severity
+    }
+
+    fn is_enabled(&self, event: &Event) -> bool {
+        self.is_kind_enabled(event.kind.as_str()) && self.meets_min_severity(event)
+    }
+
+    fn log_notify_outcomes(&self, event: &Event, outcomes: &[SinkOutcome]) {
+        for outcome in outcomes {
+            let err = match &outcome.result {
+                Ok(()) => {
+                    if let Some(failures) = self.failure_log.record_success(outcome.index) {
+                        tracing::info!(
+                            sink = outcome.name,
+                            failures,
+                            "sink recovered after consecutive notify failures"
+                        );
+                    }
+                    continue;
+                }
+                Err(err) => err,
+            };
+            match self.failure_log.record_failure(outcome.index) {
+                FailureLogDecision::Log { suppressed: 0 } => {
+                    tracing::warn!(sink = outcome.name, kind = %event.kind, "notify failed: {err:#}");
+                }
+                FailureLogDecision::Log { suppressed } => {
+                    tracing::warn!(
+                        sink = outcome.name,
+                        kind = %event.kind,
+                        suppressed,
+                        "notify failed: {err:#} ({suppressed} similar failures suppressed)"
+                    );
+                }
+                FailureLogDecision::Suppress => {}
+            }
+        }
+    }
+
+    fn apply_kind_severity_default(&self, event: &mut Event) {
+        if event.has_explicit_severity() {
+            return;
+        }
+        if let Some(&severity) = self.kind_severity_defaults.get(&event.kind) {
+            event.severity = severity;
+        }
+    }
+
+    fn claim_dedup(&self, event: &Event) -> DedupClaim {
+        let Some(dedup) = &self.dedup else {
+            return DedupClaim::Dispatch(None);
+        };
+        match dedup.claim(event) {
+            Some(key) => DedupClaim::Dispatch(Some(key)),
+            None => {
+                tracing::debug!(sink = "hub", kind = %event.kind, "duplicate event suppressed");
+                DedupClaim::Duplicate
+            }
+        }
+    }
+
+    /// Forgets the dedup key of an event no sink delivered, so retrying it is not suppressed.
+    fn release_dedup_if_undelivered(&self, key: Option<String>, outcomes: &[SinkOutcome]) {
+        if outcomes.iter().any(|outcome| outcome.result.is_ok()) {
+            return;
+        }
+        if let (Some(dedup), Some(key)) = (&self.dedup, key) {
+            dedup.release(key);
+        }
+    }
+}
+
+/// Result of checking an event against [`HubConfig::dedup`].
+enum DedupClaim {
+    Duplicate,
+    /// Dispatch the event; carries its dedup key when dedup is on.
+    Dispatch(Option<String>),
+}
+
+impl HubInner {
+    fn effective_timeout(&self, settings: &HubSettings) -> Duration {
+        if settings.per_sink_timeout_jitter.is_zero() {
+            return settings.per_sink_timeout;
+        }
+        let fraction = (self.jitter_rng)().clamp(0.0, 1.0);
+        settings
+            .per_sink_timeout
+            .saturating_add(settings.per_sink_timeout_jitter.mul_f64(fraction))
+    }
+
+    fn record_dropped(inner: &Arc<Self>, settings: &Arc<HubSettings>) {
+        let Some(self_notify) = &settings.self_notify else {
+            return;
+        };
+        if let Some(event) = self_notify.record_dropped() {
+            Self::spawn_meta(inner, settings, event);
+        }
+    }
+
+    fn record_outcome(inner: &Arc<Self>, settings: &Arc<HubSettings>, ok: bool) {
+        let Some(self_notify) = &settings.self_notify else {
+            return;
+        };
+        if let Some(event) = self_notify.record_outcome(ok) {
+            Self::spawn_meta(inner, settings, event);
+        }
+    }
+
+    fn spawn_meta(inner: &Arc<Self>, settings: &Arc<HubSettings>, mut event: Event) {
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            tracing::warn!(sink = "hub", kind = %event.kind, "meta event dropped: no tokio runtime");
+            return;
+        };
+        auto_tags::apply(&settings.auto_tags, &mut event);
+        let inner = inner.clone();
+        let settings = settings.clone();
+        handle.spawn(async move {
+            inner.send_meta(&settings, &event).await;
+        });
+    }
+
+    async fn send_meta(&self, settings: &HubSettings, event: &Event) {
+        let meta_sink = settings
+            .self_notify
+            .as_ref()
+            .and_then(|self_notify| self_notify.config.meta_sink.as_deref());
+        let labeled = meta_sink.and_then(|name| self.names.get(name).copied());
+        let sends = self
+            .sinks
+            .iter()
+            .enumerate()
+            .filter(|(idx, hub_sink)| match (labeled, meta_sink) {
+                (Some(labeled), _) => *idx == labeled,
+                (None, Some(name)) => hub_sink.name == Some(name),
+                (None, None) => true,
+            })
+            .map(|(idx, hub_sink)| {
+                Self::send_one_sink(self.effective_timeout(settings), idx, hub_sink, event)
+            });
+        for (_idx, name, result) in futures_util::future::join_all(sends).await {
+            if let Err(err) = result {
+                tracing::warn!(sink = name, kind = %event.kind, "meta event failed: {err}");
+            }
+        }
+    }
+
+    /// Sends the summary of `event` to [`HubConfig::mirror_summary_to`], if one is configured.
+    async fn send_mirror_summary(
+        &self,
+        settings: &HubSettings,
+        event: &Event,
+        delivered: usize,
+        failed: usize,
+    ) {
+        let Some(mirror) = &settings.mirror_summary_to else {
+            return;
+        };
+        let summary = summary_event(event, delivered, failed);
+        let (_idx, name, result) =
+            Self::send_one_sink(self.effective_timeout(settings), 0, mirror, &summary).await;
+        if let Err(err) = result {
+            tracing::warn!(sink = name, kind = %summary.kind, "summary mirror failed: {err:#}");
+        }
+    }
+
+    async fn send_one_sink(
+        timeout: Duration,
+        idx: usize,
+        sink: &HubSink,
+        event: &Event,
+    ) -> (usize, &'static str, crate::Result<()>) {
+        const UNKNOWN_SINK_NAME: &str = "<unknown>";
+
+        let Some(name) = sink.name else {
+            return (
+                idx,
+                UNKNOWN_SINK_NAME,
+                Err(anyhow::anyhow!("sink panicked").into()),
+            );
+        };
+        let result = AssertUnwindSafe(async move {
+            tokio::time::timeout(timeout, sink.sink.send(event))
+                .await
+                .unwrap_or_else(|_| {
+                    Err(crate::Error::timeout(anyhow::anyhow!(
+                        "timeout after {timeout:?}"
+                    )))
+                })
+        })
+        .catch_unwind()
+        .await
+        .unwrap_or_else(|_| Err(anyhow::anyhow!("sink panicked").into()));
+        (idx, name, result)
+    }
+
+    async fn send_detailed(&self, settings: &HubSettings, event: &Event) -> Vec<SinkOutcome> {
+        if self.sinks.is_empty() {
+            return Vec::new();
+        }
+
+        if self.sinks.len() == 1 {
+            if !self.sinks[0].accepts(event) {
+                return Vec::new();
+            }
+            let timeout = self.effective_timeout(settings);
+            let (index, name, result) =
+                Self::send_one_sink(timeout, 0, &self.sinks[0], event).await;
+            return vec![SinkOutcome {
+                name,
+                index,
+                result,
+            }];
+        }
+
+        let mut outcomes: Vec<SinkOutcome> = Vec::with_capacity(self.sinks.len());
+        let max_parallel = settings.max_sink_sends_in_parallel.max(1);
+        let mut sink_iter = self
+            .sinks
+            .iter()
+            .enumerate()
+            .filter(|(_, hub_sink)| hub_sink.accepts(event));
+
+        let mut pending = FuturesUnordered::new();
+        for _ in 0..max_parallel {
+            let Some((idx, hub_sink)) = sink_iter.next() else {
+                break;
+            };
+            pending.push(Self::send_one_sink(
+                self.effective_timeout(settings),
+                idx,
+                hub_sink,
+                event,
+            ));
+        }
+
+        while let Some((index, name, result)) = pending.next().await {
+            outcomes.push(SinkOutcome {
+                name,
+                index,
+                result,
+            });
+            if let Some((next_idx, next_hub_sink)) = sink_iter.next() {
+                pending.push(Self::send_one_sink(
+                    self.effective_timeout(settings),
+                    next_idx,
+                    next_hub_sink,
+                    event,
+                ));
+            }
+        }
+
+        outcomes.sort_unstable_by_key(|outcome| outcome.index);
+        outcomes
+    }
+
+    /// Also returns how many sinks failed before the result was decided.
+    async fn send_any(
+        &self,
+        settings: &HubSettings,
+        event: &Event,
+    ) -> (crate::Result<&'static str>, usize) {
+        let max_parallel = settings.max_sink_sends_in_parallel.max(1);
+        let mut sink_iter = self
+            .sinks
+            .iter()
+            .enumerate()
+            .filter(|(_, hub_sink)| hub_sink.accepts(event));
+
+        let mut pending = FuturesUnordered::new();
+        for _ in 0..max_parallel {
+            let Some((idx, hub_sink)) = sink_iter.next() else {
+                break;
+            };
+            pending.push(Self::send_one_sink(
+                self.effective_timeout(settings),
+                idx,
+                hub_sink,
+                event,
+            ));
+        }
+        if pending.is_empty() {
+            let err = anyhow::anyhow!("no sink accepts event kind `{}`", event.kind);
+            return (Err(err.into()), 0);
+        }
+
+        let mut failures = Vec::new();
+        while let Some((index, name, result)) = pending.next().await {
+            match result {
+                Ok(()) => return (Ok(name), failures.len()),
+                Err(err) => failures.push((index, name, err)),
+            }
+            if let Some((next_idx, next_hub_sink)) = sink_iter.next() {
+                pending.push(Self::send_one_sink(
+                    self.effective_timeout(settings),
+                    next_idx,
+                    next_hub_sink,
+                    event,
+                ));
+            }
+        }
+        let failed = failures.len();
+        (Err(Self::build_failures_error(failures)), failed)
+    }
+
+    fn aggregate_outcomes(
+        outcomes: Vec<SinkOutcome>,
+        error_when: SendErrorPolicy,
+    ) -> crate::Result<()> {
+        let total = outcomes.len();
+        let failures: Vec<(usize, &'static str, crate::Error)> = outcomes
+            .into_iter()
+            .filter_map(|outcome| {
+                let err = outcome.result.err()?;
+                Some((outcome.index, outcome.name, err))
+            })
+            .collect();
+        if !error_when.is_error(failures.len(), total - failures.len()) {
+            return Ok(());
+        }
+
+        Err(Self::build_failures_error(failures))
+    }
+
+    fn build_failures_error(
+        mut failures: Vec<(usize, &'static str, crate::Error)>,
+    ) -> crate::Error {
+        if failures.len() > 1 {
+            failures.sort_unstable_by_key(|(idx, _, _)| *idx);
+        }
+        // The aggregate keeps the failures' kind when they all agree (e.g. every sink timed out).
+        let mut kinds = failures.iter().map(|(_, _, err)| err.kind());
+        let first = kinds.next().unwrap_or(ErrorKind::Other);
+        let kind = if kinds.all(|kind| kind == first) {
+            first
+        } else {
+            ErrorKind::Other
+        };
+        let mut msg = String::with_capacity(24 + failures.len().saturating_mul(64));
+        msg.push_str("one or more sinks failed:");
+        for (_idx, name, err) in failures {
+            msg.push('\n');
+            msg.push_str("- ");
+            msg.push_str(name);
+            msg.push_str(": ");
+            if write!(&mut msg, "{err:#}").is_err() {
+                return anyhow::anyhow!("failed to format sink error").into();
+            }
+        }
+        crate::Error::with_kind(kind, anyhow::anyhow!(msg))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use super::*;
+    use crate::event::Severity;
+    use crate::sinks::{BoxFuture, Sink};
+
+    #[derive(Debug)]
+    struct TestSink {
+        name: &'static str,
+        behavior: TestSinkBehavior,
+    }
+
+    #[derive(Debug, Clone, Copy)]
+    enum TestSinkBehavior {
+        Ok,
+        Err,
+        Sleep(Duration),
+        PanicName,
+        Panic,
+    }
+
+    impl Sink for TestSink {
+        fn name(&self) -> &'static str {
+            if matches!(self.behavior, TestSinkBehavior::PanicName) {
+                panic!("name boom");
+            }
+            self.name
+        }
+
+        fn send<'a>(&'a self, _event: &'a Event) -> BoxFuture<'a, crate::Result<()>> {
+            Box::pin(async move {
+                match self.behavior {
+                    TestSinkBehavior::Ok => Ok(()),
+                    TestSinkBehavior::Err => Err(anyhow::anyhow!("boom").into()),
+                    TestSinkBehavior::Sleep(d) => {
+                        tokio::time::sleep(d).await;
+                        Ok(())
+                    }
+                    TestSinkBehavior::PanicName => Ok(()),
+                    TestSinkBehavior::Panic => panic!("boom"),
+                }
+            })
+        }
+    }
+
+    fn kinds(names: &[&str]) -> Option<BTreeSet<String>> {
+        Some(names.iter().map(|name| name.to_string()).collect())
+    }
+
+    #[test]
+    fn merge_prefers_fields_set_in_override() {
+        let base = HubConfig {
+            enabled_kinds: kinds(&["a"]),
+            min_severity: Some(Severity::Warning),
+            per_sink_timeout: Duration::from_secs(10),
+            max_sink_sends_in_parallel: 4,
+            failure_log_sampling: Some(FailureLogSampling::default()),
+            auto_tags: AutoTags {
+                hostname: true,
+                pid: false,
+                app_name: Some("base".to_string()),
+            },
+            ..HubConfig::default()
+        };
+        let over = HubConfig {
+            per_sink_timeout_jitter: Duration::from_millis(200),
+            max_sink_sends_in_parallel: 1,
+            self_notify: Some(SelfNotifyConfig::default()),
+            auto_tags: AutoTags {
+                hostname: false,
+                pid: true,
+                app_name: Some("over".to_string()),
+            },
+            ..HubConfig::default()
+        };
+
+        let merged = base.merge(over);
+        assert_eq!(merged.enabled_kinds, kinds(&["a"]));
+        assert_eq!(merged.min_severity, Some(Severity::Warning));
+        // Unset (default) in the override: the base value is kept.
+        assert_eq!(merged.per_sink_timeout, Duration::from_secs(10));
+        assert_eq!(
+            merged.failure_log_sampling,
+            Some(FailureLogSampling::default())
+        );
+        assert_eq!(merged.per_sink_timeout_jitter, Duration::from_millis(200));
+        assert_eq!(merged.max_sink_sends_in_parallel, 1);
+        assert!(merged.self_notify.is_some());
+        assert!(merged.dedup.is_none());
+        assert_eq!(
+            merged.auto_tags,
+            AutoTags {
+                hostname: true,
+                pid: true,
+                app_name: Some("over".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn merge_enabled_kinds_replace_or_union() {
+        let base = || HubConfig {
+            enabled_kinds: kinds(&["a", "b"]),
+            ..HubConfig::default()
+        };
+        let over = || HubConfig {
+            enabled_kinds: kinds(&["c"]),
+            ..HubConfig::default()
+        };
+
+        assert_eq!(base().merge(over()).enabled_kinds, kinds(&["c"]));
+        assert_eq!(
+            base()
+                .merge_with(over(), EnabledKindsMerge::Union)
+                .enabled_kinds,
+            kinds(&["a", "b", "c"])
+        );
+        for mode in [EnabledKindsMerge::Replace, EnabledKindsMerge::Union] {
+            assert_eq!(
+                base().merge_with(HubConfig::default(), mode).enabled_kinds,
+                kinds(&["a", "b"])
+            );
+            assert_eq!(
+                HubConfig::default().merge_with(over(), mode).enabled_kinds,
+                kinds(&["c"])
+            );
+        }
+    }
+
+    #[test]
+    fn try_notify_errors_without_tokio_runtime() {
+        let sinks: Vec<Arc<dyn Sink>> = vec![Arc::new(TestSink {
+            name: "ok",
+            behavior: TestSinkBehavior::Ok,
+        })];
+        let hub = Hub::new(HubConfig::default(), sinks);
+        let event = Event::new("kind", Severity::Info, "title");
+        assert_eq!(hub.try_notify(event), Err(TryNotifyError::NoTokioRuntime));
+    }
+
+    #[test]
+    fn send_blocking_delivers_without_tokio_runtime() {
+        let sinks: Vec<Arc<dyn Sink>> = vec![Arc::new(TestSink {
+            name: "ok",
+            behavior: TestSinkBehavior::Sleep(Duration::from_millis(5)),
+        })];
+        let hub = Hub::new(HubConfig::default(), sinks);
+        hub.send_blocking(Event::new("kind", Severity::Info, "title"))
+            .expect("send_blocking");
+
+        let sinks: Vec<Arc<dyn Sink>> = vec![Arc::new(TestSink {
+            name: "err",
+            behavior: TestSinkBehavior::Err,
+        })];
+        let hub = Hub::new(HubConfig::default(), sinks);
+        let err = hub
+            .send_blocking(Event::new("kind", Severity::Info, "title"))
+            .expect_err("sink error is returned");
+        assert!(err.to_string().contains("err"), "{err:#}");
+    }
+
+    #[tokio::test]
+    async fn send_blocking_rejects_inside_tokio_runtime() {
+        let sinks: Vec<Arc<dyn Sink>> = vec![Arc::new(TestSink {
+            name: "ok",
+            behavior: TestSinkBehavior::Ok,
+        })];
+        let hub = Hub::new(HubConfig::default(), sinks);
+        let err = hub
+            .send_blocking(Event::new("kind", Severity::Info, "title"))
+            .expect_err("inside runtime");
+        assert!(
+            err.to_string().contains("inside a tokio runtime"),
+            "{err:#}"
+        );
+    }
+
+    #[test]
+    fn try_notify_is_noop_without_tokio_runtime_when_no_sinks() {
+        let hub = Hub::new(HubConfig::default(), Vec::new());
+        let event = Event::new("kind", Severity::Info, "title");
+        assert_eq!(hub.try_notify(event), Ok(()));
+    }
+
+    #[test]
+    fn try_notify_is_noop_when_kind_disabled_even_without_runtime() {
+        let mut enabled_kinds = BTreeSet::new();
+        enabled_kinds.insert("enabled".to_string());
+
+        let hub = Hub::new(
+            HubConfig {
+                enabled_kinds: Some(enabled_kinds),
+                per_sink_timeout: Duration::from_secs(1),
+                ..HubConfig::default()
+            },
+            Vec::new(),
+        );
+
+        let event = Event::new("disabled", Severity::Info, "title");
+        assert_eq!(hub.try_notify(event), Ok(()));
+    }
+
+    #[test]
+    fn send_is_noop_without_tokio_runtime_when_no_sinks() {
+        let hub = Hub::new(HubConfig::default(), Vec::new());
+        let event = Event::new("kind", Severity::Info, "title");
+
+        let out = hub
+            .send(event)
+            .now_or_never()
+            .expect("send should complete immediately without sinks");
+        assert!(out.is_ok(), "{out:#?}");
+    }
+
+    #[test]
+    fn send_aggregates_sink_failures() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime");
+
+        rt.block_on(async {
+            let sinks: Vec<Arc<dyn Sink>> = vec![
+                Arc::new(TestSink {
+                    name: "ok",
+                    behavior: TestSinkBehavior::Ok,
+                }),
+                Arc::new(TestSink {
+                    name: "bad",
+                    behavior: TestSinkBehavior::Err,
+                }),
+            ];
+
+            let hub = Hub::new(
+                HubConfig {
+                    enabled_kinds: None,
+                    per_sink_timeout: Duration::from_secs(1),
+                    ..HubConfig::default()
+                },
+                sinks,
+            );
+            let event = Event::new("kind", Severity::Info, "title");
+
+            let err = hub.send(event).await.expect_err("expected sink failure");
+            let msg = err.to_string();
+            assert!(msg.contains("one or more sinks failed:"), "{msg}");
+            assert!(msg.contains("- bad: boom"), "{msg}");
+        });
+    }
+
+    #[test]
+    fn send_times_out_slow_sinks() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime");
+
+        rt.block_on(async {
+            let sinks: Vec<Arc<dyn Sink>> = vec![Arc::new(TestSink {
+                name: "slow",
+                behavior: TestSinkBehavior::Sleep(Duration::from_millis(50)),
+            })];
+
+            let hub = Hub::new(
+                HubConfig {
+                    enabled_kinds: None,
+                    per_sink_timeout: Duration::from_millis(5),
+                    ..HubConfig::default()
+                },
+                sinks,
+            );
+            let event = Event::new("kind", Severity::Info, "title");
+
+            let err = hub.send(event).await.expect_err("expected timeout");
+            let msg = err.to_string();
+            assert!(msg.contains("timeout after"), "{msg}");
+        });
+    }
+
+    #[test]
+    fn timeouts_map_to_timeout_kind() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime");
+
+        rt.block_on(async {
+            let slow = || -> Arc<dyn Sink> {
+                Arc::new(TestSink {
+                    name: "slow",
+                    behavior: TestSinkBehavior::Sleep(Duration::from_millis(50)),
+                })
+            };
+            let config = HubConfig {
+                enabled_kinds: None,
+                per_sink_timeout: Duration::from_millis(5),
+                ..HubConfig::default()
+            };
+
+            let hub = Hub::new(config.clone(), vec![slow(), slow()]);
+            let outcomes = hub
+                .send_detailed(Event::new("kind", Severity::Info, "title"))
+                .await
+                .expect("send detailed");
+            for outcome in &outcomes {
+                let err = outcome.result.as_ref().expect_err("expected timeout");
+                assert_eq!(err.kind(), ErrorKind::Timeout, "{err}");
+            }
+            let err = hub
+                .send(Event::new("kind", Severity::Info, "title"))
+                .await
+                .expect_err("expected timeout");
+            assert_eq!(err.kind(), ErrorKind::Timeout, "{err}");
+
+            let hub = Hub::new(
+                config,
+                vec![
+                    slow(),
+                    Arc::new(TestSink {
+                        name: "bad",
+                        behavior: TestSinkBehavior::Err,
+                    }),
+                ],
+            );
+            let err = hub
+                .send(Event::new("kind", Severity::Info, "title"))
+                .await
+                .expect_err("expected failures");
+            assert_eq!(err.kind(), ErrorKind::Other, "mixed kinds: {err}");
+        });
+    }
+
+    #[test]
+    fn try_notify_drops_when_overloaded() {
+        #[derive(Debug)]
+        struct CountingSink {
+            counter: Arc<AtomicUsize>,
+            sleep: Duration,
+        }
+
+        impl Sink for CountingSink {
+            fn name(&self) -> &'static str {
+                "counting"
+            }
+
+            fn send<'a>(&'a self, _event: &'a Event) -> BoxFuture<'a, crate::Result<()>> {
+                Box::pin(async move {
+                    self.counter.fetch_add(1, Ordering::SeqCst);
+                    tokio::time::sleep(self.sleep).await;
+                    Ok(())
+                })
+            }
+        }
+
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime");
+
+        rt.block_on(async {
+            let counter = Arc::new(AtomicUsize::new(0));
+            let sinks: Vec<Arc<dyn Sink>> = vec![Arc::new(CountingSink {
+                counter: counter.clone(),
+                sleep: Duration::from_millis(50),
+            })];
+
+            let hub = Hub::new_with_inflight_limit(
+                HubConfig {
+                    enabled_kinds: None,
+                    per_sink_timeout: Duration::from_secs(1),
+                    ..HubConfig::default()
+                },
+                sinks,
+                1,
+            );
+
+            hub.try_notify(Event::new("kind", Severity::Info, "t1"))
+                .expect("first notify ok");
+            assert_eq!(
+                hub.try_notify(Event::new("kind", Severity::Info, "t2")),
+                Err(TryNotifyError::Overloaded)
+            );
+
+            tokio::time::sleep(Duration::from_millis(80)).await;
+            assert_eq!(counter.load(Ordering::SeqCst), 1);
+        });
+    }
+
+    #[test]
+    fn send_includes_sink_name_on_panic() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime");
+
+        rt.block_on(async {
+            let sinks: Vec<Arc<dyn Sink>> = vec![Arc::new(TestSink {
+                name: "panic",
+                behavior: TestSinkBehavior::Panic,
+            })];
+
+            let hub = Hub::new(
+                HubConfig {
+                    enabled_kinds: None,
+                    per_sink_timeout: Duration::from_secs(1),
+                    ..HubConfig::default()
+                },
+                sinks,
+            );
+            let event = Event::new("kind", Severity::Info, "title");
+
+            let err = hub.send(event).await.expect_err("expected panic failure");
+            let msg = err.to_string();
+            assert!(msg.contains("- panic:"), "{msg}");
+        });
+    }
+
+    #[test]
+    fn send_handles_sink_name_panic() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime");
+
+        rt.block_on(async {
+            let sinks: Vec<Arc<dyn Sink>> = vec![Arc::new(TestSink {
+                name: "ignored",
+                behavior: TestSinkBehavior::PanicName,
+            })];
+
+            let hub = Hub::new(
+                HubConfig {
+                    enabled_kinds: None,
+                    per_sink_timeout: Duration::from_secs(1),
+                    ..HubConfig::default()
+                },
+                sinks,
+            );
+            let event = Event::new("kind", Severity::Info, "title");
+
+            let err = hub.send(event).await.expect_err("expected panic failure");
+            let msg = err.to_string();
+            assert!(msg.contains("- <unknown>: sink panicked"), "{msg}");
+        });
+    }
+
+    #[test]
+    fn send_reports_failures_in_sink_order() {
+        #[derive(Debug)]
+        struct DelayedFailSink {
+            name: &'static str,
+            sleep: Duration,
+        }
+
+        impl Sink for DelayedFailSink {
+            fn name(&self) -> &'static str {
+                self.name
+            }
+
+            fn send<'a>(&'a self, _event: &'a Event) -> BoxFuture<'a, crate::Result<()>> {
+                Box::pin(async move {
+                    tokio::time::sleep(self.sleep).await;
+                    Err(anyhow::anyhow!("boom").into())
+                })
+            }
+        }
+
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime");
+
+        rt.block_on(async {
+            let sinks: Vec<Arc<dyn Sink>> = vec![
+                Arc::new(DelayedFailSink {
+                    name: "first",
+                    sleep: Duration::from_millis(40),
+                }),
+                Arc::new(DelayedFailSink {
+                    name: "second",
+                    sleep: Duration::from_millis(1),
+                }),
+            ];
+            let hub = Hub::new(
+                HubConfig {
+                    enabled_kinds: None,
+                    per_sink_timeout: Duration::from_secs(1),
+                    ..HubConfig::default()
+                },
+                sinks,
+            );
+            let event = Event::new("kind", Severity::Info, "title");
+
+            let err = hub.send(event).await.expect_err("expected sink failure");
+            let msg = err.to_string();
+            let first = msg.find("- first:").expect("contains first");
+            let second = msg.find("- second:").expect("contains second");
+            assert!(first < second, "{msg}");
+        });
+    }
+
+    /// Records every event it receives. Shared by the meta-event, mirror, routing and tagging
+    /// tests; use [`RecordingSink::named`] where the hub reports or looks up the sink by name.
+    #[derive(Debug)]
+    struct RecordingSink {
+        name: &'static str,
+        events: Mutex<Vec<Event>>,
+        /// Signalled once per recorded event.
+        recorded: tokio::sync::Notify,
+    }
+
+    impl Default for RecordingSink {
+        fn default() -> Self {
+            Self::named("recording")
+        }
+    }
+
+    impl RecordingSink {
+        fn named(name: &'static str) -> Self {
+            Self {
+                name,
+                events: Mutex::new(Vec::new()),
+                recorded: tokio::sync::Notify::new(),
+            }
+        }
+
+        fn events(&self) -> Vec<Event> {
+            self.events.lock().expect("lock events").clone()
+        }
+
+        fn count_kind(&self, kind: &str) -> usize {
+            self.events
+                .lock()
+                .expect("lock events")
+                .iter()
+                .filter(|event| event.kind == kind)
+                .count()
+        }
+    }
+
+    impl Sink for RecordingSink {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        fn send<'a>(&'a self, event: &'a Event) -> BoxFuture<'a, crate::Result<()>> {
+            Box::pin(async move {
+                self.events.lock().expect("lock events").push(event.clone());
+                self.recorded.notify_one();
+                Ok(())
+            })
+        }
+    }
+
+    #[test]
+    fn sustained_overload_emits_one_meta_event_per_window() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime");
+
+        rt.block_on(async {
+            let meta = Arc::new(RecordingSink::named("meta"));
+            let sinks: Vec<Arc<dyn Sink>> = vec![
+                Arc::new(TestSink {
+                    name: "slow",
+                    behavior: TestSinkBehavior::Sleep(Duration::from_millis(50)),
+                }),
+                meta.clone(),
+            ];
+            let hub = Hub::new_with_inflight_limit(
+                HubConfig {
+                    enabled_kinds: Some(BTreeSet::from(["kind".to_string()])),
+                    self_notify: Some(
+                        SelfNotifyConfig::new()
+                            .with_meta_sink("meta")
+                            .with_overload_threshold(3)
+                            .with_window(Duration::from_secs(60)),
+                    ),
+                    ..HubConfig::default()
+                },
+                sinks,
+                1,
+            );
+
+            hub.try_notify(Event::new("kind", Severity::Info, "t0"))
+                .expect("first notify ok");
+            for i in 0..20 {
+                assert_eq!(
+                    hub.try_notify(Event::new("kind", Severity::Info, format!("t{i}"))),
+                    Err(TryNotifyError::Overloaded)
+                );
+            }
+
+            tokio::time::sleep(Duration::from_millis(80)).await;
+            assert_eq!(meta.count_kind("notify_kit.hub"), 1);
+            assert_eq!(meta.count_kind("kind"), 1);
+        });
+    }
+
+    #[test]
+    fn repeated_sink_failures_emit_meta_event() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime");
+
+        rt.block_on(async {
+            let meta = Arc::new(RecordingSink::named("meta"));
+            let sinks: Vec<Arc<dyn Sink>> = vec![
+                Arc::new(TestSink {
+                    name: "bad",
+                    behavior: TestSinkBehavior::Err,
+                }),
+                meta.clone(),
+            ];
+            let hub = Hub::new(
+                HubConfig {
+                    self_notify: Some(
+                        SelfNotifyConfig::new()
+                            .with_meta_sink("meta")
+                            .with_failure_threshold(2),
+                    ),
+                    ..HubConfig::default()
+                },
+                sinks,
+            );
+
+            for _ in 0..5 {
+                hub.send(Event::new("kind", Severity::Info, "title"))
+                    .await
+                    .expect_err("expected sink failure");
+            }
+
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            assert_eq!(meta.count_kind("notify_kit.hub"), 1);
+            assert_eq!(meta.count_kind("kind"), 5);
+        });
+    }
+
+    #[test]
+    fn meta_sink_resolves_add_named_labels_before_sink_names() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime");
+
+        rt.block_on(async {
+            let ops = Arc::new(RecordingSink::default());
+            let audit = Arc::new(RecordingSink::default());
+            let hub = Hub::builder()
+                .config(HubConfig {
+                    self_notify: Some(
+                        SelfNotifyConfig::new()
+                            .with_meta_sink("ops")
+                            .with_failure_threshold(2),
+                    ),
+                    ..HubConfig::default()
+                })
+                .add_sink(Arc::new(TestSink {
+                    name: "bad",
+                    behavior: TestSinkBehavior::Err,
+                }))
+                .add_named("audit", audit.clone())
+                .add_named("ops", ops.clone())
+                .build();
+
+            for _ in 0..2 {
+                hub.send(Event::new("kind", Severity::Info, "title"))
+                    .await
+                    .expect_err("expected sink failure");
+            }
+
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            assert_eq!(ops.count_kind("notify_kit.hub"), 1);
+            assert_eq!(audit.count_kind("notify_kit.hub"), 0);
+            assert_eq!(audit.count_kind("kind"), 2);
+        });
+    }
+
+    #[test]
+    fn self_notify_is_disabled_by_default() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime");
+
+        rt.block_on(async {
+            let meta = Arc::new(RecordingSink::named("meta"));
+            let sinks: Vec<Arc<dyn Sink>> = vec![
+                Arc::new(TestSink {
+                    name: "bad",
+                    behavior: TestSinkBehavior::Err,
+                }),
+                meta.clone(),
+            ];
+            let hub = Hub::new(HubConfig::default(), sinks);
+
+            for _ in 0..10 {
+                let _ = hub.send(Event::new("kind", Severity::Info, "title")).await;
+            }
+
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            assert_eq!(meta.count_kind("notify_kit.hub"), 0);
+        });
+    }
+
+    #[test]
+    fn routes_deliver_only_to_sinks_whose_filter_accepts() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime");
+
+        rt.block_on(async {
+            let chat = Arc::new(RecordingSink::default());
+            let pager = Arc::new(RecordingSink::default());
+            let deploys = Arc::new(RecordingSink::default());
+            let hub = Hub::new_with_routes(
+                HubConfig::default(),
+                vec![
+                    (chat.clone() as Arc<dyn Sink>, SinkFilter::new()),
+                    (
+                        pager.clone() as Arc<dyn Sink>,
+                        SinkFilter::new().with_min_severity(Severity::Error),
+                    ),
+                    (
+                        deploys.clone() as Arc<dyn Sink>,
+                        SinkFilter::new().with_kinds(["deploy"]),
+                    ),
+                ],
+            );
+
+            hub.send(Event::new("info", Severity::Info, "low"))
+                .await
+                .expect("send info");
+            hub.send(Event::new("deploy", Severity::Warning, "deploy"))
+                .await
+                .expect("send deploy");
+            hub.send(Event::new("crash", Severity::Error, "high"))
+                .await
+                .expect("send error");
+
+            let kinds = |sink: &RecordingSink| {
+                sink.events()
+                    .into_iter()
+                    .map(|event| event.kind)
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(chat.events().len(), 3);
+            assert_eq!(kinds(&pager), ["crash"]);
+            assert_eq!(kinds(&deploys), ["deploy"]);
+        });
+    }
+
+    #[test]
+    fn routes_skip_single_sink_when_filter_rejects() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime");
+
+        rt.block_on(async {
+            let hub = Hub::new_with_routes(
+                HubConfig::default(),
+                vec![(
+                    Arc::new(TestSink {
+                        name: "bad",
+                        behavior: TestSinkBehavior::Err,
+                    }) as Arc<dyn Sink>,
+                    SinkFilter::new().with_min_severity(Severity::Warning),
+                )],
+            );
+
+            hub.send(Event::new("kind", Severity::Info, "low"))
+                .await
+                .expect("filtered sink is not called");
+            hub.send(Event::new("kind", Severity::Warning, "high"))
+                .await
+                .expect_err("accepted sink fails");
+        });
+    }
+
+    fn hub_with_jitter_rng(config: HubConfig, sinks: Vec<Arc<dyn Sink>>, rng: JitterRng) -> Hub {
+        let mut hub = Hub::new(config, sinks);
+        Arc::get_mut(&mut hub.inner)
+            .expect("hub not shared yet")
+            .jitter_rng = rng;
+        hub
+    }
+
+    #[test]
+    fn effective_timeout_stays_within_jitter_bounds() {
+        let timeout = Duration::from_millis(100);
+        let jitter = Duration::from_millis(20);
+        let config = HubConfig {
+            per_sink_timeout: timeout,
+            per_sink_timeout_jitter: jitter,
+            ..HubConfig::default()
+        };
+
+        let low = hub_with_jitter_rng(config.clone(), Vec::new(), || 0.0);
+        assert_eq!(
+            low.inner.effective_timeout(&low.inner.settings.load()),
+            timeout
+        );
+        let high = hub_with_jitter_rng(config.clone(), Vec::new(), || 1.0);
+        assert_eq!(
+            high.inner.effective_timeout(&high.inner.settings.load()),
+            timeout + jitter
+        );
+        let mid = hub_with_jitter_rng(config.clone(), Vec::new(), || 0.5);
+        assert_eq!(
+            mid.inner.effective_timeout(&mid.inner.settings.load()),
+            timeout + Duration::from_millis(10)
+        );
+        let out_of_range = hub_with_jitter_rng(config.clone(), Vec::new(), || 7.0);
+        assert_eq!(
+            out_of_range
+                .inner
+                .effective_timeout(&out_of_range.inner.settings.load()),
+            timeout + jitter
+        );
+
+        let random = Hub::new(config, Vec::new());
+        for _ in 0..1000 {
+            let effective = random
+                .inner
+                .effective_timeout(&random.inner.settings.load());
+            assert!(
+                effective >= timeout && effective <= timeout + jitter,
+                "{effective:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn effective_timeout_without_jitter_is_per_sink_timeout() {
+        let hub = hub_with_jitter_rng(HubConfig::default(), Vec::new(), || 1.0);
+        assert_eq!(
+            hub.inner.effective_timeout(&hub.inner.settings.load()),
+            Duration::from_secs(5)
+        );
+    }
+
+    #[test]
+    fn send_applies_timeout_jitter() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime");
+
+        rt.block_on(async {
+            let sinks: Vec<Arc<dyn Sink>> = vec![Arc::new(TestSink {
+                name: "slow",
+                behavior: TestSinkBehavior::Sleep(Duration::from_millis(30)),
+            })];
+            let hub = hub_with_jitter_rng(
+                HubConfig {
+                    per_sink_timeout: Duration::from_millis(5),
+                    per_sink_timeout_jitter: Duration::from_millis(500),
+                    ..HubConfig::default()
+                },
+                sinks,
+                || 1.0,
+            );
+
+            hub.send(Event::new("kind", Severity::Info, "title"))
+                .await
+                .expect("jitter extends the timeout");
+        });
+    }
+
+    #[test]
+    fn send_detailed_reports_outcomes_in_sink_order() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime");
+
+        rt.block_on(async {
+            let sinks: Vec<Arc<dyn Sink>> = vec![
+                Arc::new(TestSink {
+                    name: "slow",
+                    behavior: TestSinkBehavior::Sleep(Duration::from_millis(200)),
+                }),
+                Arc::new(TestSink {
+                    name: "ok",
+                    behavior: TestSinkBehavior::Ok,
+                }),
+                Arc::new(TestSink {
+                    name: "bad",
+                    behavior: TestSinkBehavior::Err,
+                }),
+            ];
+            let hub = Hub::new(
+                HubConfig {
+                    per_sink_timeout: Duration::from_millis(20),
+                    ..HubConfig::default()
+                },
+                sinks,
+            );
+
+            let outcomes = hub
+                .send_detailed(Event::new("kind", Severity::Info, "title"))
+                .await
+                .expect("hub-level send ok");
+
+            let summary: Vec<(usize, &str, bool)> = outcomes
+                .iter()
+                .map(|outcome| (outcome.index, outcome.name, outcome.result.is_ok()))
+                .collect();
+            assert_eq!(
+                summary,
+                vec![(0, "slow", false), (1, "ok", true), (2, "bad", false)]
+            );
+            let timeout = outcomes[0]
+                .result
+                .as_ref()
+                .expect_err("timeout")
+                .to_string();
+            assert!(timeout.contains("timeout after"), "{timeout}");
+            let boom = outcomes[2].result.as_ref().expect_err("boom").to_string();
+            assert_eq!(boom, "boom");
+        });
+    }
+
+    #[test]
+    fn send_detailed_skips_filtered_and_disabled() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime");
+
+        rt.block_on(async {
+            let hub = Hub::new_with_routes(
+                HubConfig {
+                    enabled_kinds: Some(BTreeSet::from(["kind".to_string()])),
+                    ..HubConfig::default()
+                },
+                vec![
+                    (
+                        Arc::new(TestSink {
+                            name: "pager",
+                            behavior: TestSinkBehavior::Ok,
+                        }) as Arc<dyn Sink>,
+                        SinkFilter::new().with_min_severity(Severity::Error),
+                    ),
+                    (
+                        Arc::new(TestSink {
+                            name: "chat",
+                            behavior: TestSinkBehavior::Ok,
+                        }) as Arc<dyn Sink>,
+                        SinkFilter::new(),
+                    ),
+                ],
+            );
+
+            let outcomes = hub
+                .send_detailed(Event::new("kind", Severity::Info, "title"))
+                .await
+                .expect("send ok");
+            assert_eq!(outcomes.len(), 1);
+            assert_eq!((outcomes[0].index, outcomes[0].name), (1, "chat"));
+
+            let outcomes = hub
+                .send_detailed(Event::new("other", Severity::Error, "title"))
+                .await
+                .expect("send ok");
+            assert!(outcomes.is_empty());
+        });
+    }
+
+    #[derive(Debug)]
+    struct NamedCountingSink {
+        name: &'static str,
+        calls: AtomicUsize,
+    }
+
+    impl NamedCountingSink {
+        fn new(name: &'static str) -> Arc<Self> {
+            Arc::new(Self {
+                name,
+                calls: AtomicUsize::new(0),
+            })
+        }
+    }
+
+    impl Sink for NamedCountingSink {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        fn send<'a>(&'a self, _event: &'a Event) -> BoxFuture<'a, crate::Result<()>> {
+            Box::pin(async move {
+                self.calls.fetch_add(1, Ordering::SeqCst);
+                Ok(())
+            })
+        }
+    }
+
+    #[test]
+    fn send_to_targets_only_the_named_sink() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime");
+
+        rt.block_on(async {
+            let oncall = NamedCountingSink::new("pager");
+            let chat = NamedCountingSink::new("slack");
+            let hub = Hub::builder()
+                .add_named("oncall", oncall.clone())
+                .add_sink(chat.clone())
+                .build();
+
+            hub.send_to("oncall", Event::new("kind", Severity::Error, "page"))
+                .await
+                .expect("send_to hit");
+            assert_eq!(oncall.calls.load(Ordering::SeqCst), 1);
+            assert_eq!(chat.calls.load(Ordering::SeqCst), 0);
+
+            hub.send(Event::new("kind", Severity::Info, "broadcast"))
+                .await
+                .expect("broadcast");
+            assert_eq!(oncall.calls.load(Ordering::SeqCst), 2);
+            assert_eq!(chat.calls.load(Ordering::SeqCst), 1);
+        });
+    }
+
+    #[test]
+    fn send_to_unknown_name_returns_distinct_error() {
+        let hub = Hub::builder()
+            .add_named("oncall", NamedCountingSink::new("pager"))
+            .build();
+
+        let err = hub
+            .send_to("missing", Event::new("kind", Severity::Info, "title"))
+            .now_or_never()
+            .expect("unknown name fails without awaiting")
+            .expect_err("expected unknown sink");
+        assert_eq!(
+            err.downcast_ref::<UnknownSinkError>(),
+            Some(&UnknownSinkError {
+                name: "missing".to_string()
+            })
+        );
+        assert_eq!(err.to_string(), "unknown sink name: missing");
+    }
+
+    #[test]
+    fn duplicate_names_keep_first_registration() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime");
+
+        rt.block_on(async {
+            let first = NamedCountingSink::new("first");
+            let second = NamedCountingSink::new("second");
+            let hub = Hub::builder()
+                .add_named("dup", first.clone())
+                .add_named("dup", second.clone())
+                .build();
+
+            hub.send_to("dup", Event::new("kind", Severity::Info, "title"))
+                .await
+                .expect("send_to dup");
+            assert_eq!(first.calls.load(Ordering::SeqCst), 1);
+            assert_eq!(second.calls.load(Ordering::SeqCst), 0);
+
+            hub.send(Event::new("kind", Severity::Info, "title"))
+                .await
+                .expect("broadcast");
+            assert_eq!(second.calls.load(Ordering::SeqCst), 1);
+        });
+    }
+
+    #[test]
+    fn send_to_reports_sink_failure() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime");
+
+        rt.block_on(async {
+            let hub = Hub::builder()
+                .add_named(
+                    "bad",
+                    Arc::new(TestSink {
+                        name: "bad",
+                        behavior: TestSinkBehavior::Err,
+                    }),
+                )
+                .build();
+
+            let err = hub
+                .send_to("bad", Event::new("kind", Severity::Info, "title"))
+                .await
+                .expect_err("expected sink failure");
+            assert!(err.downcast_ref::<UnknownSinkError>().is_none());
+            assert!(err.to_string().contains("- bad: boom"), "{err}");
+        });
+    }
+
+    #[test]
+    fn send_any_returns_first_sink_to_succeed() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime");
+
+        rt.block_on(async {
+            let sinks: Vec<Arc<dyn Sink>> = vec![
+                Arc::new(TestSink {
+                    name: "bad",
+                    behavior: TestSinkBehavior::Err,
+                }),
+                Arc::new(TestSink {
+                    name: "slow",
+                    behavior: TestSinkBehavior::Sleep(Duration::from_secs(60)),
+                }),
+                Arc::new(TestSink {
+                    name: "fast",
+                    behavior: TestSinkBehavior::Sleep(Duration::from_millis(5)),
+                }),
+            ];
+            let hub = Hub::new(
+                HubConfig {
+                    per_sink_timeout: Duration::from_secs(120),
+                    ..HubConfig::default()
+                },
+                sinks,
+            );
+
+            let started = tokio::time::Instant::now();
+            let name = hub
+                .send_any(Event::new("kind", Severity::Error, "page"))
+                .await
+                .expect("one sink delivers");
+            assert_eq!(name, "fast");
+            assert!(started.elapsed() < Duration::from_secs(10));
+        });
+    }
+
+    #[test]
+    fn send_any_fails_only_when_every_sink_fails() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime");
+
+        rt.block_on(async {
+            let sinks: Vec<Arc<dyn Sink>> = vec![
+                Arc::new(TestSink {
+                    name: "a",
+                    behavior: TestSinkBehavior::Err,
+                }),
+                Arc::new(TestSink {
+                    name: "b",
+                    behavior: TestSinkBehavior::Panic,
+                }),
+            ];
+            let hub = Hub::new(HubConfig::default(), sinks);
+
+            let err = hub
+                .send_any(Event::new("kind", Severity::Error, "page"))
+                .await
+                .expect_err("all sinks fail");
+            let msg = err.to_string();
+            assert!(msg.contains("- a: boom"), "{msg}");
+            assert!(msg.contains("- b: sink panicked"), "{msg}");
+
+            let hub = Hub::new(
+                HubConfig {
+                    enabled_kinds: kinds(&["other"]),
+                    ..HubConfig::default()
+                },
+                vec![Arc::new(TestSink {
+                    name: "ok",
+                    behavior: TestSinkBehavior::Ok,
+                })],
+            );
+            let err = hub
+                .send_any(Event::new("kind", Severity::Error, "page"))
+                .await
+                .expect_err("disabled kind is not delivered");
+            assert!(err.to_string().contains("not enabled"), "{err}");
+
+            let err = Hub::new(HubConfig::default(), Vec::new())
+                .send_any(Event::new("kind", Severity::Error, "page"))
+                .await
+                .expect_err("no sinks");
+            assert!(err.to_string().contains("no sink accepts"), "{err}");
+        });
+    }
+
+    #[derive(Debug, Default)]
+    struct ConcurrencyProbeSink {
+        current: AtomicUsize,
+        max_seen: AtomicUsize,
+    }
+
+    impl Sink for ConcurrencyProbeSink {
+        fn name(&self) -> &'static str {
+            "probe"
+        }
+
+        fn send<'a>(&'a self, _event: &'a Event) -> BoxFuture<'a, crate::Result<()>> {
+            Box::pin(async move {
+                let now = self.current.fetch_add(1, Ordering::SeqCst) + 1;
+                self.max_seen.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                self.current.fetch_sub(1, Ordering::SeqCst);
+                Ok(())
+            })
+        }
+    }
+
+    #[test]
+    fn builder_honors_max_sink_sends_in_parallel() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime");
+
+        rt.block_on(async {
+            for limit in [1usize, 2, 5] {
+                let probe = Arc::new(ConcurrencyProbeSink::default());
+                let mut builder = Hub::builder().max_sink_sends_in_parallel(limit);
+                for _ in 0..5 {
+                    builder = builder.add_sink(probe.clone());
+                }
+                let hub = builder.build();
+
+                hub.send(Event::new("kind", Severity::Info, "title"))
+                    .await
+                    .expect("send");
+                assert_eq!(probe.max_seen.load(Ordering::SeqCst), limit);
+            }
+        });
+    }
+
+    #[derive(Debug)]
+    struct SequenceSink {
+        id: usize,
+        log: Arc<Mutex<Vec<(usize, bool)>>>,
+    }
+
+    impl Sink for SequenceSink {
+        fn name(&self) -> &'static str {
+            "sequence"
+        }
+
+        fn send<'a>(&'a self, _event: &'a Event) -> BoxFuture<'a, crate::Result<()>> {
+            Box::pin(async move {
+                self.log.lock().expect("lock").push((self.id, true));
+                tokio::time::sleep(Duration::from_millis(5)).await;
+                self.log.lock().expect("lock").push((self.id, false));
+                Ok(())
+            })
+        }
+    }
+
+    #[test]
+    fn config_max_sink_sends_in_parallel_one_is_sequential_in_registration_order() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime");
+
+        rt.block_on(async {
+            let log = Arc::new(Mutex::new(Vec::new()));
+            let sinks: Vec<Arc<dyn Sink>> = (0..4)
+                .map(|id| {
+                    Arc::new(SequenceSink {
+                        id,
+                        log: log.clone(),
+                    }) as Arc<dyn Sink>
+                })
+                .collect();
+            let hub = Hub::new(
+                HubConfig {
+                    max_sink_sends_in_parallel: 1,
+                    ..HubConfig::default()
+                },
+                sinks,
+            );
+
+            hub.send(Event::new("kind", Severity::Info, "title"))
+                .await
+                .expect("send");
+
+            let expected: Vec<(usize, bool)> =
+                (0..4).flat_map(|id| [(id, true), (id, false)]).collect();
+            assert_eq!(*log.lock().expect("lock"), expected);
+        });
+    }
+
+    #[test]
+    fn config_max_sink_sends_in_parallel_bounds_elapsed_time() {
+        const SINKS: u32 = 6;
+        const DELAY: Duration = Duration::from_millis(30);
+
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .start_paused(true)
+            .build()
+            .expect("build tokio runtime");
+
+        rt.block_on(async {
+            for limit in [1u32, 2, 3, 6] {
+                let sinks: Vec<Arc<dyn Sink>> = (0..SINKS)
+                    .map(|_| {
+                        Arc::new(TestSink {
+                            name: "slow",
+                            behavior: TestSinkBehavior::Sleep(DELAY),
+                        }) as Arc<dyn Sink>
+                    })
+                    .collect();
+                let hub = Hub::new(
+                    HubConfig {
+                        max_sink_sends_in_parallel: limit as usize,
+                        ..HubConfig::default()
+                    },
+                    sinks,
+                );
+
+                let started = tokio::time::Instant::now();
+                hub.send(Event::new("kind", Severity::Info, "title"))
+                    .await
+                    .expect("send");
+                let elapsed = started.elapsed();
+
+                // Sinks run in waves of `limit`; with the clock paused, virtual time advances
+                // by exactly one delay per wave.
+                let waves = SINKS.div_ceil(limit);
+                assert_eq!(elapsed, DELAY * waves, "limit {limit}");
+            }
+        });
+    }
+
+    #[test]
+    fn builder_honors_enabled_kinds_timeout_and_inflight_limit() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime");
+
+        rt.block_on(async {
+            let hub = Hub::builder()
+                .enabled_kinds(["enabled"])
+                .per_sink_timeout(Duration::from_millis(5))
+                .max_inflight_events(1)
+                .add_sink(Arc::new(TestSink {
+                    name: "slow",
+                    behavior: TestSinkBehavior::Sleep(Duration::from_millis(50)),
+                }))
+                .build();
+
+            hub.send(Event::new("disabled", Severity::Info, "title"))
+                .await
+                .expect("disabled kind is a no-op");
+
+            let err = hub
+                .send(Event::new("enabled", Severity::Info, "title"))
+                .await
+                .expect_err("expected timeout");
+            assert!(err.to_string().contains("timeout after"), "{err}");
+
+            hub.try_notify(Event::new("enabled", Severity::Info, "t1"))
+                .expect("first notify ok");
+            assert_eq!(
+                hub.try_notify(Event::new("enabled", Severity::Info, "t2")),
+                Err(TryNotifyError::Overloaded)
+            );
+        });
+    }
+
+    #[test]
+    fn min_severity_drops_events_below_threshold() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime");
+
+        rt.block_on(async {
+            let sink = Arc::new(RecordingSink::default());
+            let hub = Hub::builder()
+                .min_severity(Severity::Warning)
+                .kind_severity_default("approval_requested", Severity::Warning)
+                .add_sink(sink.clone())
+                .build();
+
+            for event in [
+                Event::new("info", Severity::Info, "title"),
+                Event::new("success", Severity::Success, "title"),
+                Event::new("warning", Severity::Warning, "title"),
+                Event::new("error", Severity::Error, "title"),
+                Event::without_severity("approval_requested", "title"),
+            ] {
+                hub.send(event).await.expect("send ok");
+            }
+            for kind in ["info", "success"] {
+                assert_eq!(sink.count_kind(kind), 0, "{kind}");
+            }
+            for kind in ["warning", "error", "approval_requested"] {
+                assert_eq!(sink.count_kind(kind), 1, "{kind}");
+            }
+
+            hub.notify(Event::new("info", Severity::Info, "title"));
+            tokio::task::yield_now().await;
+            assert_eq!(sink.count_kind("info"), 0);
+
+            let err = hub
+                .send_any(Event::new("info", Severity::Info, "title"))
+                .await
+                .expect_err("below min_severity");
+            assert!(err.to_string().contains("min_severity"), "{err}");
+            assert!(hub.diagnostic_report().contains("min_severity: warning"));
+        });
+    }
+
+    #[test]
+    fn builder_add_routed_applies_filter() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime");
+
+        rt.block_on(async {
+            let pager = NamedCountingSink::new("pager");
+            let hub = Hub::builder()
+                .add_routed(
+                    pager.clone(),
+                    SinkFilter::new().with_min_severity(Severity::Error),
+                )
+                .build();
+
+            hub.send(Event::new("kind", Severity::Warning, "title"))
+                .await
+                .expect("send");
+            hub.send(Event::new("kind", Severity::Error, "title"))
+                .await
+                .expect("send");
+            assert_eq!(pager.calls.load(Ordering::SeqCst), 1);
+        });
+    }
+
+    #[test]
+    fn auto_tags_are_added_without_clobbering_user_tags() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime");
+
+        rt.block_on(async {
+            let sink = Arc::new(RecordingSink::default());
+            let hub = Hub::builder()
+                .auto_tags(AutoTags {
+                    hostname: true,
+                    pid: true,
+                    app_name: Some("svc".to_string()),
+                })
+                .add_named("tags", sink.clone())
+                .build();
+
+            hub.send(Event::new("kind", Severity::Info, "title").with_tag("app", "mine"))
+                .await
+                .expect("send");
+            hub.send_to("tags", Event::new("kind", Severity::Info, "title"))
+                .await
+                .expect("send_to");
+
+            let recorded: Vec<_> = sink.events().into_iter().map(|event| event.tags).collect();
+            assert_eq!(recorded.len(), 2);
+            let pid = std::process::id().to_string();
+            for tags in &recorded {
+                assert_eq!(tags.get("pid"), Some(&pid));
+                if let Some(host) = gethostname::gethostname().to_str() {
+                    assert_eq!(tags.get("host").map(String::as_str), Some(host));
+                }
+            }
+            assert_eq!(recorded[0].get("app").map(String::as_str), Some("mine"));
+            assert_eq!(recorded[1].get("app").map(String::as_str), Some("svc"));
+        });
+    }
+
+    #[test]
+    fn auto_tags_are_disabled_by_default() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime");
+
+        rt.block_on(async {
+            let sink = Arc::new(RecordingSink::default());
+            let hub = Hub::new(HubConfig::default(), vec![sink.clone()]);
+
+            hub.send(Event::new("kind", Severity::Info, "title"))
+                .await
+                .expect("send");
+
+            assert!(sink.events()[0].tags.is_empty());
+        });
+    }
+
+    #[test]
+    fn kind_severity_default_applies_only_without_explicit_severity() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime");
+
+        rt.block_on(async {
+            let all = Arc::new(RecordingSink::default());
+            let pager = Arc::new(RecordingSink::default());
+            let kinds_and_severities = |sink: &RecordingSink| {
+                sink.events()
+                    .into_iter()
+                    .map(|event| (event.kind, event.severity))
+                    .collect::<Vec<_>>()
+            };
+            let hub = Hub::builder()
+                .kind_severity_default("approval_requested", Severity::Warning)
+                .add_named("all", all.clone())
+                .add_routed(
+                    pager.clone(),
+                    SinkFilter::new().with_min_severity(Severity::Warning),
+                )
+                .build();
+
+            hub.send(Event::without_severity("approval_requested", "approve?"))
+                .await
+                .expect("send");
+            for severity in [Severity::Error, Severity::Success, Severity::Info] {
+                hub.send(Event::new("approval_requested", severity, "approve?"))
+                    .await
+                    .expect("send");
+            }
+            hub.send(Event::new("other", Severity::Info, "fyi"))
+                .await
+                .expect("send");
+            hub.send_to(
+                "all",
+                Event::without_severity("approval_requested", "again"),
+            )
+            .await
+            .expect("send_to");
+            hub.send(
+                Event::without_severity("approval_requested", "overridden")
+                    .with_severity(Severity::Info),
+            )
+            .await
+            .expect("send");
+
+            assert_eq!(
+                kinds_and_severities(&all),
+                [
+                    ("approval_requested".to_string(), Severity::Warning),
+                    ("approval_requested".to_string(), Severity::Error),
+                    ("approval_requested".to_string(), Severity::Success),
+                    // An explicit `Info` is kept.
+                    ("approval_requested".to_string(), Severity::Info),
+                    ("other".to_string(), Severity::Info),
+                    ("approval_requested".to_string(), Severity::Warning),
+                    ("approval_requested".to_string(), Severity::Info),
+                ]
+            );
+            // Routing sees the mapped severity.
+            assert_eq!(
+                kinds_and_severities(&pager),
+                [
+                    ("approval_requested".to_string(), Severity::Warning),
+                    ("approval_requested".to_string(), Severity::Error),
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn merge_combines_kind_severity_defaults_per_kind() {
+        let base = HubConfig {
+            kind_severity_defaults: BTreeMap::from([
+                ("a".to_string(), Severity::Warning),
+                ("b".to_string(), Severity::Warning),
+            ]),
+            ..HubConfig::default()
+        };
+        let over = HubConfig {
+            kind_severity_defaults: BTreeMap::from([("b".to_string(), Severity::Error)]),
+            ..HubConfig::default()
+        };
+        assert_eq!(
+            base.merge(over).kind_severity_defaults,
+            BTreeMap::from([
+                ("a".to_string(), Severity::Warning),
+                ("b".to_string(), Severity::Error),
+            ])
+        );
+    }
+
+    #[test]
+    fn dedup_dispatches_identical_events_once_per_window() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime");
+
+        rt.block_on(async {
+            let sink = Arc::new(RecordingSink::default());
+            let hub = Hub::new(
+                HubConfig {
+                    dedup: Some(DedupConfig::new(Duration::from_millis(100))),
+                    ..HubConfig::default()
+                },
+                vec![sink.clone()],
+            );
+            let event = || Event::new("job", Severity::Error, "job failed");
+
+            hub.send(event()).await.expect("send");
+            let outcomes = hub.send_detailed(event()).await.expect("send_detailed");
+            assert!(outcomes.is_empty());
+            hub.send(Event::new("job", Severity::Error, "other job failed"))
+                .await
+                .expect("send");
+            assert_eq!(sink.events().len(), 2);
+
+            tokio::time::sleep(Duration::from_millis(120)).await;
+            hub.send(event()).await.expect("send");
+            assert_eq!(sink.events().len(), 3);
+        });
+    }
+
+    #[derive(Default)]
+    struct FailOnceSink {
+        attempts: std::sync::atomic::AtomicUsize,
+    }
+
+    impl Sink for FailOnceSink {
+        fn name(&self) -> &'static str {
+            "fail_once"
+        }
+
+        fn send<'a>(&'a self, _event: &'a Event) -> BoxFuture<'a, crate::Result<()>> {
+            Box::pin(async move {
+                if self.attempts.fetch_add(1, Ordering::SeqCst) == 0 {
+                    return Err(anyhow::anyhow!("transient").into());
+                }
+                Ok(())
+            })
+        }
+    }
+
+    #[test]
+    fn dedup_does_not_suppress_retry_of_failed_send() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime");
+
+        rt.block_on(async {
+            let sink = Arc::new(FailOnceSink::default());
+            let hub = Hub::new(
+                HubConfig {
+                    dedup: Some(DedupConfig::new(Duration::from_secs(60))),
+                    ..HubConfig::default()
+                },
+                vec![sink.clone()],
+            );
+            let event = || Event::new("job", Severity::Error, "job failed");
+
+            hub.send(event()).await.expect_err("first attempt fails");
+            hub.send(event()).await.expect("retry is delivered");
+            assert_eq!(sink.attempts.load(Ordering::SeqCst), 2);
+
+            // Once delivered, the window applies as usual.
+            hub.send(event()).await.expect("duplicate");
+            assert_eq!(sink.attempts.load(Ordering::SeqCst), 2);
+        });
+    }
+
+    #[test]
+    fn diagnostic_report_describes_setup_without_secrets() {
+        let slack = crate::sinks::SlackWebhookSink::new(crate::sinks::SlackWebhookConfig::new(
+            "https://hooks.slack.com/services/T000/B000/slacksecret",
+        ))
+        .expect("build slack sink");
+        let telegram = crate::sinks::TelegramBotSink::new(crate::sinks::TelegramBotConfig::new(
+            "123456:telegramsecret",
+            "42",
+        ))
+        .expect("build telegram sink");
+
+        let hub = Hub::builder()
+            .enabled_kinds(["turn_completed", "approval_requested"])
+            .per_sink_timeout(Duration::from_secs(3))
+            .max_inflight_events(8)
+            .kind_severity_default("approval_requested", Severity::Warning)
+            .add_named("alerts", Arc::new(slack))
+            .add_routed(
+                Arc::new(telegram),
+                SinkFilter::new().with_min_severity(Severity::Error),
+            )
+            .add_sink(Arc::new(TestSink {
+                name: "custom",
+                behavior: TestSinkBehavior::Ok,
+            }))
+            .build();
+
+        let report = hub.diagnostic_report();
+        assert!(!report.contains("secret"), "{report}");
+        assert!(!report.contains("T000"), "{report}");
+        assert!(report.contains("sinks: 3"), "{report}");
+        assert!(
+            report.contains("[0] slack label=alerts target=https://hooks.slack.com/<redacted>"),
+            "{report}"
+        );
+        assert!(
+            report.contains(
+                "[1] telegram target=https://api.telegram.org/<redacted> min_severity=error"
+            ),
+            "{report}"
+        );
+        assert!(report.contains("[2] custom\n"), "{report}");
+        assert!(report.contains("per_sink_timeout: 3s"), "{report}");
+        assert!(
+            report.contains("enabled_kinds: approval_requested,turn_completed"),
+            "{report}"
+        );
+        assert!(report.contains("inflight: 8 available of 8"), "{report}");
+        assert!(
+            report.contains("kind_severity_defaults: approval_requested=warning"),
+            "{report}"
+        );
+        assert!(report.contains("dedup: off"), "{report}");
+    }
+
+    #[test]
+    fn dry_run_previews_routed_sinks_without_sending() {
+        #[derive(Debug, Default)]
+        struct PreviewSink {
+            sends: AtomicUsize,
+        }
+
+        impl Sink for PreviewSink {
+            fn name(&self) -> &'static str {
+                "preview"
+            }
+
+            fn send<'a>(&'a self, _event: &'a Event) -> BoxFuture<'a, crate::Result<()>> {
+                Box::pin(async move {
+                    self.sends.fetch_add(1, Ordering::SeqCst);
+                    Ok(())
+                })
+            }
+
+            fn preview(&self, event: &Event) -> Option<serde_json::Value> {
+                Some(serde_json::json!({
+                    "title": event.title,
+                    "severity": event.severity.as_str(),
+                }))
+            }
+        }
+
+        let slack = crate::sinks::SlackWebhookSink::new(crate::sinks::SlackWebhookConfig::new(
+            "https://hooks.slack.com/services/T000/B000/slacksecret",
+        ))
+        .expect("build slack sink");
+        let preview = Arc::new(PreviewSink::default());
+        let hub = Hub::builder()
+            .enabled_kinds(["deploy", "approval_requested"])
+            .kind_severity_default("approval_requested", Severity::Warning)
+            .add_sink(Arc::new(slack))
+            .add_sink(Arc::new(TestSink {
+                name: "custom",
+                behavior: TestSinkBehavior::Ok,
+            }))
+            .add_routed(
+                preview.clone(),
+                SinkFilter::new().with_min_severity(Severity::Warning),
+            )
+            .build();
+
+        // No tokio runtime here: a dry run performs no IO at all.
+        let payloads = hub.dry_run(Event::new("deploy", Severity::Info, "shipped"));
+        assert_eq!(payloads.len(), 1, "{payloads:?}");
+        assert_eq!(payloads[0].0, "slack");
+        assert_eq!(payloads[0].1["text"], "shipped");
+
+        let payloads = hub.dry_run(Event::without_severity("approval_requested", "approve?"));
+        let names: Vec<&str> = payloads.iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, ["slack", "preview"]);
+        assert_eq!(
+            payloads[1].1,
+            serde_json::json!({ "title": "approve?", "severity": "warning" })
+        );
+
+        assert!(
+            hub.dry_run(Event::new("other", Severity::Error, "t"))
+                .is_empty()
+        );
+        assert_eq!(preview.sends.load(Ordering::SeqCst), 0);
+    }
+
+    #[derive(Debug, Default)]
+    struct FailTitleSink {
+        titles: std::sync::Mutex<Vec<String>>,
+    }
+
+    impl Sink for FailTitleSink {
+        fn name(&self) -> &'static str {
+            "fail-title"
+        }
+
+        fn send<'a>(&'a self, event: &'a Event) -> BoxFuture<'a, crate::Result<()>> {
+            Box::pin(async move {
+                self.titles
+                    .lock()
+                    .expect("lock titles")
+                    .push(event.title.clone());
+                if event.title == "bad" {
+                    return Err(anyhow::anyhow!("bad event").into());
+                }
+                Ok(())
+            })
+        }
+    }
+
+    fn batch_events() -> Vec<Event> {
+        ["one", "bad", "three"]
+            .into_iter()
+            .map(|title| Event::new("job", Severity::Info, title))
+            .collect()
+    }
+
+    #[test]
+    fn send_batch_continue_all_returns_per_event_results() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime");
+
+        rt.block_on(async {
+            let sink = Arc::new(FailTitleSink::default());
+            let hub = Hub::new(HubConfig::default(), vec![sink.clone()]);
+
+            let results = hub
+                .send_batch(batch_events(), BatchFailureMode::ContinueAll)
+                .await;
+            assert_eq!(results.len(), 3);
+            assert!(results[0].is_ok());
+            let err = results[1].as_ref().expect_err("bad event fails");
+            assert!(err.to_string().contains("bad event"), "{err:#}");
+            assert!(results[2].is_ok());
+            assert_eq!(
+                *sink.titles.lock().expect("lock titles"),
+                ["one", "bad", "three"]
+            );
+        });
+    }
+
+    #[test]
+    fn send_batch_stop_on_first_skips_remaining_events() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime");
+
+        rt.block_on(async {
+            let sink = Arc::new(FailTitleSink::default());
+            let hub = Hub::new(HubConfig::default(), vec![sink.clone()]);
+
+            let results = hub
+                .send_batch(batch_events(), BatchFailureMode::StopOnFirst)
+                .await;
+            assert_eq!(results.len(), 2);
+            assert!(results[0].is_ok());
+            assert!(results[1].is_err());
+            assert_eq!(*sink.titles.lock().expect("lock titles"), ["one", "bad"]);
+            assert_eq!(BatchFailureMode::default(), BatchFailureMode::ContinueAll);
+        });
+    }
+
+    fn mixed_outcome_hub(error_when: SendErrorPolicy, ok: usize, err: usize) -> Hub {
+        let mut builder = Hub::builder().error_when(error_when);
+        for _ in 0..ok {
+            builder = builder.add_sink(Arc::new(TestSink {
+                name: "ok",
+                behavior: TestSinkBehavior::Ok,
+            }));
+        }
+        for _ in 0..err {
+            builder = builder.add_sink(Arc::new(TestSink {
+                name: "err",
+                behavior: TestSinkBehavior::Err,
+            }));
+        }
+        builder.build()
+    }
+
+    fn send_is_ok(error_when: SendErrorPolicy, ok: usize, err: usize) -> bool {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime");
+        let hub = mixed_outcome_hub(error_when, ok, err);
+        rt.block_on(hub.send(Event::new("kind", Severity::Info, "title")))
+            .is_ok()
+    }
+
+    #[test]
+    fn error_when_any_failure_is_the_default() {
+        assert_eq!(HubConfig::default().error_when, SendErrorPolicy::AnyFailure);
+        assert!(send_is_ok(SendErrorPolicy::AnyFailure, 3, 0));
+        assert!(!send_is_ok(SendErrorPolicy::AnyFailure, 4, 1));
+        assert!(!send_is_ok(SendErrorPolicy::AnyFailure, 0, 2));
+    }
+
+    #[test]
+    fn error_when_all_failures_needs_every_sink_to_fail() {
+        assert!(send_is_ok(SendErrorPolicy::AllFailures, 2, 0));
+        assert!(send_is_ok(SendErrorPolicy::AllFailures, 1, 4));
+        assert!(!send_is_ok(SendErrorPolicy::AllFailures, 0, 2));
+    }
+
+    #[test]
+    fn error_when_threshold_exceeded_counts_failures() {
+        assert!(send_is_ok(SendErrorPolicy::ThresholdExceeded(2), 3, 2));
+        assert!(!send_is_ok(SendErrorPolicy::ThresholdExceeded(2), 2, 3));
+        assert!(!send_is_ok(SendErrorPolicy::ThresholdExceeded(0), 4, 1));
+        assert!(send_is_ok(SendErrorPolicy::ThresholdExceeded(5), 0, 5));
+    }
+
+    #[test]
+    fn error_when_error_lists_all_failed_sinks() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime");
+        let hub = mixed_outcome_hub(SendErrorPolicy::ThresholdExceeded(1), 1, 2);
+        let err = rt
+            .block_on(hub.send(Event::new("kind", Severity::Info, "title")))
+            .expect_err("two failures exceed threshold");
+        assert_eq!(err.to_string().matches("- err: boom").count(), 2, "{err:#}");
+    }
+
+    #[test]
+    fn merge_keeps_non_default_error_when() {
+        let base = HubConfig {
+            error_when: SendErrorPolicy::AllFailures,
+            ..HubConfig::default()
+        };
+        assert_eq!(
+            base.clone().merge(HubConfig::default()).error_when,
+            SendErrorPolicy::AllFailures
+        );
+        let over = HubConfig {
+            error_when: SendErrorPolicy::ThresholdExceeded(2),
+            ..HubConfig::default()
+        };
+        assert_eq!(
+            base.merge(over).error_when,
+            SendErrorPolicy::ThresholdExceeded(2)
+        );
+    }
+
+    #[test]
+    fn mirror_receives_one_summary_per_send() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime");
+
+        rt.block_on(async {
+            let mirror = Arc::new(RecordingSink::named("mirror"));
+            let sinks: Vec<Arc<dyn Sink>> = ["ok", "ok", "err"]
+                .into_iter()
+                .map(|name| -> Arc<dyn Sink> {
+                    let behavior = if name == "ok" {
+                        TestSinkBehavior::Ok
+                    } else {
+                        TestSinkBehavior::Err
+                    };
+                    Arc::new(TestSink { name, behavior })
+                })
+                .collect();
+            let hub = Hub::new(
+                HubConfig {
+                    mirror_summary_to: Some(mirror.clone()),
+                    ..HubConfig::default()
+                },
+                sinks,
+            );
+
+            let event = Event::new("turn_completed", Severity::Warning, "secret title")
+                .with_body("secret body")
+                .with_tag("thread_id", "t1");
+            hub.send(event).await.expect_err("one sink fails");
+            hub.send(Event::new("other", Severity::Info, "x"))
+                .await
+                .expect_err("one sink fails");
+
+            // Summaries are sent before `send` returns.
+            let events = mirror.events();
+            assert_eq!(events.len(), 2);
+            let summary = &events[0];
+            assert_eq!(summary.kind, "turn_completed");
+            assert_eq!(summary.severity, Severity::Warning);
+            assert_eq!(summary.title, "turn_completed");
+            assert_eq!(summary.body, None);
+            assert_eq!(
+                summary.tags.keys().map(String::as_str).collect::<Vec<_>>(),
+                ["delivered_count", "failed_count", "timestamp_ms"]
+            );
+            assert_eq!(summary.tags["delivered_count"], "2");
+            assert_eq!(summary.tags["failed_count"], "1");
+            assert!(
+                summary.tags["timestamp_ms"]
+                    .parse::<u128>()
+                    .expect("millis")
+                    > 0
+            );
+            assert_eq!(events[1].kind, "other");
+        });
+    }
+
+    #[test]
+    fn mirror_summary_covers_notify_and_skips_suppressed_events() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime");
+
+        rt.block_on(async {
+            let mirror = Arc::new(RecordingSink::named("mirror"));
+            let sink = Arc::new(RecordingSink::default());
+            let hub = Hub::builder()
+                .add_sink(sink.clone())
+                .enabled_kinds(["kind"])
+                .mirror_summary_to(mirror.clone())
+                .build();
+
+            hub.send(Event::new("disabled", Severity::Info, "x"))
+                .await
+                .expect("disabled kind is ok");
+            hub.notify(Event::new("kind", Severity::Info, "x"));
+
+            mirror.recorded.notified().await;
+            let events = mirror.events();
+            assert_eq!(events.len(), 1);
+            assert_eq!(events[0].tags["delivered_count"], "1");
+            assert_eq!(sink.count_kind("kind"), 1);
+            assert!(
+                hub.diagnostic_report()
+                    .contains("mirror_summary_to: mirror")
+            );
+        });
+    }
+
+    #[test]
+    fn mirror_failure_does_not_affect_send_result() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime");
+
+        rt.block_on(async {
+            let hub = Hub::builder()
+                .add_sink(Arc::new(TestSink {
+                    name: "ok",
+                    behavior: TestSinkBehavior::Ok,
+                }))
+                .mirror_summary_to(Arc::new(TestSink {
+                    name: "mirror",
+                    behavior: TestSinkBehavior::Err,
+                }))
+                .build();
+            hub.send(Event::new("kind", Severity::Info, "x"))
+                .await
+                .expect("mirror errors are only logged");
+        });
+    }
+
+    /// Records each event, then holds the send until the test adds a gate permit.
+    #[derive(Debug)]
+    struct GatedSink {
+        gate: tokio::sync::Semaphore,
+        events: Mutex<Vec<Event>>,
+    }
+
+    impl Sink for GatedSink {
+        fn name(&self) -> &'static str {
+            "gated"
+        }
+
+        fn send<'a>(&'a self, event: &'a Event) -> BoxFuture<'a, crate::Result<()>> {
+            Box::pin(async move {
+                self.events.lock().expect("lock events").push(event.clone());
+                self.gate.acquire().await.expect("gate open").forget();
+                Ok(())
+            })
+        }
+    }
+
+    #[test]
+    fn slow_mirror_is_bounded_by_per_sink_timeout() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .start_paused(true)
+            .build()
+            .expect("build tokio runtime");
+
+        rt.block_on(async {
+            let mirror = Arc::new(GatedSink {
+                gate: tokio::sync::Semaphore::new(0),
+                events: Mutex::new(Vec::new()),
+            });
+            let hub = Hub::builder()
+                .add_sink(Arc::new(TestSink {
+                    name: "ok",
+                    behavior: TestSinkBehavior::Ok,
+                }))
+                .per_sink_timeout(Duration::from_secs(5))
+                .mirror_summary_to(mirror.clone())
+                .build();
+
+            // The gate never opens: the mirror times out and `send` still succeeds.
+            let started = tokio::time::Instant::now();
+            hub.send(Event::new("kind", Severity::Info, "x"))
+                .await
+                .expect("mirror timeouts are only logged");
+            assert_eq!(started.elapsed(), Duration::from_secs(5));
+            assert_eq!(mirror.events.lock().expect("lock events").len(), 1);
+        });
+    }
+
+    #[test]
+    fn mirror_covers_send_any_and_send_to() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime");
+
+        rt.block_on(async {
+            let mirror = Arc::new(RecordingSink::named("mirror"));
+            let hub = Hub::builder()
+                .add_named(
+                    "bad",
+                    Arc::new(TestSink {
+                        name: "bad",
+                        behavior: TestSinkBehavior::Err,
+                    }),
+                )
+                .add_named(
+                    "ok",
+                    Arc::new(TestSink {
+                        name: "ok",
+                        behavior: TestSinkBehavior::Ok,
+                    }),
+                )
+                .max_sink_sends_in_parallel(1)
+                .mirror_summary_to(mirror.clone())
+                .build();
+
+            let winner = hub
+                .send_any(Event::new("any", Severity::Error, "page"))
+                .await
+                .expect("second sink delivers");
+            assert_eq!(winner, "ok");
+            hub.send_to("bad", Event::new("to", Severity::Info, "x"))
+                .await
+                .expect_err("named sink fails");
+
+            let counts: Vec<(String, String, String)> = mirror
+                .events()
+                .into_iter()
+                .map(|summary| {
+                    (
+                        summary.kind,
+                        summary.tags["delivered_count"].clone(),
+                        summary.tags["failed_count"].clone(),
+                    )
+                })
+                .collect();
+            assert_eq!(
+                counts,
+                [
+                    ("any".to_string(), "1".to_string(), "1".to_string()),
+                    ("to".to_string(), "0".to_string(), "1".to_string()),
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn send_blocking_delivers_the_mirror_summary() {
+        let mirror = Arc::new(RecordingSink::named("mirror"));
+        let hub = Hub::builder()
+            .add_sink(Arc::new(TestSink {
+                name: "ok",
+                behavior: TestSinkBehavior::Ok,
+            }))
+            .mirror_summary_to(mirror.clone())
+            .build();
+
+        hub.send_blocking(Event::new("kind", Severity::Info, "x"))
+            .expect("send_blocking");
+        assert_eq!(mirror.events().len(), 1);
+    }
+
+    #[test]
+    fn replace_config_mid_send_keeps_in_flight_snapshot() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime");
+
+        rt.block_on(async {
+            let gated = Arc::new(GatedSink {
+                gate: tokio::sync::Semaphore::new(0),
+                events: Mutex::new(Vec::new()),
+            });
+            let old = HubConfig {
+                enabled_kinds: kinds(&["deploy"]),
+                auto_tags: AutoTags {
+                    app_name: Some("v1".to_string()),
+                    ..AutoTags::default()
+                },
+                error_when: SendErrorPolicy::AllFailures,
+                ..HubConfig::default()
+            };
+            let hub = Hub::builder()
+                .config(old)
+                .add_sink(gated.clone())
+                .add_sink(Arc::new(TestSink {
+                    name: "err",
+                    behavior: TestSinkBehavior::Err,
+                }))
+                .build();
+
+            let in_flight = tokio::spawn({
+                let hub = hub.clone();
+                async move {
+                    hub.send(Event::new("deploy", Severity::Info, "first"))
+                        .await
+                }
+            });
+            while gated.events.lock().expect("lock events").is_empty() {
+                tokio::task::yield_now().await;
+            }
+
+            hub.replace_config(HubConfig {
+                enabled_kinds: kinds(&["build"]),
+                auto_tags: AutoTags {
+                    app_name: Some("v2".to_string()),
+                    ..AutoTags::default()
+                },
+                error_when: SendErrorPolicy::AnyFailure,
+                ..HubConfig::default()
+            });
+            gated.gate.add_permits(1);
+            // Old policy: one sink delivered, so the failing one is not an error.
+            in_flight
+                .await
+                .expect("join")
+                .expect("in-flight send keeps AllFailures");
+
+            hub.send(Event::new("deploy", Severity::Info, "second"))
+                .await
+                .expect("disabled kind is a no-op");
+            gated.gate.add_permits(1);
+            let err = hub
+                .send(Event::new("build", Severity::Info, "third"))
+                .await
+                .expect_err("new policy reports the failing sink");
+            assert!(err.to_string().contains("- err: boom"), "{err:#}");
+
+            let events = gated.events.lock().expect("lock events");
+            let seen: Vec<(&str, &str)> = events
+                .iter()
+                .map(|event| (event.title.as_str(), event.tags["app"].as_str()))
+                .collect();
+            assert_eq!(seen, [("first", "v1"), ("third", "v2")]);
+            drop(events);
+            assert!(hub.diagnostic_report().contains("enabled_kinds: build"));
+        });
+    }
+
+    #[test]
+    fn replace_config_is_shared_by_clones_and_keeps_sinks() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime");
+
+        rt.block_on(async {
+            let sink = Arc::new(RecordingSink::default());
+            let hub = Hub::builder().add_named("chat", sink.clone()).build();
+            let clone = hub.clone();
+
+            clone.replace_config(HubConfig {
+                enabled_kinds: kinds(&["a"]),
+                ..HubConfig::default()
+            });
+            for kind in ["a", "b"] {
+                hub.send(Event::new(kind, Severity::Info, "x"))
+                    .await
+                    .expect("send");
+            }
+            hub.send_to("chat", Event::new("a", Severity::Info, "x"))
+                .await
+                .expect("named sink survives reload");
+            assert_eq!(sink.count_kind("a"), 2);
+            assert_eq!(sink.count_kind("b"), 0);
+        });
+    }
+}
@@ -0,0 +1,33 @@
+use std::hash::{BuildHasher as _, Hasher as _};
+
+/// Source of uniformly distributed values in `[0, 1)`; a plain fn pointer so tests can inject a
+/// deterministic one.
+pub(crate) type JitterRng = fn() -> f64;
+
+/// Cheap, non-cryptographic randomness for spreading out timeouts and retries.
+///
+/// `RandomState` is seeded per process (and perturbed per instance), which is all jitter needs;
+/// it avoids pulling in a full RNG crate.
+pub(crate) fn random_fraction() -> f64 {
+    let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+    hasher.write_u128(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or_default(),
+    );
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn random_fraction_is_in_unit_interval() {
+        for _ in 0..1000 {
+            let value = random_fraction();
+            assert!((0.0..1.0).contains(&value), "{value}");
+        }
+    }
+}
@@ -1,21 +1,76 @@
 #![forbid(unsafe_code)]
 
+mod callback;
+#[cfg(feature = "config-file")]
+pub mod config;
 mod env;
 mod error;
 mod event;
 mod hub;
+mod jitter;
+mod lru;
+mod render;
+mod sink_spec;
 mod sinks;
 
-pub use crate::error::Error;
+pub use crate::error::{Error, ErrorKind};
 pub type Result<T> = std::result::Result<T, Error>;
 
+#[cfg(feature = "crypto")]
+pub use crate::callback::decrypt_feishu_callback;
+pub use crate::callback::{
+    DEFAULT_CALLBACK_MAX_SKEW, FeishuCallback, Interaction, check_callback_timestamp,
+    parse_feishu_callback, parse_slack_interaction, verify_feishu_signature,
+    verify_slack_signature,
+};
 pub use crate::env::{StandardEnvHubOptions, build_hub_from_standard_env};
-pub use crate::event::{Event, Severity};
-pub use crate::hub::{Hub, HubConfig, TryNotifyError};
+pub use crate::event::{
+    Attachment, AttachmentSource, Event, MAX_ATTACHMENT_TOTAL_BYTES, MAX_ATTACHMENTS, PRIORITY_TAG,
+    ParseSeverityError, Severity,
+};
+pub use crate::hub::{
+    AutoTags, BatchFailureMode, DedupConfig, DedupKeyFn, EnabledKindsMerge, FailureLogSampling,
+    Hub, HubBuilder, HubConfig, SelfNotifyConfig, SendErrorPolicy, SinkFilter, SinkOutcome,
+    TryNotifyError, UnknownSinkError,
+};
+#[cfg(feature = "unicode")]
+pub use crate::render::NfForm;
+pub use crate::render::{
+    DefaultFormatter, EventFormatter, MaxChars, RenderMode, RenderOptions, TagRenderStyle,
+    TruncationStyle, render_event,
+};
+#[cfg(feature = "test-util")]
+pub use crate::sinks::CapturingSink;
+#[cfg(feature = "amqp")]
+pub use crate::sinks::{AmqpConfig, AmqpRouting, AmqpSink};
 pub use crate::sinks::{
-    BarkConfig, BarkSink, DingTalkWebhookConfig, DingTalkWebhookSink, DiscordWebhookConfig,
-    DiscordWebhookSink, FeishuWebhookConfig, FeishuWebhookSink, GenericWebhookConfig,
-    GenericWebhookSink, GitHubCommentConfig, GitHubCommentSink, PushPlusConfig, PushPlusSink,
-    ServerChanConfig, ServerChanSink, Sink, SlackWebhookConfig, SlackWebhookSink, SoundConfig,
-    SoundSink, TelegramBotConfig, TelegramBotSink, WeComWebhookConfig, WeComWebhookSink,
+    BarkConfig, BarkLevel, BarkSink, BatchPolicy, BatchingSink, DEFAULT_DNS_LOOKUP_TIMEOUT,
+    DEFAULT_MAX_DNS_LOOKUPS_INFLIGHT, DEFAULT_MAX_PINNED_CLIENT_CACHE_ENTRIES,
+    DEFAULT_MAX_RESPONSE_BODY_BYTES, DEFAULT_PINNED_CLIENT_TTL,
+    DEFAULT_RESPONSE_BODY_DRAIN_LIMIT_BYTES, DEFAULT_USER_AGENT, DingTalkWebhookConfig,
+    DingTalkWebhookSink, DiscordAllowedMentions, DiscordMentionType, DiscordWebhookConfig,
+    DiscordWebhookSink, DnsResolver, EventTransform, FallbackSink, FeishuWebhookConfig,
+    FeishuWebhookSink, GenericWebhookConfig, GenericWebhookSink, GitHubCommentConfig,
+    GitHubCommentSink, GitHubIssueConfig, GitHubIssueSink, GitLabNoteConfig, GitLabNoteSink,
+    GitLabNoteTarget, HttpMethod, HttpPoolConfig, HttpVersion, LineNotifyConfig, LineNotifySink,
+    LineSticker, MapSink, MultiSink, ProxyConfig, PushPlusConfig, PushPlusSink, RateLimit,
+    RateLimitedError, RateLimitedSink, RetryPolicy, RetryingSink, ServerChanConfig, ServerChanSink,
+    Sink, SlackApiConfig, SlackApiSink, SlackWebhookConfig, SlackWebhookSink, SoundConfig,
+    SoundSink, TelegramBotConfig, TelegramBotSink, TwilioSmsConfig, TwilioSmsSink,
+    WeComWebhookConfig, WeComWebhookSink, ZulipConfig, ZulipSink, dns_lookup_timeout,
+    http_pool_config, http_version, max_dns_lookups_inflight, max_pinned_client_cache_entries,
+    max_response_body_bytes, pinned_client_ttl, response_body_drain_limit, set_dns_lookup_timeout,
+    set_dns_resolver, set_http_pool_config, set_http_proxy, set_http_version,
+    set_max_dns_lookups_inflight, set_max_pinned_client_cache_entries, set_max_response_body_bytes,
+    set_pinned_client_ttl, set_response_body_drain_limit, set_user_agent, user_agent,
 };
+#[cfg(feature = "crypto")]
+pub use crate::sinks::{BarkEncryption, BarkEncryptionMode};
+#[cfg(feature = "desktop")]
+pub use crate::sinks::{DesktopConfig, DesktopSink};
+#[cfg(feature = "grpc")]
+pub use crate::sinks::{GrpcConfig, GrpcSink, GrpcTls};
+#[cfg(feature = "mqtt")]
+pub use crate::sinks::{MqttConfig, MqttSink};
+#[cfg(feature = "aws")]
+pub use crate::sinks::{SnsConfig, SnsSink};
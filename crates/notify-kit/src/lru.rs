@@ -0,0 +1,246 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::time::{Duration, Instant};
+
+/// Bounded map with least-recently-used eviction and optional TTL, for per-key state (hub dedup
+/// keys, [`crate::RateLimitedSink`] per-kind buckets).
+///
+/// High-cardinality keys (e.g. titles with ids in them) would otherwise grow that state without
+/// bound: once `capacity` entries are stored, inserting a new key evicts the least recently used
+/// one. With a `ttl`, entries older than `ttl` (since their last insert) are treated as absent
+/// and dropped when encountered.
+pub(crate) struct BoundedLru<K, V> {
+    capacity: usize,
+    ttl: Option<Duration>,
+    entries: HashMap<K, LruEntry<V>>,
+    /// Recency order: smallest tick is the least recently used key.
+    order: BTreeMap<u64, K>,
+    /// Insertion order (by insert time, then insert tick): the first key expires first.
+    expiry: BTreeMap<(Instant, u64), K>,
+    next_tick: u64,
+}
+
+struct LruEntry<V> {
+    value: V,
+    inserted_at: Instant,
+    /// Tick of the insert; with `inserted_at`, the entry's key in `expiry`.
+    inserted_tick: u64,
+    /// Tick of the last use; the entry's key in `order`.
+    tick: u64,
+}
+
+impl<K: Eq + Hash + Clone, V> BoundedLru<K, V> {
+    /// `capacity` of `0` is treated as `1`.
+    pub(crate) fn new(capacity: usize, ttl: Option<Duration>) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            ttl,
+            entries: HashMap::new(),
+            order: BTreeMap::new(),
+            expiry: BTreeMap::new(),
+            next_tick: 0,
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns the live value for `key` and marks it as most recently used.
+    pub(crate) fn get(&mut self, key: &K, now: Instant) -> Option<&V> {
+        if self.is_expired(key, now) {
+            self.remove(key);
+            return None;
+        }
+        let tick = self.bump_tick();
+        let entry = self.entries.get_mut(key)?;
+        self.order.remove(&entry.tick);
+        self.order.insert(tick, key.clone());
+        entry.tick = tick;
+        Some(&entry.value)
+    }
+
+    /// Inserts or replaces `key` (resetting its TTL), evicting the least recently used entry when
+    /// full. Returns the previous live value, if any.
+    pub(crate) fn insert(&mut self, key: K, value: V, now: Instant) -> Option<V> {
+        let expired = self.is_expired(&key, now);
+        let tick = self.bump_tick();
+        let previous = self.remove(&key);
+        if previous.is_none() {
+            self.evict_to(self.capacity - 1, now);
+        }
+
+        self.order.insert(tick, key.clone());
+        self.expiry.insert((now, tick), key.clone());
+        self.entries.insert(
+            key,
+            LruEntry {
+                value,
+                inserted_at: now,
+                inserted_tick: tick,
+                tick,
+            },
+        );
+        previous.filter(|_| !expired)
+    }
+
+    pub(crate) fn remove(&mut self, key: &K) -> Option<V> {
+        let entry = self.entries.remove(key)?;
+        self.order.remove(&entry.tick);
+        self.expiry
+            .remove(&(entry.inserted_at, entry.inserted_tick));
+        Some(entry.value)
+    }
+
+    fn is_expired(&self, key: &K, now: Instant) -> bool {
+        let (Some(ttl), Some(entry)) = (self.ttl, self.entries.get(key)) else {
+            return false;
+        };
+        now.saturating_duration_since(entry.inserted_at) >= ttl
+    }
+
+    fn bump_tick(&mut self) -> u64 {
+        let tick = self.next_tick;
+        self.next_tick += 1;
+        tick
+    }
+
+    /// Drops least recently used entries until at most `max_len` remain.
+    fn evict_to(&mut self, max_len: usize, now: Instant) {
+        if self.entries.len() <= max_len {
+            return;
+        }
+        if let Some(ttl) = self.ttl {
+            // Expired entries go first, so a burst of stale keys does not push out live ones.
+            // Only the expired prefix of `expiry` is visited.
+            while self.entries.len() > max_len {
+                let Some(((inserted_at, _), key)) = self.expiry.first_key_value() else {
+                    break;
+                };
+                if now.saturating_duration_since(*inserted_at) < ttl {
+                    break;
+                }
+                let key = key.clone();
+                self.remove(&key);
+            }
+        }
+        while self.entries.len() > max_len {
+            let Some((_, key)) = self.order.first_key_value() else {
+                break;
+            };
+            let key = key.clone();
+            self.remove(&key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evicts_least_recently_used_at_capacity() {
+        let now = Instant::now();
+        let mut lru = BoundedLru::new(2, None);
+
+        lru.insert("a", 1, now);
+        lru.insert("b", 2, now);
+        assert_eq!(lru.get(&"a", now), Some(&1));
+        lru.insert("c", 3, now);
+
+        assert_eq!(lru.len(), 2);
+        assert_eq!(lru.get(&"b", now), None);
+        assert_eq!(lru.get(&"a", now), Some(&1));
+        assert_eq!(lru.get(&"c", now), Some(&3));
+    }
+
+    #[test]
+    fn replacing_a_key_does_not_evict() {
+        let now = Instant::now();
+        let mut lru = BoundedLru::new(2, None);
+
+        lru.insert("a", 1, now);
+        lru.insert("b", 2, now);
+        assert_eq!(lru.insert("a", 10, now), Some(1));
+
+        assert_eq!(lru.len(), 2);
+        assert_eq!(lru.get(&"a", now), Some(&10));
+        assert_eq!(lru.get(&"b", now), Some(&2));
+    }
+
+    #[test]
+    fn entries_expire_after_ttl() {
+        let start = Instant::now();
+        let mut lru = BoundedLru::new(8, Some(Duration::from_secs(10)));
+
+        lru.insert("a", 1, start);
+        assert_eq!(lru.get(&"a", start + Duration::from_secs(9)), Some(&1));
+        assert_eq!(lru.get(&"a", start + Duration::from_secs(10)), None);
+        assert_eq!(lru.len(), 0);
+
+        lru.insert("b", 2, start);
+        assert_eq!(
+            lru.insert("b", 3, start + Duration::from_secs(11)),
+            None,
+            "expired value is not returned"
+        );
+        assert_eq!(lru.get(&"b", start + Duration::from_secs(20)), Some(&3));
+    }
+
+    #[test]
+    fn expired_entries_are_evicted_before_live_ones() {
+        let start = Instant::now();
+        let later = start + Duration::from_secs(4);
+        let mut lru = BoundedLru::new(2, Some(Duration::from_secs(3)));
+
+        lru.insert("old", 1, start);
+        lru.insert("live", 2, start + Duration::from_secs(2));
+        assert_eq!(lru.get(&"old", start + Duration::from_secs(2)), Some(&1));
+        // "old" is now the most recently used but expired by `later`; "live" must survive.
+        lru.insert("new", 3, later);
+
+        assert_eq!(lru.len(), 2);
+        assert_eq!(lru.get(&"live", later), Some(&2));
+        assert_eq!(lru.get(&"new", later), Some(&3));
+    }
+
+    #[test]
+    fn eviction_keeps_both_indexes_in_sync() {
+        let start = Instant::now();
+        let ttl = Duration::from_secs(5);
+        let mut lru = BoundedLru::new(3, Some(ttl));
+
+        for (i, key) in ["a", "b", "c"].into_iter().enumerate() {
+            lru.insert(key, i, start + Duration::from_secs(i as u64));
+        }
+        // At t=5.5 only "a" (inserted at t=0) has expired; a new key replaces it alone.
+        let now = start + Duration::from_millis(5500);
+        lru.insert("d", 3, now);
+        assert_eq!(lru.len(), 3);
+        assert_eq!(lru.order.len(), 3);
+        assert_eq!(lru.expiry.len(), 3);
+        assert_eq!(lru.get(&"a", now), None);
+        assert_eq!(lru.get(&"b", now), Some(&1));
+
+        // Nothing else has expired: the least recently used live key ("c") goes next.
+        lru.insert("e", 4, now);
+        assert_eq!(lru.get(&"c", now), None);
+        assert_eq!(lru.order.len(), 3);
+        assert_eq!(lru.expiry.len(), 3);
+    }
+
+    #[test]
+    fn zero_capacity_keeps_one_entry() {
+        let now = Instant::now();
+        let mut lru = BoundedLru::new(0, None);
+
+        lru.insert(1, "a", now);
+        lru.insert(2, "b", now);
+
+        assert_eq!(lru.len(), 1);
+        assert_eq!(lru.get(&2, now), Some(&"b"));
+        assert_eq!(lru.remove(&2), Some("b"));
+        assert_eq!(lru.len(), 0);
+    }
+}
@@ -0,0 +1,643 @@
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+use crate::sinks::text::{
+    MAX_FLOAT_PRECISION, TextLimits, format_event_body_and_tags_limited, format_event_text_limited,
+};
+use crate::{Event, Severity};
+
+/// Overall length cap for rendered text.
+///
+/// `From<usize>` maps `0` to [`MaxChars::Unlimited`] and any other value to
+/// [`MaxChars::Limited`]; this is also how the `max_chars` fields of the built-in sink configs are
+/// interpreted.
+///
+/// ```
+/// use notify_kit::{Event, MaxChars, RenderOptions, Severity, render_event};
+///
+/// assert_eq!(MaxChars::from(0), MaxChars::Unlimited);
+/// assert_eq!(MaxChars::from(280), MaxChars::Limited(280));
+///
+/// let event = Event::new("backup", Severity::Info, "backup done").with_body("x".repeat(5000));
+/// let capped = render_event(&event, &RenderOptions::new(MaxChars::Limited(20)));
+/// assert_eq!(capped.chars().count(), 20);
+/// let full = render_event(&event, &RenderOptions::new(0));
+/// assert_eq!(full.chars().count(), "backup done\n".len() + 5000);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MaxChars {
+    Limited(usize),
+    /// No overall cap; the per-part title and tag caps still apply, the body is not capped.
+    Unlimited,
+}
+
+impl MaxChars {
+    pub(crate) fn limit(self) -> usize {
+        match self {
+            Self::Limited(max_chars) => max_chars,
+            Self::Unlimited => usize::MAX,
+        }
+    }
+
+    /// Budget left after reserving `reserved` units (for content appended by a sink).
+    pub(crate) fn saturating_sub(self, reserved: usize) -> Self {
+        match self {
+            Self::Limited(max_chars) => Self::Limited(max_chars.saturating_sub(reserved)),
+            Self::Unlimited => Self::Unlimited,
+        }
+    }
+}
+
+impl From<usize> for MaxChars {
+    fn from(max_chars: usize) -> Self {
+        if max_chars == 0 {
+            Self::Unlimited
+        } else {
+            Self::Limited(max_chars)
+        }
+    }
+}
+
+/// How truncated text ends.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TruncationStyle {
+    /// Replace the last three units with `...` (when the limit leaves room for them).
+    #[default]
+    Ellipsis,
+    /// Cut at exactly the limit, without a marker (for machine-consumed fields or tight limits).
+    Hard,
+}
+
+/// How tags are laid out in rendered text.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TagRenderStyle {
+    /// One `key=value` per line.
+    #[default]
+    KeyEqualsValue,
+    /// One `• key: value` per line.
+    BulletColon,
+    /// A two-column markdown table (`| key | value |`); `|` in cells is escaped and newlines are
+    /// flattened. Needs line breaks, so [`RenderMode::SingleLine`] falls back to `key=value`.
+    MarkdownTable,
+}
+
+/// Unicode normalization form for [`RenderOptions::normalize_unicode`].
+#[cfg(feature = "unicode")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NfForm {
+    /// Canonical composition (`e` + U+0301 becomes `é`); what most displays expect.
+    Nfc,
+    Nfd,
+    /// Compatibility composition, also folding e.g. full-width letters and ligatures.
+    Nfkc,
+    Nfkd,
+}
+
+#[cfg(feature = "unicode")]
+impl NfForm {
+    fn normalize(self, input: &str) -> String {
+        use unicode_normalization::UnicodeNormalization as _;
+
+        match self {
+            Self::Nfc => input.nfc().collect(),
+            Self::Nfd => input.nfd().collect(),
+            Self::Nfkc => input.nfkc().collect(),
+            Self::Nfkd => input.nfkd().collect(),
+        }
+    }
+
+    fn normalize_event(self, event: &mut Event) {
+        event.title = self.normalize(&event.title);
+        if let Some(body) = &mut event.body {
+            *body = self.normalize(body);
+        }
+        event.tags = std::mem::take(&mut event.tags)
+            .into_iter()
+            .map(|(key, value)| (self.normalize(&key), self.normalize(&value)))
+            .collect();
+        for key in &mut event.tag_order {
+            *key = self.normalize(key);
+        }
+    }
+}
+
+/// Layout used by [`render_event`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RenderMode {
+    /// Title, body and `key=value` tags on separate lines (what the built-in text sinks send).
+    #[default]
+    Text,
+    /// Everything on one line, parts joined with `" | "` and inner newlines flattened.
+    SingleLine,
+}
+
+/// Options for [`render_event`].
+///
+/// `max_chars` caps the whole output (in Unicode scalar values); truncated output ends with
+/// `...` unless `truncation` is [`TruncationStyle::Hard`]. Title, body and tags are additionally
+/// capped individually with the same defaults the built-in sinks use (see
+/// [`MaxChars::Unlimited`] for the exception).
+///
+/// ```
+/// use notify_kit::{Event, RenderOptions, Severity, TruncationStyle, render_event};
+///
+/// let event = Event::new("deploy", Severity::Error, "deploy failed")
+///     .with_body("rollback started")
+///     .with_tag("env", "prod");
+///
+/// let options = RenderOptions::new(200)
+///     .with_title_template(Severity::Error, "[PROD] {title}")
+///     .with_tags_as_json(true);
+/// assert_eq!(
+///     render_event(&event, &options),
+///     "[PROD] deploy failed\nrollback started\n{\"env\":\"prod\"}"
+/// );
+///
+/// let options = RenderOptions::new(10)
+///     .with_tags(false)
+///     .with_truncation(TruncationStyle::Hard);
+/// assert_eq!(render_event(&event, &options), "deploy fai");
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub struct RenderOptions {
+    pub max_chars: MaxChars,
+    pub include_title: bool,
+    pub include_tags: bool,
+    pub mode: RenderMode,
+    /// Fraction digits for tag values that are decimal numbers (`0.1 + 0.2` renders as `0.30`
+    /// with `Some(2)`), at most 17; `None` (the default) renders tag values verbatim.
+    pub float_precision: Option<usize>,
+    pub truncation: TruncationStyle,
+    /// Per-severity title templates such as `"🔥 [PROD] {title}"`; `{title}`, `{kind}` and
+    /// `{severity}` are substituted, and the result is truncated like a plain title. Severities
+    /// without an entry keep the plain title.
+    pub title_template_by_severity: BTreeMap<Severity, String>,
+    /// Render tags as a single compact JSON object line (`{"k":"v"}`) instead of `key=value`
+    /// lines, e.g. for log-ingestion webhooks. Tags that would not fit are left out of the
+    /// object rather than cutting it into invalid JSON.
+    pub tags_as_json: bool,
+    /// Layout of the tag lines; ignored when `tags_as_json` is set.
+    pub tag_style: TagRenderStyle,
+    /// Start the title with a severity emoji (`❌` error, `⚠️` warning, `ℹ️` info, `✅`
+    /// success) for at-a-glance triage. The prefix counts toward `max_chars` and is left out
+    /// rather than cut when it doesn't fit. Off by default.
+    pub severity_prefix: bool,
+    /// Normalize title, body and tags to this form before truncation, so composed and
+    /// decomposed input render (and truncate) identically; `None` (the default) leaves text as-is.
+    #[cfg(feature = "unicode")]
+    pub normalize_unicode: Option<NfForm>,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            max_chars: TextLimits::default().max_chars,
+            include_title: true,
+            include_tags: true,
+            mode: RenderMode::Text,
+            float_precision: None,
+            truncation: TruncationStyle::Ellipsis,
+            title_template_by_severity: BTreeMap::new(),
+            tags_as_json: false,
+            tag_style: TagRenderStyle::KeyEqualsValue,
+            severity_prefix: false,
+            #[cfg(feature = "unicode")]
+            normalize_unicode: None,
+        }
+    }
+}
+
+impl RenderOptions {
+    pub fn new(max_chars: impl Into<MaxChars>) -> Self {
+        Self {
+            max_chars: max_chars.into(),
+            ..Self::default()
+        }
+    }
+
+    #[must_use]
+    pub fn with_title(mut self, include_title: bool) -> Self {
+        self.include_title = include_title;
+        self
+    }
+
+    #[must_use]
+    pub fn with_tags(mut self, include_tags: bool) -> Self {
+        self.include_tags = include_tags;
+        self
+    }
+
+    #[must_use]
+    pub fn with_mode(mut self, mode: RenderMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    #[must_use]
+    /// `precision` is capped at 17, the most digits an `f64` carries.
+    pub fn with_float_precision(mut self, precision: usize) -> Self {
+        self.float_precision = Some(precision.min(MAX_FLOAT_PRECISION));
+        self
+    }
+
+    #[must_use]
+    pub fn with_truncation(mut self, truncation: TruncationStyle) -> Self {
+        self.truncation = truncation;
+        self
+    }
+
+    #[must_use]
+    pub fn with_title_template(mut self, severity: Severity, template: impl Into<String>) -> Self {
+        self.title_template_by_severity
+            .insert(severity, template.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_tags_as_json(mut self, tags_as_json: bool) -> Self {
+        self.tags_as_json = tags_as_json;
+        self
+    }
+
+    #[must_use]
+    pub fn with_tag_style(mut self, tag_style: TagRenderStyle) -> Self {
+        self.tag_style = tag_style;
+        self
+    }
+
+    #[must_use]
+    pub fn with_severity_prefix(mut self, severity_prefix: bool) -> Self {
+        self.severity_prefix = severity_prefix;
+        self
+    }
+
+    #[cfg(feature = "unicode")]
+    #[must_use]
+    pub fn with_unicode_normalization(mut self, form: NfForm) -> Self {
+        self.normalize_unicode = Some(form);
+        self
+    }
+
+    pub(crate) fn text_limits(&self) -> TextLimits {
+        let mut limits = TextLimits::new(self.max_chars);
+        if !self.include_tags {
+            limits.max_tags = 0;
+        }
+        limits.single_line = self.mode == RenderMode::SingleLine;
+        limits.float_precision = self.float_precision;
+        limits.truncation = self.truncation;
+        limits.tags_as_json = self.tags_as_json;
+        limits.tag_style = self.tag_style;
+        limits.with_severity_prefix(self.severity_prefix)
+    }
+}
+
+/// Custom message layout for text-based sinks (`with_formatter` on their configs).
+///
+/// `max_chars` is the sink's configured limit, `0` meaning unlimited. Sinks still cut the result
+/// to their limit (in their own unit, e.g. UTF-16 for Telegram), so an overlong result is
+/// truncated rather than rejected by the target API. Sink-native extras such as Slack links,
+/// Discord embeds or Telegram buttons are added around the formatted text as usual.
+pub trait EventFormatter: Send + Sync {
+    fn format(&self, event: &Event, max_chars: usize) -> String;
+}
+
+/// The built-in layout (title, body, url and `key=value` tags); what sinks use when no
+/// formatter is configured.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultFormatter;
+
+impl EventFormatter for DefaultFormatter {
+    fn format(&self, event: &Event, max_chars: usize) -> String {
+        format_event_text_limited(event, TextLimits::new(max_chars))
+    }
+}
+
+/// Lets a sink render with these options (e.g. `with_severity_prefix(true)`); the sink's own
+/// `max_chars` replaces [`RenderOptions::max_chars`].
+impl EventFormatter for RenderOptions {
+    fn format(&self, event: &Event, max_chars: usize) -> String {
+        let options = RenderOptions {
+            max_chars: max_chars.into(),
+            ..self.clone()
+        };
+        render_event(event, &options)
+    }
+}
+
+/// Renders `event` as the canonical notification text used by the built-in sinks.
+///
+/// ```
+/// use notify_kit::{Event, RenderMode, RenderOptions, Severity, render_event};
+///
+/// let event = Event::new("build", Severity::Error, "build failed")
+///     .with_body("exit code 1")
+///     .with_tag("branch", "main");
+///
+/// assert_eq!(
+///     render_event(&event, &RenderOptions::default()),
+///     "build failed\nexit code 1\nbranch=main"
+/// );
+/// assert_eq!(
+///     render_event(&event, &RenderOptions::new(64).with_mode(RenderMode::SingleLine)),
+///     "build failed | exit code 1 | branch=main"
+/// );
+/// assert_eq!(
+///     render_event(&event, &RenderOptions::new(15).with_tags(false)),
+///     "build failed..."
+/// );
+/// ```
+pub fn render_event(event: &Event, options: &RenderOptions) -> String {
+    let limits = options.text_limits();
+    let mut event = Cow::Borrowed(event);
+    if options.include_title {
+        if let Some(template) = options.title_template_by_severity.get(&event.severity) {
+            let title = expand_title_template(template, &event);
+            event.to_mut().title = title;
+        }
+    }
+    #[cfg(feature = "unicode")]
+    if let Some(form) = options.normalize_unicode {
+        form.normalize_event(event.to_mut());
+    }
+
+    if options.include_title {
+        format_event_text_limited(&event, limits)
+    } else {
+        format_event_body_and_tags_limited(&event, limits)
+    }
+}
+
+/// Single pass, so placeholders inside the substituted values are left alone; unknown
+/// placeholders are kept verbatim.
+fn expand_title_template(template: &str, event: &Event) -> String {
+    let mut out = String::with_capacity(template.len() + event.title.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let tail = &rest[start..];
+        let value = tail.find('}').and_then(|end| {
+            let value = match &tail[1..end] {
+                "title" => event.title.as_str(),
+                "kind" => event.kind.as_str(),
+                "severity" => event.severity.as_str(),
+                _ => return None,
+            };
+            Some((value, end))
+        });
+        match value {
+            Some((value, end)) => {
+                out.push_str(value);
+                rest = &tail[end + 1..];
+            }
+            None => {
+                out.push('{');
+                rest = &tail[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Severity;
+
+    #[test]
+    fn single_line_flattens_newlines_and_respects_max_chars() {
+        let event = Event::new("k", Severity::Info, "title")
+            .with_body("line one\nline two\r\n\nline three")
+            .with_tag("k", "v");
+
+        let out = render_event(
+            &event,
+            &RenderOptions::default().with_mode(RenderMode::SingleLine),
+        );
+        assert_eq!(out, "title | line one line two line three | k=v");
+
+        for max in 1..48 {
+            let out = render_event(
+                &event,
+                &RenderOptions::new(max).with_mode(RenderMode::SingleLine),
+            );
+            assert!(out.chars().count() <= max, "{max}: {out}");
+            assert!(!out.contains('\n'), "{out}");
+            assert!(!out.ends_with(" | "), "{out}");
+        }
+    }
+
+    #[test]
+    fn default_formatter_matches_render_event() {
+        let event = Event::new("k", Severity::Info, "title")
+            .with_body("body")
+            .with_tag("k", "v");
+
+        for max_chars in [0, 8, 4000] {
+            assert_eq!(
+                DefaultFormatter.format(&event, max_chars),
+                render_event(&event, &RenderOptions::new(max_chars))
+            );
+        }
+    }
+
+    #[test]
+    fn severity_prefix_option_and_formatter() {
+        let event = Event::new("k", Severity::Success, "deploy done").with_body("body");
+        assert_eq!(
+            render_event(&event, &RenderOptions::default()),
+            "deploy done\nbody"
+        );
+
+        let options = RenderOptions::default().with_severity_prefix(true);
+        assert_eq!(render_event(&event, &options), "✅ deploy done\nbody");
+        // As a sink formatter, the sink's own budget wins over `options.max_chars`.
+        assert_eq!(options.format(&event, 9), "✅ depl...");
+        assert_eq!(options.format(&event, 0), "✅ deploy done\nbody");
+    }
+
+    #[test]
+    fn hard_truncation_has_no_ellipsis() {
+        let event = Event::new("k", Severity::Info, "build failed").with_tag("k", "v");
+
+        let options = RenderOptions::new(10);
+        assert_eq!(render_event(&event, &options), "build f...");
+        let options = options.with_truncation(TruncationStyle::Hard);
+        assert_eq!(render_event(&event, &options), "build fail");
+    }
+
+    #[test]
+    fn unlimited_renders_everything() {
+        let body = "x".repeat(32 * 1024);
+        let event = Event::new("k", Severity::Info, "title")
+            .with_body(body.clone())
+            .with_tag("k", "v");
+        let expected = format!("title\n{body}\nk=v");
+
+        for options in [
+            RenderOptions::new(MaxChars::Unlimited),
+            RenderOptions::new(0),
+        ] {
+            assert_eq!(options.max_chars, MaxChars::Unlimited);
+            assert_eq!(render_event(&event, &options), expected);
+        }
+    }
+
+    #[test]
+    fn zero_max_chars_maps_to_unlimited() {
+        assert_eq!(MaxChars::from(0), MaxChars::Unlimited);
+        assert_eq!(MaxChars::from(7), MaxChars::Limited(7));
+    }
+
+    #[test]
+    fn float_tag_rendered_with_two_decimals() {
+        let event = Event::new("k", Severity::Info, "done").with_tag("elapsed", "12.3456");
+
+        let out = render_event(
+            &event,
+            &RenderOptions::default()
+                .with_mode(RenderMode::SingleLine)
+                .with_float_precision(2),
+        );
+        assert_eq!(out, "done | elapsed=12.35");
+    }
+
+    #[test]
+    fn title_template_applies_per_severity() {
+        let options = RenderOptions::default()
+            .with_tags(false)
+            .with_title_template(Severity::Error, "🔥 [PROD] {title}")
+            .with_title_template(Severity::Warning, "[{severity}/{kind}] {title} {unknown}");
+
+        let error = Event::new("deploy", Severity::Error, "deploy failed").with_body("rollback");
+        assert_eq!(
+            render_event(&error, &options),
+            "🔥 [PROD] deploy failed\nrollback"
+        );
+
+        let warning = Event::new("disk", Severity::Warning, "{kind} 90%");
+        assert_eq!(
+            render_event(&warning, &options),
+            "[warning/disk] {kind} 90% {unknown}"
+        );
+
+        let info = Event::new("deploy", Severity::Info, "deployed");
+        assert_eq!(render_event(&info, &options), "deployed");
+    }
+
+    #[test]
+    fn title_template_is_applied_before_truncation() {
+        let event = Event::new("k", Severity::Error, "database unreachable");
+        let options = RenderOptions::new(16).with_title_template(Severity::Error, "[PROD] {title}");
+        assert_eq!(render_event(&event, &options), "[PROD] databa...");
+    }
+
+    #[test]
+    fn tags_as_json_renders_one_compact_line() {
+        let event = Event::new("k", Severity::Info, "done")
+            .with_body("ok")
+            .with_tag("host", "a\nb")
+            .with_tag("elapsed", "1.2345")
+            .with_tag("quote", "say \"hi\"");
+
+        let options = RenderOptions::default()
+            .with_tags_as_json(true)
+            .with_float_precision(2);
+        assert_eq!(
+            render_event(&event, &options),
+            "done\nok\n{\"elapsed\":1.23,\"host\":\"a\\nb\",\"quote\":\"say \\\"hi\\\"\"}"
+        );
+
+        let options = options.with_mode(RenderMode::SingleLine);
+        assert_eq!(
+            render_event(&event, &options),
+            "done | ok | {\"elapsed\":1.23,\"host\":\"a\\nb\",\"quote\":\"say \\\"hi\\\"\"}"
+        );
+    }
+
+    #[test]
+    fn tags_as_json_drops_tags_instead_of_breaking_json() {
+        let event = Event::new("k", Severity::Info, "t")
+            .with_tag("a", "1")
+            .with_tag("b", "2");
+
+        // "t\n" + {"a":"1","b":"2"} is 19 chars; 17 leaves room for the first tag only.
+        let out = render_event(&event, &RenderOptions::new(17).with_tags_as_json(true));
+        assert_eq!(out, "t\n{\"a\":\"1\"}");
+
+        let out = render_event(&event, &RenderOptions::new(6).with_tags_as_json(true));
+        assert_eq!(out, "t...");
+    }
+
+    #[test]
+    fn can_omit_title_and_tags() {
+        let event = Event::new("k", Severity::Info, "title")
+            .with_body("body")
+            .with_tag("k", "v");
+
+        let out = render_event(
+            &event,
+            &RenderOptions::default().with_title(false).with_tags(false),
+        );
+        assert_eq!(out, "body");
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn unicode_normalization_makes_nfd_render_like_nfc() {
+        let nfc = "caf\u{e9} cr\u{e8}me br\u{fb}l\u{e9}e";
+        let nfd = "cafe\u{301} cre\u{300}me bru\u{302}le\u{301}e";
+        assert_ne!(nfc, nfd);
+
+        let composed = Event::new("k", Severity::Info, nfc)
+            .with_body(nfc)
+            .with_tag("dessert", nfc);
+        let decomposed = Event::new("k", Severity::Info, nfd)
+            .with_body(nfd)
+            .with_tag("dessert", nfd);
+
+        let options = RenderOptions::default().with_unicode_normalization(NfForm::Nfc);
+        let out = render_event(&decomposed, &options);
+        assert_eq!(out, render_event(&composed, &RenderOptions::default()));
+        assert!(out.contains(nfc), "{out}");
+        assert!(!out.contains('\u{301}'), "{out}");
+
+        for max in 1..40 {
+            let options = RenderOptions::new(max).with_unicode_normalization(NfForm::Nfc);
+            let out = render_event(&decomposed, &options);
+            assert_eq!(
+                out,
+                render_event(&composed, &RenderOptions::new(max)),
+                "{max}"
+            );
+            assert!(out.chars().count() <= max, "{max}: {out}");
+        }
+
+        let options = RenderOptions::default().with_unicode_normalization(NfForm::Nfd);
+        assert_eq!(
+            render_event(&composed, &options),
+            render_event(&decomposed, &RenderOptions::default())
+        );
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn unicode_normalization_is_off_by_default() {
+        let nfd = "e\u{301}";
+        let event = Event::new("k", Severity::Info, nfd);
+        assert_eq!(RenderOptions::default().normalize_unicode, None);
+        assert_eq!(render_event(&event, &RenderOptions::default()), nfd);
+        assert_eq!(
+            render_event(
+                &event,
+                &RenderOptions::default().with_unicode_normalization(NfForm::Nfkc)
+            ),
+            "\u{e9}"
+        );
+    }
+}
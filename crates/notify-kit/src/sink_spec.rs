@@ -0,0 +1,181 @@
+//! The built-in sinks that config files and environment variables can set up.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::{
+    BarkConfig, BarkSink, DingTalkWebhookConfig, DingTalkWebhookSink, DiscordWebhookConfig,
+    DiscordWebhookSink, FeishuWebhookConfig, FeishuWebhookSink, GenericWebhookConfig,
+    GenericWebhookSink, PushPlusConfig, PushPlusSink, ServerChanConfig, ServerChanSink, Sink,
+    SlackWebhookConfig, SlackWebhookSink, SoundConfig, SoundSink, TelegramBotConfig,
+    TelegramBotSink, WeComWebhookConfig, WeComWebhookSink,
+};
+
+/// One built-in sink: a `[[sinks]]` entry of a config file (tagged by `type`, see
+/// `notify_kit::config`), or one sink of [`crate::build_hub_from_standard_env`].
+#[non_exhaustive]
+#[derive(Clone)]
+#[cfg_attr(
+    feature = "config-file",
+    derive(serde::Deserialize),
+    serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)
+)]
+pub enum SinkFileConfig {
+    Sound,
+    Webhook {
+        url: String,
+        #[cfg_attr(feature = "config-file", serde(default))]
+        payload_field: Option<String>,
+    },
+    Slack {
+        webhook_url: String,
+    },
+    Discord {
+        webhook_url: String,
+    },
+    Feishu {
+        webhook_url: String,
+    },
+    Telegram {
+        bot_token: String,
+        chat_id: String,
+        #[cfg_attr(feature = "config-file", serde(default))]
+        api_base: Option<String>,
+    },
+    #[cfg_attr(feature = "config-file", serde(rename = "dingtalk"))]
+    DingTalk {
+        webhook_url: String,
+        #[cfg_attr(feature = "config-file", serde(default))]
+        secret: Option<String>,
+    },
+    #[cfg_attr(feature = "config-file", serde(rename = "wecom"))]
+    WeCom {
+        webhook_url: String,
+    },
+    Bark {
+        device_key: String,
+    },
+    #[cfg_attr(feature = "config-file", serde(rename = "pushplus"))]
+    PushPlus {
+        token: String,
+    },
+    #[cfg_attr(feature = "config-file", serde(rename = "serverchan"))]
+    ServerChan {
+        send_key: String,
+    },
+}
+
+impl std::fmt::Debug for SinkFileConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Every variant carries a URL or token, so only the type is shown.
+        write!(f, "SinkFileConfig::{}", self.type_name())
+    }
+}
+
+impl SinkFileConfig {
+    /// The `type` tag, e.g. `"slack"`.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Self::Sound => "sound",
+            Self::Webhook { .. } => "webhook",
+            Self::Slack { .. } => "slack",
+            Self::Discord { .. } => "discord",
+            Self::Feishu { .. } => "feishu",
+            Self::Telegram { .. } => "telegram",
+            Self::DingTalk { .. } => "dingtalk",
+            Self::WeCom { .. } => "wecom",
+            Self::Bark { .. } => "bark",
+            Self::PushPlus { .. } => "pushplus",
+            Self::ServerChan { .. } => "serverchan",
+        }
+    }
+
+    /// Builds the sink; `timeout` is its request timeout.
+    pub(crate) fn build(&self, timeout: Duration) -> crate::Result<Arc<dyn Sink>> {
+        let sink: Arc<dyn Sink> = match self.clone() {
+            Self::Sound => Arc::new(SoundSink::new(SoundConfig::default())),
+            Self::Webhook { url, payload_field } => {
+                let mut cfg = GenericWebhookConfig::new(url).with_timeout(timeout);
+                if let Some(field) = payload_field {
+                    cfg = cfg.with_payload_field(field);
+                }
+                Arc::new(GenericWebhookSink::new(cfg)?)
+            }
+            Self::Slack { webhook_url } => Arc::new(SlackWebhookSink::new(
+                SlackWebhookConfig::new(webhook_url).with_timeout(timeout),
+            )?),
+            Self::Discord { webhook_url } => Arc::new(DiscordWebhookSink::new(
+                DiscordWebhookConfig::new(webhook_url).with_timeout(timeout),
+            )?),
+            Self::Feishu { webhook_url } => Arc::new(FeishuWebhookSink::new(
+                FeishuWebhookConfig::new(webhook_url).with_timeout(timeout),
+            )?),
+            Self::Telegram {
+                bot_token,
+                chat_id,
+                api_base,
+            } => {
+                let mut cfg = TelegramBotConfig::new(bot_token, chat_id).with_timeout(timeout);
+                if let Some(api_base) = api_base {
+                    cfg = cfg.with_api_base(api_base);
+                }
+                Arc::new(TelegramBotSink::new(cfg)?)
+            }
+            Self::DingTalk {
+                webhook_url,
+                secret,
+            } => {
+                let mut cfg = DingTalkWebhookConfig::new(webhook_url).with_timeout(timeout);
+                if let Some(secret) = secret {
+                    cfg = cfg.with_secret(secret);
+                }
+                Arc::new(DingTalkWebhookSink::new(cfg)?)
+            }
+            Self::WeCom { webhook_url } => Arc::new(WeComWebhookSink::new(
+                WeComWebhookConfig::new(webhook_url).with_timeout(timeout),
+            )?),
+            Self::Bark { device_key } => Arc::new(BarkSink::new(
+                BarkConfig::new(device_key).with_timeout(timeout),
+            )?),
+            Self::PushPlus { token } => Arc::new(PushPlusSink::new(
+                PushPlusConfig::new(token).with_timeout(timeout),
+            )?),
+            Self::ServerChan { send_key } => Arc::new(ServerChanSink::new(
+                ServerChanConfig::new(send_key).with_timeout(timeout),
+            )?),
+        };
+        Ok(sink)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn debug_shows_only_the_type() {
+        let cfg = SinkFileConfig::Telegram {
+            bot_token: "123:secret".to_string(),
+            chat_id: "42".to_string(),
+            api_base: None,
+        };
+        assert_eq!(format!("{cfg:?}"), "SinkFileConfig::telegram");
+    }
+
+    #[test]
+    fn build_returns_the_matching_sink() {
+        let cfg = SinkFileConfig::Slack {
+            webhook_url: "https://hooks.slack.com/services/T000/B000/XXXX".to_string(),
+        };
+        let sink = cfg.build(Duration::from_secs(1)).expect("build slack sink");
+        assert_eq!(sink.name(), cfg.type_name());
+
+        let err = SinkFileConfig::Slack {
+            webhook_url: "http://hooks.slack.com/services/T000/B000/XXXX".to_string(),
+        }
+        .build(Duration::from_secs(1))
+        .err()
+        .expect("expected invalid url");
+        assert_eq!(err.kind(), crate::ErrorKind::InvalidConfig, "{err:#}");
+    }
+}
@@ -1,31 +1,133 @@
 use std::time::Duration;
 
 use crate::Event;
+use crate::event::{PRIORITY_TAG, Severity};
+#[cfg(feature = "crypto")]
+use crate::sinks::crypto::{AES_BLOCK_SIZE, aes_encrypt_base64, is_valid_aes_key_len};
 use crate::sinks::http::{
-    DEFAULT_MAX_RESPONSE_BODY_BYTES, build_http_client, parse_and_validate_https_url,
-    read_text_body_limited, redact_url, select_http_client, send_reqwest, validate_url_path_prefix,
+    build_http_client, max_response_body_bytes, parse_and_validate_https_url,
+    read_text_body_limited, redact_url, redact_url_str, select_http_client, send_reqwest,
+    validate_url_path_prefix,
 };
 use crate::sinks::text::{TextLimits, format_event_body_and_tags_limited, truncate_chars};
 use crate::sinks::{BoxFuture, Sink};
 
 const BARK_ALLOWED_HOSTS: [&str; 1] = ["api.day.app"];
 
+/// Bark interruption level (`level` parameter).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BarkLevel {
+    Active,
+    TimeSensitive,
+    Passive,
+    Critical,
+}
+
+impl BarkLevel {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Active => "active",
+            Self::TimeSensitive => "timeSensitive",
+            Self::Passive => "passive",
+            Self::Critical => "critical",
+        }
+    }
+}
+
+impl From<Severity> for BarkLevel {
+    /// Default mapping used by `BarkConfig::with_level_from_severity`.
+    ///
+    /// `Critical` is never derived implicitly: it bypasses mute/do-not-disturb and should be an
+    /// explicit choice via `BarkConfig::with_level`.
+    fn from(severity: Severity) -> Self {
+        match severity {
+            Severity::Info => Self::Passive,
+            Severity::Success => Self::Active,
+            Severity::Warning | Severity::Error => Self::TimeSensitive,
+        }
+    }
+}
+
+impl BarkLevel {
+    /// Level for a [`crate::PRIORITY_TAG`] value (`low`/`normal`/`high`/`critical`, or a Bark
+    /// level name), case-insensitive.
+    fn from_priority_tag(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "low" | "passive" => Some(Self::Passive),
+            "normal" | "active" => Some(Self::Active),
+            "high" | "timesensitive" => Some(Self::TimeSensitive),
+            "critical" => Some(Self::Critical),
+            _ => None,
+        }
+    }
+}
+
+/// AES block mode used for Bark encrypted pushes.
+#[cfg(feature = "crypto")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BarkEncryptionMode {
+    Cbc,
+    Ecb,
+}
+
+/// Bark encrypted push settings (must match the key/iv configured in the Bark app).
+///
+/// `key` must be 16/24/32 bytes (AES-128/192/256). `iv` must be 16 bytes for CBC and is ignored for
+/// ECB. Requires the `crypto` feature (or its alias `bark-encryption`).
+#[cfg(feature = "crypto")]
+#[non_exhaustive]
+#[derive(Clone)]
+pub struct BarkEncryption {
+    pub key: String,
+    pub iv: String,
+    pub mode: BarkEncryptionMode,
+}
+
+#[cfg(feature = "crypto")]
+impl std::fmt::Debug for BarkEncryption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BarkEncryption")
+            .field("key", &"<redacted>")
+            .field("iv", &self.iv)
+            .field("mode", &self.mode)
+            .finish()
+    }
+}
+
 #[non_exhaustive]
 #[derive(Clone)]
 pub struct BarkConfig {
     pub device_key: String,
     pub group: Option<String>,
+    pub sound: Option<String>,
+    pub icon: Option<String>,
+    pub url: Option<String>,
+    /// Fixed interruption level; takes precedence over `level_from_severity`.
+    pub level: Option<BarkLevel>,
+    /// Derive `level` from `Event::severity` (see `impl From<Severity> for BarkLevel`).
+    pub level_from_severity: bool,
+    /// When set, the JSON payload is AES-encrypted and sent as the `ciphertext` form field.
+    #[cfg(feature = "crypto")]
+    pub encryption: Option<BarkEncryption>,
     pub timeout: Duration,
+    /// Message length limit; `0` means unlimited (see [`crate::MaxChars`]).
     pub max_chars: usize,
     pub enforce_public_ip: bool,
 }
 
 impl std::fmt::Debug for BarkConfig {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("BarkConfig")
-            .field("device_key", &"<redacted>")
+        let mut out = f.debug_struct("BarkConfig");
+        out.field("device_key", &"<redacted>")
             .field("group", &self.group)
-            .field("timeout", &self.timeout)
+            .field("sound", &self.sound)
+            .field("icon", &self.icon)
+            .field("url", &self.url.as_deref().map(redact_url_str))
+            .field("level", &self.level)
+            .field("level_from_severity", &self.level_from_severity);
+        #[cfg(feature = "crypto")]
+        out.field("encryption", &self.encryption);
+        out.field("timeout", &self.timeout)
             .field("max_chars", &self.max_chars)
             .field("enforce_public_ip", &self.enforce_public_ip)
             .finish()
@@ -37,6 +139,13 @@ impl BarkConfig {
         Self {
             device_key: device_key.into(),
             group: None,
+            sound: None,
+            icon: None,
+            url: None,
+            level: None,
+            level_from_severity: false,
+            #[cfg(feature = "crypto")]
+            encryption: None,
             timeout: Duration::from_secs(2),
             max_chars: 8 * 1024,
             enforce_public_ip: true,
@@ -49,6 +158,52 @@ impl BarkConfig {
         self
     }
 
+    #[must_use]
+    pub fn with_sound(mut self, sound: impl Into<String>) -> Self {
+        self.sound = Some(sound.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_icon(mut self, icon: impl Into<String>) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_level(mut self, level: BarkLevel) -> Self {
+        self.level = Some(level);
+        self
+    }
+
+    #[must_use]
+    pub fn with_level_from_severity(mut self, enable: bool) -> Self {
+        self.level_from_severity = enable;
+        self
+    }
+
+    #[cfg(feature = "crypto")]
+    #[must_use]
+    pub fn with_encryption(
+        mut self,
+        key: impl Into<String>,
+        iv: impl Into<String>,
+        mode: BarkEncryptionMode,
+    ) -> Self {
+        self.encryption = Some(BarkEncryption {
+            key: key.into(),
+            iv: iv.into(),
+            mode,
+        });
+        self
+    }
+
     #[must_use]
     pub fn with_timeout(mut self, timeout: Duration) -> Self {
         self.timeout = timeout;
@@ -72,6 +227,13 @@ pub struct BarkSink {
     api_url: reqwest::Url,
     device_key: String,
     group: Option<String>,
+    sound: Option<String>,
+    icon: Option<String>,
+    url: Option<String>,
+    level: Option<BarkLevel>,
+    level_from_severity: bool,
+    #[cfg(feature = "crypto")]
+    encryption: Option<BarkEncryption>,
     client: reqwest::Client,
     timeout: Duration,
     max_chars: usize,
@@ -80,11 +242,18 @@ pub struct BarkSink {
 
 impl std::fmt::Debug for BarkSink {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("BarkSink")
-            .field("api_url", &redact_url(&self.api_url))
+        let mut out = f.debug_struct("BarkSink");
+        out.field("api_url", &redact_url(&self.api_url))
             .field("device_key", &"<redacted>")
             .field("group", &self.group)
-            .field("max_chars", &self.max_chars)
+            .field("sound", &self.sound)
+            .field("icon", &self.icon)
+            .field("url", &self.url.as_deref().map(redact_url_str))
+            .field("level", &self.level)
+            .field("level_from_severity", &self.level_from_severity);
+        #[cfg(feature = "crypto")]
+        out.field("encryption", &self.encryption);
+        out.field("max_chars", &self.max_chars)
             .field("enforce_public_ip", &self.enforce_public_ip)
             .finish_non_exhaustive()
     }
@@ -97,6 +266,11 @@ impl BarkSink {
             return Err(anyhow::anyhow!("bark device_key must not be empty").into());
         }
         let group = normalize_optional_trimmed(config.group);
+        let sound = normalize_optional_trimmed(config.sound);
+        let icon = normalize_optional_trimmed(config.icon);
+        let url = normalize_optional_trimmed(config.url);
+        #[cfg(feature = "crypto")]
+        let encryption = config.encryption.map(validate_encryption).transpose()?;
 
         let api_url =
             parse_and_validate_https_url("https://api.day.app/push", &BARK_ALLOWED_HOSTS)?;
@@ -107,6 +281,13 @@ impl BarkSink {
             api_url,
             device_key: device_key.to_string(),
             group,
+            sound,
+            icon,
+            url,
+            level: config.level,
+            level_from_severity: config.level_from_severity,
+            #[cfg(feature = "crypto")]
+            encryption,
             client,
             timeout: config.timeout,
             max_chars: config.max_chars,
@@ -114,23 +295,93 @@ impl BarkSink {
         })
     }
 
-    fn build_payload(
+    /// A valid [`PRIORITY_TAG`] wins; otherwise the configured level or severity mapping.
+    fn level_for(&self, event: &Event) -> Option<BarkLevel> {
+        if let Some(level) = event
+            .tags
+            .get(PRIORITY_TAG)
+            .and_then(|value| BarkLevel::from_priority_tag(value))
+        {
+            return Some(level);
+        }
+        if self.level.is_some() {
+            return self.level;
+        }
+        self.level_from_severity
+            .then(|| BarkLevel::from(event.severity))
+    }
+
+    fn build_payload(&self, event: &Event) -> serde_json::Value {
+        let mut obj = self.build_content(event);
+        obj.insert("device_key".to_string(), serde_json::json!(self.device_key));
+        serde_json::Value::Object(obj)
+    }
+
+    /// Form fields for an encrypted push: the content is encrypted, `device_key` stays in clear.
+    #[cfg(feature = "crypto")]
+    fn build_encrypted_form(
+        &self,
         event: &Event,
-        device_key: &str,
-        group: Option<&str>,
-        max_chars: usize,
-    ) -> serde_json::Value {
-        let title = truncate_chars(&event.title, 256);
-        let body = format_event_body_and_tags_limited(event, TextLimits::new(max_chars));
+        encryption: &BarkEncryption,
+    ) -> crate::Result<Vec<(&'static str, String)>> {
+        let content = serde_json::Value::Object(self.build_content(event));
+        let plaintext = serde_json::to_vec(&content).map_err(|err| {
+            crate::Error::serialization(anyhow::anyhow!("encode bark payload: {err}"))
+        })?;
+        let iv = match encryption.mode {
+            BarkEncryptionMode::Cbc => Some(encryption.iv.as_bytes()),
+            BarkEncryptionMode::Ecb => None,
+        };
+        let ciphertext = aes_encrypt_base64(encryption.key.as_bytes(), iv, &plaintext)?;
+
+        let mut form = Vec::with_capacity(3);
+        form.push(("device_key", self.device_key.clone()));
+        form.push(("ciphertext", ciphertext));
+        if encryption.mode == BarkEncryptionMode::Cbc {
+            form.push(("iv", encryption.iv.clone()));
+        }
+        Ok(form)
+    }
 
-        let mut obj = serde_json::Map::with_capacity(4);
-        obj.insert("device_key".to_string(), serde_json::json!(device_key));
+    fn build_content(&self, event: &Event) -> serde_json::Map<String, serde_json::Value> {
+        let title = truncate_chars(&event.title, 256);
+        let event_url = event
+            .url
+            .as_deref()
+            .map(str::trim)
+            .filter(|url| !url.is_empty());
+        let mut limits = TextLimits::new(self.max_chars);
+        if event_url.is_some() {
+            limits = limits.without_url();
+        }
+        let body = if event.tags.contains_key(PRIORITY_TAG) {
+            let mut stripped = event.clone();
+            stripped.tags.remove(PRIORITY_TAG);
+            format_event_body_and_tags_limited(&stripped, limits)
+        } else {
+            format_event_body_and_tags_limited(event, limits)
+        };
+
+        let mut obj = serde_json::Map::with_capacity(8);
         obj.insert("title".to_string(), serde_json::json!(title));
         obj.insert("body".to_string(), serde_json::json!(body));
-        if let Some(group) = group {
+        if let Some(group) = self.group.as_deref() {
             obj.insert("group".to_string(), serde_json::json!(group));
         }
-        serde_json::Value::Object(obj)
+        if let Some(sound) = self.sound.as_deref() {
+            obj.insert("sound".to_string(), serde_json::json!(sound));
+        }
+        if let Some(icon) = self.icon.as_deref() {
+            obj.insert("icon".to_string(), serde_json::json!(icon));
+        }
+        // The event's own link is more specific than the configured default.
+        if let Some(url) = event_url.or(self.url.as_deref()) {
+            obj.insert("url".to_string(), serde_json::json!(url));
+        }
+        if let Some(level) = self.level_for(event) {
+            obj.insert("level".to_string(), serde_json::json!(level.as_str()));
+        }
+        obj
     }
 }
 
@@ -142,6 +393,23 @@ fn normalize_optional_trimmed(value: Option<String>) -> Option<String> {
         .map(ToString::to_string)
 }
 
+#[cfg(feature = "crypto")]
+fn validate_encryption(encryption: BarkEncryption) -> crate::Result<BarkEncryption> {
+    if !is_valid_aes_key_len(encryption.key.len()) {
+        return Err(anyhow::anyhow!("bark encryption key must be 16, 24 or 32 bytes").into());
+    }
+    let iv = match encryption.mode {
+        BarkEncryptionMode::Cbc => {
+            if encryption.iv.len() != AES_BLOCK_SIZE {
+                return Err(anyhow::anyhow!("bark encryption iv must be 16 bytes for cbc").into());
+            }
+            encryption.iv
+        }
+        BarkEncryptionMode::Ecb => String::new(),
+    };
+    Ok(BarkEncryption { iv, ..encryption })
+}
+
 fn bark_api_error(code: i64, message: &str) -> crate::Error {
     let message = truncate_chars(message, 200);
     if message.is_empty() {
@@ -165,38 +433,41 @@ impl Sink for BarkSink {
             )
             .await?;
 
-            let payload = Self::build_payload(
-                event,
-                &self.device_key,
-                self.group.as_deref(),
-                self.max_chars,
-            );
+            let request = client.post(self.api_url.as_str());
+            #[cfg(feature = "crypto")]
+            let request = match self.encryption.as_ref() {
+                Some(encryption) => request.form(&self.build_encrypted_form(event, encryption)?),
+                None => request.json(&self.build_payload(event)),
+            };
+            #[cfg(not(feature = "crypto"))]
+            let request = request.json(&self.build_payload(event));
 
-            let resp =
-                send_reqwest(client.post(self.api_url.as_str()).json(&payload), "bark").await?;
+            let resp = send_reqwest(request, "bark").await?;
 
             let status = resp.status();
             if !status.is_success() {
-                let body = match read_text_body_limited(resp, DEFAULT_MAX_RESPONSE_BODY_BYTES).await
-                {
+                let body = match read_text_body_limited(resp, max_response_body_bytes()).await {
                     Ok(body) => body,
                     Err(err) => {
-                        return Err(anyhow::anyhow!(
-                            "bark http error: {status} (failed to read response body: {err})"
-                        )
-                        .into());
+                        return Err(crate::Error::http_status(
+                            status,
+                            anyhow::anyhow!(
+                                "bark http error: {status} (failed to read response body: {err})"
+                            ),
+                        ));
                     }
                 };
                 let summary = truncate_chars(body.trim(), 200);
                 if summary.is_empty() {
-                    return Err(anyhow::anyhow!(
-                        "bark http error: {status} (response body omitted)"
-                    )
-                    .into());
+                    return Err(crate::Error::http_status(
+                        status,
+                        anyhow::anyhow!("bark http error: {status} (response body omitted)"),
+                    ));
                 }
-                return Err(
-                    anyhow::anyhow!("bark http error: {status}, response={summary}").into(),
-                );
+                return Err(crate::Error::http_status(
+                    status,
+                    anyhow::anyhow!("bark http error: {status}, response={summary}"),
+                ));
             }
 
             let content_type_is_json = resp
@@ -209,7 +480,7 @@ impl Sink for BarkSink {
                     })
                 });
 
-            let body = match read_text_body_limited(resp, DEFAULT_MAX_RESPONSE_BODY_BYTES).await {
+            let body = match read_text_body_limited(resp, max_response_body_bytes()).await {
                 Ok(body) => body,
                 Err(err) => {
                     return Err(anyhow::anyhow!(
@@ -242,6 +513,18 @@ impl Sink for BarkSink {
             Err(bark_api_error(code, message))
         })
     }
+
+    /// The plaintext request body with `device_key` redacted (also when encryption is enabled,
+    /// where the content is sent encrypted).
+    fn preview(&self, event: &Event) -> Option<serde_json::Value> {
+        let mut payload = self.build_payload(event);
+        payload["device_key"] = serde_json::json!("<redacted>");
+        Some(payload)
+    }
+
+    fn diagnostic_target(&self) -> Option<String> {
+        Some(redact_url(&self.api_url))
+    }
 }
 
 #[cfg(test)]
@@ -255,7 +538,8 @@ mod tests {
             .with_body("ok")
             .with_tag("thread_id", "t1");
 
-        let payload = BarkSink::build_payload(&event, "k", Some("g"), 8 * 1024);
+        let sink = BarkSink::new(BarkConfig::new("k").with_group("g")).expect("build sink");
+        let payload = sink.build_payload(&event);
         assert_eq!(payload["device_key"].as_str().unwrap_or(""), "k");
         assert_eq!(payload["title"].as_str().unwrap_or(""), "done");
         let body = payload["body"].as_str().unwrap_or("");
@@ -264,6 +548,196 @@ mod tests {
         assert_eq!(payload["group"].as_str().unwrap_or(""), "g");
     }
 
+    #[test]
+    fn omits_optional_fields_when_not_configured() {
+        let event = Event::new("turn_completed", Severity::Error, "done");
+        let sink = BarkSink::new(BarkConfig::new("k")).expect("build sink");
+        let payload = sink.build_payload(&event);
+        for field in ["group", "sound", "icon", "url", "level"] {
+            assert!(payload.get(field).is_none(), "{field}: {payload}");
+        }
+    }
+
+    #[test]
+    fn includes_optional_fields_when_configured() {
+        let event = Event::new("turn_completed", Severity::Info, "done");
+        let cfg = BarkConfig::new("k")
+            .with_sound(" alarm ")
+            .with_icon("https://example.com/icon.png")
+            .with_url("https://example.com/run/1")
+            .with_level(BarkLevel::Critical);
+        let sink = BarkSink::new(cfg).expect("build sink");
+        let payload = sink.build_payload(&event);
+        assert_eq!(payload["sound"].as_str().unwrap_or(""), "alarm");
+        assert_eq!(
+            payload["icon"].as_str().unwrap_or(""),
+            "https://example.com/icon.png"
+        );
+        assert_eq!(
+            payload["url"].as_str().unwrap_or(""),
+            "https://example.com/run/1"
+        );
+        assert_eq!(payload["level"].as_str().unwrap_or(""), "critical");
+    }
+
+    #[test]
+    fn event_url_overrides_configured_url() {
+        let sink = BarkSink::new(BarkConfig::new("k").with_url("https://example.com/default"))
+            .expect("build sink");
+
+        let event = Event::new("k", Severity::Info, "done")
+            .with_body("ok")
+            .with_url("https://example.com/run/2");
+        let payload = sink.build_payload(&event);
+        assert_eq!(payload["url"].as_str(), Some("https://example.com/run/2"));
+        assert_eq!(payload["body"].as_str(), Some("ok"));
+
+        let payload = sink.build_payload(&Event::new("k", Severity::Info, "done"));
+        assert_eq!(payload["url"].as_str(), Some("https://example.com/default"));
+
+        let sink = BarkSink::new(BarkConfig::new("k")).expect("build sink");
+        let payload = sink.build_payload(&Event::new("k", Severity::Info, "done").with_body("ok"));
+        assert!(payload.get("url").is_none(), "{payload}");
+    }
+
+    #[test]
+    fn derives_level_from_severity_when_enabled() {
+        let sink =
+            BarkSink::new(BarkConfig::new("k").with_level_from_severity(true)).expect("build sink");
+        let cases = [
+            (Severity::Info, "passive"),
+            (Severity::Success, "active"),
+            (Severity::Warning, "timeSensitive"),
+            (Severity::Error, "timeSensitive"),
+        ];
+        for (severity, expected) in cases {
+            let payload = sink.build_payload(&Event::new("k", severity, "t"));
+            assert_eq!(payload["level"].as_str().unwrap_or(""), expected);
+        }
+
+        let sink = BarkSink::new(
+            BarkConfig::new("k")
+                .with_level_from_severity(true)
+                .with_level(BarkLevel::Critical),
+        )
+        .expect("build sink");
+        let payload = sink.build_payload(&Event::new("k", Severity::Info, "t"));
+        assert_eq!(payload["level"].as_str().unwrap_or(""), "critical");
+    }
+
+    #[test]
+    fn priority_tag_overrides_level_and_is_stripped_from_body() {
+        let sink =
+            BarkSink::new(BarkConfig::new("k").with_level_from_severity(true)).expect("build sink");
+        let event = Event::new("k", Severity::Info, "t")
+            .with_body("ok")
+            .with_tag("thread_id", "t1");
+
+        let payload = sink.build_payload(&event.clone().with_tag(PRIORITY_TAG, "High"));
+        assert_eq!(payload["level"].as_str(), Some("timeSensitive"));
+        let body = payload["body"].as_str().unwrap_or("");
+        assert!(body.contains("thread_id=t1"), "{body}");
+        assert!(!body.contains(PRIORITY_TAG), "{body}");
+
+        // The tag also beats an explicitly configured level.
+        let fixed =
+            BarkSink::new(BarkConfig::new("k").with_level(BarkLevel::Active)).expect("build sink");
+        let payload = fixed.build_payload(&event.clone().with_tag(PRIORITY_TAG, "critical"));
+        assert_eq!(payload["level"].as_str(), Some("critical"));
+
+        // Unknown values fall back to the severity mapping but are still stripped.
+        let payload = sink.build_payload(&event.clone().with_tag(PRIORITY_TAG, "urgent"));
+        assert_eq!(payload["level"].as_str(), Some("passive"));
+        let body = payload["body"].as_str().unwrap_or("");
+        assert!(!body.contains("urgent"), "{body}");
+
+        // Without the tag nothing changes.
+        let payload = sink.build_payload(&event);
+        assert_eq!(payload["level"].as_str(), Some("passive"));
+
+        let plain = BarkSink::new(BarkConfig::new("k")).expect("build sink");
+        let payload = plain.build_payload(&event.with_tag(PRIORITY_TAG, "low"));
+        assert_eq!(payload["level"].as_str(), Some("passive"));
+    }
+
+    #[cfg(feature = "crypto")]
+    #[test]
+    fn encrypted_form_keeps_device_key_in_clear_and_aligns_ciphertext() {
+        use base64::Engine as _;
+
+        let event = Event::new("turn_completed", Severity::Success, "done").with_body("ok");
+        let cfg = BarkConfig::new("k").with_encryption(
+            "0123456789abcdef",
+            "fedcba9876543210",
+            BarkEncryptionMode::Cbc,
+        );
+        let sink = BarkSink::new(cfg).expect("build sink");
+        let encryption = sink.encryption.clone().expect("encryption configured");
+        let form = sink
+            .build_encrypted_form(&event, &encryption)
+            .expect("build form");
+
+        let get = |name: &str| {
+            form.iter()
+                .find(|(k, _)| *k == name)
+                .map(|(_, v)| v.as_str())
+                .unwrap_or("")
+        };
+        assert_eq!(get("device_key"), "k");
+        assert_eq!(get("iv"), "fedcba9876543210");
+        let ciphertext = base64::engine::general_purpose::STANDARD
+            .decode(get("ciphertext"))
+            .expect("decode ciphertext");
+        assert!(!ciphertext.is_empty());
+        assert_eq!(ciphertext.len() % AES_BLOCK_SIZE, 0);
+    }
+
+    #[cfg(feature = "crypto")]
+    #[test]
+    fn ecb_encrypted_form_omits_iv() {
+        let event = Event::new("turn_completed", Severity::Success, "done");
+        let cfg = BarkConfig::new("k").with_encryption(
+            "0123456789abcdef0123456789abcdef",
+            "",
+            BarkEncryptionMode::Ecb,
+        );
+        let sink = BarkSink::new(cfg).expect("build sink");
+        let encryption = sink.encryption.clone().expect("encryption configured");
+        let form = sink
+            .build_encrypted_form(&event, &encryption)
+            .expect("build form");
+        assert!(form.iter().all(|(k, _)| *k != "iv"), "{form:?}");
+    }
+
+    #[cfg(feature = "crypto")]
+    #[test]
+    fn rejects_invalid_encryption_key_and_iv() {
+        let cfg = BarkConfig::new("k").with_encryption("short", "", BarkEncryptionMode::Ecb);
+        let err = BarkSink::new(cfg).expect_err("expected invalid key");
+        assert!(err.to_string().contains("16, 24 or 32"), "{err:#}");
+
+        let cfg =
+            BarkConfig::new("k").with_encryption("0123456789abcdef", "x", BarkEncryptionMode::Cbc);
+        let err = BarkSink::new(cfg).expect_err("expected invalid iv");
+        assert!(err.to_string().contains("iv"), "{err:#}");
+    }
+
+    #[cfg(feature = "crypto")]
+    #[test]
+    fn debug_redacts_encryption_key() {
+        let cfg = BarkConfig::new("k").with_encryption(
+            "supersecretkey16",
+            "fedcba9876543210",
+            BarkEncryptionMode::Cbc,
+        );
+        let cfg_dbg = format!("{cfg:?}");
+        assert!(!cfg_dbg.contains("supersecretkey16"), "{cfg_dbg}");
+
+        let sink = BarkSink::new(cfg).expect("build sink");
+        let sink_dbg = format!("{sink:?}");
+        assert!(!sink_dbg.contains("supersecretkey16"), "{sink_dbg}");
+    }
+
     #[test]
     fn debug_redacts_device_key() {
         let cfg = BarkConfig::new("secret_key");
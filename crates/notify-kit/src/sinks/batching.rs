@@ -0,0 +1,440 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+use crate::sinks::{BoxFuture, Sink};
+use crate::{Event, Severity};
+
+/// When [`BatchingSink`] flushes its buffer, and how much of it the digest title lists.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchPolicy {
+    /// How long the first buffered event waits for others before the batch is flushed.
+    pub window: Duration,
+    /// Flush as soon as this many events are buffered; `0` is treated as `1`.
+    pub max_events: usize,
+    /// Titles listed in a digest's title before the rest is summarized as `… and <n> more`
+    /// (default 5); `0` is treated as `1`. The body always lists every event.
+    pub max_titles: usize,
+}
+
+impl BatchPolicy {
+    pub fn new(window: Duration, max_events: usize) -> Self {
+        Self {
+            window,
+            max_events,
+            max_titles: 5,
+        }
+    }
+
+    #[must_use]
+    pub fn with_max_titles(mut self, max_titles: usize) -> Self {
+        self.max_titles = max_titles;
+        self
+    }
+}
+
+#[derive(Debug, Default)]
+struct Buffer {
+    events: Vec<Event>,
+    /// Bumped on every flush, so a window timer only flushes the batch it was started for.
+    generation: u64,
+}
+
+impl Buffer {
+    fn take(&mut self) -> Vec<Event> {
+        self.generation += 1;
+        std::mem::take(&mut self.events)
+    }
+}
+
+struct Shared {
+    inner: Arc<dyn Sink>,
+    buffer: Mutex<Buffer>,
+    max_titles: usize,
+}
+
+impl Shared {
+    fn lock(&self) -> std::sync::MutexGuard<'_, Buffer> {
+        self.buffer.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    async fn deliver(&self, events: Vec<Event>) -> crate::Result<()> {
+        match combine(events, self.max_titles) {
+            Some(event) => self.inner.send(&event).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Delivers `events` from a background task, where there is no caller to report errors to.
+    async fn deliver_logged(&self, events: Vec<Event>) {
+        let count = events.len();
+        if let Err(err) = self.deliver(events).await {
+            tracing::warn!(
+                sink = self.inner.name(),
+                events = count,
+                error = %err,
+                "batched send failed"
+            );
+        }
+    }
+}
+
+enum Buffered {
+    /// The batch is full and must be sent now.
+    Full(Vec<Event>),
+    /// First event of a new batch: start the window timer for this generation.
+    Opened(u64),
+    Queued,
+}
+
+/// Sink decorator that coalesces bursts of events into one digest for the wrapped sink.
+///
+/// The first event of a batch opens a [`BatchPolicy::window`]; everything sent until it elapses
+/// is combined into one event and flushed to the inner sink from a background task. A batch that
+/// reaches [`BatchPolicy::max_events`] is flushed right away by the `send` that filled it, which
+/// then reports the inner sink's result; other sends return `Ok(())` once the event is buffered,
+/// and failures of background flushes are only logged.
+///
+/// A batch of one is delivered unchanged. Larger batches become one event with:
+/// - `kind`: the shared kind, or `batch` if they differ
+/// - `severity`: the highest severity in the batch
+/// - `title`: `[<n>] ` followed by the first [`BatchPolicy::max_titles`] titles joined with `; `,
+///   then `… and <m> more` for the rest
+/// - `body`: one `[<severity>] <title>` block per event, with its body and url
+/// - `tags`: the tags every event has with the same value, plus `batch_size`
+///
+/// Attachments are not carried over. Dropping the sink flushes what is buffered on the current
+/// Tokio runtime; shutting the runtime down cancels pending flushes, so call
+/// [`BatchingSink::flush`] before that. Requires a Tokio runtime for the window timer; without
+/// one, events are sent immediately.
+pub struct BatchingSink {
+    shared: Arc<Shared>,
+    policy: BatchPolicy,
+}
+
+impl std::fmt::Debug for BatchingSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BatchingSink")
+            .field("inner", &self.shared.inner.name())
+            .field("policy", &self.policy)
+            .field("buffered", &self.shared.lock().events.len())
+            .finish()
+    }
+}
+
+impl BatchingSink {
+    pub fn new(inner: Arc<dyn Sink>, policy: BatchPolicy) -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                inner,
+                buffer: Mutex::new(Buffer::default()),
+                max_titles: policy.max_titles,
+            }),
+            policy,
+        }
+    }
+
+    /// Sends whatever is buffered now, without waiting for the window.
+    pub async fn flush(&self) -> crate::Result<()> {
+        let events = self.shared.lock().take();
+        self.shared.deliver(events).await
+    }
+
+    fn buffer(&self, event: &Event) -> Buffered {
+        let mut buffer = self.shared.lock();
+        buffer.events.push(event.clone());
+        if buffer.events.len() >= self.policy.max_events.max(1) {
+            Buffered::Full(buffer.take())
+        } else if buffer.events.len() == 1 {
+            Buffered::Opened(buffer.generation)
+        } else {
+            Buffered::Queued
+        }
+    }
+}
+
+impl Sink for BatchingSink {
+    fn name(&self) -> &'static str {
+        self.shared.inner.name()
+    }
+
+    fn send<'a>(&'a self, event: &'a Event) -> BoxFuture<'a, crate::Result<()>> {
+        Box::pin(async move {
+            match self.buffer(event) {
+                Buffered::Full(events) => self.shared.deliver(events).await,
+                Buffered::Opened(generation) => {
+                    let Ok(handle) = tokio::runtime::Handle::try_current() else {
+                        return self.flush().await;
+                    };
+                    let shared = Arc::clone(&self.shared);
+                    let window = self.policy.window;
+                    handle.spawn(async move {
+                        tokio::time::sleep(window).await;
+                        let events = {
+                            let mut buffer = shared.lock();
+                            if buffer.generation != generation {
+                                return;
+                            }
+                            buffer.take()
+                        };
+                        shared.deliver_logged(events).await;
+                    });
+                    Ok(())
+                }
+                Buffered::Queued => Ok(()),
+            }
+        })
+    }
+
+    fn diagnostic_target(&self) -> Option<String> {
+        self.shared.inner.diagnostic_target()
+    }
+
+    fn preview(&self, event: &Event) -> Option<serde_json::Value> {
+        self.shared.inner.preview(event)
+    }
+}
+
+impl Drop for BatchingSink {
+    fn drop(&mut self) {
+        let events = self.shared.lock().take();
+        if events.is_empty() {
+            return;
+        }
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            tracing::warn!(
+                sink = self.shared.inner.name(),
+                events = events.len(),
+                "batched events dropped: no tokio runtime"
+            );
+            return;
+        };
+        let shared = Arc::clone(&self.shared);
+        handle.spawn(async move { shared.deliver_logged(events).await });
+    }
+}
+
+/// Merges a batch into one event; see [`BatchingSink`] for the layout.
+fn combine(mut events: Vec<Event>, max_titles: usize) -> Option<Event> {
+    if events.len() <= 1 {
+        return events.pop();
+    }
+
+    let kind = if events.iter().all(|event| event.kind == events[0].kind) {
+        events[0].kind.clone()
+    } else {
+        "batch".to_string()
+    };
+    let severity = events
+        .iter()
+        .map(|event| event.severity)
+        .max()
+        .unwrap_or(Severity::Info);
+    let max_titles = max_titles.max(1);
+    let titles: Vec<&str> = events
+        .iter()
+        .take(max_titles)
+        .map(|event| event.title.trim())
+        .collect();
+    let mut title = format!("[{}] {}", events.len(), titles.join("; "));
+    if events.len() > max_titles {
+        title.push_str(&format!(" … and {} more", events.len() - max_titles));
+    }
+
+    let body = events
+        .iter()
+        .map(|event| {
+            let mut block = format!("[{}] {}", event.severity, event.title.trim());
+            for line in [&event.body, &event.url].into_iter().flatten() {
+                let line = line.trim();
+                if !line.is_empty() {
+                    block.push('\n');
+                    block.push_str(line);
+                }
+            }
+            block
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+    let mut tags: BTreeMap<String, String> = events[0].tags.clone();
+    tags.retain(|key, value| {
+        events[1..]
+            .iter()
+            .all(|event| event.tags.get(key) == Some(value))
+    });
+
+    let mut combined = Event::new(kind, severity, title).with_body(body);
+    for (key, value) in tags {
+        combined = combined.with_tag(key, value);
+    }
+    Some(combined.with_tag("batch_size", events.len().to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Default)]
+    struct CapturingSink {
+        events: Mutex<Vec<Event>>,
+    }
+
+    impl CapturingSink {
+        fn events(&self) -> Vec<Event> {
+            self.events.lock().expect("lock events").clone()
+        }
+    }
+
+    impl Sink for CapturingSink {
+        fn name(&self) -> &'static str {
+            "capture"
+        }
+
+        fn send<'a>(&'a self, event: &'a Event) -> BoxFuture<'a, crate::Result<()>> {
+            Box::pin(async move {
+                self.events.lock().expect("lock events").push(event.clone());
+                Ok(())
+            })
+        }
+    }
+
+    /// A runtime whose clock only moves on [`advance`].
+    fn runtime() -> tokio::runtime::Runtime {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .start_paused(true)
+            .build()
+            .expect("build tokio runtime")
+    }
+
+    /// Lets spawned window timers start, moves the paused clock forward and lets the woken
+    /// flush tasks run.
+    async fn advance(duration: Duration) {
+        tokio::task::yield_now().await;
+        tokio::time::advance(duration).await;
+        tokio::task::yield_now().await;
+    }
+
+    #[test]
+    fn events_within_window_are_sent_as_one_digest() {
+        let inner = Arc::new(CapturingSink::default());
+        let sink = BatchingSink::new(
+            inner.clone(),
+            BatchPolicy::new(Duration::from_millis(50), 100),
+        );
+
+        runtime().block_on(async {
+            for (i, severity) in [Severity::Info, Severity::Error, Severity::Warning]
+                .into_iter()
+                .enumerate()
+            {
+                let event = Event::new("disk", severity, format!("host{i} disk full"))
+                    .with_body(format!("{}% used", 90 + i))
+                    .with_tag("cluster", "eu-1")
+                    .with_tag("host", format!("host{i}"));
+                sink.send(&event).await.expect("buffer");
+            }
+            advance(Duration::from_millis(49)).await;
+            assert!(inner.events().is_empty());
+            advance(Duration::from_millis(1)).await;
+        });
+
+        let events = inner.events();
+        assert_eq!(events.len(), 1);
+        let digest = &events[0];
+        assert_eq!(digest.kind, "disk");
+        assert_eq!(digest.severity, Severity::Error);
+        assert_eq!(
+            digest.title,
+            "[3] host0 disk full; host1 disk full; host2 disk full"
+        );
+        assert_eq!(
+            digest.body.as_deref(),
+            Some(
+                "[info] host0 disk full\n90% used\n\n[error] host1 disk full\n91% used\n\n[warning] host2 disk full\n92% used"
+            )
+        );
+        assert_eq!(digest.tags.get("cluster").map(String::as_str), Some("eu-1"));
+        assert_eq!(digest.tags.get("batch_size").map(String::as_str), Some("3"));
+        assert!(!digest.tags.contains_key("host"));
+    }
+
+    #[test]
+    fn full_batch_is_sent_immediately_and_flush_drains_the_rest() {
+        let inner = Arc::new(CapturingSink::default());
+        let sink = BatchingSink::new(
+            inner.clone(),
+            BatchPolicy::new(Duration::from_secs(3600), 2),
+        );
+
+        runtime().block_on(async {
+            sink.send(&Event::new("a", Severity::Info, "one"))
+                .await
+                .expect("buffer");
+            sink.send(&Event::new("b", Severity::Info, "two"))
+                .await
+                .expect("flush full batch");
+            assert_eq!(inner.events().len(), 1);
+
+            sink.send(&Event::new("c", Severity::Success, "three"))
+                .await
+                .expect("buffer");
+            sink.flush().await.expect("flush");
+            sink.flush().await.expect("empty flush is a no-op");
+        });
+
+        let events = inner.events();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].kind, "batch");
+        assert_eq!(events[0].title, "[2] one; two");
+        // A batch of one is passed through unchanged.
+        assert_eq!(events[1], Event::new("c", Severity::Success, "three"));
+        assert_eq!(sink.name(), "capture");
+    }
+
+    #[test]
+    fn dropping_the_sink_flushes_buffered_events() {
+        let inner = Arc::new(CapturingSink::default());
+
+        runtime().block_on(async {
+            let sink = BatchingSink::new(
+                inner.clone(),
+                BatchPolicy::new(Duration::from_secs(3600), 10),
+            );
+            for title in ["one", "two"] {
+                sink.send(&Event::new("k", Severity::Info, title))
+                    .await
+                    .expect("buffer");
+            }
+            assert!(format!("{sink:?}").contains("buffered: 2"));
+            drop(sink);
+            tokio::task::yield_now().await;
+        });
+
+        let events = inner.events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].title, "[2] one; two");
+    }
+
+    #[test]
+    fn digest_title_lists_the_first_titles_and_counts_the_rest() {
+        let events: Vec<Event> = (1..=8)
+            .map(|i| Event::new("k", Severity::Info, format!("e{i}")))
+            .collect();
+        let digest = combine(events.clone(), 3).expect("digest");
+        assert_eq!(digest.title, "[8] e1; e2; e3 … and 5 more");
+        assert!(
+            digest
+                .body
+                .as_deref()
+                .is_some_and(|body| body.contains("e8"))
+        );
+
+        let digest = combine(events[..3].to_vec(), 3).expect("digest");
+        assert_eq!(digest.title, "[3] e1; e2; e3");
+        let digest = combine(events[..2].to_vec(), 0).expect("digest");
+        assert_eq!(digest.title, "[2] e1 … and 1 more");
+    }
+}
@@ -0,0 +1,157 @@
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+use crate::Event;
+use crate::sinks::{BoxFuture, Sink};
+
+/// An in-memory sink for tests: records every event it receives, optionally after a delay and
+/// optionally reporting failure.
+///
+/// Clones share the same recorded events, so keep a clone (or the `Arc`) after handing one to
+/// a [`crate::Hub`] and assert on [`CapturingSink::events`] once sends complete.
+#[derive(Clone, Debug)]
+pub struct CapturingSink {
+    name: &'static str,
+    events: Arc<Mutex<Vec<Event>>>,
+    failure: Option<String>,
+    delay: Duration,
+}
+
+impl Default for CapturingSink {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CapturingSink {
+    pub fn new() -> Self {
+        Self {
+            name: "capturing",
+            events: Arc::new(Mutex::new(Vec::new())),
+            failure: None,
+            delay: Duration::ZERO,
+        }
+    }
+
+    /// Overrides [`Sink::name`] (default `capturing`), e.g. to tell several sinks apart in a
+    /// [`crate::Hub`] error.
+    #[must_use]
+    pub fn with_name(mut self, name: &'static str) -> Self {
+        self.name = name;
+        self
+    }
+
+    /// Makes every send fail with `message`; the event is still recorded first.
+    #[must_use]
+    pub fn with_failure(mut self, message: impl Into<String>) -> Self {
+        self.failure = Some(message.into());
+        self
+    }
+
+    /// Sleeps for `delay` before recording each event, e.g. to exercise hub timeouts.
+    #[must_use]
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// A snapshot of the events received so far, in arrival order.
+    pub fn events(&self) -> Vec<Event> {
+        self.events
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// The shared event log, for callers that want to inspect or clear it in place.
+    pub fn events_handle(&self) -> Arc<Mutex<Vec<Event>>> {
+        Arc::clone(&self.events)
+    }
+}
+
+impl Sink for CapturingSink {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn send<'a>(&'a self, event: &'a Event) -> BoxFuture<'a, crate::Result<()>> {
+        Box::pin(async move {
+            if !self.delay.is_zero() {
+                tokio::time::sleep(self.delay).await;
+            }
+            self.events
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push(event.clone());
+            match &self.failure {
+                Some(message) => Err(anyhow::anyhow!("{message}").into()),
+                None => Ok(()),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Hub, HubConfig, Severity};
+
+    fn block_on<F: std::future::Future>(fut: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime")
+            .block_on(fut)
+    }
+
+    #[test]
+    fn captures_events_sent_through_a_hub() {
+        let sink = CapturingSink::new();
+        let hub = Hub::new(HubConfig::default(), vec![Arc::new(sink.clone())]);
+
+        block_on(async {
+            hub.send(Event::new("first", Severity::Info, "one"))
+                .await
+                .expect("send first");
+            hub.send(Event::new("second", Severity::Error, "two"))
+                .await
+                .expect("send second");
+        });
+
+        let events = sink.events();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].kind, "first");
+        assert_eq!(events[1].title, "two");
+        assert_eq!(events[1].severity, Severity::Error);
+
+        sink.events_handle()
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+        assert!(sink.events().is_empty());
+    }
+
+    #[test]
+    fn configured_failure_is_reported_after_recording() {
+        let sink = CapturingSink::new()
+            .with_name("flaky")
+            .with_failure("smtp down");
+        let event = Event::new("kind", Severity::Warning, "title");
+
+        let err = block_on(sink.send(&event)).expect_err("expected failure");
+        assert!(err.to_string().contains("smtp down"), "{err}");
+        assert_eq!(sink.name(), "flaky");
+        assert_eq!(sink.events(), vec![event]);
+    }
+
+    #[test]
+    fn delay_is_applied_before_recording() {
+        let sink = CapturingSink::new().with_delay(Duration::from_millis(20));
+        let event = Event::new("kind", Severity::Info, "title");
+
+        let started = std::time::Instant::now();
+        block_on(sink.send(&event)).expect("send ok");
+        assert!(started.elapsed() >= Duration::from_millis(20));
+        assert_eq!(sink.events().len(), 1);
+    }
+}
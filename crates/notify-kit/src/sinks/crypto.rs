@@ -1,6 +1,13 @@
+#[cfg(feature = "crypto")]
+use aes::cipher::block_padding::Pkcs7;
+#[cfg(feature = "crypto")]
+use aes::cipher::{BlockCipher, BlockDecryptMut, BlockEncryptMut};
 use base64::Engine as _;
 use hmac::Mac as _;
 
+#[cfg(feature = "crypto")]
+pub(crate) const AES_BLOCK_SIZE: usize = 16;
+
 pub(crate) fn hmac_sha256_base64(secret: &str, message: &str) -> crate::Result<String> {
     type HmacSha256 = hmac::Hmac<sha2::Sha256>;
 
@@ -11,3 +18,138 @@ pub(crate) fn hmac_sha256_base64(secret: &str, message: &str) -> crate::Result<S
     let out = mac.finalize().into_bytes();
     Ok(base64::engine::general_purpose::STANDARD.encode(out))
 }
+
+/// Constant-time check of an HMAC-SHA256 tag over `message`.
+pub(crate) fn hmac_sha256_verify(
+    secret: &[u8],
+    message: &[u8],
+    expected_tag: &[u8],
+) -> crate::Result<bool> {
+    type HmacSha256 = hmac::Hmac<sha2::Sha256>;
+
+    let mut mac = HmacSha256::new_from_slice(secret)
+        .map_err(|err| anyhow::anyhow!("init hmac-sha256: {err}"))?;
+    mac.update(message);
+    Ok(mac.verify_slice(expected_tag).is_ok())
+}
+
+pub(crate) fn sha256(data: &[u8]) -> [u8; 32] {
+    use sha2::Digest as _;
+
+    sha2::Sha256::digest(data).into()
+}
+
+/// Compares two byte strings without short-circuiting on the first difference.
+pub(crate) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(feature = "crypto")]
+pub(crate) fn is_valid_aes_key_len(len: usize) -> bool {
+    matches!(len, 16 | 24 | 32)
+}
+
+/// AES encrypt with PKCS#7 padding and return standard base64.
+///
+/// `iv = Some(..)` selects CBC, `None` selects ECB. The key length picks AES-128/192/256.
+#[cfg(feature = "crypto")]
+pub(crate) fn aes_encrypt_base64(
+    key: &[u8],
+    iv: Option<&[u8]>,
+    plaintext: &[u8],
+) -> crate::Result<String> {
+    let out = match key.len() {
+        16 => aes_encrypt::<aes::Aes128>(key, iv, plaintext)?,
+        24 => aes_encrypt::<aes::Aes192>(key, iv, plaintext)?,
+        32 => aes_encrypt::<aes::Aes256>(key, iv, plaintext)?,
+        _ => return Err(anyhow::anyhow!("aes key must be 16, 24 or 32 bytes").into()),
+    };
+    Ok(base64::engine::general_purpose::STANDARD.encode(out))
+}
+
+/// AES-256-CBC decrypt with PKCS#7 padding.
+#[cfg(feature = "crypto")]
+pub(crate) fn aes256_cbc_decrypt(
+    key: &[u8],
+    iv: &[u8],
+    ciphertext: &[u8],
+) -> crate::Result<Vec<u8>> {
+    use aes::cipher::KeyIvInit as _;
+
+    let cipher = cbc::Decryptor::<aes::Aes256>::new_from_slices(key, iv)
+        .map_err(|_| anyhow::anyhow!("aes-256-cbc requires a 32-byte key and a 16-byte iv"))?;
+    cipher
+        .decrypt_padded_vec_mut::<Pkcs7>(ciphertext)
+        .map_err(|_| anyhow::anyhow!("aes-256-cbc decrypt failed (bad key or padding)").into())
+}
+
+#[cfg(feature = "crypto")]
+fn aes_encrypt<C>(key: &[u8], iv: Option<&[u8]>, plaintext: &[u8]) -> crate::Result<Vec<u8>>
+where
+    C: BlockCipher + BlockEncryptMut + aes::cipher::KeyInit,
+{
+    use aes::cipher::{KeyInit as _, KeyIvInit as _};
+
+    match iv {
+        Some(iv) => {
+            let cipher = cbc::Encryptor::<C>::new_from_slices(key, iv)
+                .map_err(|_| anyhow::anyhow!("aes-cbc requires a 16-byte iv"))?;
+            Ok(cipher.encrypt_padded_vec_mut::<Pkcs7>(plaintext))
+        }
+        None => {
+            let cipher = ecb::Encryptor::<C>::new_from_slice(key)
+                .map_err(|_| anyhow::anyhow!("aes key must be 16, 24 or 32 bytes"))?;
+            Ok(cipher.encrypt_padded_vec_mut::<Pkcs7>(plaintext))
+        }
+    }
+}
+
+#[cfg(all(test, feature = "crypto"))]
+mod tests {
+    use aes::cipher::KeyIvInit as _;
+
+    use super::*;
+
+    #[test]
+    fn aes_cbc_output_is_block_aligned_and_round_trips() {
+        let key = b"0123456789abcdef0123456789abcdef";
+        let iv = b"fedcba9876543210";
+        let plaintext = br#"{"title":"done","body":"ok"}"#;
+
+        let encoded = aes_encrypt_base64(key, Some(iv), plaintext).expect("encrypt");
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(&encoded)
+            .expect("decode base64");
+        assert_eq!(bytes.len() % AES_BLOCK_SIZE, 0);
+        assert!(bytes.len() > plaintext.len());
+
+        let decrypted = cbc::Decryptor::<aes::Aes256>::new_from_slices(key, iv)
+            .expect("init decryptor")
+            .decrypt_padded_vec_mut::<Pkcs7>(&bytes)
+            .expect("decrypt");
+        assert_eq!(decrypted, plaintext);
+    }
+
+    #[test]
+    fn aes_ecb_matches_known_vector() {
+        // Full-block PKCS#7 padding appends one extra block.
+        let encoded = aes_encrypt_base64(b"0123456789abcdef", None, b"").expect("encrypt");
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(&encoded)
+            .expect("decode base64");
+        assert_eq!(bytes.len(), AES_BLOCK_SIZE);
+
+        let encoded_again = aes_encrypt_base64(b"0123456789abcdef", None, b"").expect("encrypt");
+        assert_eq!(encoded, encoded_again, "ecb must be deterministic");
+    }
+
+    #[test]
+    fn aes_rejects_invalid_key_and_iv_lengths() {
+        let err = aes_encrypt_base64(b"short", None, b"x").expect_err("invalid key");
+        assert!(err.to_string().contains("16, 24 or 32"), "{err:#}");
+
+        let err =
+            aes_encrypt_base64(b"0123456789abcdef", Some(b"short"), b"x").expect_err("invalid iv");
+        assert!(err.to_string().contains("16-byte iv"), "{err:#}");
+    }
+}
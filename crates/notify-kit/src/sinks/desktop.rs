@@ -0,0 +1,175 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use notify_rust::{Notification, Urgency};
+
+use crate::Event;
+use crate::event::Severity;
+use crate::sinks::text::truncate_chars;
+use crate::sinks::{BoxFuture, Sink};
+
+const DESKTOP_MAX_SUMMARY_CHARS: usize = 256;
+const DESKTOP_MAX_BODY_CHARS: usize = 1000;
+
+static WARNED_DESKTOP_UNAVAILABLE: AtomicBool = AtomicBool::new(false);
+
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub struct DesktopConfig {
+    /// Application name shown by the notification server.
+    pub app_name: String,
+}
+
+impl Default for DesktopConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DesktopConfig {
+    pub fn new() -> Self {
+        Self {
+            app_name: "notify-kit".to_string(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_app_name(mut self, app_name: impl Into<String>) -> Self {
+        self.app_name = app_name.into();
+        self
+    }
+}
+
+/// Native desktop notification (D-Bus on Linux/BSD, Notification Center on macOS, toast on
+/// Windows): the title is the summary, the body (and `url`) the text.
+///
+/// Where no notification service is reachable (headless hosts, CI), sends log a warning once
+/// and succeed, like [`crate::SoundSink`] falling back to the terminal bell.
+#[derive(Debug)]
+pub struct DesktopSink {
+    app_name: String,
+}
+
+impl DesktopSink {
+    pub fn new(config: DesktopConfig) -> crate::Result<Self> {
+        let app_name = config.app_name.trim();
+        if app_name.is_empty() {
+            return Err(anyhow::anyhow!("desktop app_name must not be empty").into());
+        }
+        Ok(Self {
+            app_name: app_name.to_string(),
+        })
+    }
+
+    #[cfg_attr(not(all(unix, not(target_os = "macos"))), allow(dead_code))]
+    fn urgency(severity: Severity) -> Urgency {
+        match severity {
+            Severity::Error => Urgency::Critical,
+            Severity::Warning => Urgency::Normal,
+            Severity::Info | Severity::Success => Urgency::Low,
+        }
+    }
+
+    fn build_notification(&self, event: &Event) -> Notification {
+        let mut summary = truncate_chars(event.title.trim(), DESKTOP_MAX_SUMMARY_CHARS);
+        if summary.is_empty() {
+            summary = event.kind.clone();
+        }
+
+        let mut body = String::new();
+        if let Some(text) = event.body.as_deref().map(str::trim) {
+            body.push_str(text);
+        }
+        if let Some(url) = event
+            .url
+            .as_deref()
+            .map(str::trim)
+            .filter(|u| !u.is_empty())
+        {
+            if !body.is_empty() {
+                body.push('\n');
+            }
+            body.push_str(url);
+        }
+
+        let mut notification = Notification::new();
+        notification
+            .appname(&self.app_name)
+            .summary(&summary)
+            .body(&truncate_chars(&body, DESKTOP_MAX_BODY_CHARS));
+        #[cfg(all(unix, not(target_os = "macos")))]
+        notification.urgency(Self::urgency(event.severity));
+        notification
+    }
+}
+
+impl Sink for DesktopSink {
+    fn name(&self) -> &'static str {
+        "desktop"
+    }
+
+    fn send<'a>(&'a self, event: &'a Event) -> BoxFuture<'a, crate::Result<()>> {
+        Box::pin(async move {
+            let notification = self.build_notification(event);
+            let shown = tokio::task::spawn_blocking(move || notification.show().map(|_| ()))
+                .await
+                .map_err(|err| anyhow::anyhow!("desktop notification task failed: {err}"))?;
+
+            if let Err(err) = shown {
+                if !WARNED_DESKTOP_UNAVAILABLE.swap(true, Ordering::Relaxed) {
+                    tracing::warn!(
+                        sink = "desktop",
+                        error = %err,
+                        "desktop notifications unavailable; dropping desktop notifications"
+                    );
+                }
+            }
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_with_trimmed_app_name_and_rejects_empty() {
+        let sink =
+            DesktopSink::new(DesktopConfig::new().with_app_name("  my-cli  ")).expect("build sink");
+        assert_eq!(sink.app_name, "my-cli");
+        assert_eq!(sink.name(), "desktop");
+
+        let sink = DesktopSink::new(DesktopConfig::default()).expect("build sink");
+        assert_eq!(sink.app_name, "notify-kit");
+
+        let err = DesktopSink::new(DesktopConfig::new().with_app_name(" ")).expect_err("empty");
+        assert!(err.to_string().contains("app_name"), "{err:#}");
+    }
+
+    #[test]
+    fn maps_severity_to_urgency() {
+        assert_eq!(DesktopSink::urgency(Severity::Info), Urgency::Low);
+        assert_eq!(DesktopSink::urgency(Severity::Success), Urgency::Low);
+        assert_eq!(DesktopSink::urgency(Severity::Warning), Urgency::Normal);
+        assert_eq!(DesktopSink::urgency(Severity::Error), Urgency::Critical);
+    }
+
+    #[test]
+    fn notification_uses_title_as_summary_and_body_with_url() {
+        let sink = DesktopSink::new(DesktopConfig::new()).expect("build sink");
+        let event = Event::new("build", Severity::Error, "build failed")
+            .with_body("exit code 1")
+            .with_url("https://ci.example.com/runs/42");
+        let notification = sink.build_notification(&event);
+        assert_eq!(notification.summary, "build failed");
+        assert_eq!(
+            notification.body,
+            "exit code 1\nhttps://ci.example.com/runs/42"
+        );
+        assert_eq!(notification.appname, "notify-kit");
+
+        let notification = sink.build_notification(&Event::new("build", Severity::Info, " "));
+        assert_eq!(notification.summary, "build");
+        assert_eq!(notification.body, "");
+    }
+}
@@ -1,13 +1,15 @@
+use std::sync::Arc;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use crate::Event;
+use crate::render::EventFormatter;
 use crate::sinks::crypto::hmac_sha256_base64;
 use crate::sinks::http::{
-    DEFAULT_MAX_RESPONSE_BODY_BYTES, build_http_client, parse_and_validate_https_url,
+    build_http_client, max_response_body_bytes, parse_and_validate_https_url,
     read_json_body_limited, read_text_body_limited, redact_url, redact_url_str, select_http_client,
     send_reqwest, validate_url_path_prefix,
 };
-use crate::sinks::text::{TextLimits, format_event_text_limited, truncate_chars};
+use crate::sinks::text::{TextLimits, format_event_text_with, truncate_chars};
 use crate::sinks::{BoxFuture, Sink};
 
 const DINGTALK_ALLOWED_HOSTS: [&str; 1] = ["oapi.dingtalk.com"];
@@ -18,7 +20,10 @@ pub struct DingTalkWebhookConfig {
     pub webhook_url: String,
     pub secret: Option<String>,
     pub timeout: Duration,
+    /// Message length limit; `0` means unlimited (see [`crate::MaxChars`]).
     pub max_chars: usize,
+    /// Custom message layout; `None` uses the built-in one ([`crate::DefaultFormatter`]).
+    pub formatter: Option<Arc<dyn EventFormatter>>,
     pub enforce_public_ip: bool,
 }
 
@@ -29,6 +34,7 @@ impl std::fmt::Debug for DingTalkWebhookConfig {
             .field("secret", &self.secret.as_ref().map(|_| "<redacted>"))
             .field("timeout", &self.timeout)
             .field("max_chars", &self.max_chars)
+            .field("custom_formatter", &self.formatter.is_some())
             .field("enforce_public_ip", &self.enforce_public_ip)
             .finish()
     }
@@ -41,6 +47,7 @@ impl DingTalkWebhookConfig {
             secret: None,
             timeout: Duration::from_secs(2),
             max_chars: 4000,
+            formatter: None,
             enforce_public_ip: true,
         }
     }
@@ -63,6 +70,12 @@ impl DingTalkWebhookConfig {
         self
     }
 
+    #[must_use]
+    pub fn with_formatter(mut self, formatter: Arc<dyn EventFormatter>) -> Self {
+        self.formatter = Some(formatter);
+        self
+    }
+
     #[must_use]
     pub fn with_public_ip_check(mut self, enforce_public_ip: bool) -> Self {
         self.enforce_public_ip = enforce_public_ip;
@@ -76,6 +89,7 @@ pub struct DingTalkWebhookSink {
     client: reqwest::Client,
     timeout: Duration,
     max_chars: usize,
+    formatter: Option<Arc<dyn EventFormatter>>,
     enforce_public_ip: bool,
 }
 
@@ -85,6 +99,7 @@ impl std::fmt::Debug for DingTalkWebhookSink {
             .field("webhook_url", &redact_url(&self.webhook_url))
             .field("secret", &self.secret.as_ref().map(|_| "<redacted>"))
             .field("max_chars", &self.max_chars)
+            .field("custom_formatter", &self.formatter.is_some())
             .finish_non_exhaustive()
     }
 }
@@ -96,6 +111,7 @@ impl DingTalkWebhookSink {
             secret,
             timeout,
             max_chars,
+            formatter,
             enforce_public_ip,
         } = config;
 
@@ -115,12 +131,17 @@ impl DingTalkWebhookSink {
             client,
             timeout,
             max_chars,
+            formatter,
             enforce_public_ip,
         })
     }
 
-    fn build_payload(event: &Event, max_chars: usize) -> serde_json::Value {
-        let text = format_event_text_limited(event, TextLimits::new(max_chars));
+    fn build_payload(
+        event: &Event,
+        max_chars: usize,
+        formatter: Option<&dyn EventFormatter>,
+    ) -> serde_json::Value {
+        let text = format_event_text_with(formatter, event, TextLimits::new(max_chars));
         serde_json::json!({
             "msgtype": "text",
             "text": { "content": text },
@@ -196,36 +217,39 @@ impl Sink for DingTalkWebhookSink {
             let client =
                 select_http_client(&self.client, self.timeout, &url, self.enforce_public_ip)
                     .await?;
-            let payload = Self::build_payload(event, self.max_chars);
+            let payload = Self::build_payload(event, self.max_chars, self.formatter.as_deref());
 
             let resp = send_reqwest(client.post(url).json(&payload), "dingtalk webhook").await?;
 
             let status = resp.status();
             if !status.is_success() {
-                let body = match read_text_body_limited(resp, DEFAULT_MAX_RESPONSE_BODY_BYTES).await
-                {
+                let body = match read_text_body_limited(resp, max_response_body_bytes()).await {
                     Ok(body) => body,
                     Err(err) => {
-                        return Err(anyhow::anyhow!(
-                            "dingtalk webhook http error: {status} (failed to read response body: {err})"
-                        )
-                        .into());
+                        return Err(crate::Error::http_status(
+                            status,
+                            anyhow::anyhow!(
+                                "dingtalk webhook http error: {status} (failed to read response body: {err})"
+                            ),
+                        ));
                     }
                 };
                 let summary = truncate_chars(body.trim(), 200);
                 if summary.is_empty() {
-                    return Err(anyhow::anyhow!(
-                        "dingtalk webhook http error: {status} (response body omitted)"
-                    )
-                    .into());
+                    return Err(crate::Error::http_status(
+                        status,
+                        anyhow::anyhow!(
+                            "dingtalk webhook http error: {status} (response body omitted)"
+                        ),
+                    ));
                 }
-                return Err(anyhow::anyhow!(
-                    "dingtalk webhook http error: {status}, response={summary}"
-                )
-                .into());
+                return Err(crate::Error::http_status(
+                    status,
+                    anyhow::anyhow!("dingtalk webhook http error: {status}, response={summary}"),
+                ));
             }
 
-            let body = read_json_body_limited(resp, DEFAULT_MAX_RESPONSE_BODY_BYTES).await?;
+            let body = read_json_body_limited(resp, max_response_body_bytes()).await?;
             let errcode = body["errcode"].as_i64().unwrap_or(-1);
             if errcode == 0 {
                 return Ok(());
@@ -237,6 +261,18 @@ impl Sink for DingTalkWebhookSink {
             )
         })
     }
+
+    fn preview(&self, event: &Event) -> Option<serde_json::Value> {
+        Some(Self::build_payload(
+            event,
+            self.max_chars,
+            self.formatter.as_deref(),
+        ))
+    }
+
+    fn diagnostic_target(&self) -> Option<String> {
+        Some(redact_url(&self.webhook_url))
+    }
 }
 
 #[cfg(test)]
@@ -250,7 +286,7 @@ mod tests {
             .with_body("ok")
             .with_tag("thread_id", "t1");
 
-        let payload = DingTalkWebhookSink::build_payload(&event, 4000);
+        let payload = DingTalkWebhookSink::build_payload(&event, 4000, None);
         assert_eq!(payload["msgtype"].as_str().unwrap_or(""), "text");
         let text = payload["text"]["content"].as_str().unwrap_or("");
         assert!(text.contains("done"));
@@ -1,23 +1,133 @@
+use std::sync::Arc;
 use std::time::Duration;
 
 use crate::Event;
+use crate::render::EventFormatter;
 use crate::sinks::http::{
-    DEFAULT_MAX_RESPONSE_BODY_BYTES, build_http_client, parse_and_validate_https_url,
+    build_http_client, max_response_body_bytes, parse_and_validate_https_url,
     read_text_body_limited, redact_url, redact_url_str, select_http_client, send_reqwest,
     try_drain_response_body_for_reuse, validate_url_path_prefix,
 };
-use crate::sinks::text::{TextLimits, format_event_text_limited, truncate_chars};
+use crate::sinks::text::{TextLimits, event_link_url, format_event_text_with, truncate_chars};
 use crate::sinks::{BoxFuture, Sink};
 
 const DISCORD_ALLOWED_HOSTS: [&str; 2] = ["discord.com", "discordapp.com"];
 
+/// Mention type Discord may parse out of the message content (see [`DiscordAllowedMentions`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DiscordMentionType {
+    Roles,
+    Users,
+    /// `@everyone` and `@here`.
+    Everyone,
+}
+
+impl DiscordMentionType {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Roles => "roles",
+            Self::Users => "users",
+            Self::Everyone => "everyone",
+        }
+    }
+}
+
+/// Which mentions in a message actually ping, sent as Discord's `allowed_mentions`.
+///
+/// The default allows none, so `@everyone` or `<@id>` in an event body is shown but never
+/// notifies anyone. Opt in per type with [`DiscordAllowedMentions::with_parse`], or allow
+/// specific role / user IDs only.
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DiscordAllowedMentions {
+    /// Mention types parsed from the content.
+    pub parse: Vec<DiscordMentionType>,
+    /// Role IDs that may be pinged; must be empty if `parse` contains `Roles`.
+    pub roles: Vec<String>,
+    /// User IDs that may be pinged; must be empty if `parse` contains `Users`.
+    pub users: Vec<String>,
+}
+
+impl DiscordAllowedMentions {
+    /// Allows no mentions (the default).
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn with_parse(mut self, mention: DiscordMentionType) -> Self {
+        if !self.parse.contains(&mention) {
+            self.parse.push(mention);
+        }
+        self
+    }
+
+    #[must_use]
+    pub fn with_role(mut self, role_id: impl Into<String>) -> Self {
+        self.roles.push(role_id.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_user(mut self, user_id: impl Into<String>) -> Self {
+        self.users.push(user_id.into());
+        self
+    }
+
+    fn validate(&self) -> crate::Result<()> {
+        // Discord rejects the whole message when a type is both parsed and listed by ID.
+        let conflicts = [
+            (DiscordMentionType::Roles, &self.roles),
+            (DiscordMentionType::Users, &self.users),
+        ];
+        for (mention, ids) in conflicts {
+            if self.parse.contains(&mention) && !ids.is_empty() {
+                return Err(crate::Error::invalid_config(anyhow::anyhow!(
+                    "discord allowed_mentions: `{}` is parsed, so explicit ids must be empty",
+                    mention.as_str()
+                )));
+            }
+            if ids.iter().any(|id| id.trim().is_empty()) {
+                return Err(crate::Error::invalid_config(anyhow::anyhow!(
+                    "discord allowed_mentions: {} ids must not be empty",
+                    mention.as_str()
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        let parse: Vec<&str> = self.parse.iter().map(|mention| mention.as_str()).collect();
+        let mut obj = serde_json::Map::new();
+        obj.insert("parse".to_string(), serde_json::json!(parse));
+        if !self.roles.is_empty() {
+            obj.insert("roles".to_string(), serde_json::json!(self.roles));
+        }
+        if !self.users.is_empty() {
+            obj.insert("users".to_string(), serde_json::json!(self.users));
+        }
+        serde_json::Value::Object(obj)
+    }
+}
+
 #[non_exhaustive]
 #[derive(Clone)]
 pub struct DiscordWebhookConfig {
     pub webhook_url: String,
     pub timeout: Duration,
+    /// Message length limit; `0` means unlimited (see [`crate::MaxChars`]).
     pub max_chars: usize,
+    /// Custom message layout; `None` uses the built-in one ([`crate::DefaultFormatter`]).
+    pub formatter: Option<Arc<dyn EventFormatter>>,
     pub enforce_public_ip: bool,
+    /// Overrides the webhook's display name for each message.
+    pub username: Option<String>,
+    /// Overrides the webhook's avatar for each message; must be an `https` URL.
+    pub avatar_url: Option<String>,
+    /// Mentions that may ping; defaults to none.
+    pub allowed_mentions: DiscordAllowedMentions,
 }
 
 impl std::fmt::Debug for DiscordWebhookConfig {
@@ -26,7 +136,11 @@ impl std::fmt::Debug for DiscordWebhookConfig {
             .field("webhook_url", &redact_url_str(&self.webhook_url))
             .field("timeout", &self.timeout)
             .field("max_chars", &self.max_chars)
+            .field("custom_formatter", &self.formatter.is_some())
             .field("enforce_public_ip", &self.enforce_public_ip)
+            .field("username", &self.username)
+            .field("avatar_url", &self.avatar_url)
+            .field("allowed_mentions", &self.allowed_mentions)
             .finish()
     }
 }
@@ -37,7 +151,11 @@ impl DiscordWebhookConfig {
             webhook_url: webhook_url.into(),
             timeout: Duration::from_secs(2),
             max_chars: 2000,
+            formatter: None,
             enforce_public_ip: true,
+            username: None,
+            avatar_url: None,
+            allowed_mentions: DiscordAllowedMentions::none(),
         }
     }
 
@@ -53,11 +171,77 @@ impl DiscordWebhookConfig {
         self
     }
 
+    #[must_use]
+    pub fn with_formatter(mut self, formatter: Arc<dyn EventFormatter>) -> Self {
+        self.formatter = Some(formatter);
+        self
+    }
+
     #[must_use]
     pub fn with_public_ip_check(mut self, enforce_public_ip: bool) -> Self {
         self.enforce_public_ip = enforce_public_ip;
         self
     }
+
+    #[must_use]
+    pub fn with_username(mut self, username: impl Into<String>) -> Self {
+        self.username = Some(username.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_avatar_url(mut self, avatar_url: impl Into<String>) -> Self {
+        self.avatar_url = Some(avatar_url.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_allowed_mentions(mut self, allowed_mentions: DiscordAllowedMentions) -> Self {
+        self.allowed_mentions = allowed_mentions;
+        self
+    }
+}
+
+/// Per-message `username` / `avatar_url` overrides; blank values are dropped.
+#[derive(Debug, Clone, Default)]
+struct DiscordIdentity {
+    username: Option<String>,
+    avatar_url: Option<String>,
+}
+
+impl DiscordIdentity {
+    fn from_config(config: &DiscordWebhookConfig) -> crate::Result<Self> {
+        let normalize = |value: &Option<String>| {
+            value
+                .as_deref()
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+        let avatar_url = normalize(&config.avatar_url);
+        if let Some(avatar_url) = avatar_url.as_deref() {
+            let valid = reqwest::Url::parse(avatar_url)
+                .is_ok_and(|url| url.scheme() == "https" && url.host_str().is_some());
+            if !valid {
+                return Err(crate::Error::invalid_config(anyhow::anyhow!(
+                    "discord avatar_url must be a valid https url"
+                )));
+            }
+        }
+        Ok(Self {
+            username: normalize(&config.username),
+            avatar_url,
+        })
+    }
+
+    fn apply(&self, payload: &mut serde_json::Map<String, serde_json::Value>) {
+        if let Some(username) = &self.username {
+            payload.insert("username".to_string(), serde_json::json!(username));
+        }
+        if let Some(avatar_url) = &self.avatar_url {
+            payload.insert("avatar_url".to_string(), serde_json::json!(avatar_url));
+        }
+    }
 }
 
 pub struct DiscordWebhookSink {
@@ -65,7 +249,10 @@ pub struct DiscordWebhookSink {
     client: reqwest::Client,
     timeout: Duration,
     max_chars: usize,
+    formatter: Option<Arc<dyn EventFormatter>>,
     enforce_public_ip: bool,
+    identity: DiscordIdentity,
+    allowed_mentions: serde_json::Value,
 }
 
 impl std::fmt::Debug for DiscordWebhookSink {
@@ -73,6 +260,9 @@ impl std::fmt::Debug for DiscordWebhookSink {
         f.debug_struct("DiscordWebhookSink")
             .field("webhook_url", &redact_url(&self.webhook_url))
             .field("max_chars", &self.max_chars)
+            .field("custom_formatter", &self.formatter.is_some())
+            .field("identity", &self.identity)
+            .field("allowed_mentions", &self.allowed_mentions)
             .finish_non_exhaustive()
     }
 }
@@ -82,19 +272,53 @@ impl DiscordWebhookSink {
         let webhook_url =
             parse_and_validate_https_url(&config.webhook_url, &DISCORD_ALLOWED_HOSTS)?;
         validate_url_path_prefix(&webhook_url, "/api/webhooks/")?;
+        let identity = DiscordIdentity::from_config(&config)?;
+        config.allowed_mentions.validate()?;
         let client = build_http_client(config.timeout)?;
         Ok(Self {
             webhook_url,
             client,
             timeout: config.timeout,
             max_chars: config.max_chars,
+            formatter: config.formatter,
             enforce_public_ip: config.enforce_public_ip,
+            identity,
+            allowed_mentions: config.allowed_mentions.to_json(),
         })
     }
 
-    fn build_payload(event: &Event, max_chars: usize) -> serde_json::Value {
-        let text = format_event_text_limited(event, TextLimits::new(max_chars));
-        serde_json::json!({ "content": text })
+    fn build_payload(
+        event: &Event,
+        max_chars: usize,
+        formatter: Option<&dyn EventFormatter>,
+        identity: &DiscordIdentity,
+        allowed_mentions: &serde_json::Value,
+    ) -> serde_json::Value {
+        let mut payload = serde_json::Map::new();
+        match event_link_url(event) {
+            Some(url) => {
+                let text = format_event_text_with(
+                    formatter,
+                    event,
+                    TextLimits::new(max_chars).without_url(),
+                );
+                payload.insert("content".to_string(), serde_json::json!(text));
+                payload.insert(
+                    "embeds".to_string(),
+                    serde_json::json!([{
+                        "title": truncate_chars(event.title.trim(), 256),
+                        "url": url,
+                    }]),
+                );
+            }
+            None => {
+                let text = format_event_text_with(formatter, event, TextLimits::new(max_chars));
+                payload.insert("content".to_string(), serde_json::json!(text));
+            }
+        }
+        identity.apply(&mut payload);
+        payload.insert("allowed_mentions".to_string(), allowed_mentions.clone());
+        serde_json::Value::Object(payload)
     }
 }
 
@@ -112,7 +336,13 @@ impl Sink for DiscordWebhookSink {
                 self.enforce_public_ip,
             )
             .await?;
-            let payload = Self::build_payload(event, self.max_chars);
+            let payload = Self::build_payload(
+                event,
+                self.max_chars,
+                self.formatter.as_deref(),
+                &self.identity,
+                &self.allowed_mentions,
+            );
 
             let resp = send_reqwest(
                 client.post(self.webhook_url.as_str()).json(&payload),
@@ -126,25 +356,44 @@ impl Sink for DiscordWebhookSink {
                 return Ok(());
             }
 
-            let body = match read_text_body_limited(resp, DEFAULT_MAX_RESPONSE_BODY_BYTES).await {
+            let body = match read_text_body_limited(resp, max_response_body_bytes()).await {
                 Ok(body) => body,
                 Err(err) => {
-                    return Err(anyhow::anyhow!(
-                        "discord webhook http error: {status} (failed to read response body: {err})"
-                    )
-                    .into());
+                    return Err(crate::Error::http_status(
+                        status,
+                        anyhow::anyhow!(
+                            "discord webhook http error: {status} (failed to read response body: {err})"
+                        ),
+                    ));
                 }
             };
             let summary = truncate_chars(body.trim(), 200);
             if summary.is_empty() {
-                return Err(anyhow::anyhow!(
-                    "discord webhook http error: {status} (response body omitted)"
-                )
-                .into());
+                return Err(crate::Error::http_status(
+                    status,
+                    anyhow::anyhow!("discord webhook http error: {status} (response body omitted)"),
+                ));
             }
-            Err(anyhow::anyhow!("discord webhook http error: {status}, response={summary}").into())
+            Err(crate::Error::http_status(
+                status,
+                anyhow::anyhow!("discord webhook http error: {status}, response={summary}"),
+            ))
         })
     }
+
+    fn preview(&self, event: &Event) -> Option<serde_json::Value> {
+        Some(Self::build_payload(
+            event,
+            self.max_chars,
+            self.formatter.as_deref(),
+            &self.identity,
+            &self.allowed_mentions,
+        ))
+    }
+
+    fn diagnostic_target(&self) -> Option<String> {
+        Some(redact_url(&self.webhook_url))
+    }
 }
 
 #[cfg(test)]
@@ -158,13 +407,145 @@ mod tests {
             .with_body("ok")
             .with_tag("thread_id", "t1");
 
-        let payload = DiscordWebhookSink::build_payload(&event, 2000);
+        let payload = DiscordWebhookSink::build_payload(
+            &event,
+            2000,
+            None,
+            &DiscordIdentity::default(),
+            &DiscordAllowedMentions::none().to_json(),
+        );
         let text = payload["content"].as_str().unwrap_or("");
         assert!(text.contains("done"));
         assert!(text.contains("ok"));
         assert!(text.contains("thread_id=t1"));
     }
 
+    #[test]
+    fn puts_event_url_in_embed() {
+        let event = Event::new("turn_completed", Severity::Success, "done")
+            .with_body("ok")
+            .with_url("https://example.com/run/1");
+
+        let payload = DiscordWebhookSink::build_payload(
+            &event,
+            2000,
+            None,
+            &DiscordIdentity::default(),
+            &DiscordAllowedMentions::none().to_json(),
+        );
+        assert_eq!(payload["content"].as_str(), Some("done\nok"));
+        assert_eq!(
+            payload["embeds"][0]["url"].as_str(),
+            Some("https://example.com/run/1")
+        );
+        assert_eq!(payload["embeds"][0]["title"].as_str(), Some("done"));
+
+        let payload = DiscordWebhookSink::build_payload(
+            &Event::new("k", Severity::Info, "done"),
+            2000,
+            None,
+            &DiscordIdentity::default(),
+            &DiscordAllowedMentions::none().to_json(),
+        );
+        assert!(payload.get("embeds").is_none(), "{payload}");
+    }
+
+    #[test]
+    fn identity_overrides_appear_only_when_configured() {
+        let event = Event::new("k", Severity::Info, "done");
+        let payload = DiscordWebhookSink::build_payload(
+            &event,
+            2000,
+            None,
+            &DiscordIdentity::default(),
+            &DiscordAllowedMentions::none().to_json(),
+        );
+        assert_eq!(
+            payload,
+            serde_json::json!({ "content": "done", "allowed_mentions": { "parse": [] } })
+        );
+
+        let cfg = DiscordWebhookConfig::new("https://discord.com/api/webhooks/x/y")
+            .with_username("billing-service");
+        let identity = DiscordIdentity::from_config(&cfg).expect("valid identity");
+        let payload = DiscordWebhookSink::build_payload(
+            &event,
+            2000,
+            None,
+            &identity,
+            &DiscordAllowedMentions::none().to_json(),
+        );
+        assert_eq!(
+            payload,
+            serde_json::json!({
+                "content": "done",
+                "username": "billing-service",
+                "allowed_mentions": { "parse": [] },
+            })
+        );
+
+        let sink =
+            DiscordWebhookSink::new(cfg.with_avatar_url("https://cdn.example.com/billing.png"))
+                .expect("build sink");
+        let payload = sink
+            .preview(&event.with_url("https://example.com/run/1"))
+            .expect("preview");
+        assert_eq!(payload["username"], "billing-service");
+        assert_eq!(payload["avatar_url"], "https://cdn.example.com/billing.png");
+        assert_eq!(payload["embeds"][0]["url"], "https://example.com/run/1");
+    }
+
+    #[test]
+    fn mentions_are_suppressed_by_default() {
+        let sink = DiscordWebhookSink::new(DiscordWebhookConfig::new(
+            "https://discord.com/api/webhooks/x/y",
+        ))
+        .expect("build sink");
+        let payload = sink
+            .preview(&Event::new("k", Severity::Error, "deploy failed").with_body("@everyone"))
+            .expect("preview");
+        assert_eq!(
+            payload["allowed_mentions"],
+            serde_json::json!({ "parse": [] })
+        );
+
+        let cfg = DiscordWebhookConfig::new("https://discord.com/api/webhooks/x/y")
+            .with_allowed_mentions(
+                DiscordAllowedMentions::none()
+                    .with_parse(DiscordMentionType::Users)
+                    .with_parse(DiscordMentionType::Users)
+                    .with_role("123"),
+            );
+        let sink = DiscordWebhookSink::new(cfg).expect("build sink");
+        let payload = sink
+            .preview(&Event::new("k", Severity::Error, "t"))
+            .expect("preview");
+        assert_eq!(
+            payload["allowed_mentions"],
+            serde_json::json!({ "parse": ["users"], "roles": ["123"] })
+        );
+
+        let cfg = DiscordWebhookConfig::new("https://discord.com/api/webhooks/x/y")
+            .with_allowed_mentions(
+                DiscordAllowedMentions::none()
+                    .with_parse(DiscordMentionType::Roles)
+                    .with_role("123"),
+            );
+        let err = DiscordWebhookSink::new(cfg).expect_err("conflicting mentions");
+        assert_eq!(err.kind(), crate::ErrorKind::InvalidConfig, "{err:#}");
+    }
+
+    #[test]
+    fn rejects_invalid_avatar_url() {
+        for avatar_url in ["http://cdn.example.com/a.png", "not a url", "https://"] {
+            let cfg = DiscordWebhookConfig::new("https://discord.com/api/webhooks/x/y")
+                .with_avatar_url(avatar_url);
+            let err = DiscordWebhookSink::new(cfg).expect_err("expected invalid avatar url");
+            assert!(err.to_string().contains("avatar_url"), "{err:#}");
+            assert_eq!(err.kind(), crate::ErrorKind::InvalidConfig);
+        }
+    }
+
     #[test]
     fn rejects_non_https_webhook_url() {
         let cfg = DiscordWebhookConfig::new("http://discord.com/api/webhooks/x/y");
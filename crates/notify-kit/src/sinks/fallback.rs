@@ -0,0 +1,177 @@
+use std::fmt::Write as _;
+use std::sync::Arc;
+
+use crate::Event;
+use crate::sinks::{BoxFuture, Sink};
+
+/// Sink decorator that tries an ordered chain of sinks until one delivers the event.
+///
+/// For "try Telegram; if it fails, fall back to email": sinks are tried one after another, the
+/// first success ends the send, and later sinks are not called. The send only fails when every
+/// sink fails; the error lists each failure in chain order. Registered in a `Hub` as a single
+/// sink named `"fallback"`.
+///
+/// `Hub` enforces `HubConfig::per_sink_timeout` around the whole chain, so keep the sinks' own
+/// timeouts short enough for the later ones to get a chance.
+pub struct FallbackSink {
+    sinks: Vec<Arc<dyn Sink>>,
+}
+
+impl std::fmt::Debug for FallbackSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names: Vec<&str> = self.sinks.iter().map(|sink| sink.name()).collect();
+        f.debug_struct("FallbackSink")
+            .field("sinks", &names)
+            .finish()
+    }
+}
+
+impl FallbackSink {
+    /// `sinks` in priority order; must not be empty.
+    pub fn new(sinks: Vec<Arc<dyn Sink>>) -> crate::Result<Self> {
+        if sinks.is_empty() {
+            return Err(anyhow::anyhow!("fallback sink needs at least one sink").into());
+        }
+        Ok(Self { sinks })
+    }
+}
+
+impl Sink for FallbackSink {
+    fn name(&self) -> &'static str {
+        "fallback"
+    }
+
+    fn send<'a>(&'a self, event: &'a Event) -> BoxFuture<'a, crate::Result<()>> {
+        Box::pin(async move {
+            let mut failures = Vec::with_capacity(self.sinks.len());
+            for sink in &self.sinks {
+                match sink.send(event).await {
+                    Ok(()) => return Ok(()),
+                    Err(err) => failures.push((sink.name(), err)),
+                }
+            }
+
+            let mut msg = String::from("all fallback sinks failed:");
+            for (name, err) in failures {
+                if write!(&mut msg, "\n- {name}: {err:#}").is_err() {
+                    return Err(anyhow::anyhow!("failed to format sink error").into());
+                }
+            }
+            Err(anyhow::anyhow!(msg).into())
+        })
+    }
+
+    /// The chain as `name -> name`, with each sink's own target in parentheses when it has one.
+    fn diagnostic_target(&self) -> Option<String> {
+        let chain: Vec<String> = self
+            .sinks
+            .iter()
+            .map(|sink| match sink.diagnostic_target() {
+                Some(target) => format!("{} ({target})", sink.name()),
+                None => sink.name().to_string(),
+            })
+            .collect();
+        Some(chain.join(" -> "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::Severity;
+
+    #[derive(Debug)]
+    struct TestSink {
+        name: &'static str,
+        ok: bool,
+        calls: AtomicUsize,
+    }
+
+    impl TestSink {
+        fn new(name: &'static str, ok: bool) -> Arc<Self> {
+            Arc::new(Self {
+                name,
+                ok,
+                calls: AtomicUsize::new(0),
+            })
+        }
+
+        fn calls(&self) -> usize {
+            self.calls.load(Ordering::SeqCst)
+        }
+    }
+
+    impl Sink for TestSink {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        fn send<'a>(&'a self, _event: &'a Event) -> BoxFuture<'a, crate::Result<()>> {
+            Box::pin(async move {
+                self.calls.fetch_add(1, Ordering::SeqCst);
+                if self.ok {
+                    Ok(())
+                } else {
+                    Err(anyhow::anyhow!("{} down", self.name).into())
+                }
+            })
+        }
+    }
+
+    fn run(sink: &FallbackSink) -> crate::Result<()> {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime");
+        rt.block_on(sink.send(&Event::new("kind", Severity::Error, "title")))
+    }
+
+    #[test]
+    fn primary_fails_secondary_succeeds() {
+        let telegram = TestSink::new("telegram", false);
+        let email = TestSink::new("email", true);
+        let spare = TestSink::new("spare", true);
+        let sink = FallbackSink::new(vec![telegram.clone(), email.clone(), spare.clone()])
+            .expect("build sink");
+
+        run(&sink).expect("secondary delivers");
+        assert_eq!(telegram.calls(), 1);
+        assert_eq!(email.calls(), 1);
+        assert_eq!(spare.calls(), 0, "chain stops at the first success");
+    }
+
+    #[test]
+    fn all_fail_lists_every_error_in_order() {
+        let sink = FallbackSink::new(vec![
+            TestSink::new("telegram", false),
+            TestSink::new("email", false),
+        ])
+        .expect("build sink");
+
+        let err = run(&sink).expect_err("all failed");
+        assert_eq!(
+            err.to_string(),
+            "all fallback sinks failed:\n- telegram: telegram down\n- email: email down"
+        );
+    }
+
+    #[test]
+    fn rejects_empty_chain_and_describes_itself() {
+        let err = FallbackSink::new(Vec::new()).expect_err("empty chain");
+        assert!(err.to_string().contains("at least one sink"), "{err}");
+
+        let sink = FallbackSink::new(vec![
+            TestSink::new("telegram", true),
+            TestSink::new("email", true),
+        ])
+        .expect("build sink");
+        assert_eq!(sink.name(), "fallback");
+        assert_eq!(
+            sink.diagnostic_target().as_deref(),
+            Some("telegram -> email")
+        );
+        assert!(format!("{sink:?}").contains("telegram"));
+    }
+}
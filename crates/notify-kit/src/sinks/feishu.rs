@@ -1,22 +1,33 @@
 use std::collections::{BTreeSet, HashMap};
 use std::path::Path;
+use std::sync::Arc;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-use futures_util::StreamExt;
+use futures_util::FutureExt;
 
-use crate::Event;
+use crate::render::{EventFormatter, MaxChars};
 use crate::sinks::crypto::hmac_sha256_base64;
+use crate::sinks::file_source::{
+    LoadedFile, download_limited, file_name_or, guess_mime, read_local_file,
+};
 use crate::sinks::http::{
-    DEFAULT_MAX_RESPONSE_BODY_BYTES, build_http_client, parse_and_validate_https_url,
+    build_http_client, max_response_body_bytes, parse_and_validate_https_url,
     parse_and_validate_https_url_basic, read_json_body_limited, read_text_body_limited, redact_url,
     redact_url_str, select_http_client, send_reqwest, validate_url_path_prefix,
 };
-use crate::sinks::markdown::{Inline as MarkdownInline, parse_markdown_lines};
-use crate::sinks::text::{TextLimits, format_event_text_limited, truncate_chars};
+use crate::sinks::markdown::{Inline as MarkdownInline, MarkdownOrText, render_markdown_or_text};
+use crate::sinks::text::{
+    TextLimits, attachment_note, event_link_url, format_event_text_non_empty,
+    format_event_text_with, truncate_chars,
+};
 use crate::sinks::{BoxFuture, Sink};
+use crate::{Event, Severity};
 
 const FEISHU_MAX_CHARS: usize = 4000;
 const FEISHU_DEFAULT_IMAGE_UPLOAD_MAX_BYTES: usize = 10 * 1024 * 1024;
+const FEISHU_IMAGE_UPLOAD_PATH: &str = "/open-apis/im/v1/images";
+const FEISHU_POST_LOCALES: [&str; 3] = ["zh_cn", "en_us", "ja_jp"];
+const FEISHU_TENANT_TOKEN_PATH: &str = "/open-apis/auth/v3/tenant_access_token/internal";
 
 #[derive(Debug, Clone)]
 struct FeishuAppCredentials {
@@ -30,22 +41,31 @@ struct AccessTokenCache {
     expires_at: Instant,
 }
 
-#[derive(Debug)]
-struct LoadedImage {
-    bytes: Vec<u8>,
-    file_name: String,
-    content_type: String,
-}
-
 #[non_exhaustive]
 #[derive(Clone)]
 pub struct FeishuWebhookConfig {
     pub webhook_url: String,
     pub timeout: Duration,
+    /// Message length limit; `0` means unlimited (see [`crate::MaxChars`]).
     pub max_chars: usize,
+    /// Custom message layout; `None` uses the built-in one ([`crate::DefaultFormatter`]).
+    pub formatter: Option<Arc<dyn EventFormatter>>,
     pub enforce_public_ip: bool,
     pub enable_markdown_rich_text: bool,
+    /// Send an interactive card (`msg_type: "interactive"`) instead of text / `post`.
+    pub interactive_card: bool,
+    /// Users to @-mention (`open_id` / `user_id`); `"@all"` mentions everyone in the chat.
+    pub mentions: Vec<String>,
+    /// Language key of the `post` content: `zh_cn` (default), `en_us` or `ja_jp`.
+    pub locale: String,
     pub image_upload_max_bytes: usize,
+    /// Hosts remote markdown images may be downloaded from (case-insensitive, exact match);
+    /// images on other hosts are rendered as links. Empty allows any public `https` host.
+    pub image_allowed_hosts: Vec<String>,
+    /// Upload markdown images whose source is a local file path. Off by default: an untrusted
+    /// body could otherwise exfiltrate local files (`![](/etc/passwd)`); such images are
+    /// rendered as text instead.
+    pub allow_local_image_files: bool,
     pub app_id: Option<String>,
     pub app_secret: Option<String>,
 }
@@ -56,9 +76,15 @@ impl std::fmt::Debug for FeishuWebhookConfig {
             .field("webhook_url", &redact_url_str(&self.webhook_url))
             .field("timeout", &self.timeout)
             .field("max_chars", &self.max_chars)
+            .field("custom_formatter", &self.formatter.is_some())
             .field("enforce_public_ip", &self.enforce_public_ip)
             .field("enable_markdown_rich_text", &self.enable_markdown_rich_text)
+            .field("interactive_card", &self.interactive_card)
+            .field("mentions", &self.mentions)
+            .field("locale", &self.locale)
             .field("image_upload_max_bytes", &self.image_upload_max_bytes)
+            .field("image_allowed_hosts", &self.image_allowed_hosts)
+            .field("allow_local_image_files", &self.allow_local_image_files)
             .field("app_id", &self.app_id.as_ref().map(|_| "<redacted>"))
             .field(
                 "app_secret",
@@ -74,9 +100,15 @@ impl FeishuWebhookConfig {
             webhook_url: webhook_url.into(),
             timeout: Duration::from_secs(2),
             max_chars: FEISHU_MAX_CHARS,
+            formatter: None,
             enforce_public_ip: true,
             enable_markdown_rich_text: true,
+            interactive_card: false,
+            mentions: Vec::new(),
+            locale: "zh_cn".to_string(),
             image_upload_max_bytes: FEISHU_DEFAULT_IMAGE_UPLOAD_MAX_BYTES,
+            image_allowed_hosts: Vec::new(),
+            allow_local_image_files: false,
             app_id: None,
             app_secret: None,
         }
@@ -94,6 +126,12 @@ impl FeishuWebhookConfig {
         self
     }
 
+    #[must_use]
+    pub fn with_formatter(mut self, formatter: Arc<dyn EventFormatter>) -> Self {
+        self.formatter = Some(formatter);
+        self
+    }
+
     #[must_use]
     pub fn with_public_ip_check(mut self, enforce_public_ip: bool) -> Self {
         self.enforce_public_ip = enforce_public_ip;
@@ -106,12 +144,42 @@ impl FeishuWebhookConfig {
         self
     }
 
+    #[must_use]
+    pub fn with_interactive_card(mut self, enable: bool) -> Self {
+        self.interactive_card = enable;
+        self
+    }
+
+    #[must_use]
+    pub fn with_mentions(mut self, mentions: Vec<String>) -> Self {
+        self.mentions = mentions;
+        self
+    }
+
+    #[must_use]
+    pub fn with_locale(mut self, locale: impl Into<String>) -> Self {
+        self.locale = locale.into();
+        self
+    }
+
     #[must_use]
     pub fn with_image_upload_max_bytes(mut self, max_bytes: usize) -> Self {
         self.image_upload_max_bytes = max_bytes;
         self
     }
 
+    #[must_use]
+    pub fn with_image_allowed_hosts(mut self, hosts: Vec<String>) -> Self {
+        self.image_allowed_hosts = hosts;
+        self
+    }
+
+    #[must_use]
+    pub fn with_allow_local_image_files(mut self, allow: bool) -> Self {
+        self.allow_local_image_files = allow;
+        self
+    }
+
     #[must_use]
     pub fn with_app_credentials(
         mut self,
@@ -130,9 +198,15 @@ pub struct FeishuWebhookSink {
     timeout: Duration,
     secret: Option<String>,
     max_chars: usize,
+    formatter: Option<Arc<dyn EventFormatter>>,
     enforce_public_ip: bool,
     enable_markdown_rich_text: bool,
+    interactive_card: bool,
+    mentions: Vec<String>,
+    locale: &'static str,
     image_upload_max_bytes: usize,
+    image_allowed_hosts: Vec<String>,
+    allow_local_image_files: bool,
     app_credentials: Option<FeishuAppCredentials>,
     tenant_access_token: tokio::sync::Mutex<Option<AccessTokenCache>>,
 }
@@ -143,9 +217,15 @@ impl std::fmt::Debug for FeishuWebhookSink {
             .field("webhook_url", &redact_url(&self.webhook_url))
             .field("secret", &self.secret.as_ref().map(|_| "<redacted>"))
             .field("max_chars", &self.max_chars)
+            .field("custom_formatter", &self.formatter.is_some())
             .field("enforce_public_ip", &self.enforce_public_ip)
             .field("enable_markdown_rich_text", &self.enable_markdown_rich_text)
+            .field("interactive_card", &self.interactive_card)
+            .field("mentions", &self.mentions)
+            .field("locale", &self.locale)
             .field("image_upload_max_bytes", &self.image_upload_max_bytes)
+            .field("image_allowed_hosts", &self.image_allowed_hosts)
+            .field("allow_local_image_files", &self.allow_local_image_files)
             .field(
                 "app_credentials",
                 &self.app_credentials.as_ref().map(|_| "<redacted>"),
@@ -202,6 +282,9 @@ impl FeishuWebhookSink {
         }
 
         let app_credentials = normalize_app_credentials(config.app_id, config.app_secret)?;
+        let image_allowed_hosts = normalize_image_allowed_hosts(config.image_allowed_hosts)?;
+        let mentions = normalize_mentions(config.mentions)?;
+        let locale = normalize_locale(&config.locale)?;
         let webhook_url = parse_and_validate_https_url(
             &config.webhook_url,
             &["open.feishu.cn", "open.larksuite.com"],
@@ -224,9 +307,15 @@ impl FeishuWebhookSink {
             timeout: config.timeout,
             secret,
             max_chars: config.max_chars,
+            formatter: config.formatter,
             enforce_public_ip,
             enable_markdown_rich_text: config.enable_markdown_rich_text,
+            interactive_card: config.interactive_card,
+            mentions,
+            locale,
             image_upload_max_bytes: config.image_upload_max_bytes,
+            image_allowed_hosts,
+            allow_local_image_files: config.allow_local_image_files,
             app_credentials,
             tenant_access_token: tokio::sync::Mutex::new(None),
         })
@@ -243,6 +332,9 @@ impl FeishuWebhookSink {
         }
 
         let app_credentials = normalize_app_credentials(config.app_id, config.app_secret)?;
+        let image_allowed_hosts = normalize_image_allowed_hosts(config.image_allowed_hosts)?;
+        let mentions = normalize_mentions(config.mentions)?;
+        let locale = normalize_locale(&config.locale)?;
         let webhook_url = parse_and_validate_https_url(
             &config.webhook_url,
             &["open.feishu.cn", "open.larksuite.com"],
@@ -261,9 +353,15 @@ impl FeishuWebhookSink {
             timeout: config.timeout,
             secret,
             max_chars: config.max_chars,
+            formatter: config.formatter,
             enforce_public_ip,
             enable_markdown_rich_text: config.enable_markdown_rich_text,
+            interactive_card: config.interactive_card,
+            mentions,
+            locale,
             image_upload_max_bytes: config.image_upload_max_bytes,
+            image_allowed_hosts,
+            allow_local_image_files: config.allow_local_image_files,
             app_credentials,
             tenant_access_token: tokio::sync::Mutex::new(None),
         })
@@ -285,64 +383,103 @@ impl FeishuWebhookSink {
 
     fn build_text_payload(
         event: &Event,
-        max_chars: usize,
+        max_chars: impl Into<MaxChars>,
+        formatter: Option<&dyn EventFormatter>,
+        mentions: &[String],
+        timestamp: Option<&str>,
+        sign: Option<&str>,
+    ) -> serde_json::Value {
+        let limits = TextLimits::new(max_chars);
+        let mut text = format_event_text_with(formatter, event, limits);
+        if text.trim().is_empty() {
+            text = format_event_text_non_empty(event, limits);
+        }
+        Self::text_payload(text, mentions, timestamp, sign)
+    }
+
+    fn text_payload(
+        text: String,
+        mentions: &[String],
         timestamp: Option<&str>,
         sign: Option<&str>,
     ) -> serde_json::Value {
+        let mut content: String = mentions
+            .iter()
+            .map(|id| {
+                let name = if id == "all" { "所有人" } else { "" };
+                format!("<at user_id=\"{id}\">{name}</at> ")
+            })
+            .collect();
+        content.push_str(&text);
         let mut obj = Self::base_payload(timestamp, sign);
         obj.insert("msg_type".to_string(), serde_json::json!("text"));
         obj.insert(
             "content".to_string(),
-            serde_json::json!({
-                "text": format_event_text_limited(event, TextLimits::new(max_chars)),
-            }),
+            serde_json::json!({ "text": content }),
         );
         serde_json::Value::Object(obj)
     }
 
+    /// The mentions that fit in `max_chars` (each counted as `@{id}`) and the budget left for
+    /// the message itself.
+    fn mention_budget(&self) -> (&[String], MaxChars) {
+        let max_chars = MaxChars::from(self.max_chars);
+        let mut remaining = max_chars.limit();
+        let mut used = 0;
+        let mut fitted = 0;
+        for id in &self.mentions {
+            let label = format!("@{id}");
+            if Self::take_text_budget(&label, &mut remaining) != label {
+                break;
+            }
+            used += label.chars().count();
+            fitted += 1;
+        }
+        (&self.mentions[..fitted], max_chars.saturating_sub(used))
+    }
+
+    /// With `upload == false` no images are uploaded (they fall back to text), so the payload is
+    /// built without any network IO.
     async fn build_payload(
         &self,
         event: &Event,
         timestamp: Option<&str>,
         sign: Option<&str>,
+        upload: bool,
     ) -> crate::Result<serde_json::Value> {
-        if !self.enable_markdown_rich_text {
+        let (mentions, max_chars) = self.mention_budget();
+        // A custom formatter owns the layout, so it always gets the plain-text message.
+        if !self.enable_markdown_rich_text || self.formatter.is_some() {
             return Ok(Self::build_text_payload(
                 event,
-                self.max_chars,
+                max_chars,
+                self.formatter.as_deref(),
+                mentions,
                 timestamp,
                 sign,
             ));
         }
-
-        let Some(body) = event
-            .body
-            .as_deref()
-            .map(str::trim)
-            .filter(|v| !v.is_empty())
-        else {
-            return Ok(Self::build_text_payload(
-                event,
-                self.max_chars,
-                timestamp,
-                sign,
-            ));
-        };
-
-        let markdown_lines = parse_markdown_lines(body);
-        if markdown_lines.is_empty() {
-            return Ok(Self::build_text_payload(
-                event,
-                self.max_chars,
-                timestamp,
-                sign,
+        if self.interactive_card {
+            return Ok(Self::card_payload(
+                event, max_chars, mentions, timestamp, sign,
             ));
         }
 
-        let image_keys = self.resolve_image_keys(&markdown_lines).await;
+        let markdown_lines = match render_markdown_or_text(event, TextLimits::new(max_chars)) {
+            MarkdownOrText::Markdown(lines) => lines,
+            MarkdownOrText::Text(text) => {
+                return Ok(Self::text_payload(text, mentions, timestamp, sign));
+            }
+        };
+
+        let image_keys = if upload {
+            self.resolve_image_keys(&markdown_lines).await
+        } else {
+            HashMap::new()
+        };
 
         let mut content_rows: Vec<serde_json::Value> = Vec::new();
-        let mut remaining = self.max_chars;
+        let mut remaining = max_chars.limit();
 
         for line in markdown_lines {
             let mut row: Vec<serde_json::Value> = Vec::new();
@@ -407,7 +544,22 @@ impl FeishuWebhookSink {
             }
         }
 
-        for (k, v) in &event.tags {
+        if let Some(url) = event_link_url(event) {
+            let display = Self::take_text_budget(url, &mut remaining);
+            if !display.is_empty() {
+                content_rows.push(serde_json::json!([
+                    {
+                        "tag": "a",
+                        "text": display,
+                        "href": url,
+                    }
+                ]));
+            }
+        }
+
+        content_rows.extend(self.attachment_rows(event, &mut remaining, upload).await);
+
+        for (k, v) in event.tags_in_order() {
             if remaining == 0 {
                 break;
             }
@@ -426,12 +578,15 @@ impl FeishuWebhookSink {
 
         if content_rows.is_empty() {
             return Ok(Self::build_text_payload(
-                event,
-                self.max_chars,
-                timestamp,
-                sign,
+                event, max_chars, None, mentions, timestamp, sign,
             ));
         }
+        if let Some(serde_json::Value::Array(first_row)) = content_rows.first_mut() {
+            let at_tags = mentions
+                .iter()
+                .map(|id| serde_json::json!({ "tag": "at", "user_id": id }));
+            first_row.splice(0..0, at_tags);
+        }
 
         let title = truncate_chars(event.title.trim(), 256);
         let mut obj = Self::base_payload(timestamp, sign);
@@ -440,7 +595,7 @@ impl FeishuWebhookSink {
             "content".to_string(),
             serde_json::json!({
                 "post": {
-                    "zh_cn": {
+                    self.locale: {
                         "title": title,
                         "content": content_rows,
                     }
@@ -451,6 +606,164 @@ impl FeishuWebhookSink {
         Ok(serde_json::Value::Object(obj))
     }
 
+    /// A card with a severity-colored header (title), a `lark_md` div (body), a button for
+    /// `Event::url` and a note per tag; title, body and tags share the `max_chars` budget.
+    ///
+    /// Attachments are not uploaded, only listed by filename.
+    fn card_payload(
+        event: &Event,
+        max_chars: impl Into<MaxChars>,
+        mentions: &[String],
+        timestamp: Option<&str>,
+        sign: Option<&str>,
+    ) -> serde_json::Value {
+        let mut remaining = max_chars.into().limit();
+
+        let title = if event.title.trim().is_empty() {
+            event.kind.trim()
+        } else {
+            event.title.trim()
+        };
+        let title = truncate_chars(title, 256);
+        let title = Self::take_text_budget(&title, &mut remaining);
+        let template = match event.severity {
+            Severity::Info => "blue",
+            Severity::Success => "green",
+            Severity::Warning => "orange",
+            Severity::Error => "red",
+        };
+
+        let mut elements = Vec::new();
+        let body = event.body.as_deref().map(str::trim).unwrap_or_default();
+        let body = Self::take_text_budget(body, &mut remaining);
+        let mut content: Vec<String> = mentions
+            .iter()
+            .map(|id| format!("<at id={id}></at>"))
+            .collect();
+        if !body.is_empty() {
+            content.push(escape_lark_md(&body));
+        }
+        if !content.is_empty() {
+            elements.push(serde_json::json!({
+                "tag": "div",
+                "text": { "tag": "lark_md", "content": content.join(" ") },
+            }));
+        }
+
+        if let Some(url) = event_link_url(event) {
+            elements.push(serde_json::json!({
+                "tag": "action",
+                "actions": [{
+                    "tag": "button",
+                    "text": { "tag": "plain_text", "content": "View details" },
+                    "type": "primary",
+                    "url": url,
+                }],
+            }));
+        }
+
+        let mut notes = Vec::new();
+        if !event.attachments.is_empty() {
+            let text = Self::take_text_budget(&attachment_note(event), &mut remaining);
+            if !text.is_empty() {
+                notes.push(text);
+            }
+        }
+        for (k, v) in event.tags_in_order() {
+            let text = Self::take_text_budget(&format!("{k}={v}"), &mut remaining);
+            if text.is_empty() {
+                break;
+            }
+            notes.push(text);
+        }
+        for text in notes {
+            elements.push(serde_json::json!({
+                "tag": "note",
+                "elements": [{ "tag": "plain_text", "content": text }],
+            }));
+        }
+
+        let mut obj = Self::base_payload(timestamp, sign);
+        obj.insert("msg_type".to_string(), serde_json::json!("interactive"));
+        obj.insert(
+            "card".to_string(),
+            serde_json::json!({
+                "config": { "wide_screen_mode": true },
+                "header": {
+                    "template": template,
+                    "title": { "tag": "plain_text", "content": title },
+                },
+                "elements": elements,
+            }),
+        );
+        serde_json::Value::Object(obj)
+    }
+
+    /// Inline image attachments within `image_upload_max_bytes` become `img` rows when app
+    /// credentials allow uploading them; the rest (and failed uploads) are listed by filename
+    /// like in the text layout.
+    async fn attachment_rows(
+        &self,
+        event: &Event,
+        remaining: &mut usize,
+        upload: bool,
+    ) -> Vec<serde_json::Value> {
+        let mut rows = Vec::new();
+        let mut unsent = Vec::new();
+        for attachment in &event.attachments {
+            let bytes = match attachment.bytes() {
+                Some(bytes)
+                    if upload
+                        && attachment.is_image()
+                        && self.app_credentials.is_some()
+                        && bytes.len() <= self.image_upload_max_bytes =>
+                {
+                    bytes
+                }
+                _ => {
+                    unsent.push(attachment.display_name());
+                    continue;
+                }
+            };
+            let image = LoadedFile {
+                bytes: bytes.to_vec(),
+                file_name: attachment.filename.trim().to_string(),
+                content_type: attachment.content_type.trim().to_string(),
+            };
+            match self.upload_image(image).await {
+                Ok(image_key) => rows.push(serde_json::json!([
+                    {
+                        "tag": "img",
+                        "image_key": image_key,
+                    }
+                ])),
+                Err(err) => {
+                    tracing::warn!(
+                        attachment = %attachment.filename,
+                        error = %err,
+                        "feishu attachment upload failed"
+                    );
+                    unsent.push(attachment.display_name());
+                }
+            }
+        }
+
+        unsent.retain(|name| !name.is_empty());
+        if !unsent.is_empty() {
+            let note = format!("attachments: {}", unsent.join(", "));
+            let text = Self::take_text_budget(&note, remaining);
+            if !text.is_empty() {
+                rows.push(serde_json::json!([
+                    {
+                        "tag": "text",
+                        "text": text,
+                    }
+                ]));
+            }
+        }
+        rows
+    }
+
     fn take_text_budget(input: &str, remaining: &mut usize) -> String {
         if *remaining == 0 || input.is_empty() {
             return String::new();
@@ -488,9 +801,7 @@ impl FeishuWebhookSink {
     }
 
     async fn resolve_single_image_key(&self, src: &str) -> Option<String> {
-        if self.app_credentials.is_none() {
-            return None;
-        }
+        self.app_credentials.as_ref()?;
 
         let loaded = match self.load_image(src).await {
             Ok(loaded) => loaded,
@@ -509,7 +820,7 @@ impl FeishuWebhookSink {
         }
     }
 
-    async fn load_image(&self, src: &str) -> crate::Result<LoadedImage> {
+    async fn load_image(&self, src: &str) -> crate::Result<LoadedFile> {
         if src.starts_with("https://") {
             return self.load_remote_image(src).await;
         }
@@ -517,98 +828,72 @@ impl FeishuWebhookSink {
         if src.contains("://") {
             return Err(anyhow::anyhow!("unsupported image url scheme").into());
         }
-
-        let bytes = std::fs::read(src).map_err(|err| anyhow::anyhow!("read image file: {err}"))?;
-        if bytes.is_empty() {
-            return Err(anyhow::anyhow!("image file is empty").into());
-        }
-        if bytes.len() > self.image_upload_max_bytes {
-            return Err(anyhow::anyhow!("image file too large for upload").into());
+        if !self.allow_local_image_files {
+            return Err(anyhow::anyhow!("local image files are disabled").into());
         }
 
-        let path = Path::new(src);
-        let file_name = path
-            .file_name()
-            .and_then(|v| v.to_str())
-            .filter(|v| !v.is_empty())
-            .unwrap_or("image")
-            .to_string();
-
-        let content_type = guess_image_mime(path.extension().and_then(|v| v.to_str()));
+        read_local_file(src, self.image_upload_max_bytes, "image file", "image")
+    }
 
-        Ok(LoadedImage {
-            bytes,
-            file_name,
-            content_type,
-        })
+    fn check_image_host(&self, url: &reqwest::Url) -> crate::Result<()> {
+        if self.image_allowed_hosts.is_empty() {
+            return Ok(());
+        }
+        let Some(host) = url.host_str() else {
+            return Err(anyhow::anyhow!("image url must have a host").into());
+        };
+        if self
+            .image_allowed_hosts
+            .iter()
+            .any(|allowed| host.eq_ignore_ascii_case(allowed))
+        {
+            return Ok(());
+        }
+        Err(anyhow::anyhow!("image host is not allowed").into())
     }
 
-    async fn load_remote_image(&self, src: &str) -> crate::Result<LoadedImage> {
+    async fn load_remote_image(&self, src: &str) -> crate::Result<LoadedFile> {
         let url = parse_and_validate_https_url_basic(src)?;
+        self.check_image_host(&url)?;
         let client =
             select_http_client(&self.client, self.timeout, &url, self.enforce_public_ip).await?;
 
-        let resp = send_reqwest(client.get(url.clone()), "feishu image download").await?;
-        let status = resp.status();
-        if !status.is_success() {
-            let body = match read_text_body_limited(resp, DEFAULT_MAX_RESPONSE_BODY_BYTES).await {
-                Ok(body) => body,
-                Err(err) => {
-                    return Err(anyhow::anyhow!(
-                        "feishu image download http error: {status} (failed to read response body: {err})"
-                    )
-                    .into());
-                }
-            };
-            let summary = truncate_chars(body.trim(), 200);
-            if summary.is_empty() {
-                return Err(anyhow::anyhow!(
-                    "feishu image download http error: {status} (response body omitted)"
-                )
-                .into());
-            }
-            return Err(anyhow::anyhow!(
-                "feishu image download http error: {status}, response={summary}"
-            )
-            .into());
-        }
-
-        let content_type = resp
-            .headers()
-            .get(reqwest::header::CONTENT_TYPE)
-            .and_then(|v| v.to_str().ok())
-            .and_then(|v| v.split(';').next())
-            .map(str::trim)
-            .filter(|v| v.starts_with("image/"))
-            .map(ToString::to_string)
-            .unwrap_or_else(|| {
-                guess_image_mime(Path::new(url.path()).extension().and_then(|v| v.to_str()))
-            });
-
-        let bytes = read_bytes_body_limited(resp, self.image_upload_max_bytes).await?;
+        let (bytes, content_type) = download_limited(
+            &client,
+            &url,
+            self.image_upload_max_bytes,
+            "feishu image download",
+        )
+        .await?;
         if bytes.is_empty() {
             return Err(anyhow::anyhow!("downloaded image is empty").into());
         }
 
-        let file_name = Path::new(url.path())
-            .file_name()
-            .and_then(|v| v.to_str())
-            .filter(|v| !v.is_empty())
-            .unwrap_or("image")
-            .to_string();
+        let path = Path::new(url.path());
+        let content_type = content_type
+            .filter(|v| v.starts_with("image/"))
+            .unwrap_or_else(|| guess_mime(path.extension().and_then(|v| v.to_str())));
 
-        Ok(LoadedImage {
+        Ok(LoadedFile {
             bytes,
-            file_name,
+            file_name: file_name_or(path, "image"),
             content_type,
         })
     }
 
-    async fn upload_image(&self, image: LoadedImage) -> crate::Result<String> {
+    /// Open API endpoint on the webhook's host, so `open.feishu.cn` and `open.larksuite.com`
+    /// (international) tenants each talk to their own domain.
+    fn open_api_url(&self, path: &str) -> reqwest::Url {
+        let mut url = self.webhook_url.clone();
+        url.set_path(path);
+        url.set_query(None);
+        url.set_fragment(None);
+        url
+    }
+
+    async fn upload_image(&self, image: LoadedFile) -> crate::Result<String> {
         let access_token = self.ensure_tenant_access_token().await?;
-        let mut upload_url = self.webhook_url.clone();
-        upload_url.set_path("/open-apis/im/v1/images");
-        upload_url.set_query(None);
+        let upload_url = self.open_api_url(FEISHU_IMAGE_UPLOAD_PATH);
 
         let client = select_http_client(
             &self.client,
@@ -637,29 +922,33 @@ impl FeishuWebhookSink {
 
         let status = resp.status();
         if !status.is_success() {
-            let body = match read_text_body_limited(resp, DEFAULT_MAX_RESPONSE_BODY_BYTES).await {
+            let body = match read_text_body_limited(resp, max_response_body_bytes()).await {
                 Ok(body) => body,
                 Err(err) => {
-                    return Err(anyhow::anyhow!(
-                        "feishu image upload http error: {status} (failed to read response body: {err})"
-                    )
-                    .into());
+                    return Err(crate::Error::http_status(
+                        status,
+                        anyhow::anyhow!(
+                            "feishu image upload http error: {status} (failed to read response body: {err})"
+                        ),
+                    ));
                 }
             };
             let summary = truncate_chars(body.trim(), 200);
             if summary.is_empty() {
-                return Err(anyhow::anyhow!(
-                    "feishu image upload http error: {status} (response body omitted)"
-                )
-                .into());
+                return Err(crate::Error::http_status(
+                    status,
+                    anyhow::anyhow!(
+                        "feishu image upload http error: {status} (response body omitted)"
+                    ),
+                ));
             }
-            return Err(anyhow::anyhow!(
-                "feishu image upload http error: {status}, response={summary}"
-            )
-            .into());
+            return Err(crate::Error::http_status(
+                status,
+                anyhow::anyhow!("feishu image upload http error: {status}, response={summary}"),
+            ));
         }
 
-        let body = read_json_body_limited(resp, DEFAULT_MAX_RESPONSE_BODY_BYTES).await?;
+        let body = read_json_body_limited(resp, max_response_body_bytes()).await?;
         let code = body["code"].as_i64().unwrap_or(-1);
         if code != 0 {
             return Err(anyhow::anyhow!("feishu image upload api error: code={code}").into());
@@ -691,9 +980,7 @@ impl FeishuWebhookSink {
             }
         }
 
-        let mut token_url = self.webhook_url.clone();
-        token_url.set_path("/open-apis/auth/v3/tenant_access_token/internal");
-        token_url.set_query(None);
+        let token_url = self.open_api_url(FEISHU_TENANT_TOKEN_PATH);
 
         let client = select_http_client(
             &self.client,
@@ -716,29 +1003,35 @@ impl FeishuWebhookSink {
 
         let status = resp.status();
         if !status.is_success() {
-            let body = match read_text_body_limited(resp, DEFAULT_MAX_RESPONSE_BODY_BYTES).await {
+            let body = match read_text_body_limited(resp, max_response_body_bytes()).await {
                 Ok(body) => body,
                 Err(err) => {
-                    return Err(anyhow::anyhow!(
-                        "feishu tenant access token http error: {status} (failed to read response body: {err})"
-                    )
-                    .into());
+                    return Err(crate::Error::http_status(
+                        status,
+                        anyhow::anyhow!(
+                            "feishu tenant access token http error: {status} (failed to read response body: {err})"
+                        ),
+                    ));
                 }
             };
             let summary = truncate_chars(body.trim(), 200);
             if summary.is_empty() {
-                return Err(anyhow::anyhow!(
-                    "feishu tenant access token http error: {status} (response body omitted)"
-                )
-                .into());
+                return Err(crate::Error::http_status(
+                    status,
+                    anyhow::anyhow!(
+                        "feishu tenant access token http error: {status} (response body omitted)"
+                    ),
+                ));
             }
-            return Err(anyhow::anyhow!(
-                "feishu tenant access token http error: {status}, response={summary}"
-            )
-            .into());
+            return Err(crate::Error::http_status(
+                status,
+                anyhow::anyhow!(
+                    "feishu tenant access token http error: {status}, response={summary}"
+                ),
+            ));
         }
 
-        let body = read_json_body_limited(resp, DEFAULT_MAX_RESPONSE_BODY_BYTES).await?;
+        let body = read_json_body_limited(resp, max_response_body_bytes()).await?;
         let code = body["code"].as_i64().unwrap_or(-1);
         if code != 0 {
             return Err(
@@ -805,40 +1098,51 @@ impl FeishuWebhookSink {
     }
 }
 
-fn read_bytes_body_limited(
-    resp: reqwest::Response,
-    max_bytes: usize,
-) -> BoxFuture<'static, crate::Result<Vec<u8>>> {
-    Box::pin(async move {
-        let mut stream = resp.bytes_stream();
-        let mut out = Vec::new();
-        while let Some(chunk) = stream.next().await {
-            let chunk = chunk.map_err(|err| anyhow::anyhow!("read response bytes: {err}"))?;
-            if out.len().saturating_add(chunk.len()) > max_bytes {
-                return Err(anyhow::anyhow!("response body exceeds byte limit").into());
-            }
-            out.extend_from_slice(&chunk);
+/// Escapes `<` / `>` so the body cannot inject `lark_md` tags such as `<at id=all></at>`.
+fn escape_lark_md(text: &str) -> String {
+    text.replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// Trims each mention and maps the `"@all"` sentinel to Feishu's `all`; ids are embedded in
+/// markup, so quotes, angle brackets and whitespace are rejected.
+fn normalize_mentions(mentions: Vec<String>) -> crate::Result<Vec<String>> {
+    let mut normalized: Vec<String> = Vec::with_capacity(mentions.len());
+    for mention in mentions {
+        let mention = mention.trim();
+        let id = if mention.eq_ignore_ascii_case("@all") {
+            "all"
+        } else {
+            mention
+        };
+        if id.is_empty()
+            || id
+                .chars()
+                .any(|ch| ch.is_whitespace() || matches!(ch, '"' | '<' | '>' | '&'))
+        {
+            return Err(
+                anyhow::anyhow!("feishu mentions must be non-empty user ids or `@all`").into(),
+            );
+        }
+        if !normalized.iter().any(|existing| existing == id) {
+            normalized.push(id.to_string());
         }
-        Ok(out)
-    })
+    }
+    Ok(normalized)
 }
 
-fn guess_image_mime(ext: Option<&str>) -> String {
-    match ext
-        .map(|v| v.trim().to_ascii_lowercase())
-        .as_deref()
-        .unwrap_or("")
-    {
-        "png" => "image/png",
-        "jpg" | "jpeg" => "image/jpeg",
-        "gif" => "image/gif",
-        "webp" => "image/webp",
-        "bmp" => "image/bmp",
-        "svg" => "image/svg+xml",
-        "heic" => "image/heic",
-        _ => "application/octet-stream",
-    }
-    .to_string()
+fn normalize_locale(locale: &str) -> crate::Result<&'static str> {
+    let locale = locale.trim();
+    FEISHU_POST_LOCALES
+        .iter()
+        .copied()
+        .find(|allowed| allowed.eq_ignore_ascii_case(locale))
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "feishu locale must be one of {}",
+                FEISHU_POST_LOCALES.join(", ")
+            )
+            .into()
+        })
 }
 
 fn normalize_secret(secret: impl Into<String>) -> crate::Result<String> {
@@ -863,6 +1167,24 @@ fn normalize_optional_trimmed(value: Option<String>, field: &str) -> crate::Resu
     }
 }
 
+/// Blank entries are rejected rather than dropped: an all-blank list would otherwise
+/// silently allow every host.
+fn normalize_image_allowed_hosts(hosts: Vec<String>) -> crate::Result<Vec<String>> {
+    hosts
+        .into_iter()
+        .map(|host| {
+            let host = host.trim();
+            if host.is_empty() {
+                return Err(anyhow::anyhow!(
+                    "feishu image_allowed_hosts must not contain empty hosts"
+                )
+                .into());
+            }
+            Ok(host.to_string())
+        })
+        .collect()
+}
+
 fn normalize_app_credentials(
     app_id: Option<String>,
     app_secret: Option<String>,
@@ -887,6 +1209,7 @@ impl Sink for FeishuWebhookSink {
 
     fn send<'a>(&'a self, event: &'a Event) -> BoxFuture<'a, crate::Result<()>> {
         Box::pin(async move {
+            event.validate_attachments()?;
             let client = select_http_client(
                 &self.client,
                 self.timeout,
@@ -910,7 +1233,7 @@ impl Sink for FeishuWebhookSink {
             };
 
             let payload = self
-                .build_payload(event, timestamp.as_deref(), sign.as_deref())
+                .build_payload(event, timestamp.as_deref(), sign.as_deref(), true)
                 .await?;
 
             let resp = send_reqwest(
@@ -921,33 +1244,48 @@ impl Sink for FeishuWebhookSink {
 
             let status = resp.status();
             if !status.is_success() {
-                let body = match read_text_body_limited(resp, DEFAULT_MAX_RESPONSE_BODY_BYTES).await
-                {
+                let body = match read_text_body_limited(resp, max_response_body_bytes()).await {
                     Ok(body) => body,
                     Err(err) => {
-                        return Err(anyhow::anyhow!(
-                            "feishu webhook http error: {status} (failed to read response body: {err})"
-                        )
-                        .into());
+                        return Err(crate::Error::http_status(
+                            status,
+                            anyhow::anyhow!(
+                                "feishu webhook http error: {status} (failed to read response body: {err})"
+                            ),
+                        ));
                     }
                 };
                 let summary = truncate_chars(body.trim(), 200);
                 if summary.is_empty() {
-                    return Err(anyhow::anyhow!(
-                        "feishu webhook http error: {status} (response body omitted)"
-                    )
-                    .into());
+                    return Err(crate::Error::http_status(
+                        status,
+                        anyhow::anyhow!(
+                            "feishu webhook http error: {status} (response body omitted)"
+                        ),
+                    ));
                 }
-                return Err(anyhow::anyhow!(
-                    "feishu webhook http error: {status}, response={summary}"
-                )
-                .into());
+                return Err(crate::Error::http_status(
+                    status,
+                    anyhow::anyhow!("feishu webhook http error: {status}, response={summary}"),
+                ));
             }
 
-            let body = read_json_body_limited(resp, DEFAULT_MAX_RESPONSE_BODY_BYTES).await?;
+            let body = read_json_body_limited(resp, max_response_body_bytes()).await?;
             Self::ensure_success_response(&body)
         })
     }
+
+    /// Images are not uploaded in a preview: they appear as their text fallback.
+    fn preview(&self, event: &Event) -> Option<serde_json::Value> {
+        // Without uploads nothing in `build_payload` awaits, so it completes on the first poll.
+        self.build_payload(event, None, None, false)
+            .now_or_never()?
+            .ok()
+    }
+
+    fn diagnostic_target(&self) -> Option<String> {
+        Some(redact_url(&self.webhook_url))
+    }
 }
 
 #[cfg(test)]
@@ -960,7 +1298,8 @@ mod tests {
             .with_body("ok")
             .with_tag("thread_id", "t1");
 
-        let payload = FeishuWebhookSink::build_text_payload(&event, FEISHU_MAX_CHARS, None, None);
+        let payload =
+            FeishuWebhookSink::build_text_payload(&event, FEISHU_MAX_CHARS, None, &[], None, None);
         assert_eq!(payload["msg_type"].as_str().unwrap_or(""), "text");
         let text = payload["content"]["text"].as_str().unwrap_or("");
         assert!(text.contains("done"));
@@ -984,7 +1323,7 @@ mod tests {
             .build()
             .expect("build runtime");
         let payload = rt
-            .block_on(sink.build_payload(&event, None, None))
+            .block_on(sink.build_payload(&event, None, None, true))
             .expect("build payload");
         assert_eq!(payload["msg_type"].as_str().unwrap_or(""), "post");
 
@@ -997,6 +1336,275 @@ mod tests {
         assert!(text_payload.contains("\"tag\":\"a\""), "{text_payload}");
         assert!(text_payload.contains("thread_id=t1"), "{text_payload}");
         assert!(text_payload.contains("[image:img]"), "{text_payload}");
+        assert!(
+            !text_payload.contains("example.com/run/1"),
+            "{text_payload}"
+        );
+
+        let payload = rt
+            .block_on(sink.build_payload(
+                &event.with_url("https://example.com/run/1"),
+                None,
+                None,
+                true,
+            ))
+            .expect("build payload");
+        let content = payload["content"]["post"]["zh_cn"]["content"]
+            .as_array()
+            .expect("array content");
+        assert!(
+            content.iter().any(|row| {
+                row[0]["tag"] == "a" && row[0]["href"] == "https://example.com/run/1"
+            }),
+            "{payload}"
+        );
+    }
+
+    #[test]
+    fn builds_interactive_card_payload() {
+        let sink = FeishuWebhookSink::new(
+            FeishuWebhookConfig::new("https://open.feishu.cn/open-apis/bot/v2/hook/x")
+                .with_interactive_card(true),
+        )
+        .expect("build sink");
+        let event = Event::new("deploy", crate::Severity::Warning, "deploy slow")
+            .with_body("**p99** is up <at id=all></at>")
+            .with_url("https://example.com/run/1")
+            .with_ordered_tags([("env", "prod"), ("region", "eu")]);
+
+        let payload = sink.preview(&event).expect("payload");
+        assert_eq!(payload["msg_type"], "interactive", "{payload}");
+        let card = &payload["card"];
+        assert_eq!(card["header"]["template"], "orange");
+        assert_eq!(card["header"]["title"]["content"], "deploy slow");
+        let elements = card["elements"].as_array().expect("elements");
+        assert_eq!(elements.len(), 4, "{payload}");
+        assert_eq!(elements[0]["tag"], "div");
+        assert_eq!(elements[0]["text"]["tag"], "lark_md");
+        assert_eq!(
+            elements[0]["text"]["content"],
+            "**p99** is up &lt;at id=all&gt;&lt;/at&gt;"
+        );
+        assert_eq!(
+            elements[1]["actions"][0]["url"],
+            "https://example.com/run/1"
+        );
+        assert_eq!(elements[2]["tag"], "note");
+        assert_eq!(elements[2]["elements"][0]["content"], "env=prod");
+        assert_eq!(elements[3]["elements"][0]["content"], "region=eu");
+
+        let colors: Vec<_> = [
+            crate::Severity::Info,
+            crate::Severity::Success,
+            crate::Severity::Error,
+        ]
+        .into_iter()
+        .map(|severity| {
+            let payload = FeishuWebhookSink::card_payload(
+                &Event::new("k", severity, "t"),
+                100,
+                &[],
+                None,
+                None,
+            );
+            payload["card"]["header"]["template"].clone()
+        })
+        .collect();
+        assert_eq!(colors, ["blue", "green", "red"]);
+    }
+
+    #[test]
+    fn interactive_card_respects_max_chars() {
+        let event = Event::new("kind", crate::Severity::Info, "title")
+            .with_body("x".repeat(100))
+            .with_tag("k", "v");
+        let payload = FeishuWebhookSink::card_payload(&event, 20, &[], Some("1"), Some("sig"));
+        assert_eq!(payload["timestamp"], "1");
+        assert_eq!(payload["sign"], "sig");
+        assert_eq!(payload["card"]["header"]["title"]["content"], "title");
+        let elements = payload["card"]["elements"].as_array().expect("elements");
+        assert_eq!(elements.len(), 1, "{payload}");
+        let body = elements[0]["text"]["content"].as_str().unwrap_or("");
+        assert_eq!(body.chars().count(), 15, "{body}");
+        assert!(body.ends_with("..."), "{body}");
+
+        let payload = FeishuWebhookSink::card_payload(
+            &Event::new("kind", crate::Severity::Info, " "),
+            0,
+            &[],
+            None,
+            None,
+        );
+        assert_eq!(payload["card"]["header"]["title"]["content"], "kind");
+        assert_eq!(payload["card"]["elements"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn mentions_become_at_tags() {
+        let cfg = FeishuWebhookConfig::new("https://open.feishu.cn/open-apis/bot/v2/hook/x")
+            .with_mentions(vec!["ou_abc".to_string(), " @all ".to_string()]);
+        let sink = FeishuWebhookSink::new(cfg.clone()).expect("build sink");
+
+        let event = Event::new("kind", crate::Severity::Error, "down")
+            .with_body("see [dashboard](https://example.com/d)");
+        let payload = sink.preview(&event).expect("payload");
+        assert_eq!(payload["msg_type"], "post", "{payload}");
+        let first_row = &payload["content"]["post"]["zh_cn"]["content"][0];
+        assert_eq!(
+            first_row[0],
+            serde_json::json!({ "tag": "at", "user_id": "ou_abc" })
+        );
+        assert_eq!(
+            first_row[1],
+            serde_json::json!({ "tag": "at", "user_id": "all" })
+        );
+        assert_eq!(first_row[2]["tag"], "text", "{payload}");
+
+        let payload = sink
+            .preview(&Event::new("kind", crate::Severity::Error, "down"))
+            .expect("payload");
+        assert_eq!(payload["msg_type"], "text", "{payload}");
+        assert_eq!(
+            payload["content"]["text"],
+            "<at user_id=\"ou_abc\"></at> <at user_id=\"all\">所有人</at> down"
+        );
+
+        let sink = FeishuWebhookSink::new(cfg.with_interactive_card(true)).expect("build sink");
+        let payload = sink.preview(&event).expect("payload");
+        assert_eq!(
+            payload["card"]["elements"][0]["text"]["content"],
+            "<at id=ou_abc></at> <at id=all></at> see [dashboard](https://example.com/d)"
+        );
+    }
+
+    #[test]
+    fn mentions_count_against_max_chars() {
+        let cfg = FeishuWebhookConfig::new("https://open.feishu.cn/open-apis/bot/v2/hook/x")
+            .with_markdown_rich_text(false)
+            .with_max_chars(20)
+            .with_mentions(vec!["ou_abcdefgh".to_string(), "ou_ijklmnop".to_string()]);
+        let sink = FeishuWebhookSink::new(cfg).expect("build sink");
+        let (mentions, budget) = sink.mention_budget();
+        assert_eq!(mentions, ["ou_abcdefgh"]);
+        assert_eq!(budget, MaxChars::Limited(8));
+
+        let payload = sink
+            .preview(&Event::new("kind", crate::Severity::Info, "x".repeat(50)))
+            .expect("payload");
+        assert_eq!(
+            payload["content"]["text"],
+            "<at user_id=\"ou_abcdefgh\"></at> xxxxx..."
+        );
+
+        for bad in ["", " ", "ou abc", "ou\"x", "<at>"] {
+            let cfg = FeishuWebhookConfig::new("https://open.feishu.cn/open-apis/bot/v2/hook/x")
+                .with_mentions(vec![bad.to_string()]);
+            assert!(FeishuWebhookSink::new(cfg).is_err(), "{bad:?}");
+        }
+    }
+
+    #[test]
+    fn post_payload_uses_configured_locale() {
+        let event = Event::new("kind", crate::Severity::Info, "title")
+            .with_body("see [dashboard](https://example.com/d)");
+        let cfg = FeishuWebhookConfig::new("https://open.larksuite.com/open-apis/bot/v2/hook/x");
+        let payload = FeishuWebhookSink::new(cfg.clone())
+            .expect("build sink")
+            .preview(&event)
+            .expect("payload");
+        assert_eq!(payload["content"]["post"]["zh_cn"]["title"], "title");
+
+        let payload = FeishuWebhookSink::new(cfg.clone().with_locale(" EN_US "))
+            .expect("build sink")
+            .preview(&event)
+            .expect("payload");
+        let post = payload["content"]["post"].as_object().expect("post");
+        assert_eq!(post.keys().collect::<Vec<_>>(), ["en_us"]);
+        assert_eq!(post["en_us"]["title"], "title");
+
+        let err = FeishuWebhookSink::new(cfg.with_locale("fr_fr")).expect_err("unknown locale");
+        assert!(err.to_string().contains("zh_cn, en_us, ja_jp"), "{err}");
+    }
+
+    #[test]
+    fn attachments_without_app_credentials_are_listed_by_name() {
+        let event = Event::new("k", crate::Severity::Error, "crash")
+            .with_body("see **logs**")
+            .with_attachment(crate::Attachment::new(
+                "screen.png",
+                "image/png",
+                vec![0x89, b'P', b'N', b'G'],
+            ))
+            .with_attachment(crate::Attachment::new(
+                "trace.log",
+                "text/plain",
+                b"boom".to_vec(),
+            ));
+
+        let sink = FeishuWebhookSink::new(FeishuWebhookConfig::new(
+            "https://open.feishu.cn/open-apis/bot/v2/hook/x",
+        ))
+        .expect("build sink");
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("build runtime");
+
+        let payload = rt
+            .block_on(sink.build_payload(&event, None, None, true))
+            .expect("build payload");
+        assert_eq!(payload["msg_type"], "post");
+        let content = payload["content"]["post"]["zh_cn"]["content"]
+            .as_array()
+            .expect("array content");
+        assert!(
+            content
+                .iter()
+                .any(|row| row[0]["text"] == "attachments: screen.png, trace.log"),
+            "{payload}"
+        );
+
+        let sink = FeishuWebhookSink::new(
+            FeishuWebhookConfig::new("https://open.feishu.cn/open-apis/bot/v2/hook/x")
+                .with_markdown_rich_text(false),
+        )
+        .expect("build sink");
+        let payload = rt
+            .block_on(sink.build_payload(&event, None, None, true))
+            .expect("build payload");
+        assert!(
+            payload["content"]["text"]
+                .as_str()
+                .is_some_and(|text| text.contains("attachments: screen.png, trace.log")),
+            "{payload}"
+        );
+    }
+
+    #[test]
+    fn preview_never_uploads_images() {
+        let sink = FeishuWebhookSink::new(
+            FeishuWebhookConfig::new("https://open.feishu.cn/open-apis/bot/v2/hook/x")
+                .with_app_credentials("app", "secret"),
+        )
+        .expect("build sink");
+        let event = Event::new("kind", crate::Severity::Info, "done")
+            .with_body("see ![chart](https://example.com/chart.png)")
+            .with_attachment(crate::Attachment::new(
+                "screen.png",
+                "image/png",
+                vec![1, 2, 3],
+            ));
+
+        // No runtime: a preview that tried to upload could not complete.
+        let payload = sink.preview(&event).expect("preview");
+        let rendered = payload.to_string();
+        assert_eq!(payload["msg_type"], "post", "{rendered}");
+        assert!(!rendered.contains("\"img\""), "{rendered}");
+        assert!(
+            rendered.contains("[image:chart] https://example.com/chart.png"),
+            "{rendered}"
+        );
+        assert!(rendered.contains("attachments: screen.png"), "{rendered}");
     }
 
     #[test]
@@ -1064,6 +1672,8 @@ mod tests {
         let payload = FeishuWebhookSink::build_text_payload(
             &event,
             FEISHU_MAX_CHARS,
+            None,
+            &[],
             Some("123"),
             Some("sig"),
         );
@@ -1082,12 +1692,128 @@ mod tests {
     #[test]
     fn payload_respects_max_chars() {
         let event = Event::new("kind", crate::Severity::Info, "title").with_body("x".repeat(100));
-        let payload = FeishuWebhookSink::build_text_payload(&event, 10, None, None);
+        let payload = FeishuWebhookSink::build_text_payload(&event, 10, None, &[], None, None);
         let text = payload["content"]["text"].as_str().unwrap_or("");
         assert!(text.chars().count() <= 10, "{text}");
         assert!(text.ends_with("..."), "{text}");
     }
 
+    #[test]
+    fn open_api_urls_follow_webhook_host() {
+        for host in ["open.feishu.cn", "open.larksuite.com"] {
+            let cfg = FeishuWebhookConfig::new(format!(
+                "https://{host}/open-apis/bot/v2/hook/secret_token?x=1"
+            ))
+            .with_app_credentials("app_id", "app_secret");
+            let sink = FeishuWebhookSink::new(cfg).expect("build sink");
+
+            let upload_url = sink.open_api_url(FEISHU_IMAGE_UPLOAD_PATH);
+            assert_eq!(
+                upload_url.as_str(),
+                format!("https://{host}/open-apis/im/v1/images")
+            );
+            let token_url = sink.open_api_url(FEISHU_TENANT_TOKEN_PATH);
+            assert_eq!(
+                token_url.as_str(),
+                format!("https://{host}/open-apis/auth/v3/tenant_access_token/internal")
+            );
+        }
+    }
+
+    #[test]
+    fn image_allowed_hosts_restrict_remote_images() {
+        let cfg = FeishuWebhookConfig::new("https://open.feishu.cn/open-apis/bot/v2/hook/x")
+            .with_image_allowed_hosts(vec![" cdn.example.com ".to_string()]);
+        let sink = FeishuWebhookSink::new(cfg).expect("build sink");
+        assert_eq!(sink.image_allowed_hosts, ["cdn.example.com"]);
+
+        for (src, allowed) in [
+            ("https://cdn.example.com/a.png", true),
+            ("https://CDN.Example.com/a.png", true),
+            ("https://evil.example.com/a.png", false),
+            ("https://example.com/a.png", false),
+            ("https://cdn.example.com.evil.test/a.png", false),
+        ] {
+            let url = reqwest::Url::parse(src).expect("parse url");
+            assert_eq!(sink.check_image_host(&url).is_ok(), allowed, "{src}");
+        }
+
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("build tokio runtime");
+        let err = rt
+            .block_on(sink.load_image("https://evil.example.com/a.png"))
+            .expect_err("disallowed host must not be fetched");
+        assert!(
+            err.to_string().contains("image host is not allowed"),
+            "{err:#}"
+        );
+    }
+
+    #[test]
+    fn local_image_files_are_disabled_by_default() {
+        let path = std::env::temp_dir().join(format!(
+            "notify-kit-feishu-local-image-{}.png",
+            std::process::id()
+        ));
+        std::fs::write(&path, b"\x89PNG\r\n\x1a\nsecret").expect("write image");
+        let src = path.to_string_lossy().into_owned();
+        let event =
+            Event::new("kind", crate::Severity::Info, "title").with_body(format!("![x]({src})"));
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("build tokio runtime");
+
+        let cfg = FeishuWebhookConfig::new("https://open.feishu.cn/open-apis/bot/v2/hook/x")
+            .with_app_credentials("app_id", "app_secret");
+        assert!(!cfg.allow_local_image_files);
+        let sink = FeishuWebhookSink::new(cfg.clone()).expect("build sink");
+        let err = rt
+            .block_on(sink.load_image(&src))
+            .expect_err("local file must not be read");
+        assert!(
+            err.to_string().contains("local image files are disabled"),
+            "{err:#}"
+        );
+
+        let payload = rt
+            .block_on(sink.build_payload(&event, None, None, true))
+            .expect("build payload");
+        let rendered = payload.to_string();
+        assert!(!rendered.contains("\"img\""), "{rendered}");
+        assert!(
+            payload["content"]["post"]["zh_cn"]["content"][0][0]["text"]
+                .as_str()
+                .is_some_and(|text| text.starts_with("[image:x] ")),
+            "{rendered}"
+        );
+
+        let sink =
+            FeishuWebhookSink::new(cfg.with_allow_local_image_files(true)).expect("build sink");
+        let loaded = rt
+            .block_on(sink.load_image(&src))
+            .expect("load local image");
+        assert!(loaded.bytes.ends_with(b"secret"));
+        assert_eq!(loaded.content_type, "image/png");
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn empty_image_allowed_hosts_allow_any_host() {
+        let cfg = FeishuWebhookConfig::new("https://open.feishu.cn/open-apis/bot/v2/hook/x");
+        let sink = FeishuWebhookSink::new(cfg).expect("build sink");
+        let url = reqwest::Url::parse("https://any.example.com/a.png").expect("parse url");
+        assert!(sink.check_image_host(&url).is_ok());
+
+        let cfg = FeishuWebhookConfig::new("https://open.feishu.cn/open-apis/bot/v2/hook/x")
+            .with_image_allowed_hosts(vec!["  ".to_string()]);
+        let err = FeishuWebhookSink::new(cfg).expect_err("blank host");
+        assert!(err.to_string().contains("image_allowed_hosts"), "{err:#}");
+    }
+
     #[test]
     fn normalizes_app_credentials() {
         let cfg = FeishuWebhookConfig::new("https://open.feishu.cn/open-apis/bot/v2/hook/x")
@@ -0,0 +1,137 @@
+//! Files referenced by events (Feishu markdown images, webhook attachments): local paths or
+//! `https` URLs, always size-limited.
+
+use std::path::Path;
+
+use futures_util::StreamExt;
+
+use crate::sinks::BoxFuture;
+use crate::sinks::http::{max_response_body_bytes, read_text_body_limited, send_reqwest};
+use crate::sinks::text::truncate_chars;
+
+#[derive(Debug)]
+pub(crate) struct LoadedFile {
+    pub(crate) bytes: Vec<u8>,
+    pub(crate) file_name: String,
+    pub(crate) content_type: String,
+}
+
+/// Reads a local file of at most `max_bytes`; `what` names it in errors (e.g. `"image file"`).
+pub(crate) fn read_local_file(
+    src: &str,
+    max_bytes: usize,
+    what: &str,
+    default_name: &str,
+) -> crate::Result<LoadedFile> {
+    let bytes = std::fs::read(src).map_err(|err| anyhow::anyhow!("read {what}: {err}"))?;
+    if bytes.is_empty() {
+        return Err(anyhow::anyhow!("{what} is empty").into());
+    }
+    if bytes.len() > max_bytes {
+        return Err(anyhow::anyhow!("{what} too large for upload").into());
+    }
+
+    let path = Path::new(src);
+    Ok(LoadedFile {
+        bytes,
+        file_name: file_name_or(path, default_name),
+        content_type: guess_mime(path.extension().and_then(|v| v.to_str())),
+    })
+}
+
+/// GETs an already validated `url` and reads at most `max_bytes` of the body.
+///
+/// Returns the bytes and the response `Content-Type` (without parameters); non-2xx responses
+/// become `"{context} http error"`s.
+pub(crate) async fn download_limited(
+    client: &reqwest::Client,
+    url: &reqwest::Url,
+    max_bytes: usize,
+    context: &str,
+) -> crate::Result<(Vec<u8>, Option<String>)> {
+    let resp = send_reqwest(client.get(url.clone()), context).await?;
+    let status = resp.status();
+    if !status.is_success() {
+        let body = match read_text_body_limited(resp, max_response_body_bytes()).await {
+            Ok(body) => body,
+            Err(err) => {
+                return Err(crate::Error::http_status(
+                    status,
+                    anyhow::anyhow!(
+                        "{context} http error: {status} (failed to read response body: {err})"
+                    ),
+                ));
+            }
+        };
+        let summary = truncate_chars(body.trim(), 200);
+        if summary.is_empty() {
+            return Err(crate::Error::http_status(
+                status,
+                anyhow::anyhow!("{context} http error: {status} (response body omitted)"),
+            ));
+        }
+        return Err(crate::Error::http_status(
+            status,
+            anyhow::anyhow!("{context} http error: {status}, response={summary}"),
+        ));
+    }
+
+    let content_type = resp
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.split(';').next())
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(ToString::to_string);
+    let bytes = read_bytes_body_limited(resp, max_bytes).await?;
+    Ok((bytes, content_type))
+}
+
+pub(crate) fn file_name_or(path: &Path, default_name: &str) -> String {
+    path.file_name()
+        .and_then(|v| v.to_str())
+        .filter(|v| !v.is_empty())
+        .unwrap_or(default_name)
+        .to_string()
+}
+
+pub(crate) fn guess_mime(ext: Option<&str>) -> String {
+    match ext
+        .map(|v| v.trim().to_ascii_lowercase())
+        .as_deref()
+        .unwrap_or("")
+    {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "bmp" => "image/bmp",
+        "svg" => "image/svg+xml",
+        "heic" => "image/heic",
+        "txt" | "log" => "text/plain",
+        "json" => "application/json",
+        "pdf" => "application/pdf",
+        "zip" => "application/zip",
+        _ => "application/octet-stream",
+    }
+    .to_string()
+}
+
+fn read_bytes_body_limited(
+    resp: reqwest::Response,
+    max_bytes: usize,
+) -> BoxFuture<'static, crate::Result<Vec<u8>>> {
+    Box::pin(async move {
+        let mut stream = resp.bytes_stream();
+        let mut out = Vec::new();
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(|err| anyhow::anyhow!("read response bytes: {err}"))?;
+            if out.len().saturating_add(chunk.len()) > max_bytes {
+                return Err(anyhow::anyhow!("response body exceeds byte limit").into());
+            }
+            out.extend_from_slice(&chunk);
+        }
+        Ok(out)
+    })
+}
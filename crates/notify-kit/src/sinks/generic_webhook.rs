@@ -1,13 +1,45 @@
-use std::time::Duration;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use crate::Event;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+
+use crate::render::EventFormatter;
+use crate::sinks::file_source::{
+    LoadedFile, download_limited, file_name_or, guess_mime, read_local_file,
+};
 use crate::sinks::http::{
-    DEFAULT_MAX_RESPONSE_BODY_BYTES, build_http_client, parse_and_validate_https_url_basic,
-    read_text_body_limited, redact_url, redact_url_str, select_http_client, send_reqwest,
-    try_drain_response_body_for_reuse, validate_url_path_prefix,
+    build_http_client, max_response_body_bytes, parse_and_validate_https_url_basic,
+    parse_and_validate_private_target_url, read_text_body_limited, redact_url, redact_url_str,
+    select_http_client, send_reqwest, try_drain_response_body_for_reuse, validate_url_path_prefix,
+};
+use crate::sinks::text::{
+    MAX_FLOAT_PRECISION, TextLimits, format_event_text_with, tag_value_json, truncate_chars,
 };
-use crate::sinks::text::{TextLimits, format_event_text_limited, truncate_chars};
 use crate::sinks::{BoxFuture, Sink};
+use crate::{Attachment, AttachmentSource, Event};
+
+const DEFAULT_STRUCTURED_MAX_TAGS: usize = 64;
+const DEFAULT_ATTACHMENT_MAX_BYTES: usize = 10 * 1024 * 1024;
+
+/// HTTP method used by [`GenericWebhookSink`].
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HttpMethod {
+    #[default]
+    Post,
+    Put,
+    Patch,
+}
+
+impl HttpMethod {
+    fn as_reqwest(self) -> reqwest::Method {
+        match self {
+            Self::Post => reqwest::Method::POST,
+            Self::Put => reqwest::Method::PUT,
+            Self::Patch => reqwest::Method::PATCH,
+        }
+    }
+}
 
 #[non_exhaustive]
 #[derive(Clone)]
@@ -15,10 +47,52 @@ pub struct GenericWebhookConfig {
     pub url: String,
     pub payload_field: String,
     pub timeout: Duration,
+    /// Message length limit; `0` means unlimited (see [`crate::MaxChars`]).
     pub max_chars: usize,
+    /// Custom message layout; `None` uses the built-in one ([`crate::DefaultFormatter`]).
+    pub formatter: Option<Arc<dyn EventFormatter>>,
     pub enforce_public_ip: bool,
+    /// Local-testing escape hatch: accept IP / `localhost` hosts, any port and (for loopback)
+    /// plain `http`, and skip the public-IP check. Requires `allowed_hosts`; rejected by
+    /// [`GenericWebhookSink::new_strict`]. Off by default.
+    pub allow_private_targets: bool,
     pub path_prefix: Option<String>,
     pub allowed_hosts: Vec<String>,
+    /// Post the event as a JSON object (`kind`, `severity`, `title`, `body`, `url`, `tags`,
+    /// `timestamp`) instead of `{payload_field: text}`; `formatter` and `payload_field` are then
+    /// unused.
+    pub structured_body: bool,
+    /// Maximum number of tags in the structured body (in key order); extra tags are dropped.
+    pub max_tags: usize,
+    /// JSON body template with placeholders, replacing the `{payload_field: text}` body.
+    ///
+    /// `{{kind}}`, `{{severity}}`, `{{title}}`, `{{body}}`, `{{url}}` and `{{text}}` (the
+    /// formatted message) are substituted as JSON-escaped string contents, so they belong inside
+    /// quotes; `{{tags_json}}` is substituted as a JSON object. Templates that are not valid JSON
+    /// after substitution, or use unknown placeholders, are rejected. Cannot be combined with
+    /// `structured_body`.
+    pub template: Option<String>,
+    /// Fraction digits for tag values that are decimal numbers: applied to the text, and in the
+    /// structured body such values are sent as JSON numbers. `None` keeps them verbatim strings.
+    /// At most 17.
+    pub float_precision: Option<usize>,
+    /// Extra request headers (e.g. `Authorization`, `X-Api-Key`); values of secret-looking
+    /// headers are redacted in `Debug`.
+    pub headers: Vec<(String, String)>,
+    pub method: HttpMethod,
+    /// Send `application/x-www-form-urlencoded` instead of JSON, for legacy endpoints: the
+    /// `payload_field` as one field, or with `structured_body` each event field plus one
+    /// `tags[<key>]` field per tag. Cannot be combined with `template` or
+    /// `multipart_attachments`. Off by default.
+    pub form_encoded: bool,
+    /// Send `multipart/form-data` instead of JSON: the JSON payload in a `payload` part plus one
+    /// `attachment` file part per [`Event::attachments`] entry. Off by default.
+    pub multipart_attachments: bool,
+    /// Total size limit for one event's attachments (default 10 MiB); larger sends fail.
+    pub attachment_max_bytes: usize,
+    /// Also accept local file paths as attachments; otherwise only `https` URLs (public IPs only)
+    /// are fetched. Rejected by [`GenericWebhookSink::new_strict`]. Off by default.
+    pub allow_local_attachment_files: bool,
 }
 
 impl std::fmt::Debug for GenericWebhookConfig {
@@ -28,9 +102,24 @@ impl std::fmt::Debug for GenericWebhookConfig {
             .field("payload_field", &self.payload_field)
             .field("timeout", &self.timeout)
             .field("max_chars", &self.max_chars)
+            .field("custom_formatter", &self.formatter.is_some())
             .field("enforce_public_ip", &self.enforce_public_ip)
+            .field("allow_private_targets", &self.allow_private_targets)
             .field("path_prefix", &self.path_prefix)
             .field("allowed_hosts", &self.allowed_hosts)
+            .field("structured_body", &self.structured_body)
+            .field("max_tags", &self.max_tags)
+            .field("template", &self.template)
+            .field("float_precision", &self.float_precision)
+            .field("headers", &RedactedHeaders(&self.headers))
+            .field("method", &self.method)
+            .field("form_encoded", &self.form_encoded)
+            .field("multipart_attachments", &self.multipart_attachments)
+            .field("attachment_max_bytes", &self.attachment_max_bytes)
+            .field(
+                "allow_local_attachment_files",
+                &self.allow_local_attachment_files,
+            )
             .finish()
     }
 }
@@ -42,9 +131,21 @@ impl GenericWebhookConfig {
             payload_field: "text".to_string(),
             timeout: Duration::from_secs(2),
             max_chars: 16 * 1024,
+            formatter: None,
             enforce_public_ip: true,
+            allow_private_targets: false,
             path_prefix: None,
             allowed_hosts: Vec::new(),
+            structured_body: false,
+            max_tags: DEFAULT_STRUCTURED_MAX_TAGS,
+            template: None,
+            float_precision: None,
+            headers: Vec::new(),
+            method: HttpMethod::default(),
+            form_encoded: false,
+            multipart_attachments: false,
+            attachment_max_bytes: DEFAULT_ATTACHMENT_MAX_BYTES,
+            allow_local_attachment_files: false,
         }
     }
 
@@ -58,9 +159,21 @@ impl GenericWebhookConfig {
             payload_field: "text".to_string(),
             timeout: Duration::from_secs(2),
             max_chars: 16 * 1024,
+            formatter: None,
             enforce_public_ip: true,
+            allow_private_targets: false,
             path_prefix: Some(path_prefix.into()),
             allowed_hosts,
+            structured_body: false,
+            max_tags: DEFAULT_STRUCTURED_MAX_TAGS,
+            template: None,
+            float_precision: None,
+            headers: Vec::new(),
+            method: HttpMethod::default(),
+            form_encoded: false,
+            multipart_attachments: false,
+            attachment_max_bytes: DEFAULT_ATTACHMENT_MAX_BYTES,
+            allow_local_attachment_files: false,
         }
     }
 
@@ -82,12 +195,24 @@ impl GenericWebhookConfig {
         self
     }
 
+    #[must_use]
+    pub fn with_formatter(mut self, formatter: Arc<dyn EventFormatter>) -> Self {
+        self.formatter = Some(formatter);
+        self
+    }
+
     #[must_use]
     pub fn with_public_ip_check(mut self, enforce_public_ip: bool) -> Self {
         self.enforce_public_ip = enforce_public_ip;
         self
     }
 
+    #[must_use]
+    pub fn with_allow_private_targets(mut self, allow_private_targets: bool) -> Self {
+        self.allow_private_targets = allow_private_targets;
+        self
+    }
+
     #[must_use]
     pub fn with_path_prefix(mut self, prefix: impl Into<String>) -> Self {
         self.path_prefix = Some(prefix.into());
@@ -99,6 +224,67 @@ impl GenericWebhookConfig {
         self.allowed_hosts = allowed_hosts;
         self
     }
+
+    #[must_use]
+    pub fn with_structured_body(mut self, structured_body: bool) -> Self {
+        self.structured_body = structured_body;
+        self
+    }
+
+    #[must_use]
+    pub fn with_max_tags(mut self, max_tags: usize) -> Self {
+        self.max_tags = max_tags;
+        self
+    }
+
+    #[must_use]
+    pub fn with_template(mut self, template: impl Into<String>) -> Self {
+        self.template = Some(template.into());
+        self
+    }
+
+    #[must_use]
+    /// `precision` is capped at 17, the most digits an `f64` carries.
+    pub fn with_float_precision(mut self, precision: usize) -> Self {
+        self.float_precision = Some(precision.min(MAX_FLOAT_PRECISION));
+        self
+    }
+
+    #[must_use]
+    pub fn with_headers(mut self, headers: Vec<(String, String)>) -> Self {
+        self.headers = headers;
+        self
+    }
+
+    #[must_use]
+    pub fn with_method(mut self, method: HttpMethod) -> Self {
+        self.method = method;
+        self
+    }
+
+    #[must_use]
+    pub fn with_form_encoded(mut self, form_encoded: bool) -> Self {
+        self.form_encoded = form_encoded;
+        self
+    }
+
+    #[must_use]
+    pub fn with_multipart_attachments(mut self, multipart_attachments: bool) -> Self {
+        self.multipart_attachments = multipart_attachments;
+        self
+    }
+
+    #[must_use]
+    pub fn with_attachment_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.attachment_max_bytes = max_bytes;
+        self
+    }
+
+    #[must_use]
+    pub fn with_allow_local_attachment_files(mut self, allow: bool) -> Self {
+        self.allow_local_attachment_files = allow;
+        self
+    }
 }
 
 pub struct GenericWebhookSink {
@@ -107,7 +293,18 @@ pub struct GenericWebhookSink {
     client: reqwest::Client,
     timeout: Duration,
     max_chars: usize,
+    formatter: Option<Arc<dyn EventFormatter>>,
     enforce_public_ip: bool,
+    structured_body: bool,
+    max_tags: usize,
+    template: Option<String>,
+    float_precision: Option<usize>,
+    headers: HeaderMap,
+    method: HttpMethod,
+    form_encoded: bool,
+    multipart_attachments: bool,
+    attachment_max_bytes: usize,
+    allow_local_attachment_files: bool,
 }
 
 impl std::fmt::Debug for GenericWebhookSink {
@@ -116,7 +313,20 @@ impl std::fmt::Debug for GenericWebhookSink {
             .field("url", &redact_url(&self.url))
             .field("payload_field", &self.payload_field)
             .field("max_chars", &self.max_chars)
+            .field("custom_formatter", &self.formatter.is_some())
             .field("enforce_public_ip", &self.enforce_public_ip)
+            .field("structured_body", &self.structured_body)
+            .field("max_tags", &self.max_tags)
+            .field("template", &self.template.is_some())
+            .field("headers", &self.headers.keys().collect::<Vec<_>>())
+            .field("method", &self.method)
+            .field("form_encoded", &self.form_encoded)
+            .field("multipart_attachments", &self.multipart_attachments)
+            .field("attachment_max_bytes", &self.attachment_max_bytes)
+            .field(
+                "allow_local_attachment_files",
+                &self.allow_local_attachment_files,
+            )
             .finish_non_exhaustive()
     }
 }
@@ -128,15 +338,37 @@ impl GenericWebhookSink {
             payload_field,
             timeout,
             max_chars,
+            formatter,
             enforce_public_ip,
+            allow_private_targets,
             path_prefix,
             allowed_hosts,
+            structured_body,
+            max_tags,
+            template,
+            float_precision,
+            headers,
+            method,
+            form_encoded,
+            multipart_attachments,
+            attachment_max_bytes,
+            allow_local_attachment_files,
         } = config;
 
         let payload_field = payload_field.trim();
         if payload_field.is_empty() {
             return Err(anyhow::anyhow!("generic webhook payload_field must not be empty").into());
         }
+        if let Some(template) = &template {
+            validate_template(template, structured_body)?;
+        }
+        if form_encoded && (template.is_some() || multipart_attachments) {
+            return Err(anyhow::anyhow!(
+                "generic webhook form_encoded cannot be combined with template or multipart_attachments"
+            )
+            .into());
+        }
+        let headers = build_header_map(&headers)?;
         let path_prefix = path_prefix.and_then(normalize_optional_trimmed);
         let allowed_hosts = normalize_nonempty_trimmed_vec(allowed_hosts);
 
@@ -147,7 +379,18 @@ impl GenericWebhookSink {
             .into());
         }
 
-        let url = parse_and_validate_https_url_basic(&url)?;
+        if allow_private_targets && allowed_hosts.is_empty() {
+            return Err(anyhow::anyhow!(
+                "generic webhook allow_private_targets requires allowed_hosts"
+            )
+            .into());
+        }
+
+        let url = if allow_private_targets {
+            parse_and_validate_private_target_url(&url)?
+        } else {
+            parse_and_validate_https_url_basic(&url)?
+        };
         if let Some(prefix) = path_prefix.as_deref() {
             validate_url_path_prefix(&url, prefix)?;
         }
@@ -156,7 +399,10 @@ impl GenericWebhookSink {
             let Some(host) = url.host_str() else {
                 return Err(anyhow::anyhow!("url must have a host").into());
             };
-            let allowed = allowed_hosts.iter().any(|h| host.eq_ignore_ascii_case(h));
+            let host = strip_ipv6_brackets(host);
+            let allowed = allowed_hosts
+                .iter()
+                .any(|h| host.eq_ignore_ascii_case(strip_ipv6_brackets(h)));
             if !allowed {
                 return Err(anyhow::anyhow!("url host is not allowed").into());
             }
@@ -169,7 +415,18 @@ impl GenericWebhookSink {
             client,
             timeout,
             max_chars,
-            enforce_public_ip,
+            formatter,
+            enforce_public_ip: enforce_public_ip && !allow_private_targets,
+            structured_body,
+            max_tags,
+            template,
+            float_precision,
+            headers,
+            method,
+            form_encoded,
+            multipart_attachments,
+            attachment_max_bytes,
+            allow_local_attachment_files,
         })
     }
 
@@ -179,9 +436,21 @@ impl GenericWebhookSink {
             payload_field,
             timeout,
             max_chars,
+            formatter,
             enforce_public_ip,
+            allow_private_targets,
             path_prefix,
             allowed_hosts,
+            structured_body,
+            max_tags,
+            template,
+            float_precision,
+            headers,
+            method,
+            form_encoded,
+            multipart_attachments,
+            attachment_max_bytes,
+            allow_local_attachment_files,
         } = config;
 
         if !enforce_public_ip {
@@ -189,6 +458,18 @@ impl GenericWebhookSink {
                 anyhow::anyhow!("generic webhook strict mode requires public ip check").into(),
             );
         }
+        if allow_private_targets {
+            return Err(anyhow::anyhow!(
+                "generic webhook strict mode does not allow private targets"
+            )
+            .into());
+        }
+        if allow_local_attachment_files {
+            return Err(anyhow::anyhow!(
+                "generic webhook strict mode does not allow local attachment files"
+            )
+            .into());
+        }
         if allowed_hosts.is_empty() {
             return Err(
                 anyhow::anyhow!("generic webhook strict mode requires allowed_hosts").into(),
@@ -210,6 +491,16 @@ impl GenericWebhookSink {
         if payload_field.is_empty() {
             return Err(anyhow::anyhow!("generic webhook payload_field must not be empty").into());
         }
+        if let Some(template) = &template {
+            validate_template(template, structured_body)?;
+        }
+        if form_encoded && (template.is_some() || multipart_attachments) {
+            return Err(anyhow::anyhow!(
+                "generic webhook form_encoded cannot be combined with template or multipart_attachments"
+            )
+            .into());
+        }
+        let headers = build_header_map(&headers)?;
         let allowed_hosts = normalize_nonempty_trimmed_vec(allowed_hosts);
 
         let url = parse_and_validate_https_url_basic(&url)?;
@@ -230,14 +521,433 @@ impl GenericWebhookSink {
             client,
             timeout,
             max_chars,
+            formatter,
             enforce_public_ip,
+            structured_body,
+            max_tags,
+            template,
+            float_precision,
+            headers,
+            method,
+            form_encoded,
+            multipart_attachments,
+            attachment_max_bytes,
+            allow_local_attachment_files,
+        })
+    }
+
+    fn request(
+        &self,
+        client: &reqwest::Client,
+        payload: &serde_json::Value,
+    ) -> reqwest::RequestBuilder {
+        let request = client
+            .request(self.method.as_reqwest(), self.url.as_str())
+            .headers(self.headers.clone());
+        if self.form_encoded {
+            request.form(&form_fields(payload))
+        } else {
+            request.json(payload)
+        }
+    }
+
+    fn multipart_request(
+        &self,
+        client: &reqwest::Client,
+        payload: &serde_json::Value,
+        attachments: Vec<LoadedFile>,
+    ) -> crate::Result<reqwest::RequestBuilder> {
+        let payload = serde_json::to_string(payload).map_err(|err| {
+            crate::Error::serialization(anyhow::anyhow!("encode generic webhook payload: {err}"))
+        })?;
+        let mut form = reqwest::multipart::Form::new().part(
+            "payload",
+            reqwest::multipart::Part::text(payload)
+                .mime_str("application/json")
+                .map_err(|err| anyhow::anyhow!("set payload part mime: {err}"))?,
+        );
+        for file in attachments {
+            let part = reqwest::multipart::Part::bytes(file.bytes)
+                .file_name(file.file_name)
+                .mime_str(&file.content_type)
+                .map_err(|err| anyhow::anyhow!("set attachment part mime: {err}"))?;
+            form = form.part("attachment", part);
+        }
+        Ok(client
+            .request(self.method.as_reqwest(), self.url.as_str())
+            .headers(self.headers.clone())
+            .multipart(form))
+    }
+
+    /// Collects inline `event.attachments` and loads URL and path ones, in order, failing once
+    /// their total size exceeds `attachment_max_bytes`.
+    async fn load_attachments(&self, event: &Event) -> crate::Result<Vec<LoadedFile>> {
+        event.validate_attachments()?;
+        let mut files = Vec::with_capacity(event.attachments.len());
+        let mut remaining = self.attachment_max_bytes;
+        for (idx, attachment) in event.attachments.iter().enumerate() {
+            let file = self
+                .load_attachment(attachment, remaining)
+                .await
+                .map_err(|err| anyhow::anyhow!("generic webhook attachment {idx}: {err}"))?;
+            remaining = remaining.saturating_sub(file.bytes.len());
+            files.push(file);
+        }
+        Ok(files)
+    }
+
+    async fn load_attachment(
+        &self,
+        attachment: &Attachment,
+        max_bytes: usize,
+    ) -> crate::Result<LoadedFile> {
+        let mut file = match &attachment.source {
+            AttachmentSource::Inline(bytes) => {
+                if bytes.len() > max_bytes {
+                    return Err(anyhow::anyhow!("attachment too large for upload").into());
+                }
+                LoadedFile {
+                    bytes: bytes.clone(),
+                    file_name: String::new(),
+                    content_type: String::new(),
+                }
+            }
+            AttachmentSource::Url(src) => self.download_attachment(src.trim(), max_bytes).await?,
+            AttachmentSource::Path(src) => {
+                if !self.allow_local_attachment_files {
+                    return Err(anyhow::anyhow!("local attachment files are disabled").into());
+                }
+                read_local_file(src.trim(), max_bytes, "attachment file", "attachment")?
+            }
+        };
+        // Explicit names and types win over the ones derived from the URL or path.
+        if !attachment.filename.trim().is_empty() {
+            file.file_name = attachment.filename.trim().to_string();
+        }
+        if !attachment.content_type.trim().is_empty() {
+            file.content_type = attachment.content_type.trim().to_string();
+        }
+        Ok(file)
+    }
+
+    async fn download_attachment(&self, src: &str, max_bytes: usize) -> crate::Result<LoadedFile> {
+        if !src.starts_with("https://") {
+            return Err(anyhow::anyhow!("unsupported attachment url scheme").into());
+        }
+        let url = parse_and_validate_https_url_basic(src)?;
+        let client = select_http_client(&self.client, self.timeout, &url, true).await?;
+        let (bytes, content_type) =
+            download_limited(&client, &url, max_bytes, "attachment download").await?;
+        let path = std::path::Path::new(url.path());
+        Ok(LoadedFile {
+            bytes,
+            file_name: file_name_or(path, "attachment"),
+            content_type: content_type
+                .unwrap_or_else(|| guess_mime(path.extension().and_then(|v| v.to_str()))),
+        })
+    }
+
+    fn limits(&self) -> TextLimits {
+        let mut limits = TextLimits::new(self.max_chars);
+        limits.float_precision = self.float_precision;
+        if self.multipart_attachments {
+            limits = limits.without_attachment_note();
+        }
+        limits
+    }
+
+    /// The request body: the rendered `template` if set, otherwise [`Self::build_payload`].
+    fn payload(&self, event: &Event, timestamp: Option<u64>) -> crate::Result<serde_json::Value> {
+        let Some(template) = &self.template else {
+            return Ok(self.build_payload(event, timestamp));
+        };
+        let rendered = render_template(
+            template,
+            event,
+            self.limits(),
+            self.max_tags,
+            self.formatter.as_deref(),
+        )?;
+        serde_json::from_str(&rendered).map_err(|err| {
+            crate::Error::serialization(anyhow::anyhow!(
+                "generic webhook template is not valid JSON after substitution: {err}"
+            ))
         })
     }
 
-    fn build_payload(event: &Event, payload_field: &str, max_chars: usize) -> serde_json::Value {
-        let text = format_event_text_limited(event, TextLimits::new(max_chars));
+    fn build_payload(&self, event: &Event, timestamp: Option<u64>) -> serde_json::Value {
+        let limits = self.limits();
+        if self.structured_body {
+            return Self::build_structured_payload(event, limits, self.max_tags, timestamp);
+        }
+        Self::build_text_payload(
+            event,
+            &self.payload_field,
+            limits,
+            self.formatter.as_deref(),
+        )
+    }
+
+    fn build_text_payload(
+        event: &Event,
+        payload_field: &str,
+        limits: TextLimits,
+        formatter: Option<&dyn EventFormatter>,
+    ) -> serde_json::Value {
+        let text = format_event_text_with(formatter, event, limits);
         serde_json::json!({ payload_field: text })
     }
+
+    fn build_structured_payload(
+        event: &Event,
+        limits: TextLimits,
+        max_tags: usize,
+        timestamp: Option<u64>,
+    ) -> serde_json::Value {
+        let mut obj = serde_json::Map::with_capacity(7);
+        obj.insert("kind".to_string(), serde_json::json!(event.kind));
+        obj.insert(
+            "severity".to_string(),
+            serde_json::json!(event.severity.as_str()),
+        );
+        obj.insert(
+            "title".to_string(),
+            serde_json::json!(truncate_chars(event.title.trim(), limits.max_title_chars)),
+        );
+        if let Some(body) = trimmed_non_empty(event.body.as_deref()) {
+            let max_body_chars = limits.max_body_chars.min(limits.max_chars.limit());
+            obj.insert(
+                "body".to_string(),
+                serde_json::json!(truncate_chars(body, max_body_chars)),
+            );
+        }
+        if let Some(url) = trimmed_non_empty(event.url.as_deref()) {
+            obj.insert(
+                "url".to_string(),
+                serde_json::json!(truncate_chars(url, limits.max_url_chars)),
+            );
+        }
+        obj.insert(
+            "tags".to_string(),
+            serde_json::Value::Object(structured_tags(event, limits, max_tags)),
+        );
+        if let Some(timestamp) = timestamp {
+            obj.insert("timestamp".to_string(), serde_json::json!(timestamp));
+        }
+        serde_json::Value::Object(obj)
+    }
+}
+
+fn structured_tags(
+    event: &Event,
+    limits: TextLimits,
+    max_tags: usize,
+) -> serde_json::Map<String, serde_json::Value> {
+    event
+        .tags_in_order()
+        .take(max_tags)
+        .map(|(key, value)| {
+            let value = truncate_chars(value, limits.max_tag_value_chars);
+            (
+                truncate_chars(key, limits.max_tag_key_chars),
+                tag_value_json(&value, limits.float_precision),
+            )
+        })
+        .collect()
+}
+
+/// Flattens a JSON payload object into form fields: strings as-is, other scalars in their JSON
+/// form, nested objects as `outer[inner]`; nulls are skipped.
+fn form_fields(payload: &serde_json::Value) -> Vec<(String, String)> {
+    fn push(fields: &mut Vec<(String, String)>, key: String, value: &serde_json::Value) {
+        match value {
+            serde_json::Value::Null => {}
+            serde_json::Value::String(value) => fields.push((key, value.clone())),
+            serde_json::Value::Object(obj) => {
+                for (inner, value) in obj {
+                    push(fields, format!("{key}[{inner}]"), value);
+                }
+            }
+            other => fields.push((key, other.to_string())),
+        }
+    }
+
+    let mut fields = Vec::new();
+    if let serde_json::Value::Object(obj) = payload {
+        for (key, value) in obj {
+            push(&mut fields, key.clone(), value);
+        }
+    }
+    fields
+}
+
+const TEMPLATE_PLACEHOLDERS: [&str; 7] = [
+    "kind",
+    "severity",
+    "title",
+    "body",
+    "url",
+    "text",
+    "tags_json",
+];
+
+/// Substitutes `{{name}}` placeholders in `template`; see [`GenericWebhookConfig::template`].
+fn render_template(
+    template: &str,
+    event: &Event,
+    limits: TextLimits,
+    max_tags: usize,
+    formatter: Option<&dyn EventFormatter>,
+) -> crate::Result<String> {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            return Err(
+                anyhow::anyhow!("generic webhook template has an unclosed placeholder").into(),
+            );
+        };
+        let name = after[..end].trim();
+        let value = match name {
+            "kind" => json_string_contents(&event.kind),
+            "severity" => json_string_contents(event.severity.as_str()),
+            "title" => {
+                json_string_contents(&truncate_chars(event.title.trim(), limits.max_title_chars))
+            }
+            "body" => json_string_contents(&truncate_chars(
+                trimmed_non_empty(event.body.as_deref()).unwrap_or_default(),
+                limits.max_body_chars.min(limits.max_chars.limit()),
+            )),
+            "url" => json_string_contents(&truncate_chars(
+                trimmed_non_empty(event.url.as_deref()).unwrap_or_default(),
+                limits.max_url_chars,
+            )),
+            "text" => json_string_contents(&format_event_text_with(formatter, event, limits)),
+            "tags_json" => {
+                serde_json::Value::Object(structured_tags(event, limits, max_tags)).to_string()
+            }
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "generic webhook template has an unknown placeholder: {name:?} (expected one of {})",
+                    TEMPLATE_PLACEHOLDERS.join(", ")
+                )
+                .into());
+            }
+        };
+        out.push_str(&value);
+        rest = &after[end + 2..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// `value` JSON-escaped, without the surrounding quotes.
+fn json_string_contents(value: &str) -> String {
+    let quoted = serde_json::Value::String(value.to_string()).to_string();
+    quoted[1..quoted.len() - 1].to_string()
+}
+
+fn validate_template(template: &str, structured_body: bool) -> crate::Result<()> {
+    if structured_body {
+        return Err(anyhow::anyhow!(
+            "generic webhook template cannot be combined with structured_body"
+        )
+        .into());
+    }
+    // Render against an empty and a filled sample so placeholders outside string quotes fail.
+    let filled = Event::new("kind", crate::Severity::Info, "title")
+        .with_body("body")
+        .with_url("https://example.com")
+        .with_tag("key", "value");
+    for sample in [Event::new("", crate::Severity::Info, ""), filled] {
+        let rendered = render_template(template, &sample, TextLimits::new(0), 1, None)?;
+        serde_json::from_str::<serde_json::Value>(&rendered).map_err(|err| {
+            anyhow::anyhow!("generic webhook template is not valid JSON after substitution: {err}")
+        })?;
+    }
+    Ok(())
+}
+
+/// Headers the sink manages itself (or that would bypass the host allow-list).
+const RESERVED_HEADERS: [&str; 5] = [
+    "host",
+    "content-length",
+    "content-type",
+    "transfer-encoding",
+    "connection",
+];
+
+fn is_secret_header(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    name == "cookie"
+        || [
+            "auth",
+            "token",
+            "key",
+            "secret",
+            "password",
+            "signature",
+            "session",
+        ]
+        .iter()
+        .any(|needle| name.contains(needle))
+}
+
+fn build_header_map(headers: &[(String, String)]) -> crate::Result<HeaderMap> {
+    let mut map = HeaderMap::with_capacity(headers.len());
+    for (name, value) in headers {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(anyhow::anyhow!("generic webhook header name must not be empty").into());
+        }
+        let header_name = HeaderName::from_bytes(name.as_bytes())
+            .map_err(|_| anyhow::anyhow!("generic webhook header name is invalid: {name:?}"))?;
+        if RESERVED_HEADERS.contains(&header_name.as_str()) {
+            return Err(
+                anyhow::anyhow!("generic webhook header is not allowed: {header_name}").into(),
+            );
+        }
+        // Checked explicitly (HeaderValue accepts some, e.g. tab) and without echoing the value.
+        if value.chars().any(char::is_control) {
+            return Err(anyhow::anyhow!(
+                "generic webhook header value must not contain control characters: {header_name}"
+            )
+            .into());
+        }
+        let mut header_value = HeaderValue::from_str(value).map_err(|_| {
+            anyhow::anyhow!("generic webhook header value is invalid: {header_name}")
+        })?;
+        header_value.set_sensitive(is_secret_header(name));
+        map.append(header_name, header_value);
+    }
+    Ok(map)
+}
+
+struct RedactedHeaders<'a>(&'a [(String, String)]);
+
+impl std::fmt::Debug for RedactedHeaders<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.0.iter().map(|(name, value)| {
+                let value = if is_secret_header(name) {
+                    "<redacted>"
+                } else {
+                    value.as_str()
+                };
+                (name.as_str(), value)
+            }))
+            .finish()
+    }
+}
+
+fn strip_ipv6_brackets(host: &str) -> &str {
+    host.trim_start_matches('[').trim_end_matches(']')
+}
+
+fn trimmed_non_empty(value: Option<&str>) -> Option<&str> {
+    value.map(str::trim).filter(|value| !value.is_empty())
 }
 
 fn normalize_optional_trimmed(value: String) -> Option<String> {
@@ -270,13 +980,19 @@ impl Sink for GenericWebhookSink {
             )
             .await?;
 
-            let payload = Self::build_payload(event, &self.payload_field, self.max_chars);
+            let timestamp = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .ok()
+                .map(|elapsed| elapsed.as_secs());
+            let payload = self.payload(event, timestamp)?;
 
-            let resp = send_reqwest(
-                client.post(self.url.as_str()).json(&payload),
-                "generic webhook",
-            )
-            .await?;
+            let request = if self.multipart_attachments {
+                let attachments = self.load_attachments(event).await?;
+                self.multipart_request(&client, &payload, attachments)?
+            } else {
+                self.request(&client, &payload)
+            };
+            let resp = send_reqwest(request, "generic webhook").await?;
 
             let status = resp.status();
             if status.is_success() {
@@ -284,31 +1000,48 @@ impl Sink for GenericWebhookSink {
                 return Ok(());
             }
 
-            let body = match read_text_body_limited(resp, DEFAULT_MAX_RESPONSE_BODY_BYTES).await {
+            let body = match read_text_body_limited(resp, max_response_body_bytes()).await {
                 Ok(body) => body,
                 Err(err) => {
-                    return Err(anyhow::anyhow!(
-                        "generic webhook http error: {status} (failed to read response body: {err})"
-                    )
-                    .into());
+                    return Err(crate::Error::http_status(
+                        status,
+                        anyhow::anyhow!(
+                            "generic webhook http error: {status} (failed to read response body: {err})"
+                        ),
+                    ));
                 }
             };
             let summary = truncate_chars(body.trim(), 200);
             if summary.is_empty() {
-                return Err(anyhow::anyhow!(
-                    "generic webhook http error: {status} (response body omitted)"
-                )
-                .into());
+                return Err(crate::Error::http_status(
+                    status,
+                    anyhow::anyhow!("generic webhook http error: {status} (response body omitted)"),
+                ));
             }
-            Err(anyhow::anyhow!("generic webhook http error: {status}, response={summary}").into())
+            Err(crate::Error::http_status(
+                status,
+                anyhow::anyhow!("generic webhook http error: {status}, response={summary}"),
+            ))
         })
     }
+
+    fn preview(&self, event: &Event) -> Option<serde_json::Value> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .ok()
+            .map(|elapsed| elapsed.as_secs());
+        self.payload(event, timestamp).ok()
+    }
+
+    fn diagnostic_target(&self) -> Option<String> {
+        Some(redact_url(&self.url))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::Severity;
+    use crate::{Attachment, Severity};
 
     #[test]
     fn builds_expected_payload() {
@@ -316,13 +1049,211 @@ mod tests {
             .with_body("ok")
             .with_tag("thread_id", "t1");
 
-        let payload = GenericWebhookSink::build_payload(&event, "content", 16 * 1024);
+        let payload = GenericWebhookSink::build_text_payload(
+            &event,
+            "content",
+            TextLimits::new(16 * 1024),
+            None,
+        );
         let text = payload["content"].as_str().unwrap_or("");
         assert!(text.contains("done"));
         assert!(text.contains("ok"));
         assert!(text.contains("thread_id=t1"));
     }
 
+    struct SeverityPrefixFormatter;
+
+    impl EventFormatter for SeverityPrefixFormatter {
+        fn format(&self, event: &Event, _max_chars: usize) -> String {
+            format!("[{}] {}", event.severity, event.title)
+        }
+    }
+
+    #[test]
+    fn custom_formatter_replaces_layout_and_is_truncated() {
+        let sink = GenericWebhookSink::new(
+            GenericWebhookConfig::new("https://example.com/webhook")
+                .with_formatter(Arc::new(SeverityPrefixFormatter)),
+        )
+        .expect("build sink");
+        let event = Event::new("k", Severity::Warning, "disk almost full")
+            .with_body("ignored by the formatter")
+            .with_tag("host", "a");
+
+        let payload = sink.build_payload(&event, None);
+        assert_eq!(payload["text"].as_str(), Some("[warning] disk almost full"));
+
+        let payload = GenericWebhookSink::build_text_payload(
+            &event,
+            "text",
+            TextLimits::new(12),
+            Some(&SeverityPrefixFormatter),
+        );
+        assert_eq!(payload["text"].as_str(), Some("[warning]..."));
+        assert!(format!("{sink:?}").contains("custom_formatter: true"));
+    }
+
+    fn structured_sink(config: GenericWebhookConfig) -> GenericWebhookSink {
+        GenericWebhookSink::new(config.with_structured_body(true)).expect("build sink")
+    }
+
+    #[test]
+    fn structured_body_posts_event_fields() {
+        let sink = structured_sink(GenericWebhookConfig::new("https://example.com/webhook"));
+        let event = Event::new("build", Severity::Error, "build failed")
+            .with_body("exit code 1")
+            .with_url("https://ci.example.com/runs/42")
+            .with_tag("branch", "main")
+            .with_tag("elapsed_s", "1.25");
+
+        let payload = sink.build_payload(&event, Some(1_700_000_000));
+        assert_eq!(
+            payload,
+            serde_json::json!({
+                "kind": "build",
+                "severity": "error",
+                "title": "build failed",
+                "body": "exit code 1",
+                "url": "https://ci.example.com/runs/42",
+                "tags": { "branch": "main", "elapsed_s": "1.25" },
+                "timestamp": 1_700_000_000u64,
+            })
+        );
+
+        let payload = sink.build_payload(&Event::new("k", Severity::Info, "t"), None);
+        assert_eq!(
+            payload,
+            serde_json::json!({ "kind": "k", "severity": "info", "title": "t", "tags": {} })
+        );
+
+        // Flattened text stays the default.
+        let sink =
+            GenericWebhookSink::new(GenericWebhookConfig::new("https://example.com/webhook"))
+                .expect("build sink");
+        let payload = sink.build_payload(&event, Some(1_700_000_000));
+        let text = payload["text"].as_str().unwrap_or("");
+        assert!(text.contains("branch=main"), "{payload}");
+        assert_eq!(payload.as_object().map(|obj| obj.len()), Some(1));
+    }
+
+    #[test]
+    fn structured_body_caps_tags() {
+        let mut event = Event::new("k", Severity::Info, "t");
+        for i in 0..100 {
+            event = event.with_tag(format!("tag{i:03}"), i.to_string());
+        }
+
+        let sink = structured_sink(GenericWebhookConfig::new("https://example.com/webhook"));
+        let payload = sink.build_payload(&event, None);
+        let tags = payload["tags"].as_object().expect("tags object");
+        assert_eq!(tags.len(), 64);
+        assert_eq!(tags.keys().next().map(String::as_str), Some("tag000"));
+        assert_eq!(tags.keys().next_back().map(String::as_str), Some("tag063"));
+
+        let sink = structured_sink(
+            GenericWebhookConfig::new("https://example.com/webhook").with_max_tags(3),
+        );
+        let payload = sink.build_payload(&event, None);
+        assert_eq!(payload["tags"].as_object().map(|tags| tags.len()), Some(3));
+    }
+
+    #[test]
+    fn float_precision_setters_cap_the_precision() {
+        let config = GenericWebhookConfig::new("https://example.com/webhook")
+            .with_float_precision(1_000_000_000);
+        assert_eq!(config.float_precision, Some(17));
+        let options = crate::RenderOptions::default().with_float_precision(usize::MAX);
+        assert_eq!(options.float_precision, Some(17));
+    }
+
+    #[test]
+    fn float_precision_formats_text_and_emits_json_numbers() {
+        let event = Event::new("k", Severity::Info, "t")
+            .with_tag("ratio", "0.30000000000000004")
+            .with_tag("version", "1.2.3")
+            .with_tag("count", "7");
+        let config =
+            GenericWebhookConfig::new("https://example.com/webhook").with_float_precision(2);
+
+        let sink = structured_sink(config.clone());
+        let payload = sink.build_payload(&event, None);
+        assert_eq!(
+            payload["tags"],
+            serde_json::json!({ "count": "7", "ratio": 0.3, "version": "1.2.3" })
+        );
+
+        let sink = GenericWebhookSink::new(config).expect("build sink");
+        let payload = sink.build_payload(&event, None);
+        assert_eq!(
+            payload["text"].as_str(),
+            Some("t\ncount=7\nratio=0.30\nversion=1.2.3")
+        );
+    }
+
+    fn template_sink(template: &str) -> crate::Result<GenericWebhookSink> {
+        GenericWebhookSink::new(
+            GenericWebhookConfig::new("https://example.com/webhook").with_template(template),
+        )
+    }
+
+    #[test]
+    fn template_substitutes_placeholders() {
+        let sink = template_sink(
+            r#"{"msg": {"summary": "[{{severity}}] {{title}}", "detail": "{{body}}"}, "kind": "{{ kind }}", "labels": {{tags_json}}}"#,
+        )
+        .expect("build sink");
+        let event = Event::new("build", Severity::Error, "build failed")
+            .with_body("exit code 1")
+            .with_tag("branch", "main");
+
+        let payload = sink.payload(&event, None).expect("render template");
+        assert_eq!(
+            payload,
+            serde_json::json!({
+                "msg": { "summary": "[error] build failed", "detail": "exit code 1" },
+                "kind": "build",
+                "labels": { "branch": "main" },
+            })
+        );
+        assert_eq!(sink.preview(&event), Some(payload));
+    }
+
+    #[test]
+    fn template_escapes_special_characters_in_title() {
+        let sink =
+            template_sink(r#"{"title": "{{title}}", "text": "{{text}}"}"#).expect("build sink");
+        let title = "say \"hi\" \\ {{body}}\ttab";
+        let event = Event::new("k", Severity::Info, title).with_body("line1\nline2");
+
+        let payload = sink.payload(&event, None).expect("render template");
+        assert_eq!(payload["title"].as_str(), Some(title));
+        assert_eq!(
+            payload["text"].as_str(),
+            Some(format!("{title}\nline1\nline2").as_str())
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_templates() {
+        for (template, expected) in [
+            (r#"{"title": {{title}}}"#, "not valid JSON"),
+            (r#"{"title": "{{title}}""#, "not valid JSON"),
+            (r#"{"title": "{{subject}}"}"#, "unknown placeholder"),
+            (r#"{"title": "{{title"}"#, "unclosed placeholder"),
+        ] {
+            let err = template_sink(template).expect_err("expected invalid template");
+            assert!(err.to_string().contains(expected), "{template}: {err:#}");
+        }
+
+        let err = GenericWebhookSink::new(
+            GenericWebhookConfig::new("https://example.com/webhook")
+                .with_template(r#"{"title": "{{title}}"}"#)
+                .with_structured_body(true),
+        )
+        .expect_err("expected conflict");
+        assert!(err.to_string().contains("structured_body"), "{err:#}");
+    }
+
     #[test]
     fn rejects_non_https_url() {
         let cfg = GenericWebhookConfig::new("http://example.com/webhook");
@@ -345,6 +1276,192 @@ mod tests {
         assert!(cfg_dbg.contains("<redacted>"), "{cfg_dbg}");
     }
 
+    fn header_pairs(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn validates_custom_headers() {
+        for (name, value, needle) in [
+            (" ", "v", "name must not be empty"),
+            ("X Api Key", "v", "name is invalid"),
+            ("X-Api\nKey", "v", "name is invalid"),
+            ("X-Api-Key", "abc\r\nX-Evil: 1", "control characters"),
+            ("X-Api-Key", "a\tb", "control characters"),
+            ("Host", "internal.example.com", "not allowed"),
+            ("content-type", "text/plain", "not allowed"),
+        ] {
+            let cfg = GenericWebhookConfig::new("https://example.com/webhook")
+                .with_headers(header_pairs(&[(name, value)]));
+            let err = GenericWebhookSink::new(cfg).expect_err("expected invalid header");
+            let msg = err.to_string();
+            assert!(msg.contains(needle), "{name:?}: {msg}");
+            assert!(!msg.contains("X-Evil"), "{msg}");
+        }
+    }
+
+    #[test]
+    fn applies_custom_headers_to_request() {
+        let sink = GenericWebhookSink::new(
+            GenericWebhookConfig::new("https://example.com/webhook").with_headers(header_pairs(&[
+                ("Authorization", "Bearer s3cr3t"),
+                ("X-Api-Key", "k3y"),
+                ("X-Team", "ops"),
+            ])),
+        )
+        .expect("build sink");
+
+        let req = sink
+            .request(&sink.client, &serde_json::json!({ "text": "hi" }))
+            .build()
+            .expect("build request");
+        let headers = req.headers();
+        assert_eq!(
+            headers.get("authorization").and_then(|v| v.to_str().ok()),
+            Some("Bearer s3cr3t")
+        );
+        assert!(
+            headers
+                .get("authorization")
+                .is_some_and(|v| v.is_sensitive())
+        );
+        assert_eq!(
+            headers.get("x-api-key").and_then(|v| v.to_str().ok()),
+            Some("k3y")
+        );
+        assert_eq!(
+            headers.get("x-team").and_then(|v| v.to_str().ok()),
+            Some("ops")
+        );
+        assert_eq!(
+            headers.get("content-type").and_then(|v| v.to_str().ok()),
+            Some("application/json")
+        );
+    }
+
+    fn form_request(sink: &GenericWebhookSink, event: &Event) -> (Option<String>, String) {
+        let payload = sink.payload(event, Some(1_700_000_000)).expect("payload");
+        let req = sink
+            .request(&sink.client, &payload)
+            .build()
+            .expect("build request");
+        let content_type = req
+            .headers()
+            .get("content-type")
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        let body = req
+            .body()
+            .and_then(|body| body.as_bytes())
+            .map(|bytes| String::from_utf8_lossy(bytes).into_owned())
+            .unwrap_or_default();
+        (content_type, body)
+    }
+
+    #[test]
+    fn form_encoded_sends_payload_field_as_form_data() {
+        let sink = GenericWebhookSink::new(
+            GenericWebhookConfig::new("https://example.com/webhook")
+                .with_payload_field("message")
+                .with_form_encoded(true),
+        )
+        .expect("build sink");
+        let event = Event::new("k", Severity::Info, "a&b = c").with_body("100% done");
+
+        let (content_type, body) = form_request(&sink, &event);
+        assert_eq!(
+            content_type.as_deref(),
+            Some("application/x-www-form-urlencoded")
+        );
+        assert_eq!(body, "message=a%26b+%3D+c%0A100%25+done");
+    }
+
+    #[test]
+    fn form_encoded_structured_body_flattens_tags() {
+        let sink = structured_sink(
+            GenericWebhookConfig::new("https://example.com/webhook").with_form_encoded(true),
+        );
+        let event = Event::new("build", Severity::Error, "build failed")
+            .with_body("exit code 1")
+            .with_tag("branch", "main")
+            .with_tag("run id", "42");
+
+        let (content_type, body) = form_request(&sink, &event);
+        assert_eq!(
+            content_type.as_deref(),
+            Some("application/x-www-form-urlencoded")
+        );
+        let fields: Vec<&str> = body.split('&').collect();
+        for expected in [
+            "title=build+failed",
+            "body=exit+code+1",
+            "severity=error",
+            "kind=build",
+            "tags%5Bbranch%5D=main",
+            "tags%5Brun+id%5D=42",
+            "timestamp=1700000000",
+        ] {
+            assert!(fields.contains(&expected), "{expected} missing from {body}");
+        }
+    }
+
+    #[test]
+    fn form_encoded_rejects_template_and_multipart() {
+        for cfg in [
+            GenericWebhookConfig::new("https://example.com/webhook")
+                .with_template(r#"{"title": "{{title}}"}"#),
+            GenericWebhookConfig::new("https://example.com/webhook")
+                .with_multipart_attachments(true),
+        ] {
+            let err = GenericWebhookSink::new(cfg.with_form_encoded(true))
+                .expect_err("expected conflict");
+            assert!(err.to_string().contains("form_encoded"), "{err:#}");
+        }
+    }
+
+    #[test]
+    fn uses_configured_http_method() {
+        let payload = serde_json::json!({ "text": "hi" });
+        for (method, expected) in [
+            (None, reqwest::Method::POST),
+            (Some(HttpMethod::Put), reqwest::Method::PUT),
+            (Some(HttpMethod::Patch), reqwest::Method::PATCH),
+        ] {
+            let mut cfg = GenericWebhookConfig::new("https://example.com/webhook");
+            if let Some(method) = method {
+                cfg = cfg.with_method(method);
+            }
+            let sink = GenericWebhookSink::new(cfg).expect("build sink");
+            let req = sink
+                .request(&sink.client, &payload)
+                .build()
+                .expect("build request");
+            assert_eq!(req.method(), expected);
+        }
+    }
+
+    #[test]
+    fn debug_redacts_secret_header_values() {
+        let cfg =
+            GenericWebhookConfig::new("https://example.com/webhook").with_headers(header_pairs(&[
+                ("Authorization", "Bearer s3cr3t"),
+                ("X-Api-Key", "k3y"),
+                ("X-Team", "ops"),
+            ]));
+        let dbg = format!("{cfg:?}");
+        assert!(!dbg.contains("s3cr3t"), "{dbg}");
+        assert!(!dbg.contains("k3y"), "{dbg}");
+        assert!(dbg.contains("ops"), "{dbg}");
+
+        let sink = GenericWebhookSink::new(cfg).expect("build sink");
+        let dbg = format!("{sink:?}");
+        assert!(!dbg.contains("s3cr3t"), "{dbg}");
+        assert!(dbg.contains("x-api-key"), "{dbg}");
+    }
+
     #[test]
     fn disabling_public_ip_check_requires_allowed_hosts() {
         let cfg =
@@ -353,6 +1470,164 @@ mod tests {
         assert!(err.to_string().contains("allowed_hosts"), "{err:#}");
     }
 
+    #[test]
+    fn private_targets_are_rejected_unless_opted_in() {
+        for url in [
+            "http://127.0.0.1:8080/hook",
+            "https://127.0.0.1/hook",
+            "https://localhost/hook",
+        ] {
+            let cfg = GenericWebhookConfig::new(url)
+                .with_allowed_hosts(vec!["127.0.0.1".to_string(), "localhost".to_string()])
+                .with_public_ip_check(false);
+            let err = GenericWebhookSink::new(cfg).expect_err("expected invalid url");
+            assert!(err.to_string().contains("url"), "{url}: {err:#}");
+        }
+    }
+
+    #[test]
+    fn private_targets_require_allowed_hosts() {
+        let cfg = GenericWebhookConfig::new("http://127.0.0.1:8080/hook")
+            .with_allow_private_targets(true);
+        let err = GenericWebhookSink::new(cfg).expect_err("expected invalid config");
+        assert!(err.to_string().contains("allowed_hosts"), "{err:#}");
+
+        let cfg = GenericWebhookConfig::new("http://127.0.0.1:8080/hook")
+            .with_allow_private_targets(true)
+            .with_allowed_hosts(vec!["localhost".to_string()]);
+        let err = GenericWebhookSink::new(cfg).expect_err("expected invalid host");
+        assert!(err.to_string().contains("host is not allowed"), "{err:#}");
+    }
+
+    #[test]
+    fn private_targets_allow_loopback_http_only() {
+        let cfg = GenericWebhookConfig::new("http://127.0.0.1:8080/hook")
+            .with_allow_private_targets(true)
+            .with_allowed_hosts(vec!["127.0.0.1".to_string()]);
+        let sink = GenericWebhookSink::new(cfg).expect("build sink");
+        assert!(!sink.enforce_public_ip);
+        assert_eq!(sink.url.port(), Some(8080));
+
+        let cfg = GenericWebhookConfig::new("http://[::1]:9000/hook")
+            .with_allow_private_targets(true)
+            .with_allowed_hosts(vec!["::1".to_string()]);
+        GenericWebhookSink::new(cfg).expect("build ipv6 loopback sink");
+
+        let cfg = GenericWebhookConfig::new("https://10.0.0.5:8443/hook")
+            .with_allow_private_targets(true)
+            .with_allowed_hosts(vec!["10.0.0.5".to_string()]);
+        GenericWebhookSink::new(cfg).expect("build private https sink");
+
+        let cfg = GenericWebhookConfig::new("http://10.0.0.5/hook")
+            .with_allow_private_targets(true)
+            .with_allowed_hosts(vec!["10.0.0.5".to_string()]);
+        let err = GenericWebhookSink::new(cfg).expect_err("expected https requirement");
+        assert!(err.to_string().contains("https"), "{err:#}");
+
+        let cfg = GenericWebhookConfig::new("http://user:pw@127.0.0.1/hook")
+            .with_allow_private_targets(true)
+            .with_allowed_hosts(vec!["127.0.0.1".to_string()]);
+        let err = GenericWebhookSink::new(cfg).expect_err("expected credentials rejection");
+        assert!(err.to_string().contains("credentials"), "{err:#}");
+    }
+
+    #[test]
+    fn strict_rejects_private_targets() {
+        let cfg = GenericWebhookConfig::new_strict(
+            "https://example.com/hooks/notify",
+            "/hooks/",
+            vec!["example.com".to_string()],
+        )
+        .with_allow_private_targets(true);
+        let err = GenericWebhookSink::new_strict(cfg).expect_err("expected strict validation");
+        assert!(err.to_string().contains("private targets"), "{err:#}");
+    }
+
+    /// Serves a single request on a local port and replies with `status` (e.g. `"200 OK"`) and
+    /// `body`. The handle resolves to the raw request, read up to the end of its body.
+    async fn spawn_one_shot_http_server(
+        status: &'static str,
+        body: &'static str,
+    ) -> (std::net::SocketAddr, tokio::task::JoinHandle<String>) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind");
+        let addr = listener.local_addr().expect("addr");
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.expect("accept");
+            let mut buf = vec![0_u8; 4096];
+            let mut request = Vec::new();
+            loop {
+                let text = String::from_utf8_lossy(&request).into_owned();
+                if let Some(header_end) = text.find("\r\n\r\n") {
+                    let headers = text[..header_end].to_ascii_lowercase();
+                    let content_length = headers
+                        .lines()
+                        .find_map(|line| line.strip_prefix("content-length:"))
+                        .and_then(|len| len.trim().parse::<usize>().ok());
+                    let complete = match content_length {
+                        Some(len) => request.len() >= header_end + 4 + len,
+                        None => request.ends_with(b"0\r\n\r\n"),
+                    };
+                    if complete {
+                        break;
+                    }
+                }
+                let n = stream.read(&mut buf).await.expect("read");
+                if n == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buf[..n]);
+            }
+            let response = format!(
+                "HTTP/1.1 {status}\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            let _ = stream.write_all(response.as_bytes()).await;
+            String::from_utf8_lossy(&request).into_owned()
+        });
+        (addr, server)
+    }
+
+    #[tokio::test]
+    async fn private_target_sends_to_local_server() {
+        let (addr, server) = spawn_one_shot_http_server("200 OK", "").await;
+
+        let cfg = GenericWebhookConfig::new(format!("http://{addr}/hooks/notify"))
+            .with_allow_private_targets(true)
+            .with_allowed_hosts(vec!["127.0.0.1".to_string()]);
+        let sink = GenericWebhookSink::new(cfg).expect("build sink");
+        sink.send(&Event::new("kind", Severity::Info, "hello"))
+            .await
+            .expect("send");
+
+        let request = server.await.expect("server");
+        assert!(request.starts_with("POST /hooks/notify "), "{request}");
+        assert!(request.contains(r#"{"text":"hello"}"#), "{request}");
+    }
+
+    #[tokio::test]
+    async fn http_500_maps_to_http_status_kind() {
+        let (addr, _server) = spawn_one_shot_http_server("500 Internal Server Error", "oops").await;
+
+        let cfg = GenericWebhookConfig::new(format!("http://{addr}/hooks/notify"))
+            .with_allow_private_targets(true)
+            .with_allowed_hosts(vec!["127.0.0.1".to_string()]);
+        let sink = GenericWebhookSink::new(cfg).expect("build sink");
+        let err = sink
+            .send(&Event::new("kind", Severity::Info, "hello"))
+            .await
+            .expect_err("expected http error");
+        assert_eq!(err.kind(), crate::ErrorKind::HttpStatus(500), "{err:#}");
+        assert!(err.to_string().contains("http error: 500"), "{err:#}");
+
+        let err = GenericWebhookSink::new(GenericWebhookConfig::new("http://example.com/hook"))
+            .expect_err("expected invalid url");
+        assert_eq!(err.kind(), crate::ErrorKind::InvalidConfig, "{err:#}");
+    }
+
     #[test]
     fn strict_requires_allowed_hosts_and_path_prefix() {
         let cfg = GenericWebhookConfig::new("https://example.com/webhook");
@@ -423,4 +1698,137 @@ mod tests {
         assert_eq!(sink.url.host_str().unwrap_or(""), "example.com");
         assert!(sink.url.path().starts_with("/hooks/"));
     }
+
+    fn temp_attachment(name: &str, contents: &[u8]) -> String {
+        let path =
+            std::env::temp_dir().join(format!("notify-kit-webhook-{}-{name}", std::process::id()));
+        std::fs::write(&path, contents).expect("write attachment");
+        path.to_string_lossy().into_owned()
+    }
+
+    #[tokio::test]
+    async fn multipart_sends_payload_and_local_attachment() {
+        let src = temp_attachment("report.txt", b"disk usage report");
+        let (addr, server) = spawn_one_shot_http_server("200 OK", "").await;
+
+        let cfg = GenericWebhookConfig::new(format!("http://{addr}/hooks/notify"))
+            .with_allow_private_targets(true)
+            .with_allowed_hosts(vec!["127.0.0.1".to_string()])
+            .with_multipart_attachments(true)
+            .with_allow_local_attachment_files(true);
+        let sink = GenericWebhookSink::new(cfg).expect("build sink");
+        sink.send(
+            &Event::new("kind", Severity::Info, "hello")
+                .with_attachment(Attachment::from_path(&src)),
+        )
+        .await
+        .expect("send");
+        let _ = std::fs::remove_file(&src);
+
+        let request = server.await.expect("server");
+        assert!(request.starts_with("POST /hooks/notify "), "{request}");
+        assert!(
+            request.contains("content-type: multipart/form-data; boundary="),
+            "{request}"
+        );
+        assert!(request.contains(r#"name="payload""#), "{request}");
+        assert!(request.contains(r#"{"text":"hello"}"#), "{request}");
+        assert!(
+            request.contains(&format!(
+                r#"name="attachment"; filename="notify-kit-webhook-{}-report.txt""#,
+                std::process::id()
+            )),
+            "{request}"
+        );
+        assert!(request.contains("Content-Type: text/plain"), "{request}");
+        assert!(request.contains("disk usage report"), "{request}");
+    }
+
+    #[tokio::test]
+    async fn attachments_enforce_local_opt_in_scheme_and_total_size() {
+        let src = temp_attachment("big.bin", &[7_u8; 64]);
+        let event = Event::new("kind", Severity::Info, "hello")
+            .with_attachment(Attachment::from_path(&src))
+            .with_attachment(Attachment::from_path(&src));
+
+        let sink = GenericWebhookSink::new(
+            GenericWebhookConfig::new("https://example.com/webhook")
+                .with_multipart_attachments(true),
+        )
+        .expect("build sink");
+        let err = sink.load_attachments(&event).await.expect_err("local off");
+        assert!(
+            err.to_string()
+                .contains("local attachment files are disabled"),
+            "{err:#}"
+        );
+
+        let err = sink
+            .load_attachments(
+                &Event::new("kind", Severity::Info, "hello")
+                    .with_attachment(Attachment::from_url("http://example.com/a.txt")),
+            )
+            .await
+            .expect_err("plain http rejected");
+        assert!(err.to_string().contains("scheme"), "{err:#}");
+
+        let sink = GenericWebhookSink::new(
+            GenericWebhookConfig::new("https://example.com/webhook")
+                .with_multipart_attachments(true)
+                .with_allow_local_attachment_files(true)
+                .with_attachment_max_bytes(100),
+        )
+        .expect("build sink");
+        let err = sink
+            .load_attachments(&event)
+            .await
+            .expect_err("total over limit");
+        assert!(err.to_string().contains("attachment 1"), "{err:#}");
+        assert!(err.to_string().contains("too large"), "{err:#}");
+
+        let files = sink
+            .load_attachments(
+                &Event::new("kind", Severity::Info, "hello")
+                    .with_attachment(Attachment::from_path(&src)),
+            )
+            .await
+            .expect("within limit");
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].bytes.len(), 64);
+        assert_eq!(files[0].content_type, "application/octet-stream");
+
+        // In-memory attachments come first and share the same budget.
+        let event = Event::new("kind", Severity::Info, "hello")
+            .with_attachment(Attachment::new("notes.txt", "text/plain", vec![b'n'; 30]))
+            .with_attachment(Attachment::from_path(&src));
+        let files = sink.load_attachments(&event).await.expect("within limit");
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].file_name, "notes.txt");
+        assert_eq!(files[0].content_type, "text/plain");
+        assert_eq!(files[1].bytes.len(), 64);
+        let err = sink
+            .load_attachments(
+                &Event::new("kind", Severity::Info, "hello").with_attachment(Attachment::new(
+                    "big.txt",
+                    "text/plain",
+                    vec![0; 101],
+                )),
+            )
+            .await
+            .expect_err("in-memory over limit");
+        assert!(err.to_string().contains("attachment 0"), "{err:#}");
+        let _ = std::fs::remove_file(&src);
+    }
+
+    #[test]
+    fn strict_rejects_local_attachment_files() {
+        let cfg = GenericWebhookConfig::new_strict(
+            "https://example.com/hooks/notify",
+            "/hooks/",
+            vec!["example.com".to_string()],
+        )
+        .with_allow_local_attachment_files(true);
+        let err = GenericWebhookSink::new_strict(cfg).expect_err("expected strict validation");
+        assert!(err.to_string().contains("local attachment"), "{err:#}");
+    }
 }
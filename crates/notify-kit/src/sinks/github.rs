@@ -1,14 +1,17 @@
+use std::sync::Arc;
 use std::time::Duration;
 
 use crate::Event;
+use crate::render::EventFormatter;
 use crate::sinks::http::{
-    DEFAULT_MAX_RESPONSE_BODY_BYTES, build_http_client, read_text_body_limited, redact_url,
-    send_reqwest, try_drain_response_body_for_reuse,
+    build_http_client, max_response_body_bytes, read_json_body_limited, read_text_body_limited,
+    redact_url, send_reqwest, try_drain_response_body_for_reuse,
 };
-use crate::sinks::text::{TextLimits, format_event_text_limited, truncate_chars};
+use crate::sinks::text::{TextLimits, format_event_text_with, truncate_chars};
 use crate::sinks::{BoxFuture, Sink};
 
 const GITHUB_API_BASE: &str = "https://api.github.com";
+const GITHUB_MAX_ISSUE_TITLE_CHARS: usize = 256;
 
 #[non_exhaustive]
 #[derive(Clone)]
@@ -18,7 +21,10 @@ pub struct GitHubCommentConfig {
     pub issue_number: u64,
     pub token: String,
     pub timeout: Duration,
+    /// Message length limit; `0` means unlimited (see [`crate::MaxChars`]).
     pub max_chars: usize,
+    /// Custom message layout; `None` uses the built-in one ([`crate::DefaultFormatter`]).
+    pub formatter: Option<Arc<dyn EventFormatter>>,
 }
 
 impl std::fmt::Debug for GitHubCommentConfig {
@@ -30,6 +36,7 @@ impl std::fmt::Debug for GitHubCommentConfig {
             .field("token", &"<redacted>")
             .field("timeout", &self.timeout)
             .field("max_chars", &self.max_chars)
+            .field("custom_formatter", &self.formatter.is_some())
             .finish()
     }
 }
@@ -48,6 +55,7 @@ impl GitHubCommentConfig {
             token: token.into(),
             timeout: Duration::from_secs(2),
             max_chars: 65000,
+            formatter: None,
         }
     }
 
@@ -62,6 +70,12 @@ impl GitHubCommentConfig {
         self.max_chars = max_chars;
         self
     }
+
+    #[must_use]
+    pub fn with_formatter(mut self, formatter: Arc<dyn EventFormatter>) -> Self {
+        self.formatter = Some(formatter);
+        self
+    }
 }
 
 pub struct GitHubCommentSink {
@@ -72,6 +86,7 @@ pub struct GitHubCommentSink {
     token: String,
     client: reqwest::Client,
     max_chars: usize,
+    formatter: Option<Arc<dyn EventFormatter>>,
 }
 
 impl std::fmt::Debug for GitHubCommentSink {
@@ -83,6 +98,7 @@ impl std::fmt::Debug for GitHubCommentSink {
             .field("issue_number", &self.issue_number)
             .field("token", &"<redacted>")
             .field("max_chars", &self.max_chars)
+            .field("custom_formatter", &self.formatter.is_some())
             .finish_non_exhaustive()
     }
 }
@@ -110,15 +126,224 @@ impl GitHubCommentSink {
             token: token.to_string(),
             client,
             max_chars: config.max_chars,
+            formatter: config.formatter,
         })
     }
 
-    fn build_payload(event: &Event, max_chars: usize) -> serde_json::Value {
-        let text = format_event_text_limited(event, TextLimits::new(max_chars));
+    fn build_payload(
+        event: &Event,
+        max_chars: usize,
+        formatter: Option<&dyn EventFormatter>,
+    ) -> serde_json::Value {
+        let text = format_event_text_with(formatter, event, TextLimits::new(max_chars));
         serde_json::json!({ "body": text })
     }
 }
 
+#[non_exhaustive]
+#[derive(Clone)]
+pub struct GitHubIssueConfig {
+    pub owner: String,
+    pub repo: String,
+    pub token: String,
+    /// Labels applied to every created issue.
+    pub labels: Vec<String>,
+    /// GitHub logins assigned to every created issue.
+    pub assignees: Vec<String>,
+    pub timeout: Duration,
+    /// Issue body length limit; `0` means unlimited (see [`crate::MaxChars`]).
+    pub max_chars: usize,
+    /// Custom body layout; `None` uses the built-in one ([`crate::DefaultFormatter`]).
+    pub formatter: Option<Arc<dyn EventFormatter>>,
+}
+
+impl std::fmt::Debug for GitHubIssueConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GitHubIssueConfig")
+            .field("owner", &self.owner)
+            .field("repo", &self.repo)
+            .field("token", &"<redacted>")
+            .field("labels", &self.labels)
+            .field("assignees", &self.assignees)
+            .field("timeout", &self.timeout)
+            .field("max_chars", &self.max_chars)
+            .field("custom_formatter", &self.formatter.is_some())
+            .finish()
+    }
+}
+
+impl GitHubIssueConfig {
+    pub fn new(
+        owner: impl Into<String>,
+        repo: impl Into<String>,
+        token: impl Into<String>,
+    ) -> Self {
+        Self {
+            owner: owner.into(),
+            repo: repo.into(),
+            token: token.into(),
+            labels: Vec::new(),
+            assignees: Vec::new(),
+            timeout: Duration::from_secs(2),
+            max_chars: 65000,
+            formatter: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_labels<I, S>(mut self, labels: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.labels = labels.into_iter().map(Into::into).collect();
+        self
+    }
+
+    #[must_use]
+    pub fn with_assignees<I, S>(mut self, assignees: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.assignees = assignees.into_iter().map(Into::into).collect();
+        self
+    }
+
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    #[must_use]
+    pub fn with_max_chars(mut self, max_chars: usize) -> Self {
+        self.max_chars = max_chars;
+        self
+    }
+
+    #[must_use]
+    pub fn with_formatter(mut self, formatter: Arc<dyn EventFormatter>) -> Self {
+        self.formatter = Some(formatter);
+        self
+    }
+}
+
+/// Opens a new GitHub issue per event (title = event title, body = rendered text).
+///
+/// Every event it receives creates an issue, so register it with a [`crate::SinkFilter`]
+/// (e.g. `min_severity = Error`) rather than as a broadcast sink.
+pub struct GitHubIssueSink {
+    api_url: reqwest::Url,
+    owner: String,
+    repo: String,
+    token: String,
+    labels: Vec<String>,
+    assignees: Vec<String>,
+    client: reqwest::Client,
+    max_chars: usize,
+    formatter: Option<Arc<dyn EventFormatter>>,
+}
+
+impl std::fmt::Debug for GitHubIssueSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GitHubIssueSink")
+            .field("api_url", &redact_url(&self.api_url))
+            .field("owner", &self.owner)
+            .field("repo", &self.repo)
+            .field("token", &"<redacted>")
+            .field("labels", &self.labels)
+            .field("assignees", &self.assignees)
+            .field("max_chars", &self.max_chars)
+            .field("custom_formatter", &self.formatter.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl GitHubIssueSink {
+    pub fn new(config: GitHubIssueConfig) -> crate::Result<Self> {
+        let owner = normalize_github_identifier("owner", &config.owner)?;
+        let repo = normalize_github_identifier("repo", &config.repo)?;
+        let token = config.token.trim();
+        if token.is_empty() {
+            return Err(anyhow::anyhow!("github token must not be empty").into());
+        }
+        let mut labels = Vec::with_capacity(config.labels.len());
+        for label in &config.labels {
+            let label = label.trim();
+            if label.is_empty() {
+                return Err(anyhow::anyhow!("github label must not be empty").into());
+            }
+            labels.push(label.to_string());
+        }
+        let assignees = config
+            .assignees
+            .iter()
+            .map(|assignee| normalize_github_identifier("assignee", assignee).map(str::to_string))
+            .collect::<crate::Result<Vec<_>>>()?;
+
+        let api_url = build_repo_api_url(owner, repo, &["issues"])?;
+        let client = build_http_client(config.timeout)?;
+
+        Ok(Self {
+            api_url,
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+            token: token.to_string(),
+            labels,
+            assignees,
+            client,
+            max_chars: config.max_chars,
+            formatter: config.formatter,
+        })
+    }
+
+    /// Creates an issue for `event` and returns its number.
+    pub async fn create_issue(&self, event: &Event) -> crate::Result<u64> {
+        let payload = Self::build_payload(
+            event,
+            &self.labels,
+            &self.assignees,
+            self.max_chars,
+            self.formatter.as_deref(),
+        );
+        let resp = post_github(
+            &self.client,
+            &self.api_url,
+            &self.token,
+            &payload,
+            "github issue",
+        )
+        .await?;
+        let body = read_json_body_limited(resp, max_response_body_bytes()).await?;
+        body["number"]
+            .as_u64()
+            .ok_or_else(|| anyhow::anyhow!("github issue response missing number").into())
+    }
+
+    fn build_payload(
+        event: &Event,
+        labels: &[String],
+        assignees: &[String],
+        max_chars: usize,
+        formatter: Option<&dyn EventFormatter>,
+    ) -> serde_json::Value {
+        let mut title = truncate_chars(event.title.trim(), GITHUB_MAX_ISSUE_TITLE_CHARS);
+        if title.is_empty() {
+            title = event.kind.clone();
+        }
+        let text = format_event_text_with(formatter, event, TextLimits::new(max_chars));
+        let mut payload = serde_json::json!({ "title": title, "body": text });
+        if !labels.is_empty() {
+            payload["labels"] = serde_json::json!(labels);
+        }
+        if !assignees.is_empty() {
+            payload["assignees"] = serde_json::json!(assignees);
+        }
+        payload
+    }
+}
+
 fn normalize_github_identifier<'a>(kind: &'static str, value: &'a str) -> crate::Result<&'a str> {
     let value = value.trim();
     if value.is_empty() {
@@ -136,25 +361,72 @@ fn normalize_github_identifier<'a>(kind: &'static str, value: &'a str) -> crate:
     Ok(value)
 }
 
+fn build_repo_api_url(owner: &str, repo: &str, segments: &[&str]) -> crate::Result<reqwest::Url> {
+    let mut url = reqwest::Url::parse(GITHUB_API_BASE)
+        .map_err(|err| anyhow::anyhow!("invalid github api base url: {err}"))?;
+    url.path_segments_mut()
+        .map_err(|_| anyhow::anyhow!("invalid github api base url"))?
+        .extend(["repos", owner, repo])
+        .extend(segments);
+    Ok(url)
+}
+
 fn build_issue_comment_url(
     owner: &str,
     repo: &str,
     issue_number: u64,
 ) -> crate::Result<reqwest::Url> {
-    let mut url = reqwest::Url::parse(GITHUB_API_BASE)
-        .map_err(|err| anyhow::anyhow!("invalid github api base url: {err}"))?;
     let issue_segment = issue_number.to_string();
-    url.path_segments_mut()
-        .map_err(|_| anyhow::anyhow!("invalid github api base url"))?
-        .extend([
-            "repos",
-            owner,
-            repo,
-            "issues",
-            issue_segment.as_str(),
-            "comments",
-        ]);
-    Ok(url)
+    build_repo_api_url(owner, repo, &["issues", issue_segment.as_str(), "comments"])
+}
+
+/// POSTs `payload` to the GitHub API; non-2xx responses become `"{context} http error"`s.
+async fn post_github(
+    client: &reqwest::Client,
+    url: &reqwest::Url,
+    token: &str,
+    payload: &serde_json::Value,
+    context: &str,
+) -> crate::Result<reqwest::Response> {
+    let resp = send_reqwest(
+        client
+            .post(url.as_str())
+            .header("Accept", "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .bearer_auth(token)
+            .json(payload),
+        context,
+    )
+    .await?;
+
+    let status = resp.status();
+    if status.is_success() {
+        return Ok(resp);
+    }
+
+    let body = match read_text_body_limited(resp, max_response_body_bytes()).await {
+        Ok(body) => body,
+        Err(err) => {
+            return Err(crate::Error::http_status(
+                status,
+                anyhow::anyhow!(
+                    "{context} http error: {status} (failed to read response body: {err})"
+                ),
+            ));
+        }
+    };
+    let summary = truncate_chars(body.trim(), 200);
+    if summary.is_empty() {
+        return Err(crate::Error::http_status(
+            status,
+            anyhow::anyhow!("{context} http error: {status} (response body omitted)"),
+        ));
+    }
+
+    Err(crate::Error::http_status(
+        status,
+        anyhow::anyhow!("{context} http error: {status}, response={summary}"),
+    ))
 }
 
 impl Sink for GitHubCommentSink {
@@ -164,46 +436,70 @@ impl Sink for GitHubCommentSink {
 
     fn send<'a>(&'a self, event: &'a Event) -> BoxFuture<'a, crate::Result<()>> {
         Box::pin(async move {
-            let payload = Self::build_payload(event, self.max_chars);
-
-            let resp = send_reqwest(
-                self.client
-                    .post(self.api_url.as_str())
-                    .header("Accept", "application/vnd.github+json")
-                    .header("User-Agent", "notify-kit")
-                    .header("X-GitHub-Api-Version", "2022-11-28")
-                    .bearer_auth(&self.token)
-                    .json(&payload),
+            let payload = Self::build_payload(event, self.max_chars, self.formatter.as_deref());
+
+            let resp = post_github(
+                &self.client,
+                &self.api_url,
+                &self.token,
+                &payload,
                 "github comment",
             )
             .await?;
+            try_drain_response_body_for_reuse(resp).await;
+            Ok(())
+        })
+    }
 
-            let status = resp.status();
-            if status.is_success() {
-                try_drain_response_body_for_reuse(resp).await;
-                return Ok(());
-            }
+    fn preview(&self, event: &Event) -> Option<serde_json::Value> {
+        Some(Self::build_payload(
+            event,
+            self.max_chars,
+            self.formatter.as_deref(),
+        ))
+    }
 
-            let body = match read_text_body_limited(resp, DEFAULT_MAX_RESPONSE_BODY_BYTES).await {
-                Ok(body) => body,
-                Err(err) => {
-                    return Err(anyhow::anyhow!(
-                        "github comment http error: {status} (failed to read response body: {err})"
-                    )
-                    .into());
-                }
-            };
-            let summary = truncate_chars(body.trim(), 200);
-            if summary.is_empty() {
-                return Err(anyhow::anyhow!(
-                    "github comment http error: {status} (response body omitted)"
-                )
-                .into());
-            }
+    fn diagnostic_target(&self) -> Option<String> {
+        Some(format!(
+            "{} ({}/{}#{})",
+            redact_url(&self.api_url),
+            self.owner,
+            self.repo,
+            self.issue_number
+        ))
+    }
+}
 
-            Err(anyhow::anyhow!("github comment http error: {status}, response={summary}").into())
+impl Sink for GitHubIssueSink {
+    fn name(&self) -> &'static str {
+        "github_issue"
+    }
+
+    fn send<'a>(&'a self, event: &'a Event) -> BoxFuture<'a, crate::Result<()>> {
+        Box::pin(async move {
+            self.create_issue(event).await?;
+            Ok(())
         })
     }
+
+    fn preview(&self, event: &Event) -> Option<serde_json::Value> {
+        Some(Self::build_payload(
+            event,
+            &self.labels,
+            &self.assignees,
+            self.max_chars,
+            self.formatter.as_deref(),
+        ))
+    }
+
+    fn diagnostic_target(&self) -> Option<String> {
+        Some(format!(
+            "{} ({}/{})",
+            redact_url(&self.api_url),
+            self.owner,
+            self.repo
+        ))
+    }
 }
 
 #[cfg(test)]
@@ -217,13 +513,25 @@ mod tests {
             .with_body("ok")
             .with_tag("thread_id", "t1");
 
-        let payload = GitHubCommentSink::build_payload(&event, 65000);
+        let payload = GitHubCommentSink::build_payload(&event, 65000, None);
         let text = payload["body"].as_str().unwrap_or("");
         assert!(text.contains("done"));
         assert!(text.contains("ok"));
         assert!(text.contains("thread_id=t1"));
     }
 
+    #[test]
+    fn zero_max_chars_is_unlimited() {
+        let body = "x".repeat(100 * 1024);
+        let event = Event::new("turn_completed", Severity::Success, "done").with_body(body.clone());
+
+        let payload = GitHubCommentSink::build_payload(&event, 0, None);
+        assert_eq!(
+            payload["body"].as_str(),
+            Some(format!("done\n{body}").as_str())
+        );
+    }
+
     #[test]
     fn rejects_empty_owner() {
         let cfg = GitHubCommentConfig::new("", "repo", 1, "tok");
@@ -267,4 +575,79 @@ mod tests {
         assert_eq!(sink.repo, "repo");
         assert_eq!(sink.token, "tok");
     }
+
+    #[test]
+    fn issue_sink_validates_identifiers() {
+        let err = GitHubIssueSink::new(GitHubIssueConfig::new("a/b", "repo", "tok"))
+            .expect_err("expected invalid owner");
+        assert!(err.to_string().contains("owner"), "{err:#}");
+
+        let err = GitHubIssueSink::new(GitHubIssueConfig::new("owner", " ", "tok"))
+            .expect_err("expected invalid repo");
+        assert!(err.to_string().contains("repo"), "{err:#}");
+
+        let err = GitHubIssueSink::new(
+            GitHubIssueConfig::new("owner", "repo", "tok").with_assignees(["ok", "bad user"]),
+        )
+        .expect_err("expected invalid assignee");
+        assert!(err.to_string().contains("assignee"), "{err:#}");
+
+        let err = GitHubIssueSink::new(
+            GitHubIssueConfig::new("owner", "repo", "tok").with_labels(["bug", " "]),
+        )
+        .expect_err("expected invalid label");
+        assert!(err.to_string().contains("label"), "{err:#}");
+
+        let sink = GitHubIssueSink::new(
+            GitHubIssueConfig::new(" owner ", " repo ", " tok ")
+                .with_labels([" bug "])
+                .with_assignees([" octocat "]),
+        )
+        .expect("build sink");
+        assert_eq!(
+            sink.api_url.as_str(),
+            "https://api.github.com/repos/owner/repo/issues"
+        );
+        assert_eq!(sink.token, "tok");
+        assert_eq!(sink.labels, ["bug"]);
+        assert_eq!(sink.assignees, ["octocat"]);
+    }
+
+    #[test]
+    fn issue_payload_has_title_body_and_label_arrays() {
+        let event = Event::new("job_failed", Severity::Error, "nightly build failed")
+            .with_body("exit code 1");
+        let labels = vec!["bug".to_string(), "ci failure".to_string()];
+        let assignees = vec!["octocat".to_string()];
+
+        let payload = GitHubIssueSink::build_payload(&event, &labels, &assignees, 65000, None);
+        assert_eq!(payload["title"], "nightly build failed");
+        let body = payload["body"].as_str().unwrap_or("");
+        assert!(body.contains("exit code 1"), "{body}");
+        assert_eq!(payload["labels"], serde_json::json!(["bug", "ci failure"]));
+        assert_eq!(payload["assignees"], serde_json::json!(["octocat"]));
+
+        let payload = GitHubIssueSink::build_payload(
+            &Event::new("job_failed", Severity::Error, " "),
+            &[],
+            &[],
+            65000,
+            None,
+        );
+        assert_eq!(payload["title"], "job_failed");
+        assert!(payload.get("labels").is_none(), "{payload}");
+        assert!(payload.get("assignees").is_none(), "{payload}");
+    }
+
+    #[test]
+    fn issue_sink_debug_redacts_token() {
+        let cfg = GitHubIssueConfig::new("owner", "repo", "tok_secret");
+        let cfg_dbg = format!("{cfg:?}");
+        assert!(!cfg_dbg.contains("tok_secret"), "{cfg_dbg}");
+
+        let sink = GitHubIssueSink::new(cfg).expect("build sink");
+        let sink_dbg = format!("{sink:?}");
+        assert!(!sink_dbg.contains("tok_secret"), "{sink_dbg}");
+        assert!(sink_dbg.contains("<redacted>"), "{sink_dbg}");
+    }
 }
@@ -0,0 +1,410 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::Event;
+use crate::render::EventFormatter;
+use crate::sinks::http::{
+    build_http_client, max_response_body_bytes, parse_and_validate_https_url_basic,
+    read_text_body_limited, redact_url, redact_url_str, select_http_client, send_reqwest,
+    try_drain_response_body_for_reuse,
+};
+use crate::sinks::text::{TextLimits, format_event_text_with, truncate_chars};
+use crate::sinks::{BoxFuture, Sink};
+
+const GITLAB_DEFAULT_BASE_URL: &str = "https://gitlab.com";
+
+/// What a [`GitLabNoteSink`] comments on.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GitLabNoteTarget {
+    #[default]
+    Issue,
+    MergeRequest,
+}
+
+impl GitLabNoteTarget {
+    fn path_segment(self) -> &'static str {
+        match self {
+            Self::Issue => "issues",
+            Self::MergeRequest => "merge_requests",
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Clone)]
+pub struct GitLabNoteConfig {
+    /// GitLab instance, e.g. `https://gitlab.example.com` (may include a path prefix).
+    pub base_url: String,
+    /// Numeric project ID, or the project path (`group/project`, also accepted URL-encoded).
+    pub project: String,
+    pub target: GitLabNoteTarget,
+    /// Issue / merge request IID (the per-project number shown in the UI).
+    pub iid: u64,
+    pub token: String,
+    pub timeout: Duration,
+    /// Message length limit; `0` means unlimited (see [`crate::MaxChars`]).
+    pub max_chars: usize,
+    /// Custom message layout; `None` uses the built-in one ([`crate::DefaultFormatter`]).
+    pub formatter: Option<Arc<dyn EventFormatter>>,
+    pub enforce_public_ip: bool,
+}
+
+impl std::fmt::Debug for GitLabNoteConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GitLabNoteConfig")
+            .field("base_url", &redact_url_str(&self.base_url))
+            .field("project", &self.project)
+            .field("target", &self.target)
+            .field("iid", &self.iid)
+            .field("token", &"<redacted>")
+            .field("timeout", &self.timeout)
+            .field("max_chars", &self.max_chars)
+            .field("custom_formatter", &self.formatter.is_some())
+            .field("enforce_public_ip", &self.enforce_public_ip)
+            .finish()
+    }
+}
+
+impl GitLabNoteConfig {
+    /// Comments on issue `iid` of `project` on `gitlab.com`.
+    pub fn new(project: impl Into<String>, iid: u64, token: impl Into<String>) -> Self {
+        Self {
+            base_url: GITLAB_DEFAULT_BASE_URL.to_string(),
+            project: project.into(),
+            target: GitLabNoteTarget::default(),
+            iid,
+            token: token.into(),
+            timeout: Duration::from_secs(2),
+            max_chars: 65000,
+            formatter: None,
+            enforce_public_ip: true,
+        }
+    }
+
+    #[must_use]
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    #[must_use]
+    pub fn with_target(mut self, target: GitLabNoteTarget) -> Self {
+        self.target = target;
+        self
+    }
+
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    #[must_use]
+    pub fn with_max_chars(mut self, max_chars: usize) -> Self {
+        self.max_chars = max_chars;
+        self
+    }
+
+    #[must_use]
+    pub fn with_formatter(mut self, formatter: Arc<dyn EventFormatter>) -> Self {
+        self.formatter = Some(formatter);
+        self
+    }
+
+    #[must_use]
+    pub fn with_public_ip_check(mut self, enforce_public_ip: bool) -> Self {
+        self.enforce_public_ip = enforce_public_ip;
+        self
+    }
+}
+
+pub struct GitLabNoteSink {
+    api_url: reqwest::Url,
+    project: String,
+    target: GitLabNoteTarget,
+    iid: u64,
+    token: String,
+    client: reqwest::Client,
+    timeout: Duration,
+    max_chars: usize,
+    formatter: Option<Arc<dyn EventFormatter>>,
+    enforce_public_ip: bool,
+}
+
+impl std::fmt::Debug for GitLabNoteSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GitLabNoteSink")
+            .field("api_url", &redact_url(&self.api_url))
+            .field("project", &self.project)
+            .field("target", &self.target)
+            .field("iid", &self.iid)
+            .field("token", &"<redacted>")
+            .field("max_chars", &self.max_chars)
+            .field("custom_formatter", &self.formatter.is_some())
+            .field("enforce_public_ip", &self.enforce_public_ip)
+            .finish_non_exhaustive()
+    }
+}
+
+impl GitLabNoteSink {
+    pub fn new(config: GitLabNoteConfig) -> crate::Result<Self> {
+        let project = normalize_gitlab_project(&config.project)?;
+        if config.iid == 0 {
+            return Err(anyhow::anyhow!("gitlab iid must be > 0").into());
+        }
+        let token = config.token.trim();
+        if token.is_empty() {
+            return Err(anyhow::anyhow!("gitlab token must not be empty").into());
+        }
+
+        let base_url = parse_and_validate_https_url_basic(config.base_url.trim())?;
+        if base_url.query().is_some() || base_url.fragment().is_some() {
+            return Err(anyhow::anyhow!("gitlab base_url must not contain a query").into());
+        }
+        let api_url = build_note_url(&base_url, &project, config.target, config.iid)?;
+        let client = build_http_client(config.timeout)?;
+
+        Ok(Self {
+            api_url,
+            project,
+            target: config.target,
+            iid: config.iid,
+            token: token.to_string(),
+            client,
+            timeout: config.timeout,
+            max_chars: config.max_chars,
+            formatter: config.formatter,
+            enforce_public_ip: config.enforce_public_ip,
+        })
+    }
+
+    fn build_payload(
+        event: &Event,
+        max_chars: usize,
+        formatter: Option<&dyn EventFormatter>,
+    ) -> serde_json::Value {
+        let text = format_event_text_with(formatter, event, TextLimits::new(max_chars));
+        serde_json::json!({ "body": text })
+    }
+}
+
+/// Numeric ID, or a `group/subgroup/project` path (`%2F`-encoded slashes are decoded first).
+fn normalize_gitlab_project(value: &str) -> crate::Result<String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err(anyhow::anyhow!("gitlab project must not be empty").into());
+    }
+    let path = value.replace("%2F", "/").replace("%2f", "/");
+    let valid_segment = |segment: &str| {
+        !matches!(segment, "" | "." | "..")
+            && segment
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.'))
+    };
+    if !path.split('/').all(valid_segment) {
+        return Err(anyhow::anyhow!("gitlab project contains invalid characters").into());
+    }
+    Ok(path)
+}
+
+fn build_note_url(
+    base_url: &reqwest::Url,
+    project: &str,
+    target: GitLabNoteTarget,
+    iid: u64,
+) -> crate::Result<reqwest::Url> {
+    let mut url = base_url.clone();
+    let iid_segment = iid.to_string();
+    // `push` percent-encodes the `/` of a project path as `%2F`, as the API expects.
+    url.path_segments_mut()
+        .map_err(|_| anyhow::anyhow!("invalid gitlab base_url"))?
+        .pop_if_empty()
+        .extend(["api", "v4", "projects"])
+        .push(project)
+        .extend([target.path_segment(), iid_segment.as_str(), "notes"]);
+    Ok(url)
+}
+
+impl Sink for GitLabNoteSink {
+    fn name(&self) -> &'static str {
+        "gitlab"
+    }
+
+    fn send<'a>(&'a self, event: &'a Event) -> BoxFuture<'a, crate::Result<()>> {
+        Box::pin(async move {
+            let client = select_http_client(
+                &self.client,
+                self.timeout,
+                &self.api_url,
+                self.enforce_public_ip,
+            )
+            .await?;
+            let payload = Self::build_payload(event, self.max_chars, self.formatter.as_deref());
+
+            let mut token = reqwest::header::HeaderValue::from_str(&self.token)
+                .map_err(|_| anyhow::anyhow!("gitlab token is not a valid header value"))?;
+            token.set_sensitive(true);
+            let resp = send_reqwest(
+                client
+                    .post(self.api_url.as_str())
+                    .header("PRIVATE-TOKEN", token)
+                    .json(&payload),
+                "gitlab note",
+            )
+            .await?;
+
+            let status = resp.status();
+            if status.is_success() {
+                try_drain_response_body_for_reuse(resp).await;
+                return Ok(());
+            }
+
+            let body = match read_text_body_limited(resp, max_response_body_bytes()).await {
+                Ok(body) => body,
+                Err(err) => {
+                    return Err(crate::Error::http_status(
+                        status,
+                        anyhow::anyhow!(
+                            "gitlab note http error: {status} (failed to read response body: {err})"
+                        ),
+                    ));
+                }
+            };
+            let summary = truncate_chars(body.trim(), 200);
+            if summary.is_empty() {
+                return Err(crate::Error::http_status(
+                    status,
+                    anyhow::anyhow!("gitlab note http error: {status} (response body omitted)"),
+                ));
+            }
+
+            Err(crate::Error::http_status(
+                status,
+                anyhow::anyhow!("gitlab note http error: {status}, response={summary}"),
+            ))
+        })
+    }
+
+    fn preview(&self, event: &Event) -> Option<serde_json::Value> {
+        Some(Self::build_payload(
+            event,
+            self.max_chars,
+            self.formatter.as_deref(),
+        ))
+    }
+
+    fn diagnostic_target(&self) -> Option<String> {
+        let marker = match self.target {
+            GitLabNoteTarget::Issue => '#',
+            GitLabNoteTarget::MergeRequest => '!',
+        };
+        Some(format!(
+            "{} ({}{marker}{})",
+            redact_url(&self.api_url),
+            self.project,
+            self.iid
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Severity;
+
+    fn sink(config: GitLabNoteConfig) -> GitLabNoteSink {
+        GitLabNoteSink::new(config).expect("build sink")
+    }
+
+    #[test]
+    fn builds_note_url_for_numeric_project_id() {
+        let sink = sink(GitLabNoteConfig::new("12345", 7, "glpat-x"));
+        assert_eq!(
+            sink.api_url.as_str(),
+            "https://gitlab.com/api/v4/projects/12345/issues/7/notes"
+        );
+
+        let sink = sink_mr("12345");
+        assert_eq!(
+            sink.api_url.as_str(),
+            "https://gitlab.com/api/v4/projects/12345/merge_requests/3/notes"
+        );
+    }
+
+    fn sink_mr(project: &str) -> GitLabNoteSink {
+        sink(
+            GitLabNoteConfig::new(project, 3, "glpat-x")
+                .with_target(GitLabNoteTarget::MergeRequest),
+        )
+    }
+
+    #[test]
+    fn builds_note_url_for_path_encoded_project() {
+        let expected = "https://gitlab.com/api/v4/projects/group%2Fsub%2Fproject/issues/7/notes";
+        for project in [
+            "group/sub/project",
+            "group%2Fsub%2Fproject",
+            " group%2fsub/project ",
+        ] {
+            let sink = sink(GitLabNoteConfig::new(project, 7, "glpat-x"));
+            assert_eq!(sink.api_url.as_str(), expected, "{project}");
+            assert_eq!(sink.project, "group/sub/project");
+        }
+
+        let sink = sink(
+            GitLabNoteConfig::new("group/project", 7, "glpat-x")
+                .with_base_url("https://git.example.com/gitlab/"),
+        );
+        assert_eq!(
+            sink.api_url.as_str(),
+            "https://git.example.com/gitlab/api/v4/projects/group%2Fproject/issues/7/notes"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_config() {
+        let cases = [
+            (GitLabNoteConfig::new(" ", 1, "tok"), "project"),
+            (GitLabNoteConfig::new("group/", 1, "tok"), "project"),
+            (GitLabNoteConfig::new("group/pro ject", 1, "tok"), "project"),
+            (GitLabNoteConfig::new("../admin", 1, "tok"), "project"),
+            (GitLabNoteConfig::new("1", 0, "tok"), "iid"),
+            (GitLabNoteConfig::new("1", 1, " "), "token"),
+            (
+                GitLabNoteConfig::new("1", 1, "tok").with_base_url("http://gitlab.example.com"),
+                "https",
+            ),
+        ];
+        for (cfg, needle) in cases {
+            let err = GitLabNoteSink::new(cfg).expect_err("expected invalid config");
+            assert!(err.to_string().contains(needle), "{needle}: {err:#}");
+        }
+    }
+
+    #[test]
+    fn builds_expected_payload() {
+        let event = Event::new("turn_completed", Severity::Success, "done")
+            .with_body("ok")
+            .with_tag("thread_id", "t1");
+
+        let payload = GitLabNoteSink::build_payload(&event, 65000, None);
+        let text = payload["body"].as_str().unwrap_or("");
+        assert!(text.contains("done"));
+        assert!(text.contains("ok"));
+        assert!(text.contains("thread_id=t1"));
+    }
+
+    #[test]
+    fn debug_redacts_token() {
+        let cfg = GitLabNoteConfig::new("group/project", 1, "glpat-secret");
+        let cfg_dbg = format!("{cfg:?}");
+        assert!(!cfg_dbg.contains("glpat-secret"), "{cfg_dbg}");
+        assert!(cfg_dbg.contains("<redacted>"), "{cfg_dbg}");
+
+        let sink = sink(cfg);
+        let sink_dbg = format!("{sink:?}");
+        assert!(!sink_dbg.contains("glpat-secret"), "{sink_dbg}");
+        assert!(sink_dbg.contains("gitlab.com"), "{sink_dbg}");
+        assert!(sink_dbg.contains("<redacted>"), "{sink_dbg}");
+    }
+}
@@ -0,0 +1,60 @@
+//! Rust stubs for `proto/notify_kit/v1/notify.proto` (prost messages only; the client call is
+//! made through `tonic::client::Grpc` in `grpc.rs`). Keep field tags in sync with the proto.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub(crate) enum Severity {
+    Unspecified = 0,
+    Info = 1,
+    Success = 2,
+    Warning = 3,
+    Error = 4,
+}
+
+impl From<crate::Severity> for Severity {
+    fn from(severity: crate::Severity) -> Self {
+        match severity {
+            crate::Severity::Info => Self::Info,
+            crate::Severity::Success => Self::Success,
+            crate::Severity::Warning => Self::Warning,
+            crate::Severity::Error => Self::Error,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub(crate) struct NotifyRequest {
+    #[prost(string, tag = "1")]
+    pub kind: ::prost::alloc::string::String,
+    #[prost(enumeration = "Severity", tag = "2")]
+    pub severity: i32,
+    #[prost(string, tag = "3")]
+    pub title: ::prost::alloc::string::String,
+    #[prost(string, optional, tag = "4")]
+    pub body: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(btree_map = "string, string", tag = "5")]
+    pub tags: ::prost::alloc::collections::BTreeMap<
+        ::prost::alloc::string::String,
+        ::prost::alloc::string::String,
+    >,
+}
+
+impl From<&crate::Event> for NotifyRequest {
+    fn from(event: &crate::Event) -> Self {
+        Self {
+            kind: event.kind.clone(),
+            severity: Severity::from(event.severity) as i32,
+            title: event.title.clone(),
+            body: event.body.clone(),
+            tags: event.tags.clone(),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub(crate) struct Ack {
+    #[prost(bool, tag = "1")]
+    pub accepted: bool,
+    #[prost(string, tag = "2")]
+    pub message: ::prost::alloc::string::String,
+}
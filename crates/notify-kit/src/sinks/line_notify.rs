@@ -0,0 +1,394 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::render::EventFormatter;
+use crate::sinks::http::{
+    build_http_client, max_response_body_bytes, parse_and_validate_https_url,
+    read_json_body_limited, read_text_body_limited, redact_url, select_http_client, send_reqwest,
+    validate_url_path_prefix,
+};
+use crate::sinks::text::{TextLimits, format_event_text_with, truncate_chars};
+use crate::sinks::{BoxFuture, Sink};
+use crate::{Event, Severity};
+
+const LINE_NOTIFY_ALLOWED_HOSTS: [&str; 1] = ["notify-api.line.me"];
+const LINE_NOTIFY_API_URL: &str = "https://notify-api.line.me/api/notify";
+
+/// LINE Notify rejects messages longer than this.
+const LINE_NOTIFY_MAX_CHARS: usize = 1000;
+
+/// A sticker from LINE's sticker list, sent along with the message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LineSticker {
+    pub package_id: u32,
+    pub sticker_id: u32,
+}
+
+impl LineSticker {
+    pub fn new(package_id: u32, sticker_id: u32) -> Self {
+        Self {
+            package_id,
+            sticker_id,
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Clone)]
+pub struct LineNotifyConfig {
+    pub access_token: String,
+    /// Sticker attached per severity; severities without an entry send text only.
+    pub stickers: BTreeMap<Severity, LineSticker>,
+    /// Deliver without a push notification sound/banner (`notificationDisabled`).
+    pub notification_disabled: bool,
+    pub timeout: Duration,
+    /// Message length limit; `0` means LINE's own limit of 1000 characters.
+    pub max_chars: usize,
+    /// Custom message layout; `None` uses the built-in one ([`crate::DefaultFormatter`]).
+    pub formatter: Option<Arc<dyn EventFormatter>>,
+    pub enforce_public_ip: bool,
+}
+
+impl std::fmt::Debug for LineNotifyConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LineNotifyConfig")
+            .field("access_token", &"<redacted>")
+            .field("stickers", &self.stickers)
+            .field("notification_disabled", &self.notification_disabled)
+            .field("timeout", &self.timeout)
+            .field("max_chars", &self.max_chars)
+            .field("custom_formatter", &self.formatter.is_some())
+            .field("enforce_public_ip", &self.enforce_public_ip)
+            .finish()
+    }
+}
+
+impl LineNotifyConfig {
+    pub fn new(access_token: impl Into<String>) -> Self {
+        Self {
+            access_token: access_token.into(),
+            stickers: BTreeMap::new(),
+            notification_disabled: false,
+            timeout: Duration::from_secs(2),
+            max_chars: LINE_NOTIFY_MAX_CHARS,
+            formatter: None,
+            enforce_public_ip: true,
+        }
+    }
+
+    #[must_use]
+    pub fn with_sticker(mut self, severity: Severity, sticker: LineSticker) -> Self {
+        self.stickers.insert(severity, sticker);
+        self
+    }
+
+    #[must_use]
+    pub fn with_notification_disabled(mut self, notification_disabled: bool) -> Self {
+        self.notification_disabled = notification_disabled;
+        self
+    }
+
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    #[must_use]
+    pub fn with_max_chars(mut self, max_chars: usize) -> Self {
+        self.max_chars = max_chars;
+        self
+    }
+
+    #[must_use]
+    pub fn with_formatter(mut self, formatter: Arc<dyn EventFormatter>) -> Self {
+        self.formatter = Some(formatter);
+        self
+    }
+
+    #[must_use]
+    pub fn with_public_ip_check(mut self, enforce_public_ip: bool) -> Self {
+        self.enforce_public_ip = enforce_public_ip;
+        self
+    }
+}
+
+pub struct LineNotifySink {
+    api_url: reqwest::Url,
+    access_token: String,
+    stickers: BTreeMap<Severity, LineSticker>,
+    notification_disabled: bool,
+    client: reqwest::Client,
+    timeout: Duration,
+    max_chars: usize,
+    formatter: Option<Arc<dyn EventFormatter>>,
+    enforce_public_ip: bool,
+}
+
+impl std::fmt::Debug for LineNotifySink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LineNotifySink")
+            .field("api_url", &redact_url(&self.api_url))
+            .field("access_token", &"<redacted>")
+            .field("stickers", &self.stickers)
+            .field("notification_disabled", &self.notification_disabled)
+            .field("max_chars", &self.max_chars)
+            .field("custom_formatter", &self.formatter.is_some())
+            .field("enforce_public_ip", &self.enforce_public_ip)
+            .finish_non_exhaustive()
+    }
+}
+
+impl LineNotifySink {
+    pub fn new(config: LineNotifyConfig) -> crate::Result<Self> {
+        let access_token = config.access_token.trim();
+        if access_token.is_empty() {
+            return Err(crate::Error::invalid_config(anyhow::anyhow!(
+                "line notify access_token must not be empty"
+            )));
+        }
+        let max_chars = match config.max_chars {
+            0 => LINE_NOTIFY_MAX_CHARS,
+            max_chars => max_chars.min(LINE_NOTIFY_MAX_CHARS),
+        };
+
+        let api_url = parse_api_url(LINE_NOTIFY_API_URL)?;
+        let client = build_http_client(config.timeout)?;
+        Ok(Self {
+            api_url,
+            access_token: access_token.to_string(),
+            stickers: config.stickers,
+            notification_disabled: config.notification_disabled,
+            client,
+            timeout: config.timeout,
+            max_chars,
+            formatter: config.formatter,
+            enforce_public_ip: config.enforce_public_ip,
+        })
+    }
+
+    fn build_form(&self, event: &Event) -> Vec<(&'static str, String)> {
+        let message = format_event_text_with(
+            self.formatter.as_deref(),
+            event,
+            TextLimits::new(self.max_chars),
+        );
+        let mut form = vec![("message", message)];
+        if let Some(sticker) = self.stickers.get(&event.severity) {
+            form.push(("stickerPackageId", sticker.package_id.to_string()));
+            form.push(("stickerId", sticker.sticker_id.to_string()));
+        }
+        if self.notification_disabled {
+            form.push(("notificationDisabled", "true".to_string()));
+        }
+        form
+    }
+
+    fn build_request(&self, client: &reqwest::Client, event: &Event) -> reqwest::RequestBuilder {
+        client
+            .post(self.api_url.as_str())
+            .bearer_auth(&self.access_token)
+            .form(&self.build_form(event))
+    }
+}
+
+fn parse_api_url(url: &str) -> crate::Result<reqwest::Url> {
+    let api_url = parse_and_validate_https_url(url, &LINE_NOTIFY_ALLOWED_HOSTS)?;
+    validate_url_path_prefix(&api_url, "/api/notify")?;
+    Ok(api_url)
+}
+
+/// `"line notify api error: ..."` from a `{"status": ..., "message": ...}` response.
+fn line_notify_api_error(body: &serde_json::Value) -> anyhow::Error {
+    let status = body["status"].as_i64().unwrap_or(-1);
+    let message = truncate_chars(body["message"].as_str().unwrap_or("").trim(), 200);
+    if message.is_empty() {
+        return anyhow::anyhow!("line notify api error: status={status} (no message)");
+    }
+    anyhow::anyhow!("line notify api error: status={status}, message={message}")
+}
+
+impl Sink for LineNotifySink {
+    fn name(&self) -> &'static str {
+        "line"
+    }
+
+    fn send<'a>(&'a self, event: &'a Event) -> BoxFuture<'a, crate::Result<()>> {
+        Box::pin(async move {
+            let client = select_http_client(
+                &self.client,
+                self.timeout,
+                &self.api_url,
+                self.enforce_public_ip,
+            )
+            .await?;
+
+            let resp = send_reqwest(self.build_request(&client, event), "line notify").await?;
+
+            let status = resp.status();
+            if !status.is_success() {
+                let body = match read_text_body_limited(resp, max_response_body_bytes()).await {
+                    Ok(body) => body,
+                    Err(err) => {
+                        return Err(crate::Error::http_status(
+                            status,
+                            anyhow::anyhow!(
+                                "line notify http error: {status} (failed to read response body: {err})"
+                            ),
+                        ));
+                    }
+                };
+                if let Ok(json) = serde_json::from_str::<serde_json::Value>(&body) {
+                    if json.get("message").is_some() {
+                        return Err(crate::Error::http_status(
+                            status,
+                            line_notify_api_error(&json),
+                        ));
+                    }
+                }
+                let summary = truncate_chars(body.trim(), 200);
+                if summary.is_empty() {
+                    return Err(crate::Error::http_status(
+                        status,
+                        anyhow::anyhow!("line notify http error: {status} (response body omitted)"),
+                    ));
+                }
+                return Err(crate::Error::http_status(
+                    status,
+                    anyhow::anyhow!("line notify http error: {status}, response={summary}"),
+                ));
+            }
+
+            let body = read_json_body_limited(resp, max_response_body_bytes()).await?;
+            if body["status"].as_i64() == Some(200) {
+                return Ok(());
+            }
+            Err(line_notify_api_error(&body).into())
+        })
+    }
+
+    /// The form fields as a JSON object (the access token travels in a header and is omitted).
+    fn preview(&self, event: &Event) -> Option<serde_json::Value> {
+        let fields = self
+            .build_form(event)
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), serde_json::Value::String(value)))
+            .collect();
+        Some(serde_json::Value::Object(fields))
+    }
+
+    fn diagnostic_target(&self) -> Option<String> {
+        Some(redact_url(&self.api_url))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn form_body(sink: &LineNotifySink, event: &Event) -> (reqwest::Request, String) {
+        let req = sink
+            .build_request(&sink.client, event)
+            .build()
+            .expect("build request");
+        let body = req
+            .body()
+            .and_then(|body| body.as_bytes())
+            .map(|body| String::from_utf8_lossy(body).into_owned())
+            .expect("buffered body");
+        (req, body)
+    }
+
+    #[test]
+    fn request_is_form_encoded_with_bearer_token() {
+        let sink = LineNotifySink::new(LineNotifyConfig::new(" line-secret ")).expect("build sink");
+        let event = Event::new("kind", Severity::Info, "hello world").with_tag("k", "v");
+        let (req, body) = form_body(&sink, &event);
+
+        assert_eq!(req.url().as_str(), LINE_NOTIFY_API_URL);
+        let auth = req
+            .headers()
+            .get(reqwest::header::AUTHORIZATION)
+            .expect("authorization header");
+        assert_eq!(auth.to_str().expect("ascii"), "Bearer line-secret");
+        assert!(auth.is_sensitive());
+        assert_eq!(
+            req.headers()
+                .get(reqwest::header::CONTENT_TYPE)
+                .and_then(|v| v.to_str().ok()),
+            Some("application/x-www-form-urlencoded")
+        );
+        assert_eq!(body, "message=hello+world%0Ak%3Dv");
+    }
+
+    #[test]
+    fn sticker_and_silent_flags_are_added_to_form() {
+        let cfg = LineNotifyConfig::new("tok")
+            .with_sticker(Severity::Error, LineSticker::new(446, 1988))
+            .with_notification_disabled(true);
+        let sink = LineNotifySink::new(cfg).expect("build sink");
+
+        let (_, body) = form_body(&sink, &Event::new("kind", Severity::Error, "down"));
+        assert_eq!(
+            body,
+            "message=down&stickerPackageId=446&stickerId=1988&notificationDisabled=true"
+        );
+        let (_, body) = form_body(&sink, &Event::new("kind", Severity::Info, "up"));
+        assert_eq!(body, "message=up&notificationDisabled=true");
+    }
+
+    #[test]
+    fn message_is_capped_at_line_limit() {
+        let event = Event::new("kind", Severity::Info, "t").with_body("x".repeat(5000));
+        for max_chars in [0, 4000] {
+            let sink = LineNotifySink::new(LineNotifyConfig::new("tok").with_max_chars(max_chars))
+                .expect("build sink");
+            let form = sink.build_form(&event);
+            assert_eq!(form[0].1.chars().count(), LINE_NOTIFY_MAX_CHARS);
+        }
+    }
+
+    #[test]
+    fn api_url_host_is_restricted() {
+        parse_api_url(LINE_NOTIFY_API_URL).expect("default url");
+        for url in [
+            "https://notify-api.line.me.evil.example/api/notify",
+            "https://evil.example/api/notify",
+            "http://notify-api.line.me/api/notify",
+            "https://notify-api.line.me/api/status",
+        ] {
+            assert!(parse_api_url(url).is_err(), "{url}");
+        }
+    }
+
+    #[test]
+    fn rejects_empty_token_and_redacts_debug() {
+        let err = LineNotifySink::new(LineNotifyConfig::new("  ")).expect_err("empty token");
+        assert!(err.to_string().contains("access_token"), "{err:#}");
+        assert_eq!(err.kind(), crate::ErrorKind::InvalidConfig);
+
+        let cfg = LineNotifyConfig::new("line-secret");
+        let cfg_dbg = format!("{cfg:?}");
+        assert!(!cfg_dbg.contains("line-secret"), "{cfg_dbg}");
+        let sink = LineNotifySink::new(cfg).expect("build sink");
+        let sink_dbg = format!("{sink:?}");
+        assert!(!sink_dbg.contains("line-secret"), "{sink_dbg}");
+        assert!(sink_dbg.contains("notify-api.line.me"), "{sink_dbg}");
+
+        let preview = sink
+            .preview(&Event::new("kind", Severity::Info, "t"))
+            .expect("preview");
+        assert!(!preview.to_string().contains("line-secret"), "{preview}");
+    }
+
+    #[test]
+    fn api_errors_include_status_and_message() {
+        let body = serde_json::json!({ "status": 401, "message": "Invalid access token" });
+        let msg = line_notify_api_error(&body).to_string();
+        assert!(msg.contains("status=401"), "{msg}");
+        assert!(msg.contains("Invalid access token"), "{msg}");
+        let msg = line_notify_api_error(&serde_json::json!({})).to_string();
+        assert!(msg.contains("no message"), "{msg}");
+    }
+}
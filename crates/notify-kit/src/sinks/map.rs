@@ -0,0 +1,147 @@
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use crate::Event;
+use crate::sinks::{BoxFuture, Sink};
+
+/// Event rewrite applied by [`MapSink`]; return `Cow::Borrowed` to pass the event through as-is.
+pub type EventTransform = Arc<dyn Fn(&Event) -> Cow<'_, Event> + Send + Sync>;
+
+/// Sink decorator that rewrites each event before handing it to the wrapped sink.
+///
+/// Useful for per-channel redaction or enrichment, e.g. stripping internal tags before an
+/// external Slack, without touching the other sinks. The wrapper reports the inner sink's
+/// `name()`, so it can be registered in a `Hub` (or nested in other decorators) in place of the
+/// sink it wraps.
+pub struct MapSink {
+    inner: Arc<dyn Sink>,
+    transform: EventTransform,
+}
+
+impl std::fmt::Debug for MapSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MapSink")
+            .field("inner", &self.inner.name())
+            .finish_non_exhaustive()
+    }
+}
+
+impl MapSink {
+    pub fn new<F>(inner: Arc<dyn Sink>, transform: F) -> Self
+    where
+        F: Fn(&Event) -> Cow<'_, Event> + Send + Sync + 'static,
+    {
+        Self {
+            inner,
+            transform: Arc::new(transform),
+        }
+    }
+
+    /// Like [`MapSink::new`], sharing an existing transform between several sinks.
+    pub fn from_transform(inner: Arc<dyn Sink>, transform: EventTransform) -> Self {
+        Self { inner, transform }
+    }
+}
+
+impl Sink for MapSink {
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn send<'a>(&'a self, event: &'a Event) -> BoxFuture<'a, crate::Result<()>> {
+        Box::pin(async move {
+            let event = (self.transform)(event);
+            self.inner.send(&event).await
+        })
+    }
+
+    fn diagnostic_target(&self) -> Option<String> {
+        self.inner.diagnostic_target()
+    }
+
+    fn preview(&self, event: &Event) -> Option<serde_json::Value> {
+        self.inner.preview(&(self.transform)(event))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::Severity;
+
+    #[derive(Debug, Default)]
+    struct CapturingSink {
+        events: Mutex<Vec<Event>>,
+    }
+
+    impl Sink for CapturingSink {
+        fn name(&self) -> &'static str {
+            "capture"
+        }
+
+        fn send<'a>(&'a self, event: &'a Event) -> BoxFuture<'a, crate::Result<()>> {
+            Box::pin(async move {
+                self.events.lock().expect("lock events").push(event.clone());
+                Ok(())
+            })
+        }
+    }
+
+    fn run(sink: &MapSink, event: &Event) -> crate::Result<()> {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime");
+        rt.block_on(sink.send(event))
+    }
+
+    #[test]
+    fn transform_drops_tag_before_delivery() {
+        let inner = Arc::new(CapturingSink::default());
+        let sink = MapSink::new(inner.clone(), |event: &Event| {
+            if !event.tags.contains_key("internal_host") {
+                return Cow::Borrowed(event);
+            }
+            let mut event = event.clone();
+            event.tags.remove("internal_host");
+            Cow::Owned(event)
+        });
+
+        let event = Event::new("kind", Severity::Info, "title")
+            .with_tag("internal_host", "db-7.corp")
+            .with_tag("thread_id", "t1");
+        run(&sink, &event).expect("send");
+        run(&sink, &Event::new("kind", Severity::Info, "plain")).expect("send");
+
+        let events = inner.events.lock().expect("lock events");
+        assert_eq!(events.len(), 2);
+        assert!(!events[0].tags.contains_key("internal_host"));
+        assert_eq!(
+            events[0].tags.get("thread_id").map(String::as_str),
+            Some("t1")
+        );
+        assert_eq!(events[1].title, "plain");
+        // The caller's event is untouched.
+        assert!(event.tags.contains_key("internal_host"));
+    }
+
+    #[test]
+    fn shared_transform_and_name_delegation() {
+        let transform: EventTransform =
+            Arc::new(|event: &Event| Cow::Owned(event.clone().with_tag("channel", "external")));
+        let inner = Arc::new(CapturingSink::default());
+        let sink = MapSink::from_transform(inner.clone(), transform.clone());
+        assert_eq!(sink.name(), "capture");
+        assert_eq!(sink.diagnostic_target(), None);
+
+        run(&sink, &Event::new("kind", Severity::Info, "title")).expect("send");
+        let events = inner.events.lock().expect("lock events");
+        assert_eq!(
+            events[0].tags.get("channel").map(String::as_str),
+            Some("external")
+        );
+        assert!(format!("{sink:?}").contains("capture"));
+    }
+}
@@ -1,4 +1,6 @@
-use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
+use pulldown_cmark::{CodeBlockKind, Event, Options, Parser, Tag, TagEnd};
+
+use crate::sinks::text::{TextLimits, format_event_text_non_empty};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) enum Inline {
@@ -12,6 +14,45 @@ pub(crate) struct Line {
     pub inlines: Vec<Inline>,
 }
 
+impl Line {
+    fn has_content(&self) -> bool {
+        self.inlines.iter().any(|inline| match inline {
+            Inline::Text(text) => !text.trim().is_empty(),
+            Inline::Link { text, href } => !text.trim().is_empty() || !href.trim().is_empty(),
+            Inline::Image { src, .. } => !src.trim().is_empty(),
+        })
+    }
+}
+
+/// What a markdown-capable sink should send for an event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum MarkdownOrText {
+    /// Body lines with visible content.
+    Markdown(Vec<Line>),
+    /// Plain-text rendering of the whole event; never blank.
+    Text(String),
+}
+
+/// Parses `event.body` as markdown, falling back to the plain-text rendering when there is no body
+/// or it renders to nothing (whitespace only, images/links without a target, ...).
+///
+/// Markdown sinks should go through this instead of calling [`parse_markdown_lines`] directly, so
+/// they never send an empty message.
+pub(crate) fn render_markdown_or_text(event: &crate::Event, limits: TextLimits) -> MarkdownOrText {
+    let lines: Vec<Line> = event
+        .body
+        .as_deref()
+        .map(parse_markdown_lines)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(Line::has_content)
+        .collect();
+    if lines.is_empty() {
+        return MarkdownOrText::Text(format_event_text_non_empty(event, limits));
+    }
+    MarkdownOrText::Markdown(lines)
+}
+
 #[derive(Debug, Clone)]
 struct LinkCtx {
     href: String,
@@ -57,12 +98,46 @@ pub(crate) fn parse_markdown_lines(input: &str) -> Vec<Line> {
     let mut links: Vec<LinkCtx> = Vec::new();
     let mut images: Vec<ImageCtx> = Vec::new();
     let mut in_code_block = false;
+    // One entry per open list: the next number for ordered lists, `None` for bullet lists.
+    let mut lists: Vec<Option<u64>> = Vec::new();
+    // Cells seen so far in the current table row; later cells are preceded by ` | `.
+    let mut row_cells = 0usize;
 
     for event in parser {
         match event {
             Event::Start(tag) => match tag {
-                Tag::Item => push_text(&mut current, "• "),
-                Tag::CodeBlock(_) => in_code_block = true,
+                Tag::List(start) => {
+                    // A nested list starts inside its parent item's text.
+                    flush_line(&mut lines, &mut current);
+                    lists.push(start);
+                }
+                Tag::Item => {
+                    let indent = "  ".repeat(lists.len().saturating_sub(1));
+                    let marker = match lists.last_mut() {
+                        Some(Some(number)) => {
+                            let marker = format!("{indent}{number}. ");
+                            *number += 1;
+                            marker
+                        }
+                        _ => format!("{indent}• "),
+                    };
+                    push_text(&mut current, &marker);
+                }
+                Tag::CodeBlock(kind) => {
+                    in_code_block = true;
+                    flush_line(&mut lines, &mut current);
+                    let lang = match &kind {
+                        CodeBlockKind::Fenced(lang) => lang.trim(),
+                        CodeBlockKind::Indented => "",
+                    };
+                    push_text(&mut current, &format!("```{lang}\n"));
+                }
+                Tag::TableCell => {
+                    if row_cells > 0 {
+                        push_text(&mut current, " | ");
+                    }
+                    row_cells += 1;
+                }
                 Tag::Link { dest_url, .. } => {
                     links.push(LinkCtx {
                         href: dest_url.to_string(),
@@ -86,10 +161,21 @@ pub(crate) fn parse_markdown_lines(input: &str) -> Vec<Line> {
                 | TagEnd::Table => {
                     if matches!(tag_end, TagEnd::CodeBlock) {
                         in_code_block = false;
+                        if !matches!(current.last(), Some(Inline::Text(text)) if text.ends_with('\n'))
+                        {
+                            push_text(&mut current, "\n");
+                        }
+                        push_text(&mut current, "```");
                     }
                     flush_line(&mut lines, &mut current);
                 }
-                TagEnd::TableRow => flush_line(&mut lines, &mut current),
+                TagEnd::List(_) => {
+                    lists.pop();
+                }
+                TagEnd::TableHead | TagEnd::TableRow => {
+                    flush_line(&mut lines, &mut current);
+                    row_cells = 0;
+                }
                 TagEnd::Link => {
                     if let Some(link) = links.pop() {
                         let text = if link.text.trim().is_empty() {
@@ -128,7 +214,7 @@ pub(crate) fn parse_markdown_lines(input: &str) -> Vec<Line> {
                 } else if let Some(link) = links.last_mut() {
                     link.text.push_str(text.as_ref());
                 } else {
-                    push_text(&mut current, text.as_ref());
+                    push_text(&mut current, &format!("`{text}`"));
                 }
             }
             Event::SoftBreak | Event::HardBreak => {
@@ -196,6 +282,126 @@ mod tests {
         );
     }
 
+    #[test]
+    fn falls_back_to_title_for_whitespace_only_body() {
+        let event = crate::Event::new("k", crate::Severity::Info, "done").with_body(" \n\t\n ");
+
+        assert_eq!(
+            render_markdown_or_text(&event, TextLimits::default()),
+            MarkdownOrText::Text("done".to_string())
+        );
+
+        let blank = crate::Event::new("build", crate::Severity::Info, " ").with_body("\n");
+        assert_eq!(
+            render_markdown_or_text(&blank, TextLimits::default()),
+            MarkdownOrText::Text("build".to_string())
+        );
+    }
+
+    #[test]
+    fn image_only_body_is_kept_or_falls_back_to_text() {
+        let event = crate::Event::new("k", crate::Severity::Info, "done")
+            .with_body("![chart](https://x/y.png)");
+        let MarkdownOrText::Markdown(lines) =
+            render_markdown_or_text(&event, TextLimits::default())
+        else {
+            panic!("expected markdown lines");
+        };
+        assert_eq!(lines.len(), 1);
+
+        // An image without a source renders to nothing.
+        let event = crate::Event::new("k", crate::Severity::Info, "done").with_body("![]()");
+        let MarkdownOrText::Text(text) = render_markdown_or_text(&event, TextLimits::default())
+        else {
+            panic!("expected text fallback");
+        };
+        assert!(text.starts_with("done"), "{text}");
+    }
+
+    fn texts(lines: &[Line]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|line| match line.inlines.as_slice() {
+                [Inline::Text(text)] => text.clone(),
+                other => panic!("expected a single text inline, got {other:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn fences_code_blocks_with_language() {
+        let lines = parse_markdown_lines(
+            "build log:\n\n```rust\nfn main() {\n    panic!();\n}\n```\n\ndone",
+        );
+        assert_eq!(
+            texts(&lines),
+            [
+                "build log:",
+                "```rust\nfn main() {\n    panic!();\n}\n```",
+                "done"
+            ]
+        );
+
+        let lines = parse_markdown_lines("```\nplain\n```");
+        assert_eq!(texts(&lines), ["```\nplain\n```"]);
+    }
+
+    #[test]
+    fn wraps_inline_code_in_backticks() {
+        let lines = parse_markdown_lines("run `cargo test` in [`ci`](https://x/ci)");
+        assert_eq!(
+            lines[0].inlines,
+            vec![
+                Inline::Text("run `cargo test` in ".to_string()),
+                Inline::Link {
+                    text: "ci".to_string(),
+                    href: "https://x/ci".to_string()
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn indents_nested_bullet_lists() {
+        let lines = parse_markdown_lines("- a\n  - a1\n  - a2\n- b\n\nafter");
+        assert_eq!(texts(&lines), ["• a", "  • a1", "  • a2", "• b", "after"]);
+    }
+
+    #[test]
+    fn numbers_ordered_lists() {
+        let lines = parse_markdown_lines("1. one\n2. two\n   - detail\n3. three");
+        assert_eq!(
+            texts(&lines),
+            ["1. one", "2. two", "  • detail", "3. three"]
+        );
+
+        let lines = parse_markdown_lines("7. seven\n8. eight");
+        assert_eq!(texts(&lines), ["7. seven", "8. eight"]);
+    }
+
+    #[test]
+    fn renders_table_rows_as_pipe_delimited_lines() {
+        let lines = parse_markdown_lines(
+            "| host | status |\n| --- | --- |\n| a | [up](https://x/a) |\n| b | down |",
+        );
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[0].inlines,
+            vec![Inline::Text("host | status".to_string())]
+        );
+        assert_eq!(
+            lines[1].inlines,
+            vec![
+                Inline::Text("a | ".to_string()),
+                Inline::Link {
+                    text: "up".to_string(),
+                    href: "https://x/a".to_string()
+                }
+            ]
+        );
+        assert_eq!(lines[2].inlines, vec![Inline::Text("b | down".to_string())]);
+    }
+
     #[test]
     fn parses_task_list_items() {
         let lines = parse_markdown_lines("- [x] done\n- [ ] todo");
@@ -1,41 +1,124 @@
+#[cfg(feature = "amqp")]
+mod amqp;
 mod bark;
-mod crypto;
+mod batching;
+#[cfg(feature = "test-util")]
+mod capturing;
+pub(crate) mod crypto;
+#[cfg(feature = "desktop")]
+mod desktop;
 mod dingtalk;
 mod discord;
+mod fallback;
 mod feishu;
+mod file_source;
 mod generic_webhook;
 mod github;
+mod gitlab;
+#[cfg(feature = "grpc")]
+mod grpc;
+#[cfg(feature = "grpc")]
+mod grpc_proto;
 mod http;
+mod line_notify;
+mod map;
 mod markdown;
+#[cfg(feature = "mqtt")]
+mod mqtt;
+mod multi;
 mod pushplus;
+mod ratelimit;
+mod retry;
 mod serverchan;
+#[cfg(feature = "aws")]
+mod sigv4;
 mod slack;
+mod slack_api;
+#[cfg(feature = "aws")]
+mod sns;
 mod sound;
 mod telegram;
-mod text;
+pub(crate) mod text;
+mod twilio;
 mod wecom;
+mod zulip;
 
 use std::future::Future;
 use std::pin::Pin;
 
 use crate::event::Event;
 
-pub use bark::{BarkConfig, BarkSink};
+#[cfg(feature = "amqp")]
+pub use amqp::{AmqpConfig, AmqpRouting, AmqpSink};
+pub use bark::{BarkConfig, BarkLevel, BarkSink};
+#[cfg(feature = "crypto")]
+pub use bark::{BarkEncryption, BarkEncryptionMode};
+pub use batching::{BatchPolicy, BatchingSink};
+#[cfg(feature = "test-util")]
+pub use capturing::CapturingSink;
+#[cfg(feature = "desktop")]
+pub use desktop::{DesktopConfig, DesktopSink};
 pub use dingtalk::{DingTalkWebhookConfig, DingTalkWebhookSink};
-pub use discord::{DiscordWebhookConfig, DiscordWebhookSink};
+pub use discord::{
+    DiscordAllowedMentions, DiscordMentionType, DiscordWebhookConfig, DiscordWebhookSink,
+};
+pub use fallback::FallbackSink;
 pub use feishu::{FeishuWebhookConfig, FeishuWebhookSink};
-pub use generic_webhook::{GenericWebhookConfig, GenericWebhookSink};
-pub use github::{GitHubCommentConfig, GitHubCommentSink};
+pub use generic_webhook::{GenericWebhookConfig, GenericWebhookSink, HttpMethod};
+pub use github::{GitHubCommentConfig, GitHubCommentSink, GitHubIssueConfig, GitHubIssueSink};
+pub use gitlab::{GitLabNoteConfig, GitLabNoteSink, GitLabNoteTarget};
+#[cfg(feature = "grpc")]
+pub use grpc::{GrpcConfig, GrpcSink, GrpcTls};
+pub use http::{
+    DEFAULT_DNS_LOOKUP_TIMEOUT, DEFAULT_MAX_DNS_LOOKUPS_INFLIGHT,
+    DEFAULT_MAX_PINNED_CLIENT_CACHE_ENTRIES, DEFAULT_MAX_RESPONSE_BODY_BYTES,
+    DEFAULT_PINNED_CLIENT_TTL, DEFAULT_RESPONSE_BODY_DRAIN_LIMIT_BYTES, DEFAULT_USER_AGENT,
+    DnsResolver, HttpPoolConfig, HttpVersion, ProxyConfig, RateLimitedError, dns_lookup_timeout,
+    http_pool_config, http_version, max_dns_lookups_inflight, max_pinned_client_cache_entries,
+    max_response_body_bytes, pinned_client_ttl, response_body_drain_limit, set_dns_lookup_timeout,
+    set_dns_resolver, set_http_pool_config, set_http_proxy, set_http_version,
+    set_max_dns_lookups_inflight, set_max_pinned_client_cache_entries, set_max_response_body_bytes,
+    set_pinned_client_ttl, set_response_body_drain_limit, set_user_agent, user_agent,
+};
+pub use line_notify::{LineNotifyConfig, LineNotifySink, LineSticker};
+pub use map::{EventTransform, MapSink};
+#[cfg(feature = "mqtt")]
+pub use mqtt::{MqttConfig, MqttSink};
+pub use multi::MultiSink;
 pub use pushplus::{PushPlusConfig, PushPlusSink};
+pub use ratelimit::{RateLimit, RateLimitedSink};
+pub use retry::{RetryPolicy, RetryingSink};
 pub use serverchan::{ServerChanConfig, ServerChanSink};
 pub use slack::{SlackWebhookConfig, SlackWebhookSink};
+pub use slack_api::{SlackApiConfig, SlackApiSink};
+#[cfg(feature = "aws")]
+pub use sns::{SnsConfig, SnsSink};
 pub use sound::{SoundConfig, SoundSink};
 pub use telegram::{TelegramBotConfig, TelegramBotSink};
+pub use twilio::{TwilioSmsConfig, TwilioSmsSink};
 pub use wecom::{WeComWebhookConfig, WeComWebhookSink};
+pub use zulip::{ZulipConfig, ZulipSink};
 
 pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
 
 pub trait Sink: Send + Sync {
     fn name(&self) -> &'static str;
     fn send<'a>(&'a self, event: &'a Event) -> BoxFuture<'a, crate::Result<()>>;
+
+    /// Where this sink delivers, with secrets redacted (e.g.
+    /// `https://hooks.slack.com/<redacted>`); shown by [`crate::Hub::diagnostic_report`].
+    ///
+    /// Must never include tokens, keys or full webhook URLs. Defaults to `None`.
+    fn diagnostic_target(&self) -> Option<String> {
+        None
+    }
+
+    /// The JSON payload this sink would send for `event`, built without any network IO; used by
+    /// [`crate::Hub::dry_run`].
+    ///
+    /// Secrets carried in the payload itself (tokens, device keys) must be redacted. Defaults to
+    /// `None` (no preview).
+    fn preview(&self, _event: &Event) -> Option<serde_json::Value> {
+        None
+    }
 }
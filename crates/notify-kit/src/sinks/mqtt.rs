@@ -0,0 +1,635 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use rumqttc::{AsyncClient, EventLoop, MqttOptions, Outgoing, Packet, QoS, Transport};
+
+use crate::Event;
+use crate::sinks::http::is_loopback_host;
+use crate::sinks::{BoxFuture, Sink};
+
+/// MQTT limit for UTF-8 strings (topic names, client ids, credentials).
+const MAX_STRING_BYTES: usize = 65_535;
+/// MQTT 3.1.1 maximum remaining length of a packet.
+const MAX_PACKET_BYTES: usize = 268_435_455;
+const KEEP_ALIVE: Duration = Duration::from_secs(60);
+
+static CLIENT_SEQ: AtomicU64 = AtomicU64::new(0);
+
+#[non_exhaustive]
+#[derive(Clone)]
+pub struct MqttConfig {
+    /// Broker host name or IP address.
+    pub host: String,
+    /// Defaults to 8883 (MQTT over TLS).
+    pub port: u16,
+    /// Topic name to publish to; wildcards (`+`, `#`) are rejected.
+    pub topic: String,
+    /// 0 (at most once), 1 (at least once) or 2 (exactly once). Defaults to 1.
+    pub qos: u8,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// Defaults to `notify-kit-<pid>-<n>`, unique per sink within the process.
+    pub client_id: String,
+    /// Connect over TLS (webpki roots). Plaintext is only allowed for loopback hosts.
+    pub tls: bool,
+    pub timeout: Duration,
+}
+
+impl std::fmt::Debug for MqttConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MqttConfig")
+            .field("host", &self.host)
+            .field("port", &self.port)
+            .field("topic", &self.topic)
+            .field("qos", &self.qos)
+            .field("username", &self.username)
+            .field("password", &self.password.as_ref().map(|_| "<redacted>"))
+            .field("client_id", &self.client_id)
+            .field("tls", &self.tls)
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+impl MqttConfig {
+    pub fn new(host: impl Into<String>, topic: impl Into<String>) -> Self {
+        Self {
+            host: host.into(),
+            port: 8883,
+            topic: topic.into(),
+            qos: 1,
+            username: None,
+            password: None,
+            client_id: format!(
+                "notify-kit-{}-{}",
+                std::process::id(),
+                CLIENT_SEQ.fetch_add(1, Ordering::Relaxed)
+            ),
+            tls: true,
+            timeout: Duration::from_secs(2),
+        }
+    }
+
+    #[must_use]
+    pub fn with_port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+
+    #[must_use]
+    pub fn with_qos(mut self, qos: u8) -> Self {
+        self.qos = qos;
+        self
+    }
+
+    #[must_use]
+    pub fn with_credentials(
+        mut self,
+        username: impl Into<String>,
+        password: impl Into<String>,
+    ) -> Self {
+        self.username = Some(username.into());
+        self.password = Some(password.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_client_id(mut self, client_id: impl Into<String>) -> Self {
+        self.client_id = client_id.into();
+        self
+    }
+
+    #[must_use]
+    pub fn with_tls(mut self, tls: bool) -> Self {
+        self.tls = tls;
+        self
+    }
+
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+struct MqttConnection {
+    client: AsyncClient,
+    eventloop: EventLoop,
+    last_used: Instant,
+}
+
+/// Publishes each event as JSON to an MQTT topic (MQTT 3.1.1), waiting for the broker's
+/// `PUBACK`/`PUBCOMP` at QoS 1/2.
+///
+/// The broker connection is kept open and reused across sends. It is only driven while sending,
+/// so a connection idle for longer than the keep-alive interval is replaced rather than reused.
+pub struct MqttSink {
+    host: String,
+    port: u16,
+    topic: String,
+    qos: QoS,
+    username: Option<String>,
+    password: Option<String>,
+    client_id: String,
+    tls: bool,
+    timeout: Duration,
+    connection: tokio::sync::Mutex<Option<MqttConnection>>,
+}
+
+impl std::fmt::Debug for MqttSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MqttSink")
+            .field("host", &self.host)
+            .field("port", &self.port)
+            .field("topic", &self.topic)
+            .field("qos", &self.qos)
+            .field("client_id", &self.client_id)
+            .field("tls", &self.tls)
+            .finish_non_exhaustive()
+    }
+}
+
+impl MqttSink {
+    pub fn new(config: MqttConfig) -> crate::Result<Self> {
+        let host = config.host.trim();
+        if host.is_empty() {
+            return Err(crate::Error::invalid_config(anyhow::anyhow!(
+                "mqtt host must not be empty"
+            )));
+        }
+        if config.port == 0 {
+            return Err(crate::Error::invalid_config(anyhow::anyhow!(
+                "mqtt port must not be 0"
+            )));
+        }
+        if !config.tls && !is_loopback_host(host) {
+            return Err(crate::Error::invalid_config(anyhow::anyhow!(
+                "plaintext mqtt must target a loopback host; enable tls"
+            )));
+        }
+        validate_topic(&config.topic)?;
+        let qos = parse_qos(config.qos)?;
+        validate_string("client_id", &config.client_id)?;
+        if config.client_id.is_empty() {
+            return Err(crate::Error::invalid_config(anyhow::anyhow!(
+                "mqtt client_id must not be empty"
+            )));
+        }
+        if config.password.is_some() && config.username.is_none() {
+            return Err(crate::Error::invalid_config(anyhow::anyhow!(
+                "mqtt password requires a username"
+            )));
+        }
+        if let Some(username) = &config.username {
+            validate_string("username", username)?;
+        }
+        if config
+            .password
+            .as_ref()
+            .is_some_and(|password| password.len() > MAX_STRING_BYTES)
+        {
+            return Err(crate::Error::invalid_config(anyhow::anyhow!(
+                "mqtt password is too long"
+            )));
+        }
+
+        Ok(Self {
+            host: host.to_string(),
+            port: config.port,
+            topic: config.topic,
+            qos,
+            username: config.username,
+            password: config.password,
+            client_id: config.client_id,
+            tls: config.tls,
+            timeout: config.timeout,
+            connection: tokio::sync::Mutex::new(None),
+        })
+    }
+
+    fn options(&self) -> crate::Result<MqttOptions> {
+        let mut options = MqttOptions::new(&self.client_id, &self.host, self.port);
+        options
+            .set_keep_alive(KEEP_ALIVE)
+            .set_clean_session(true)
+            .set_max_packet_size(10 * 1024, MAX_PACKET_BYTES);
+        if let Some(username) = &self.username {
+            options.set_credentials(username, self.password.as_deref().unwrap_or_default());
+        }
+        if self.tls {
+            options.set_transport(Transport::tls_with_config(tls_config()?.into()));
+        }
+        Ok(options)
+    }
+
+    fn payload(event: &Event) -> crate::Result<Vec<u8>> {
+        serde_json::to_vec(event).map_err(|err| {
+            crate::Error::serialization(anyhow::anyhow!("serialize mqtt payload: {err}"))
+        })
+    }
+
+    async fn publish(&self, payload: &[u8], deadline: tokio::time::Instant) -> crate::Result<()> {
+        let mut guard = self.connection.lock().await;
+        let reused = guard
+            .as_ref()
+            .is_some_and(|conn| conn.last_used.elapsed() < KEEP_ALIVE);
+        if !reused {
+            *guard = None;
+        }
+
+        let mut result = self.publish_before(&mut guard, payload, deadline).await;
+        if result.is_err() && reused && tokio::time::Instant::now() < deadline {
+            // The broker may have dropped the pooled connection; retry once on a fresh one.
+            *guard = None;
+            result = self.publish_before(&mut guard, payload, deadline).await;
+        }
+        match &result {
+            Ok(()) => {
+                if let Some(conn) = guard.as_mut() {
+                    conn.last_used = Instant::now();
+                }
+            }
+            Err(_) => *guard = None,
+        }
+        result
+    }
+
+    /// Times out inside the lock so an abandoned publish never leaves a half-used connection
+    /// in the pool.
+    async fn publish_before(
+        &self,
+        slot: &mut Option<MqttConnection>,
+        payload: &[u8],
+        deadline: tokio::time::Instant,
+    ) -> crate::Result<()> {
+        match tokio::time::timeout_at(deadline, self.publish_on(slot, payload)).await {
+            Ok(result) => result,
+            Err(_) => {
+                Err(anyhow::anyhow!("mqtt publish timed out after {:?}", self.timeout).into())
+            }
+        }
+    }
+
+    async fn publish_on(
+        &self,
+        slot: &mut Option<MqttConnection>,
+        payload: &[u8],
+    ) -> crate::Result<()> {
+        let conn = match slot {
+            Some(conn) => conn,
+            None => slot.insert(self.connect().await?),
+        };
+
+        conn.client
+            .publish(self.topic.as_str(), self.qos, false, payload)
+            .await
+            .map_err(|err| anyhow::anyhow!("mqtt publish failed: {err}"))?;
+        loop {
+            let event = conn
+                .eventloop
+                .poll()
+                .await
+                .map_err(|err| anyhow::anyhow!("mqtt publish failed: {err}"))?;
+            let done = matches!(
+                (self.qos, event),
+                (
+                    QoS::AtMostOnce,
+                    rumqttc::Event::Outgoing(Outgoing::Publish(_))
+                ) | (
+                    QoS::AtLeastOnce,
+                    rumqttc::Event::Incoming(Packet::PubAck(_))
+                ) | (
+                    QoS::ExactlyOnce,
+                    rumqttc::Event::Incoming(Packet::PubComp(_))
+                )
+            );
+            if done {
+                return Ok(());
+            }
+        }
+    }
+
+    async fn connect(&self) -> crate::Result<MqttConnection> {
+        let (client, mut eventloop) = AsyncClient::new(self.options()?, 10);
+        loop {
+            let event = eventloop
+                .poll()
+                .await
+                .map_err(|err| anyhow::anyhow!("mqtt connect failed: {err}"))?;
+            if let rumqttc::Event::Incoming(Packet::ConnAck(_)) = event {
+                break;
+            }
+        }
+        Ok(MqttConnection {
+            client,
+            eventloop,
+            last_used: Instant::now(),
+        })
+    }
+}
+
+fn tls_config() -> crate::Result<rustls::ClientConfig> {
+    let roots = rustls::RootCertStore {
+        roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+    };
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    Ok(rustls::ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .map_err(|err| anyhow::anyhow!("mqtt tls config: {err}"))?
+        .with_root_certificates(roots)
+        .with_no_client_auth())
+}
+
+fn validate_string(field: &str, value: &str) -> crate::Result<()> {
+    if value.len() > MAX_STRING_BYTES {
+        return Err(crate::Error::invalid_config(anyhow::anyhow!(
+            "mqtt {field} is too long"
+        )));
+    }
+    if value.contains('\0') {
+        return Err(crate::Error::invalid_config(anyhow::anyhow!(
+            "mqtt {field} must not contain NUL"
+        )));
+    }
+    Ok(())
+}
+
+fn validate_topic(topic: &str) -> crate::Result<()> {
+    if topic.is_empty() {
+        return Err(crate::Error::invalid_config(anyhow::anyhow!(
+            "mqtt topic must not be empty"
+        )));
+    }
+    validate_string("topic", topic)?;
+    if topic.contains(['+', '#']) {
+        return Err(crate::Error::invalid_config(anyhow::anyhow!(
+            "mqtt topic must not contain wildcards (+ or #)"
+        )));
+    }
+    Ok(())
+}
+
+fn parse_qos(qos: u8) -> crate::Result<QoS> {
+    match qos {
+        0 => Ok(QoS::AtMostOnce),
+        1 => Ok(QoS::AtLeastOnce),
+        2 => Ok(QoS::ExactlyOnce),
+        _ => Err(crate::Error::invalid_config(anyhow::anyhow!(
+            "mqtt qos must be 0, 1 or 2 (got {qos})"
+        ))),
+    }
+}
+
+impl Sink for MqttSink {
+    fn name(&self) -> &'static str {
+        "mqtt"
+    }
+
+    fn send<'a>(&'a self, event: &'a Event) -> BoxFuture<'a, crate::Result<()>> {
+        Box::pin(async move {
+            let payload = Self::payload(event)?;
+            let deadline = tokio::time::Instant::now() + self.timeout;
+            self.publish(&payload, deadline).await
+        })
+    }
+
+    fn preview(&self, event: &Event) -> Option<serde_json::Value> {
+        serde_json::to_value(event).ok()
+    }
+
+    fn diagnostic_target(&self) -> Option<String> {
+        let scheme = if self.tls { "mqtts" } else { "mqtt" };
+        Some(format!(
+            "{scheme}://{}:{} topic={:?}",
+            self.host, self.port, self.topic
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    use super::*;
+    use crate::Severity;
+
+    fn block_on<F: std::future::Future>(fut: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("build tokio runtime")
+            .block_on(fut)
+    }
+
+    async fn read_packet(stream: &mut tokio::net::TcpStream) -> Option<(u8, Vec<u8>)> {
+        let header = stream.read_u8().await.ok()?;
+        let mut len = 0usize;
+        for shift in (0..4).map(|i| i * 7) {
+            let byte = stream.read_u8().await.ok()?;
+            len |= usize::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                break;
+            }
+        }
+        let mut body = vec![0; len];
+        stream.read_exact(&mut body).await.ok()?;
+        Some((header, body))
+    }
+
+    /// A minimal QoS 1 broker: acks CONNECT and PUBLISH, records payloads and connections.
+    async fn fake_broker(
+        connections: Arc<AtomicUsize>,
+        payloads: Arc<std::sync::Mutex<Vec<Vec<u8>>>>,
+    ) -> std::net::SocketAddr {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind listener");
+        let addr = listener.local_addr().expect("local addr");
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                connections.fetch_add(1, Ordering::SeqCst);
+                let payloads = Arc::clone(&payloads);
+                tokio::spawn(async move {
+                    while let Some((header, body)) = read_packet(&mut stream).await {
+                        let reply = match header >> 4 {
+                            1 => vec![0x20, 0x02, 0x00, 0x00],
+                            3 => {
+                                let topic_len = usize::from(u16::from_be_bytes([body[0], body[1]]));
+                                let pkid = &body[2 + topic_len..4 + topic_len];
+                                payloads
+                                    .lock()
+                                    .expect("payloads lock")
+                                    .push(body[4 + topic_len..].to_vec());
+                                vec![0x40, 0x02, pkid[0], pkid[1]]
+                            }
+                            12 => vec![0xd0, 0x00],
+                            _ => continue,
+                        };
+                        if stream.write_all(&reply).await.is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+        });
+        addr
+    }
+
+    #[test]
+    fn validates_config() {
+        let invalid = [
+            (MqttConfig::new("", "notify"), "host"),
+            (
+                MqttConfig::new("broker.example.com", "notify").with_port(0),
+                "port",
+            ),
+            (MqttConfig::new("broker.example.com", ""), "topic"),
+            (
+                MqttConfig::new("broker.example.com", "notify/+"),
+                "wildcards",
+            ),
+            (
+                MqttConfig::new("broker.example.com", "notify/#"),
+                "wildcards",
+            ),
+            (MqttConfig::new("broker.example.com", "a\0b"), "NUL"),
+            (
+                MqttConfig::new("broker.example.com", "x".repeat(MAX_STRING_BYTES + 1)),
+                "too long",
+            ),
+            (
+                MqttConfig::new("broker.example.com", "notify").with_qos(3),
+                "qos",
+            ),
+            (
+                MqttConfig::new("broker.example.com", "notify").with_client_id(""),
+                "client_id",
+            ),
+            (
+                MqttConfig::new("broker.example.com", "notify").with_tls(false),
+                "loopback",
+            ),
+        ];
+        for (config, needle) in invalid {
+            let err = MqttSink::new(config).expect_err("expected invalid config");
+            assert!(err.to_string().contains(needle), "{needle}: {err}");
+        }
+
+        let mut config = MqttConfig::new("broker.example.com", "notify");
+        config.password = Some("hunter2".to_string());
+        let err = MqttSink::new(config).expect_err("expected password without username");
+        assert!(err.to_string().contains("username"), "{err}");
+
+        for qos in 0..=2 {
+            MqttSink::new(MqttConfig::new("broker.example.com", "alerts/ci").with_qos(qos))
+                .expect("valid qos")
+                .options()
+                .expect("tls options");
+        }
+        MqttSink::new(
+            MqttConfig::new("127.0.0.1", "alerts/ci")
+                .with_port(1883)
+                .with_tls(false),
+        )
+        .expect("loopback plaintext ok");
+    }
+
+    #[test]
+    fn default_client_ids_are_unique() {
+        let a = MqttConfig::new("broker.example.com", "notify");
+        let b = MqttConfig::new("broker.example.com", "notify");
+        assert_ne!(a.client_id, b.client_id);
+        assert!(a.client_id.starts_with("notify-kit-"), "{}", a.client_id);
+    }
+
+    #[test]
+    fn payload_is_the_serialized_event() {
+        let event = Event::new("turn_completed", Severity::Warning, "done")
+            .with_body("all green")
+            .with_tag("repo", "notify-kit");
+        let payload = MqttSink::payload(&event).expect("payload");
+        let value: serde_json::Value = serde_json::from_slice(&payload).expect("json");
+        assert_eq!(value["kind"], "turn_completed");
+        assert_eq!(value["severity"], "warning");
+        assert_eq!(value["title"], "done");
+        assert_eq!(value["body"], "all green");
+        assert_eq!(value["tags"]["repo"], "notify-kit");
+
+        let sink = MqttSink::new(MqttConfig::new("broker.example.com", "notify")).expect("sink");
+        assert_eq!(sink.preview(&event), Some(value));
+    }
+
+    #[test]
+    fn publishes_and_reuses_the_connection() {
+        block_on(async {
+            let connections = Arc::new(AtomicUsize::new(0));
+            let payloads = Arc::new(std::sync::Mutex::new(Vec::new()));
+            let addr = fake_broker(Arc::clone(&connections), Arc::clone(&payloads)).await;
+
+            let sink = MqttSink::new(
+                MqttConfig::new(addr.ip().to_string(), "alerts/ci")
+                    .with_port(addr.port())
+                    .with_tls(false)
+                    .with_credentials("bot", "hunter2")
+                    .with_timeout(Duration::from_secs(5)),
+            )
+            .expect("build sink");
+            for title in ["first", "second"] {
+                let event = Event::new("kind", Severity::Info, title);
+                sink.send(&event).await.expect("send ok");
+            }
+
+            assert_eq!(connections.load(Ordering::SeqCst), 1);
+            let payloads = payloads.lock().expect("payloads lock").clone();
+            assert_eq!(payloads.len(), 2);
+            let value: serde_json::Value = serde_json::from_slice(&payloads[1]).expect("json");
+            assert_eq!(value["title"], "second");
+        });
+    }
+
+    #[test]
+    fn connection_failure_is_reported_without_credentials() {
+        block_on(async {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+                .await
+                .expect("bind listener");
+            let addr = listener.local_addr().expect("local addr");
+            drop(listener);
+
+            let sink = MqttSink::new(
+                MqttConfig::new("127.0.0.1", "notify")
+                    .with_port(addr.port())
+                    .with_tls(false)
+                    .with_credentials("user", "hunter2")
+                    .with_timeout(Duration::from_secs(5)),
+            )
+            .expect("build sink");
+            let event = Event::new("kind", Severity::Info, "title");
+            let err = sink.send(&event).await.expect_err("expected failure");
+            let msg = format!("{err:#}");
+            assert!(msg.contains("mqtt"), "{msg}");
+            assert!(!msg.contains("hunter2"), "{msg}");
+        });
+    }
+
+    #[test]
+    fn debug_redacts_credentials() {
+        let cfg =
+            MqttConfig::new("broker.example.com", "notify").with_credentials("bot", "hunter2");
+        let dbg = format!("{cfg:?}");
+        assert!(!dbg.contains("hunter2"), "{dbg}");
+        assert!(dbg.contains("<redacted>"), "{dbg}");
+
+        let sink = MqttSink::new(cfg).expect("build sink");
+        let dbg = format!("{sink:?}");
+        assert!(!dbg.contains("hunter2"), "{dbg}");
+        assert!(dbg.contains("broker.example.com"), "{dbg}");
+        assert_eq!(
+            sink.diagnostic_target().as_deref(),
+            Some("mqtts://broker.example.com:8883 topic=\"notify\"")
+        );
+    }
+}
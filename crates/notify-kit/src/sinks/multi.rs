@@ -0,0 +1,188 @@
+use std::fmt::Write as _;
+use std::sync::Arc;
+
+use crate::Event;
+use crate::sinks::{BoxFuture, Sink};
+
+/// Sink that delivers each event to several sinks at once, as one logical destination.
+///
+/// For example two Slack channels that together form "the team channel": all sinks are sent to
+/// concurrently, and the send succeeds only if every one of them succeeds; otherwise the error
+/// lists each failure in registration order. Unlike `Hub` there is no filtering, per-sink
+/// timeout or failure policy, which keeps it composable: a `MultiSink` can be nested in other
+/// decorators (e.g. as one step of a [`crate::FallbackSink`]) or in another `MultiSink`.
+/// Registered in a `Hub` as a single sink named `"multi"`.
+pub struct MultiSink {
+    sinks: Vec<Arc<dyn Sink>>,
+}
+
+impl std::fmt::Debug for MultiSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names: Vec<&str> = self.sinks.iter().map(|sink| sink.name()).collect();
+        f.debug_struct("MultiSink").field("sinks", &names).finish()
+    }
+}
+
+impl MultiSink {
+    /// `sinks` must not be empty.
+    pub fn new(sinks: Vec<Arc<dyn Sink>>) -> crate::Result<Self> {
+        if sinks.is_empty() {
+            return Err(anyhow::anyhow!("multi sink needs at least one sink").into());
+        }
+        Ok(Self { sinks })
+    }
+}
+
+impl Sink for MultiSink {
+    fn name(&self) -> &'static str {
+        "multi"
+    }
+
+    fn send<'a>(&'a self, event: &'a Event) -> BoxFuture<'a, crate::Result<()>> {
+        Box::pin(async move {
+            let results =
+                futures_util::future::join_all(self.sinks.iter().map(|sink| sink.send(event)))
+                    .await;
+
+            let mut msg = String::from("one or more multi sinks failed:");
+            let mut failed = false;
+            for (sink, result) in self.sinks.iter().zip(results) {
+                let Err(err) = result else {
+                    continue;
+                };
+                failed = true;
+                if write!(&mut msg, "\n- {}: {err:#}", sink.name()).is_err() {
+                    return Err(anyhow::anyhow!("failed to format sink error").into());
+                }
+            }
+            if !failed {
+                return Ok(());
+            }
+            Err(anyhow::anyhow!(msg).into())
+        })
+    }
+
+    /// The members as `name + name`, with each sink's own target in parentheses when it has one.
+    fn diagnostic_target(&self) -> Option<String> {
+        let members: Vec<String> = self
+            .sinks
+            .iter()
+            .map(|sink| match sink.diagnostic_target() {
+                Some(target) => format!("{} ({target})", sink.name()),
+                None => sink.name().to_string(),
+            })
+            .collect();
+        Some(members.join(" + "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::Severity;
+
+    #[derive(Debug)]
+    enum Behavior {
+        Ok,
+        Err,
+        Barrier(Arc<tokio::sync::Barrier>),
+    }
+
+    #[derive(Debug)]
+    struct TestSink {
+        name: &'static str,
+        behavior: Behavior,
+    }
+
+    impl TestSink {
+        fn new(name: &'static str, behavior: Behavior) -> Arc<Self> {
+            Arc::new(Self { name, behavior })
+        }
+    }
+
+    impl Sink for TestSink {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        fn send<'a>(&'a self, _event: &'a Event) -> BoxFuture<'a, crate::Result<()>> {
+            Box::pin(async move {
+                match &self.behavior {
+                    Behavior::Ok => Ok(()),
+                    Behavior::Err => Err(anyhow::anyhow!("{} down", self.name).into()),
+                    Behavior::Barrier(barrier) => {
+                        barrier.wait().await;
+                        Ok(())
+                    }
+                }
+            })
+        }
+    }
+
+    fn run(sink: &MultiSink) -> crate::Result<()> {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime");
+        rt.block_on(async {
+            tokio::time::timeout(
+                Duration::from_secs(5),
+                sink.send(&Event::new("kind", Severity::Info, "title")),
+            )
+            .await
+            .unwrap_or_else(|_| Err(anyhow::anyhow!("multi sink send timed out").into()))
+        })
+    }
+
+    #[test]
+    fn all_ok_is_ok() {
+        let sink = MultiSink::new(vec![
+            TestSink::new("slack-a", Behavior::Ok),
+            TestSink::new("slack-b", Behavior::Ok),
+        ])
+        .expect("build sink");
+        run(&sink).expect("all delivered");
+        assert_eq!(sink.name(), "multi");
+        assert_eq!(
+            sink.diagnostic_target().as_deref(),
+            Some("slack-a + slack-b")
+        );
+    }
+
+    #[test]
+    fn partial_failure_aggregates_errors_in_order() {
+        let sink = MultiSink::new(vec![
+            TestSink::new("slack-a", Behavior::Err),
+            TestSink::new("slack-b", Behavior::Ok),
+            TestSink::new("slack-c", Behavior::Err),
+        ])
+        .expect("build sink");
+        let err = run(&sink).expect_err("partial failure");
+        assert_eq!(
+            err.to_string(),
+            "one or more multi sinks failed:\n- slack-a: slack-a down\n- slack-c: slack-c down"
+        );
+    }
+
+    #[test]
+    fn sends_run_concurrently_and_nest() {
+        // Each member waits for all others: sequential sends would never get past the first.
+        let barrier = Arc::new(tokio::sync::Barrier::new(3));
+        let inner = MultiSink::new(vec![
+            TestSink::new("a", Behavior::Barrier(barrier.clone())),
+            TestSink::new("b", Behavior::Barrier(barrier.clone())),
+        ])
+        .expect("build sink");
+        let sink = MultiSink::new(vec![
+            Arc::new(inner),
+            TestSink::new("c", Behavior::Barrier(barrier)),
+        ])
+        .expect("build sink");
+        run(&sink).expect("concurrent sends");
+
+        let err = MultiSink::new(Vec::new()).expect_err("empty");
+        assert!(err.to_string().contains("at least one sink"), "{err}");
+    }
+}
@@ -2,7 +2,7 @@ use std::time::Duration;
 
 use crate::Event;
 use crate::sinks::http::{
-    DEFAULT_MAX_RESPONSE_BODY_BYTES, build_http_client, parse_and_validate_https_url,
+    build_http_client, max_response_body_bytes, parse_and_validate_https_url,
     read_json_body_limited, read_text_body_limited, redact_url, select_http_client, send_reqwest,
     validate_url_path_prefix,
 };
@@ -19,6 +19,7 @@ pub struct PushPlusConfig {
     pub template: Option<String>,
     pub topic: Option<String>,
     pub timeout: Duration,
+    /// Message length limit; `0` means unlimited (see [`crate::MaxChars`]).
     pub max_chars: usize,
     pub enforce_public_ip: bool,
 }
@@ -227,29 +228,31 @@ impl Sink for PushPlusSink {
 
             let status = resp.status();
             if !status.is_success() {
-                let body = match read_text_body_limited(resp, DEFAULT_MAX_RESPONSE_BODY_BYTES).await
-                {
+                let body = match read_text_body_limited(resp, max_response_body_bytes()).await {
                     Ok(body) => body,
                     Err(err) => {
-                        return Err(anyhow::anyhow!(
-                            "pushplus http error: {status} (failed to read response body: {err})"
-                        )
-                        .into());
+                        return Err(crate::Error::http_status(
+                            status,
+                            anyhow::anyhow!(
+                                "pushplus http error: {status} (failed to read response body: {err})"
+                            ),
+                        ));
                     }
                 };
                 let summary = truncate_chars(body.trim(), 200);
                 if summary.is_empty() {
-                    return Err(anyhow::anyhow!(
-                        "pushplus http error: {status} (response body omitted)"
-                    )
-                    .into());
+                    return Err(crate::Error::http_status(
+                        status,
+                        anyhow::anyhow!("pushplus http error: {status} (response body omitted)"),
+                    ));
                 }
-                return Err(
-                    anyhow::anyhow!("pushplus http error: {status}, response={summary}").into(),
-                );
+                return Err(crate::Error::http_status(
+                    status,
+                    anyhow::anyhow!("pushplus http error: {status}, response={summary}"),
+                ));
             }
 
-            let body = read_json_body_limited(resp, DEFAULT_MAX_RESPONSE_BODY_BYTES).await?;
+            let body = read_json_body_limited(resp, max_response_body_bytes()).await?;
 
             let code = body["code"].as_i64().unwrap_or(-1);
             if code == 200 {
@@ -260,6 +263,24 @@ impl Sink for PushPlusSink {
             Err(pushplus_api_error(code, msg))
         })
     }
+
+    /// The request body with `token` redacted.
+    fn preview(&self, event: &Event) -> Option<serde_json::Value> {
+        let mut payload = Self::build_payload(
+            event,
+            &self.token,
+            self.channel.as_deref(),
+            self.template.as_deref(),
+            self.topic.as_deref(),
+            self.max_chars,
+        );
+        payload["token"] = serde_json::json!("<redacted>");
+        Some(payload)
+    }
+
+    fn diagnostic_target(&self) -> Option<String> {
+        Some(redact_url(&self.api_url))
+    }
 }
 
 #[cfg(test)]
@@ -283,6 +304,17 @@ mod tests {
         assert_eq!(payload["template"].as_str().unwrap_or(""), "txt");
     }
 
+    #[test]
+    fn preview_redacts_token() {
+        let sink = PushPlusSink::new(PushPlusConfig::new("tok_secret")).expect("build sink");
+        let payload = sink
+            .preview(&Event::new("kind", Severity::Info, "done"))
+            .expect("preview");
+        assert_eq!(payload["token"], "<redacted>");
+        assert_eq!(payload["title"], "done");
+        assert!(!payload.to_string().contains("tok_secret"), "{payload}");
+    }
+
     #[test]
     fn debug_redacts_token() {
         let cfg = PushPlusConfig::new("tok_secret");
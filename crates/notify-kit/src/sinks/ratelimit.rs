@@ -0,0 +1,371 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::Event;
+use crate::lru::BoundedLru;
+use crate::sinks::http::RateLimitedError;
+use crate::sinks::{BoxFuture, Sink};
+
+/// Token-bucket limits for [`RateLimitedSink`].
+///
+/// The bucket holds up to `max_per_interval` tokens and refills continuously at
+/// `max_per_interval / interval`, so bursts up to `max_per_interval` pass immediately and the
+/// sustained rate never exceeds the configured one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    /// Bucket size; `0` is treated as `1`.
+    pub max_per_interval: u32,
+    pub interval: Duration,
+    /// How long `send` may wait for a token before failing; `Duration::ZERO` (the default) fails
+    /// immediately when the bucket is empty, `Duration::MAX` waits as long as it takes.
+    ///
+    /// Like `RetryPolicy::budget`, keep this below `HubConfig::per_sink_timeout`.
+    pub max_wait: Duration,
+    /// Give each event kind its own bucket, remembering at most this many kinds (the least
+    /// recently used kind is forgotten first); `None` (the default) shares one bucket across all
+    /// events. A forgotten kind starts again with a full bucket.
+    pub per_kind: Option<usize>,
+}
+
+impl RateLimit {
+    pub fn new(max_per_interval: u32, interval: Duration) -> Self {
+        Self {
+            max_per_interval,
+            interval,
+            max_wait: Duration::ZERO,
+            per_kind: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_max_wait(mut self, max_wait: Duration) -> Self {
+        self.max_wait = max_wait;
+        self
+    }
+
+    #[must_use]
+    pub fn with_per_kind(mut self, max_kinds: usize) -> Self {
+        self.per_kind = Some(max_kinds);
+        self
+    }
+
+    fn capacity(&self) -> f64 {
+        f64::from(self.max_per_interval.max(1))
+    }
+
+    fn time_per_token(&self) -> Duration {
+        self.interval.div_f64(self.capacity())
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// Sink decorator that throttles sends with a token bucket.
+///
+/// When no token is available, `send` waits up to [`RateLimit::max_wait`] for one and otherwise
+/// fails without calling the inner sink, with a [`crate::RateLimitedError`] whose `retry_after` is
+/// the time until the next token. The wrapper reports the inner sink's `name()`, so it can
+/// be registered in a `Hub` or combined with [`crate::RetryingSink`] (wrap the rate limiter in
+/// the retrying sink to also throttle retries).
+pub struct RateLimitedSink {
+    inner: Arc<dyn Sink>,
+    limit: RateLimit,
+    /// Keyed by event kind with [`RateLimit::per_kind`], otherwise a single entry under `""`.
+    /// A bucket left alone for `interval` is full again, so expiring it after that loses nothing.
+    buckets: Mutex<BoundedLru<String, Bucket>>,
+}
+
+impl std::fmt::Debug for RateLimitedSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RateLimitedSink")
+            .field("limit", &self.limit)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RateLimitedSink {
+    pub fn new(inner: Arc<dyn Sink>, limit: RateLimit) -> Self {
+        let buckets = match limit.per_kind {
+            Some(max_kinds) => BoundedLru::new(max_kinds, Some(limit.interval)),
+            None => BoundedLru::new(1, None),
+        };
+        Self {
+            inner,
+            limit,
+            buckets: Mutex::new(buckets),
+        }
+    }
+
+    /// Takes a token from `kind`'s bucket, or returns how long until the next one is available.
+    fn try_acquire(&self, kind: &str, now: Instant) -> Result<(), Duration> {
+        let per_token = self.limit.time_per_token();
+        if per_token.is_zero() {
+            return Ok(());
+        }
+
+        let key = if self.limit.per_kind.is_some() {
+            kind.to_string()
+        } else {
+            String::new()
+        };
+        let mut buckets = self
+            .buckets
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let mut bucket = buckets.get(&key, now).copied().unwrap_or(Bucket {
+            tokens: self.limit.capacity(),
+            last_refill: now,
+        });
+        let elapsed = now.saturating_duration_since(bucket.last_refill);
+        bucket.tokens = (bucket.tokens + elapsed.as_secs_f64() / per_token.as_secs_f64())
+            .min(self.limit.capacity());
+        bucket.last_refill = now;
+
+        let result = if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(per_token.mul_f64(1.0 - bucket.tokens))
+        };
+        buckets.insert(key, bucket, now);
+        result
+    }
+
+    async fn acquire(&self, kind: &str) -> crate::Result<()> {
+        // A `max_wait` too large to add to `now` (e.g. `Duration::MAX`) means no deadline.
+        let deadline = Instant::now().checked_add(self.limit.max_wait);
+        loop {
+            let now = Instant::now();
+            let wait = match self.try_acquire(kind, now) {
+                Ok(()) => return Ok(()),
+                Err(wait) => wait,
+            };
+            let past_deadline = deadline.is_some_and(|deadline| {
+                now.checked_add(wait)
+                    .is_none_or(|next_token| next_token > deadline)
+            });
+            if past_deadline {
+                let message = format!(
+                    "rate limited: more than {} sends per {:?}",
+                    self.limit.max_per_interval, self.limit.interval
+                );
+                return Err(anyhow::Error::from(RateLimitedError::new(Some(wait), message)).into());
+            }
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+impl Sink for RateLimitedSink {
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn send<'a>(&'a self, event: &'a Event) -> BoxFuture<'a, crate::Result<()>> {
+        Box::pin(async move {
+            self.acquire(&event.kind).await?;
+            self.inner.send(event).await
+        })
+    }
+
+    fn diagnostic_target(&self) -> Option<String> {
+        self.inner.diagnostic_target()
+    }
+
+    fn preview(&self, event: &Event) -> Option<serde_json::Value> {
+        self.inner.preview(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::Severity;
+    use crate::sinks::{RetryPolicy, RetryingSink};
+
+    #[derive(Debug, Default)]
+    struct CountingSink {
+        calls: AtomicUsize,
+    }
+
+    impl Sink for CountingSink {
+        fn name(&self) -> &'static str {
+            "counting"
+        }
+
+        fn send<'a>(&'a self, _event: &'a Event) -> BoxFuture<'a, crate::Result<()>> {
+            Box::pin(async move {
+                self.calls.fetch_add(1, Ordering::SeqCst);
+                Ok(())
+            })
+        }
+    }
+
+    fn block_on<F: std::future::Future>(fut: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime")
+            .block_on(fut)
+    }
+
+    #[test]
+    fn drops_sends_beyond_limit_within_interval() {
+        let inner = Arc::new(CountingSink::default());
+        let sink = RateLimitedSink::new(inner.clone(), RateLimit::new(3, Duration::from_secs(60)));
+        let event = Event::new("kind", Severity::Info, "title");
+
+        let results: Vec<crate::Result<()>> = block_on(async {
+            futures_util::future::join_all((0..10).map(|_| sink.send(&event))).await
+        });
+
+        assert_eq!(results.iter().filter(|result| result.is_ok()).count(), 3);
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 3);
+        let err = results
+            .into_iter()
+            .find_map(Result::err)
+            .expect("expected rate limit error");
+        assert!(err.to_string().contains("rate limited"), "{err:#}");
+        assert_eq!(sink.name(), "counting");
+    }
+
+    #[test]
+    fn waits_for_tokens_up_to_max_wait() {
+        let inner = Arc::new(CountingSink::default());
+        let sink = RateLimitedSink::new(
+            inner.clone(),
+            RateLimit::new(2, Duration::from_millis(100)).with_max_wait(Duration::from_secs(1)),
+        );
+        let event = Event::new("kind", Severity::Info, "title");
+
+        let started = Instant::now();
+        block_on(async {
+            for _ in 0..4 {
+                sink.send(&event).await.expect("send");
+            }
+        });
+        let elapsed = started.elapsed();
+
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 4);
+        // Two tokens up front, then one every 50ms.
+        assert!(elapsed >= Duration::from_millis(90), "{elapsed:?}");
+    }
+
+    #[test]
+    fn unbounded_max_wait_waits_for_the_next_token() {
+        let inner = Arc::new(CountingSink::default());
+        let sink = RateLimitedSink::new(
+            inner.clone(),
+            RateLimit::new(1, Duration::from_millis(20)).with_max_wait(Duration::MAX),
+        );
+        let event = Event::new("kind", Severity::Info, "title");
+
+        block_on(async {
+            sink.send(&event).await.expect("first send");
+            sink.send(&event)
+                .await
+                .expect("second send waits for a token");
+        });
+
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn gives_up_when_next_token_is_beyond_max_wait() {
+        let inner = Arc::new(CountingSink::default());
+        let sink = RateLimitedSink::new(
+            inner.clone(),
+            RateLimit::new(1, Duration::from_secs(60)).with_max_wait(Duration::from_millis(20)),
+        );
+        let event = Event::new("kind", Severity::Info, "title");
+
+        let started = Instant::now();
+        block_on(async {
+            sink.send(&event).await.expect("first send");
+            sink.send(&event).await.expect_err("second send is limited");
+        });
+
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn per_kind_buckets_limit_each_kind_separately() {
+        let inner = Arc::new(CountingSink::default());
+        let sink = RateLimitedSink::new(
+            inner.clone(),
+            RateLimit::new(2, Duration::from_secs(60)).with_per_kind(16),
+        );
+        let deploy = Event::new("deploy", Severity::Info, "title");
+        let backup = Event::new("backup", Severity::Info, "title");
+
+        block_on(async {
+            for _ in 0..2 {
+                sink.send(&deploy).await.expect("deploy within limit");
+            }
+            sink.send(&deploy)
+                .await
+                .expect_err("deploy bucket is empty");
+            for _ in 0..2 {
+                sink.send(&backup).await.expect("backup has its own bucket");
+            }
+        });
+
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn per_kind_state_is_bounded() {
+        let inner = Arc::new(CountingSink::default());
+        let sink = RateLimitedSink::new(
+            inner.clone(),
+            RateLimit::new(1, Duration::from_secs(60)).with_per_kind(2),
+        );
+
+        block_on(async {
+            for i in 0..100 {
+                sink.send(&Event::new(format!("kind{i}"), Severity::Info, "title"))
+                    .await
+                    .expect("new kinds start with a full bucket");
+            }
+        });
+
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 100);
+        assert_eq!(sink.buckets.lock().expect("lock buckets").len(), 2);
+    }
+
+    #[test]
+    fn nests_inside_retrying_sink() {
+        let inner = Arc::new(CountingSink::default());
+        let limited = Arc::new(RateLimitedSink::new(
+            inner.clone(),
+            RateLimit::new(1, Duration::from_millis(40)),
+        ));
+        let sink = RetryingSink::new(
+            limited,
+            RetryPolicy {
+                max_retries: 5,
+                base_delay: Duration::from_millis(40),
+                max_delay: Duration::from_millis(80),
+                budget: None,
+            },
+        );
+        let event = Event::new("kind", Severity::Info, "title");
+
+        block_on(async {
+            sink.send(&event).await.expect("first send");
+            sink.send(&event)
+                .await
+                .expect("retried until a token is free");
+        });
+
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+        assert_eq!(sink.name(), "counting");
+    }
+}
@@ -0,0 +1,429 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::sinks::http::RateLimitedError;
+use crate::sinks::{BoxFuture, Sink};
+use crate::{ErrorKind, Event};
+
+/// Backoff policy for [`RetryingSink`].
+///
+/// Delays grow exponentially from `base_delay` (doubling per retry), are capped at `max_delay`, and
+/// get "equal jitter" (a random value in `[delay / 2, delay]`) so that many hosts retrying the same
+/// endpoint do not synchronize.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Number of retries after the first attempt (`0` disables retrying).
+    pub max_retries: usize,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    /// Optional upper bound for the whole `send` (all attempts plus backoff sleeps).
+    ///
+    /// `Hub` enforces `HubConfig::per_sink_timeout` around the wrapped sink; keep `budget` below
+    /// that value, otherwise the hub may cancel the retry loop before it can report the last error.
+    pub budget: Option<Duration>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 2,
+            base_delay: Duration::from_millis(200),
+            max_delay: Duration::from_secs(2),
+            budget: None,
+        }
+    }
+}
+
+impl RetryPolicy {
+    #[must_use]
+    pub fn with_budget(mut self, budget: Duration) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    fn backoff(&self, retry: usize) -> Duration {
+        let shift = u32::try_from(retry).unwrap_or(u32::MAX).min(31);
+        let delay = self
+            .base_delay
+            .saturating_mul(1u32 << shift)
+            .min(self.max_delay);
+        let half = delay / 2;
+        half + half.mul_f64(crate::jitter::random_fraction())
+    }
+}
+
+/// Sink decorator that retries failed sends with exponential backoff and jitter.
+///
+/// Only transient errors are retried: [`ErrorKind::Timeout`], [`ErrorKind::Connect`],
+/// `HttpStatus` 5xx and 429, and [`crate::RateLimitedSink`]'s "no token" errors. Anything else
+/// (invalid config, SSRF rejections, other 4xx, uncategorized errors) is returned right away, as
+/// is a success. When the provider rate limits
+/// the send ([`crate::RateLimitedError`] with a `Retry-After`), the next attempt waits at least
+/// that long, even beyond `max_delay`; if that would overrun `budget`, the error is returned
+/// right away instead. The wrapper reports the inner sink's `name()`, so it can be registered in
+/// a `Hub` (or nested in other decorators) in place of the sink it wraps.
+pub struct RetryingSink {
+    inner: Arc<dyn Sink>,
+    policy: RetryPolicy,
+}
+
+impl std::fmt::Debug for RetryingSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RetryingSink")
+            .field("policy", &self.policy)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RetryingSink {
+    pub fn new(inner: Arc<dyn Sink>, policy: RetryPolicy) -> Self {
+        Self { inner, policy }
+    }
+
+    fn is_retryable(err: &crate::Error) -> bool {
+        matches!(
+            err.kind(),
+            ErrorKind::Timeout | ErrorKind::Connect | ErrorKind::HttpStatus(429 | 500..=599)
+        ) || err.downcast_ref::<RateLimitedError>().is_some()
+    }
+
+    async fn send_attempt(&self, event: &Event, deadline: Option<Instant>) -> crate::Result<()> {
+        let Some(deadline) = deadline else {
+            return self.inner.send(event).await;
+        };
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        tokio::time::timeout(remaining, self.inner.send(event))
+            .await
+            .unwrap_or_else(|_| {
+                Err(crate::Error::timeout(anyhow::anyhow!(
+                    "retry budget exhausted"
+                )))
+            })
+    }
+}
+
+impl Sink for RetryingSink {
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn send<'a>(&'a self, event: &'a Event) -> BoxFuture<'a, crate::Result<()>> {
+        Box::pin(async move {
+            // A budget too large to add to `now` means no deadline.
+            let deadline = self
+                .policy
+                .budget
+                .and_then(|budget| Instant::now().checked_add(budget));
+            let mut retry = 0usize;
+            loop {
+                let err = match self.send_attempt(event, deadline).await {
+                    Ok(()) => return Ok(()),
+                    Err(err) => err,
+                };
+                if retry >= self.policy.max_retries || !Self::is_retryable(&err) {
+                    return Err(err);
+                }
+
+                let mut delay = self.policy.backoff(retry);
+                if let Some(retry_after) = err
+                    .downcast_ref::<RateLimitedError>()
+                    .and_then(|rate_limited| rate_limited.retry_after)
+                {
+                    delay = delay.max(retry_after);
+                }
+                if let Some(deadline) = deadline {
+                    // A delay too large to add to `now` is past any deadline.
+                    if Instant::now()
+                        .checked_add(delay)
+                        .is_none_or(|retry_at| retry_at >= deadline)
+                    {
+                        return Err(err);
+                    }
+                }
+                tracing::debug!(
+                    sink = self.inner.name(),
+                    retry = retry + 1,
+                    delay_ms = delay.as_millis() as u64,
+                    "send failed; retrying: {err}"
+                );
+                tokio::time::sleep(delay).await;
+                retry += 1;
+            }
+        })
+    }
+
+    fn diagnostic_target(&self) -> Option<String> {
+        self.inner.diagnostic_target()
+    }
+
+    fn preview(&self, event: &Event) -> Option<serde_json::Value> {
+        self.inner.preview(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::Severity;
+
+    #[derive(Debug)]
+    struct FlakySink {
+        failures_before_success: usize,
+        calls: AtomicUsize,
+    }
+
+    impl Sink for FlakySink {
+        fn name(&self) -> &'static str {
+            "flaky"
+        }
+
+        fn send<'a>(&'a self, _event: &'a Event) -> BoxFuture<'a, crate::Result<()>> {
+            Box::pin(async move {
+                let call = self.calls.fetch_add(1, Ordering::SeqCst);
+                if call < self.failures_before_success {
+                    return Err(crate::Error::timeout(anyhow::anyhow!("boom {call}")));
+                }
+                Ok(())
+            })
+        }
+    }
+
+    fn flaky(failures_before_success: usize) -> Arc<FlakySink> {
+        Arc::new(FlakySink {
+            failures_before_success,
+            calls: AtomicUsize::new(0),
+        })
+    }
+
+    fn policy(max_retries: usize) -> RetryPolicy {
+        RetryPolicy {
+            max_retries,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(4),
+            budget: None,
+        }
+    }
+
+    fn block_on<F: std::future::Future>(fut: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("build tokio runtime")
+            .block_on(fut)
+    }
+
+    #[test]
+    fn retries_until_success() {
+        let inner = flaky(2);
+        let sink = RetryingSink::new(inner.clone(), policy(3));
+        let event = Event::new("kind", Severity::Info, "title");
+
+        block_on(sink.send(&event)).expect("third attempt succeeds");
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 3);
+        assert_eq!(sink.name(), "flaky");
+    }
+
+    #[test]
+    fn returns_last_error_when_retries_exhausted() {
+        let inner = flaky(2);
+        let sink = RetryingSink::new(inner.clone(), policy(1));
+        let event = Event::new("kind", Severity::Info, "title");
+
+        let err = block_on(sink.send(&event)).expect_err("expected failure");
+        assert_eq!(err.to_string(), "boom 1");
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn does_not_retry_success() {
+        let inner = flaky(0);
+        let sink = RetryingSink::new(inner.clone(), policy(3));
+        let event = Event::new("kind", Severity::Info, "title");
+
+        block_on(sink.send(&event)).expect("first attempt succeeds");
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[derive(Debug)]
+    struct FailingSink {
+        kind: ErrorKind,
+        calls: AtomicUsize,
+    }
+
+    impl Sink for FailingSink {
+        fn name(&self) -> &'static str {
+            "failing"
+        }
+
+        fn send<'a>(&'a self, _event: &'a Event) -> BoxFuture<'a, crate::Result<()>> {
+            Box::pin(async move {
+                self.calls.fetch_add(1, Ordering::SeqCst);
+                Err(crate::Error::with_kind(
+                    self.kind,
+                    anyhow::anyhow!("failed"),
+                ))
+            })
+        }
+    }
+
+    #[test]
+    fn retries_only_transient_errors() {
+        let cases = [
+            (ErrorKind::Timeout, 3),
+            (ErrorKind::Connect, 3),
+            (ErrorKind::HttpStatus(503), 3),
+            (ErrorKind::HttpStatus(429), 3),
+            (ErrorKind::HttpStatus(400), 1),
+            (ErrorKind::HttpStatus(404), 1),
+            (ErrorKind::InvalidConfig, 1),
+            (ErrorKind::Ssrf, 1),
+            (ErrorKind::Serialization, 1),
+            (ErrorKind::Other, 1),
+        ];
+        for (kind, expected_calls) in cases {
+            let inner = Arc::new(FailingSink {
+                kind,
+                calls: AtomicUsize::new(0),
+            });
+            let sink = RetryingSink::new(inner.clone(), policy(2));
+            let event = Event::new("kind", Severity::Info, "title");
+
+            let err = block_on(sink.send(&event)).expect_err("always fails");
+            assert_eq!(err.kind(), kind);
+            assert_eq!(
+                inner.calls.load(Ordering::SeqCst),
+                expected_calls,
+                "{kind:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn unbounded_budget_does_not_overflow() {
+        let inner = flaky(1);
+        let sink = RetryingSink::new(inner.clone(), policy(1).with_budget(Duration::MAX));
+        let event = Event::new("kind", Severity::Info, "title");
+
+        block_on(sink.send(&event)).expect("second attempt succeeds");
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn total_retry_time_stays_within_budget() {
+        let inner = flaky(usize::MAX);
+        let budget = Duration::from_millis(60);
+        let sink = RetryingSink::new(
+            inner.clone(),
+            RetryPolicy {
+                max_retries: 100,
+                base_delay: Duration::from_millis(20),
+                max_delay: Duration::from_millis(20),
+                budget: Some(budget),
+            },
+        );
+        let event = Event::new("kind", Severity::Info, "title");
+
+        let started = Instant::now();
+        block_on(sink.send(&event)).expect_err("expected failure");
+        let elapsed = started.elapsed();
+        assert!(elapsed <= budget + Duration::from_millis(20), "{elapsed:?}");
+        let calls = inner.calls.load(Ordering::SeqCst);
+        assert!((2..100).contains(&calls), "{calls}");
+    }
+
+    #[derive(Debug)]
+    struct RateLimitedSink {
+        retry_after: Duration,
+        calls: AtomicUsize,
+    }
+
+    impl Sink for RateLimitedSink {
+        fn name(&self) -> &'static str {
+            "rate-limited"
+        }
+
+        fn send<'a>(&'a self, _event: &'a Event) -> BoxFuture<'a, crate::Result<()>> {
+            Box::pin(async move {
+                if self.calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                    return Err(anyhow::Error::from(RateLimitedError::new(
+                        Some(self.retry_after),
+                        "rate limited",
+                    ))
+                    .into());
+                }
+                Ok(())
+            })
+        }
+    }
+
+    #[test]
+    fn honors_retry_after_beyond_max_delay() {
+        let inner = Arc::new(RateLimitedSink {
+            retry_after: Duration::from_millis(50),
+            calls: AtomicUsize::new(0),
+        });
+        let sink = RetryingSink::new(inner.clone(), policy(1));
+        let event = Event::new("kind", Severity::Info, "title");
+
+        let started = Instant::now();
+        block_on(sink.send(&event)).expect("second attempt succeeds");
+        assert!(
+            started.elapsed() >= Duration::from_millis(50),
+            "{:?}",
+            started.elapsed()
+        );
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn retry_after_beyond_budget_fails_immediately() {
+        let inner = Arc::new(RateLimitedSink {
+            retry_after: Duration::from_secs(60),
+            calls: AtomicUsize::new(0),
+        });
+        let sink = RetryingSink::new(inner.clone(), policy(3).with_budget(Duration::from_secs(1)));
+        let event = Event::new("kind", Severity::Info, "title");
+
+        let started = Instant::now();
+        let err = block_on(sink.send(&event)).expect_err("gives up");
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert_eq!(
+            err.downcast_ref::<RateLimitedError>()
+                .and_then(|err| err.retry_after),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn huge_retry_after_fails_without_overflowing() {
+        let inner = Arc::new(RateLimitedSink {
+            retry_after: Duration::MAX,
+            calls: AtomicUsize::new(0),
+        });
+        let sink = RetryingSink::new(inner.clone(), policy(3).with_budget(Duration::from_secs(1)));
+        let event = Event::new("kind", Severity::Info, "title");
+
+        let err = block_on(sink.send(&event)).expect_err("gives up");
+        assert!(err.downcast_ref::<RateLimitedError>().is_some());
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn backoff_is_capped_and_jittered() {
+        let policy = RetryPolicy {
+            max_retries: 10,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(400),
+            budget: None,
+        };
+        for retry in 0..10 {
+            let expected = Duration::from_millis(100 * (1 << retry.min(2)));
+            let delay = policy.backoff(retry);
+            assert!(delay >= expected / 2, "{retry}: {delay:?}");
+            assert!(delay <= expected, "{retry}: {delay:?}");
+        }
+    }
+}
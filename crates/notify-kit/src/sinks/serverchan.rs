@@ -2,7 +2,7 @@ use std::time::Duration;
 
 use crate::Event;
 use crate::sinks::http::{
-    DEFAULT_MAX_RESPONSE_BODY_BYTES, build_http_client, parse_and_validate_https_url,
+    build_http_client, max_response_body_bytes, parse_and_validate_https_url,
     parse_and_validate_https_url_basic, read_json_body_limited, read_text_body_limited, redact_url,
     select_http_client, send_reqwest, validate_url_path_prefix,
 };
@@ -16,6 +16,7 @@ const SERVERCHAN_TURBO_ALLOWED_HOSTS: [&str; 1] = ["sctapi.ftqq.com"];
 pub struct ServerChanConfig {
     pub send_key: String,
     pub timeout: Duration,
+    /// Message length limit; `0` means unlimited (see [`crate::MaxChars`]).
     pub max_chars: usize,
     pub enforce_public_ip: bool,
 }
@@ -215,32 +216,42 @@ impl Sink for ServerChanSink {
 
             let status = resp.status();
             if !status.is_success() {
-                let body = match read_text_body_limited(resp, DEFAULT_MAX_RESPONSE_BODY_BYTES).await
-                {
+                let body = match read_text_body_limited(resp, max_response_body_bytes()).await {
                     Ok(body) => body,
                     Err(err) => {
-                        return Err(anyhow::anyhow!(
-                            "serverchan http error: {status} (failed to read response body: {err})"
-                        )
-                        .into());
+                        return Err(crate::Error::http_status(
+                            status,
+                            anyhow::anyhow!(
+                                "serverchan http error: {status} (failed to read response body: {err})"
+                            ),
+                        ));
                     }
                 };
                 let summary = truncate_chars(body.trim(), 200);
                 if summary.is_empty() {
-                    return Err(anyhow::anyhow!(
-                        "serverchan http error: {status} (response body omitted)"
-                    )
-                    .into());
+                    return Err(crate::Error::http_status(
+                        status,
+                        anyhow::anyhow!("serverchan http error: {status} (response body omitted)"),
+                    ));
                 }
-                return Err(
-                    anyhow::anyhow!("serverchan http error: {status}, response={summary}").into(),
-                );
+                return Err(crate::Error::http_status(
+                    status,
+                    anyhow::anyhow!("serverchan http error: {status}, response={summary}"),
+                ));
             }
 
-            let body = read_json_body_limited(resp, DEFAULT_MAX_RESPONSE_BODY_BYTES).await?;
+            let body = read_json_body_limited(resp, max_response_body_bytes()).await?;
             Self::ensure_success_response(&body)
         })
     }
+
+    fn preview(&self, event: &Event) -> Option<serde_json::Value> {
+        Some(Self::build_payload(event, self.max_chars))
+    }
+
+    fn diagnostic_target(&self) -> Option<String> {
+        Some(redact_url(&self.api_url))
+    }
 }
 
 #[cfg(test)]
@@ -1,12 +1,14 @@
+use std::sync::Arc;
 use std::time::Duration;
 
 use crate::Event;
+use crate::render::{EventFormatter, MaxChars};
 use crate::sinks::http::{
-    DEFAULT_MAX_RESPONSE_BODY_BYTES, build_http_client, parse_and_validate_https_url,
+    build_http_client, max_response_body_bytes, parse_and_validate_https_url,
     read_text_body_limited, redact_url, redact_url_str, select_http_client, send_reqwest,
     validate_url_path_prefix,
 };
-use crate::sinks::text::{TextLimits, format_event_text_limited, truncate_chars};
+use crate::sinks::text::{TextLimits, event_link_url, format_event_text_with, truncate_chars};
 use crate::sinks::{BoxFuture, Sink};
 
 const SLACK_ALLOWED_HOSTS: [&str; 1] = ["hooks.slack.com"];
@@ -16,8 +18,17 @@ const SLACK_ALLOWED_HOSTS: [&str; 1] = ["hooks.slack.com"];
 pub struct SlackWebhookConfig {
     pub webhook_url: String,
     pub timeout: Duration,
+    /// Message length limit; `0` means unlimited (see [`crate::MaxChars`]).
     pub max_chars: usize,
+    /// Custom message layout; `None` uses the built-in one ([`crate::DefaultFormatter`]).
+    pub formatter: Option<Arc<dyn EventFormatter>>,
     pub enforce_public_ip: bool,
+    /// Posts to this channel (e.g. `#alerts`) instead of the webhook's default one.
+    pub channel: Option<String>,
+    /// Overrides the webhook's display name.
+    pub username: Option<String>,
+    /// Overrides the webhook's icon with an emoji (e.g. `:rotating_light:`).
+    pub icon_emoji: Option<String>,
 }
 
 impl std::fmt::Debug for SlackWebhookConfig {
@@ -26,7 +37,11 @@ impl std::fmt::Debug for SlackWebhookConfig {
             .field("webhook_url", &redact_url_str(&self.webhook_url))
             .field("timeout", &self.timeout)
             .field("max_chars", &self.max_chars)
+            .field("custom_formatter", &self.formatter.is_some())
             .field("enforce_public_ip", &self.enforce_public_ip)
+            .field("channel", &self.channel)
+            .field("username", &self.username)
+            .field("icon_emoji", &self.icon_emoji)
             .finish()
     }
 }
@@ -37,7 +52,11 @@ impl SlackWebhookConfig {
             webhook_url: webhook_url.into(),
             timeout: Duration::from_secs(2),
             max_chars: 4000,
+            formatter: None,
             enforce_public_ip: true,
+            channel: None,
+            username: None,
+            icon_emoji: None,
         }
     }
 
@@ -53,11 +72,72 @@ impl SlackWebhookConfig {
         self
     }
 
+    #[must_use]
+    pub fn with_formatter(mut self, formatter: Arc<dyn EventFormatter>) -> Self {
+        self.formatter = Some(formatter);
+        self
+    }
+
     #[must_use]
     pub fn with_public_ip_check(mut self, enforce_public_ip: bool) -> Self {
         self.enforce_public_ip = enforce_public_ip;
         self
     }
+
+    #[must_use]
+    pub fn with_channel(mut self, channel: impl Into<String>) -> Self {
+        self.channel = Some(channel.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_username(mut self, username: impl Into<String>) -> Self {
+        self.username = Some(username.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_icon_emoji(mut self, icon_emoji: impl Into<String>) -> Self {
+        self.icon_emoji = Some(icon_emoji.into());
+        self
+    }
+}
+
+/// Optional incoming-webhook fields sent next to `text`; blank values are dropped.
+#[derive(Debug, Clone, Default)]
+struct SlackOverrides {
+    channel: Option<String>,
+    username: Option<String>,
+    icon_emoji: Option<String>,
+}
+
+impl SlackOverrides {
+    fn from_config(config: &SlackWebhookConfig) -> Self {
+        let normalize = |value: &Option<String>| {
+            value
+                .as_deref()
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+        Self {
+            channel: normalize(&config.channel),
+            username: normalize(&config.username),
+            icon_emoji: normalize(&config.icon_emoji),
+        }
+    }
+
+    fn apply(&self, payload: &mut serde_json::Map<String, serde_json::Value>) {
+        for (key, value) in [
+            ("channel", &self.channel),
+            ("username", &self.username),
+            ("icon_emoji", &self.icon_emoji),
+        ] {
+            if let Some(value) = value {
+                payload.insert(key.to_string(), serde_json::json!(value));
+            }
+        }
+    }
 }
 
 pub struct SlackWebhookSink {
@@ -65,7 +145,9 @@ pub struct SlackWebhookSink {
     client: reqwest::Client,
     timeout: Duration,
     max_chars: usize,
+    formatter: Option<Arc<dyn EventFormatter>>,
     enforce_public_ip: bool,
+    overrides: SlackOverrides,
 }
 
 impl std::fmt::Debug for SlackWebhookSink {
@@ -73,6 +155,8 @@ impl std::fmt::Debug for SlackWebhookSink {
         f.debug_struct("SlackWebhookSink")
             .field("webhook_url", &redact_url(&self.webhook_url))
             .field("max_chars", &self.max_chars)
+            .field("custom_formatter", &self.formatter.is_some())
+            .field("overrides", &self.overrides)
             .finish_non_exhaustive()
     }
 }
@@ -82,18 +166,58 @@ impl SlackWebhookSink {
         let webhook_url = parse_and_validate_https_url(&config.webhook_url, &SLACK_ALLOWED_HOSTS)?;
         validate_url_path_prefix(&webhook_url, "/services/")?;
         let client = build_http_client(config.timeout)?;
+        let overrides = SlackOverrides::from_config(&config);
         Ok(Self {
             webhook_url,
             client,
             timeout: config.timeout,
             max_chars: config.max_chars,
+            formatter: config.formatter,
             enforce_public_ip: config.enforce_public_ip,
+            overrides,
         })
     }
 
-    fn build_payload(event: &Event, max_chars: usize) -> serde_json::Value {
-        let text = format_event_text_limited(event, TextLimits::new(max_chars));
-        serde_json::json!({ "text": text })
+    fn build_payload(
+        event: &Event,
+        max_chars: usize,
+        formatter: Option<&dyn EventFormatter>,
+        overrides: &SlackOverrides,
+    ) -> serde_json::Value {
+        let mut payload = serde_json::Map::new();
+        payload.insert(
+            "text".to_string(),
+            serde_json::json!(Self::build_text(event, max_chars, formatter)),
+        );
+        overrides.apply(&mut payload);
+        serde_json::Value::Object(payload)
+    }
+
+    /// The mrkdwn `text` field, also used by [`crate::SlackApiSink`].
+    pub(super) fn build_text(
+        event: &Event,
+        max_chars: usize,
+        formatter: Option<&dyn EventFormatter>,
+    ) -> String {
+        let Some(url) = event_link_url(event).filter(|url| !url.contains(['|', '<', '>'])) else {
+            return format_event_text_with(formatter, event, TextLimits::new(max_chars));
+        };
+
+        // Slack mrkdwn link, kept out of truncation so it is never cut in half.
+        let link = format!("<{url}|View details>");
+        let link_chars = link.chars().count() + 1;
+        let budget = MaxChars::from(max_chars);
+        if budget.limit() <= link_chars {
+            return format_event_text_with(formatter, event, TextLimits::new(budget));
+        }
+        let mut text = format_event_text_with(
+            formatter,
+            event,
+            TextLimits::new(budget.saturating_sub(link_chars)).without_url(),
+        );
+        text.push('\n');
+        text.push_str(&link);
+        text
     }
 }
 
@@ -111,7 +235,12 @@ impl Sink for SlackWebhookSink {
                 self.enforce_public_ip,
             )
             .await?;
-            let payload = Self::build_payload(event, self.max_chars);
+            let payload = Self::build_payload(
+                event,
+                self.max_chars,
+                self.formatter.as_deref(),
+                &self.overrides,
+            );
 
             let resp = send_reqwest(
                 client.post(self.webhook_url.as_str()).json(&payload),
@@ -119,7 +248,7 @@ impl Sink for SlackWebhookSink {
             )
             .await?;
             let status = resp.status();
-            let body = match read_text_body_limited(resp, DEFAULT_MAX_RESPONSE_BODY_BYTES).await {
+            let body = match read_text_body_limited(resp, max_response_body_bytes()).await {
                 Ok(body) => body,
                 Err(err) => {
                     if status.is_success() {
@@ -128,10 +257,12 @@ impl Sink for SlackWebhookSink {
                         )
                         .into());
                     }
-                    return Err(anyhow::anyhow!(
-                        "slack webhook http error: {status} (failed to read response body: {err})"
-                    )
-                    .into());
+                    return Err(crate::Error::http_status(
+                        status,
+                        anyhow::anyhow!(
+                            "slack webhook http error: {status} (failed to read response body: {err})"
+                        ),
+                    ));
                 }
             };
             let body = body.trim();
@@ -139,16 +270,18 @@ impl Sink for SlackWebhookSink {
             if !status.is_success() {
                 let summary = truncate_chars(body, 200);
                 if summary.is_empty() {
-                    return Err(anyhow::anyhow!(
-                        "slack webhook http error: {status} (response body omitted)"
-                    )
-                    .into());
+                    return Err(crate::Error::http_status(
+                        status,
+                        anyhow::anyhow!(
+                            "slack webhook http error: {status} (response body omitted)"
+                        ),
+                    ));
                 }
 
-                return Err(anyhow::anyhow!(
-                    "slack webhook http error: {status}, response={summary}"
-                )
-                .into());
+                return Err(crate::Error::http_status(
+                    status,
+                    anyhow::anyhow!("slack webhook http error: {status}, response={summary}"),
+                ));
             }
 
             if body.is_empty() || body.eq_ignore_ascii_case("ok") {
@@ -159,6 +292,19 @@ impl Sink for SlackWebhookSink {
             Err(anyhow::anyhow!("slack webhook api error: response={summary}").into())
         })
     }
+
+    fn preview(&self, event: &Event) -> Option<serde_json::Value> {
+        Some(Self::build_payload(
+            event,
+            self.max_chars,
+            self.formatter.as_deref(),
+            &self.overrides,
+        ))
+    }
+
+    fn diagnostic_target(&self) -> Option<String> {
+        Some(redact_url(&self.webhook_url))
+    }
 }
 
 #[cfg(test)]
@@ -172,13 +318,94 @@ mod tests {
             .with_body("ok")
             .with_tag("thread_id", "t1");
 
-        let payload = SlackWebhookSink::build_payload(&event, 4000);
+        let payload =
+            SlackWebhookSink::build_payload(&event, 4000, None, &SlackOverrides::default());
         let text = payload["text"].as_str().unwrap_or("");
         assert!(text.contains("done"));
         assert!(text.contains("ok"));
         assert!(text.contains("thread_id=t1"));
     }
 
+    #[test]
+    fn renders_event_url_as_mrkdwn_link() {
+        let event = Event::new("turn_completed", Severity::Success, "done")
+            .with_body("ok")
+            .with_url("https://example.com/run/1");
+
+        let payload =
+            SlackWebhookSink::build_payload(&event, 4000, None, &SlackOverrides::default());
+        assert_eq!(
+            payload["text"].as_str(),
+            Some("done\nok\n<https://example.com/run/1|View details>")
+        );
+
+        let long = event.clone().with_body("x".repeat(100));
+        let payload = SlackWebhookSink::build_payload(&long, 60, None, &SlackOverrides::default());
+        let text = payload["text"].as_str().unwrap_or("");
+        assert!(text.chars().count() <= 60, "{text}");
+        assert!(
+            text.ends_with("\n<https://example.com/run/1|View details>"),
+            "{text}"
+        );
+
+        let payload = SlackWebhookSink::build_payload(
+            &Event::new("k", Severity::Info, "done"),
+            4000,
+            None,
+            &SlackOverrides::default(),
+        );
+        assert_eq!(payload["text"].as_str(), Some("done"));
+    }
+
+    #[test]
+    fn overrides_appear_only_when_configured() {
+        let event = Event::new("k", Severity::Info, "done");
+
+        let payload =
+            SlackWebhookSink::build_payload(&event, 4000, None, &SlackOverrides::default());
+        assert_eq!(payload, serde_json::json!({ "text": "done" }));
+
+        let cfg = SlackWebhookConfig::new("https://hooks.slack.com/services/x/y/z")
+            .with_channel("#alerts")
+            .with_icon_emoji(":rotating_light:")
+            .with_username("  ");
+        let overrides = SlackOverrides::from_config(&cfg);
+        let payload = SlackWebhookSink::build_payload(&event, 4000, None, &overrides);
+        assert_eq!(
+            payload,
+            serde_json::json!({
+                "text": "done",
+                "channel": "#alerts",
+                "icon_emoji": ":rotating_light:",
+            })
+        );
+
+        let sink = SlackWebhookSink::new(cfg.with_username("deploy-bot")).expect("build sink");
+        let payload = sink.preview(&event).expect("preview");
+        assert_eq!(payload["text"], "done");
+        assert_eq!(payload["channel"], "#alerts");
+        assert_eq!(payload["username"], "deploy-bot");
+        assert_eq!(payload["icon_emoji"], ":rotating_light:");
+    }
+
+    #[test]
+    fn preview_matches_sent_payload() {
+        let sink = SlackWebhookSink::new(SlackWebhookConfig::new(
+            "https://hooks.slack.com/services/x/y/z",
+        ))
+        .expect("build sink");
+        let event = Event::new("turn_completed", Severity::Success, "done").with_body("ok");
+        assert_eq!(
+            sink.preview(&event),
+            Some(SlackWebhookSink::build_payload(
+                &event,
+                4000,
+                None,
+                &SlackOverrides::default()
+            ))
+        );
+    }
+
     #[test]
     fn rejects_non_https_webhook_url() {
         let cfg = SlackWebhookConfig::new("http://hooks.slack.com/services/x/y/z");
@@ -0,0 +1,679 @@
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use crate::Event;
+use crate::render::EventFormatter;
+use crate::sinks::http::{
+    build_http_client, max_response_body_bytes, parse_and_validate_https_url,
+    read_text_body_limited, redact_url, select_http_client, send_reqwest,
+};
+use crate::sinks::sigv4::{SigV4, SignableRequest, amz_date};
+use crate::sinks::text::{TextLimits, TruncateUnit, format_event_text_with, truncate_chars};
+use crate::sinks::{BoxFuture, Sink};
+
+/// SNS rejects messages larger than this (in bytes).
+const SNS_MAX_MESSAGE_BYTES: usize = 256 * 1024;
+/// SNS rejects subjects longer than this.
+const SNS_MAX_SUBJECT_CHARS: usize = 100;
+const FORM_CONTENT_TYPE: &str = "application/x-www-form-urlencoded; charset=utf-8";
+
+#[non_exhaustive]
+#[derive(Clone)]
+pub struct SnsConfig {
+    /// `arn:aws:sns:<region>:<account-id>:<topic>`; its region must match `region`.
+    pub topic_arn: String,
+    /// e.g. `us-east-1`.
+    pub region: String,
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    /// Session token for temporary (STS) credentials.
+    pub session_token: Option<String>,
+    pub timeout: Duration,
+    /// Message length limit in bytes; `0` means SNS's own limit of 256 KiB.
+    pub max_chars: usize,
+    /// Custom message layout; `None` uses the built-in one ([`crate::DefaultFormatter`]).
+    pub formatter: Option<Arc<dyn EventFormatter>>,
+    pub enforce_public_ip: bool,
+}
+
+impl std::fmt::Debug for SnsConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SnsConfig")
+            .field("topic_arn", &self.topic_arn)
+            .field("region", &self.region)
+            .field("access_key_id", &self.access_key_id)
+            .field("secret_access_key", &"<redacted>")
+            .field(
+                "session_token",
+                &self.session_token.as_ref().map(|_| "<redacted>"),
+            )
+            .field("timeout", &self.timeout)
+            .field("max_chars", &self.max_chars)
+            .field("custom_formatter", &self.formatter.is_some())
+            .field("enforce_public_ip", &self.enforce_public_ip)
+            .finish()
+    }
+}
+
+impl SnsConfig {
+    pub fn new(
+        topic_arn: impl Into<String>,
+        region: impl Into<String>,
+        access_key_id: impl Into<String>,
+        secret_access_key: impl Into<String>,
+    ) -> Self {
+        Self {
+            topic_arn: topic_arn.into(),
+            region: region.into(),
+            access_key_id: access_key_id.into(),
+            secret_access_key: secret_access_key.into(),
+            session_token: None,
+            timeout: Duration::from_secs(5),
+            max_chars: 16 * 1024,
+            formatter: None,
+            enforce_public_ip: true,
+        }
+    }
+
+    #[must_use]
+    pub fn with_session_token(mut self, session_token: impl Into<String>) -> Self {
+        self.session_token = Some(session_token.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    #[must_use]
+    pub fn with_max_chars(mut self, max_chars: usize) -> Self {
+        self.max_chars = max_chars;
+        self
+    }
+
+    #[must_use]
+    pub fn with_formatter(mut self, formatter: Arc<dyn EventFormatter>) -> Self {
+        self.formatter = Some(formatter);
+        self
+    }
+
+    #[must_use]
+    pub fn with_public_ip_check(mut self, enforce_public_ip: bool) -> Self {
+        self.enforce_public_ip = enforce_public_ip;
+        self
+    }
+}
+
+/// Publishes the rendered event to an Amazon SNS topic, signing requests with SigV4.
+///
+/// The event title becomes the `Subject` (used by email subscriptions); FIFO topics get the
+/// event kind as `MessageGroupId` and need content-based deduplication enabled.
+pub struct SnsSink {
+    api_url: reqwest::Url,
+    host: String,
+    topic_arn: String,
+    region: String,
+    fifo: bool,
+    access_key_id: String,
+    secret_access_key: String,
+    session_token: Option<String>,
+    client: reqwest::Client,
+    timeout: Duration,
+    max_chars: usize,
+    formatter: Option<Arc<dyn EventFormatter>>,
+    enforce_public_ip: bool,
+}
+
+impl std::fmt::Debug for SnsSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SnsSink")
+            .field("api_url", &redact_url(&self.api_url))
+            .field("topic_arn", &self.topic_arn)
+            .field("access_key_id", &self.access_key_id)
+            .field("secret_access_key", &"<redacted>")
+            .field(
+                "session_token",
+                &self.session_token.as_ref().map(|_| "<redacted>"),
+            )
+            .field("max_chars", &self.max_chars)
+            .field("custom_formatter", &self.formatter.is_some())
+            .field("enforce_public_ip", &self.enforce_public_ip)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SnsSink {
+    pub fn new(config: SnsConfig) -> crate::Result<Self> {
+        let region = config.region.trim();
+        if !is_region(region) {
+            return Err(crate::Error::invalid_config(anyhow::anyhow!(
+                "sns region must look like `us-east-1`"
+            )));
+        }
+        let topic_arn = config.topic_arn.trim();
+        let Some(arn) = TopicArn::parse(topic_arn) else {
+            return Err(crate::Error::invalid_config(anyhow::anyhow!(
+                "sns topic_arn must look like `arn:aws:sns:<region>:<account-id>:<topic>`"
+            )));
+        };
+        if arn.region != region {
+            return Err(crate::Error::invalid_config(anyhow::anyhow!(
+                "sns topic_arn region `{}` does not match region `{region}`",
+                arn.region
+            )));
+        }
+        let access_key_id = config.access_key_id.trim();
+        if access_key_id.is_empty() || !access_key_id.bytes().all(|b| b.is_ascii_alphanumeric()) {
+            return Err(crate::Error::invalid_config(anyhow::anyhow!(
+                "sns access_key_id must be non-empty and alphanumeric"
+            )));
+        }
+        let secret_access_key = config.secret_access_key.trim();
+        if secret_access_key.is_empty() {
+            return Err(crate::Error::invalid_config(anyhow::anyhow!(
+                "sns secret_access_key must not be empty"
+            )));
+        }
+        let session_token = config
+            .session_token
+            .as_deref()
+            .map(str::trim)
+            .filter(|token| !token.is_empty())
+            .map(ToString::to_string);
+        let max_chars = match config.max_chars {
+            0 => SNS_MAX_MESSAGE_BYTES,
+            max_chars => max_chars.min(SNS_MAX_MESSAGE_BYTES),
+        };
+
+        let suffix = if arn.partition == "aws-cn" {
+            "amazonaws.com.cn"
+        } else {
+            "amazonaws.com"
+        };
+        let host = format!("sns.{region}.{suffix}");
+        let api_url = parse_and_validate_https_url(&format!("https://{host}/"), &[host.as_str()])?;
+        let client = build_http_client(config.timeout)?;
+        Ok(Self {
+            api_url,
+            host,
+            topic_arn: topic_arn.to_string(),
+            region: region.to_string(),
+            fifo: arn.topic.ends_with(".fifo"),
+            access_key_id: access_key_id.to_string(),
+            secret_access_key: secret_access_key.to_string(),
+            session_token,
+            client,
+            timeout: config.timeout,
+            max_chars,
+            formatter: config.formatter,
+            enforce_public_ip: config.enforce_public_ip,
+        })
+    }
+
+    fn build_form(&self, event: &Event) -> Vec<(&'static str, String)> {
+        let message = format_event_text_with(
+            self.formatter.as_deref(),
+            event,
+            TextLimits::new(self.max_chars).with_unit(TruncateUnit::Bytes),
+        );
+        let mut form = vec![
+            ("Action", "Publish".to_string()),
+            ("Version", "2010-03-31".to_string()),
+            ("TopicArn", self.topic_arn.clone()),
+            ("Message", message),
+        ];
+        if let Some(subject) = sns_subject(&event.title) {
+            form.push(("Subject", subject));
+        }
+        if self.fifo {
+            form.push(("MessageGroupId", truncate_chars(event.kind.trim(), 128)));
+        }
+        form
+    }
+
+    fn build_request(
+        &self,
+        client: &reqwest::Client,
+        event: &Event,
+        amz_date: &str,
+    ) -> crate::Result<reqwest::RequestBuilder> {
+        let body = encode_form(&self.api_url, &self.build_form(event));
+
+        let mut headers = vec![
+            ("content-type", FORM_CONTENT_TYPE),
+            ("host", self.host.as_str()),
+            ("x-amz-date", amz_date),
+        ];
+        if let Some(token) = self.session_token.as_deref() {
+            headers.push(("x-amz-security-token", token));
+        }
+        let signer = SigV4 {
+            access_key_id: &self.access_key_id,
+            secret_access_key: &self.secret_access_key,
+            region: &self.region,
+            service: "sns",
+        };
+        let authorization = signer.authorization(
+            amz_date,
+            &SignableRequest {
+                method: "POST",
+                path: self.api_url.path(),
+                query: "",
+                headers: &headers,
+                payload: body.as_bytes(),
+            },
+        )?;
+        let mut authorization = reqwest::header::HeaderValue::from_str(&authorization)
+            .map_err(|err| anyhow::anyhow!("invalid sns authorization header: {err}"))?;
+        authorization.set_sensitive(true);
+
+        let mut req = client
+            .post(self.api_url.as_str())
+            .header(reqwest::header::CONTENT_TYPE, FORM_CONTENT_TYPE)
+            .header("x-amz-date", amz_date)
+            .header(reqwest::header::AUTHORIZATION, authorization);
+        if let Some(token) = self.session_token.as_deref() {
+            let mut token = reqwest::header::HeaderValue::from_str(token).map_err(|_| {
+                crate::Error::invalid_config(anyhow::anyhow!(
+                    "sns session_token is not a valid header value"
+                ))
+            })?;
+            token.set_sensitive(true);
+            req = req.header("x-amz-security-token", token);
+        }
+        Ok(req.body(body))
+    }
+}
+
+struct TopicArn<'a> {
+    partition: &'a str,
+    region: &'a str,
+    topic: &'a str,
+}
+
+impl<'a> TopicArn<'a> {
+    fn parse(arn: &'a str) -> Option<Self> {
+        let mut parts = arn.splitn(6, ':');
+        if parts.next()? != "arn" {
+            return None;
+        }
+        let partition = parts.next()?;
+        if !matches!(partition, "aws" | "aws-cn" | "aws-us-gov") || parts.next()? != "sns" {
+            return None;
+        }
+        let region = parts.next()?;
+        let account = parts.next()?;
+        let topic = parts.next()?;
+        let name = topic.strip_suffix(".fifo").unwrap_or(topic);
+        let valid = is_region(region)
+            && account.len() == 12
+            && account.bytes().all(|b| b.is_ascii_digit())
+            && !name.is_empty()
+            && topic.len() <= 256
+            && name
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_'));
+        valid.then_some(Self {
+            partition,
+            region,
+            topic,
+        })
+    }
+}
+
+/// `xx-name-N` (e.g. `us-east-1`, `us-gov-west-1`).
+fn is_region(region: &str) -> bool {
+    let parts: Vec<&str> = region.split('-').collect();
+    let [first, middle @ .., last] = parts.as_slice() else {
+        return false;
+    };
+    first.len() == 2
+        && first.bytes().all(|b| b.is_ascii_lowercase())
+        && !middle.is_empty()
+        && middle
+            .iter()
+            .all(|part| !part.is_empty() && part.bytes().all(|b| b.is_ascii_lowercase()))
+        && !last.is_empty()
+        && last.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// SNS subjects must be printable ASCII on one line; other characters are dropped.
+fn sns_subject(title: &str) -> Option<String> {
+    let subject: String = title
+        .chars()
+        .filter(|ch| ch.is_ascii_graphic() || ch.is_whitespace())
+        .collect();
+    let subject = subject.split_whitespace().collect::<Vec<_>>().join(" ");
+    let subject = truncate_chars(&subject, SNS_MAX_SUBJECT_CHARS);
+    (!subject.is_empty()).then_some(subject)
+}
+
+/// `application/x-www-form-urlencoded` body; built by hand because the exact bytes get signed.
+fn encode_form(base: &reqwest::Url, fields: &[(&str, String)]) -> String {
+    let mut url = base.clone();
+    url.query_pairs_mut()
+        .clear()
+        .extend_pairs(fields.iter().map(|(key, value)| (*key, value.as_str())));
+    url.query().unwrap_or_default().to_string()
+}
+
+/// The text of the first `<tag>...</tag>` element, with the basic XML entities decoded.
+fn xml_text(body: &str, tag: &str) -> Option<String> {
+    let start = body.find(&format!("<{tag}>"))? + tag.len() + 2;
+    let len = body[start..].find(&format!("</{tag}>"))?;
+    let text = body[start..start + len]
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&");
+    Some(text.trim().to_string())
+}
+
+/// `"sns api error: ..."` from an `<ErrorResponse>` body; `None` if it has no error code.
+fn sns_api_error(body: &str) -> Option<anyhow::Error> {
+    let code = xml_text(body, "Code").filter(|code| !code.is_empty())?;
+    let code = truncate_chars(&code, 64);
+    let message = truncate_chars(&xml_text(body, "Message").unwrap_or_default(), 200);
+    if message.is_empty() {
+        return Some(anyhow::anyhow!("sns api error: code={code}"));
+    }
+    Some(anyhow::anyhow!(
+        "sns api error: code={code}, message={message}"
+    ))
+}
+
+impl Sink for SnsSink {
+    fn name(&self) -> &'static str {
+        "sns"
+    }
+
+    fn send<'a>(&'a self, event: &'a Event) -> BoxFuture<'a, crate::Result<()>> {
+        Box::pin(async move {
+            let client = select_http_client(
+                &self.client,
+                self.timeout,
+                &self.api_url,
+                self.enforce_public_ip,
+            )
+            .await?;
+
+            let amz_date = amz_date(SystemTime::now())?;
+            let req = self.build_request(&client, event, &amz_date)?;
+            let resp = send_reqwest(req, "sns").await?;
+
+            let status = resp.status();
+            if status.is_success() {
+                return Ok(());
+            }
+            let body = match read_text_body_limited(resp, max_response_body_bytes()).await {
+                Ok(body) => body,
+                Err(err) => {
+                    return Err(crate::Error::http_status(
+                        status,
+                        anyhow::anyhow!(
+                            "sns http error: {status} (failed to read response body: {err})"
+                        ),
+                    ));
+                }
+            };
+            if let Some(err) = sns_api_error(&body) {
+                return Err(crate::Error::http_status(status, err));
+            }
+            let summary = truncate_chars(body.trim(), 200);
+            if summary.is_empty() {
+                return Err(crate::Error::http_status(
+                    status,
+                    anyhow::anyhow!("sns http error: {status} (response body omitted)"),
+                ));
+            }
+            Err(crate::Error::http_status(
+                status,
+                anyhow::anyhow!("sns http error: {status}, response={summary}"),
+            ))
+        })
+    }
+
+    /// The form fields as a JSON object (credentials travel in signed headers and are omitted).
+    fn preview(&self, event: &Event) -> Option<serde_json::Value> {
+        let fields = self
+            .build_form(event)
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), serde_json::Value::String(value)))
+            .collect();
+        Some(serde_json::Value::Object(fields))
+    }
+
+    fn diagnostic_target(&self) -> Option<String> {
+        Some(format!(
+            "{} (topic={})",
+            redact_url(&self.api_url),
+            self.topic_arn
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Severity;
+
+    const ARN: &str = "arn:aws:sns:us-east-1:123456789012:alerts";
+
+    fn config() -> SnsConfig {
+        SnsConfig::new(ARN, "us-east-1", "AKIDEXAMPLE", "sns-secret-key")
+    }
+
+    #[test]
+    fn validates_topic_arn_and_region() {
+        for arn in [
+            ARN,
+            "arn:aws:sns:us-gov-west-1:123456789012:alerts_v2",
+            "arn:aws-cn:sns:cn-north-1:123456789012:Alerts-1.fifo",
+        ] {
+            assert!(TopicArn::parse(arn).is_some(), "{arn}");
+        }
+        for arn in [
+            "arn:aws:sqs:us-east-1:123456789012:alerts",
+            "arn:aws:sns:us-east-1:12345:alerts",
+            "arn:aws:sns:useast1:123456789012:alerts",
+            "arn:aws:sns:us-east-1:123456789012:",
+            "arn:aws:sns:us-east-1:123456789012:a.b",
+            "arn:aws:sns:us-east-1:123456789012:alerts:extra",
+            "arn:other:sns:us-east-1:123456789012:alerts",
+            "alerts",
+        ] {
+            assert!(TopicArn::parse(arn).is_none(), "{arn}");
+        }
+
+        let mut cases = Vec::new();
+        let mut cfg = config();
+        cfg.region = "US-EAST-1".to_string();
+        cases.push((cfg, "region must"));
+        let mut cfg = config();
+        cfg.region = "eu-west-1".to_string();
+        cases.push((cfg, "does not match"));
+        let mut cfg = config();
+        cfg.topic_arn = "arn:aws:sns:us-east-1:alerts".to_string();
+        cases.push((cfg, "topic_arn"));
+        let mut cfg = config();
+        cfg.access_key_id = "AKID EXAMPLE".to_string();
+        cases.push((cfg, "access_key_id"));
+        let mut cfg = config();
+        cfg.secret_access_key = " ".to_string();
+        cases.push((cfg, "secret_access_key"));
+
+        for (cfg, needle) in cases {
+            let err = SnsSink::new(cfg).expect_err("expected invalid config");
+            assert!(err.to_string().contains(needle), "{needle}: {err:#}");
+            assert_eq!(err.kind(), crate::ErrorKind::InvalidConfig);
+        }
+    }
+
+    #[test]
+    fn targets_regional_endpoint() {
+        let sink = SnsSink::new(config()).expect("build sink");
+        assert_eq!(
+            sink.api_url.as_str(),
+            "https://sns.us-east-1.amazonaws.com/"
+        );
+
+        let cfg = SnsConfig::new(
+            "arn:aws-cn:sns:cn-north-1:123456789012:alerts",
+            "cn-north-1",
+            "AKIDEXAMPLE",
+            "secret",
+        );
+        let sink = SnsSink::new(cfg).expect("build sink");
+        assert_eq!(
+            sink.api_url.as_str(),
+            "https://sns.cn-north-1.amazonaws.com.cn/"
+        );
+    }
+
+    #[test]
+    fn builds_publish_form() {
+        let sink = SnsSink::new(config()).expect("build sink");
+        let event = Event::new(
+            "deploy",
+            Severity::Error,
+            "deploy failed: 部署失败\nretrying",
+        )
+        .with_body("exit 1");
+        let form = sink.build_form(&event);
+        assert_eq!(
+            form,
+            vec![
+                ("Action", "Publish".to_string()),
+                ("Version", "2010-03-31".to_string()),
+                ("TopicArn", ARN.to_string()),
+                (
+                    "Message",
+                    "deploy failed: 部署失败\nretrying\nexit 1".to_string()
+                ),
+                ("Subject", "deploy failed: retrying".to_string()),
+            ]
+        );
+        assert_eq!(sns_subject("部署"), None);
+
+        let mut cfg = config();
+        cfg.topic_arn = "arn:aws:sns:us-east-1:123456789012:alerts.fifo".to_string();
+        let sink = SnsSink::new(cfg).expect("build sink");
+        let form = sink.build_form(&event);
+        assert_eq!(form.last(), Some(&("MessageGroupId", "deploy".to_string())));
+    }
+
+    #[test]
+    fn request_is_signed_with_sigv4() {
+        let sink = SnsSink::new(config().with_session_token("session-token")).expect("build sink");
+        let event = Event::new("kind", Severity::Info, "hello world");
+        let req = sink
+            .build_request(&sink.client, &event, "20240102T030405Z")
+            .expect("sign")
+            .build()
+            .expect("build request");
+
+        let body = req
+            .body()
+            .and_then(|body| body.as_bytes())
+            .map(|body| String::from_utf8_lossy(body).into_owned())
+            .expect("buffered body");
+        assert_eq!(
+            body,
+            "Action=Publish&Version=2010-03-31&\
+             TopicArn=arn%3Aaws%3Asns%3Aus-east-1%3A123456789012%3Aalerts&\
+             Message=hello+world&Subject=hello+world"
+        );
+
+        let header = |name: &str| {
+            req.headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .unwrap_or_default()
+                .to_string()
+        };
+        assert_eq!(header("x-amz-date"), "20240102T030405Z");
+        assert_eq!(header("x-amz-security-token"), "session-token");
+        assert_eq!(header("content-type"), FORM_CONTENT_TYPE);
+
+        // Same signature as signing the canonical request directly.
+        let expected = SigV4 {
+            access_key_id: "AKIDEXAMPLE",
+            secret_access_key: "sns-secret-key",
+            region: "us-east-1",
+            service: "sns",
+        }
+        .authorization(
+            "20240102T030405Z",
+            &SignableRequest {
+                method: "POST",
+                path: "/",
+                query: "",
+                headers: &[
+                    ("Content-Type", FORM_CONTENT_TYPE),
+                    ("Host", "sns.us-east-1.amazonaws.com"),
+                    ("X-Amz-Date", "20240102T030405Z"),
+                    ("X-Amz-Security-Token", "session-token"),
+                ],
+                payload: body.as_bytes(),
+            },
+        )
+        .expect("sign");
+        let authorization = req
+            .headers()
+            .get(reqwest::header::AUTHORIZATION)
+            .expect("authorization header");
+        assert!(authorization.is_sensitive());
+        assert_eq!(authorization.to_str().expect("ascii"), expected);
+        assert!(
+            expected.starts_with(
+                "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20240102/us-east-1/sns/aws4_request, \
+                 SignedHeaders=content-type;host;x-amz-date;x-amz-security-token, Signature="
+            ),
+            "{expected}"
+        );
+    }
+
+    #[test]
+    fn parses_error_xml() {
+        let body = r#"<ErrorResponse xmlns="http://sns.amazonaws.com/doc/2010-03-31/">
+  <Error>
+    <Type>Sender</Type>
+    <Code>AuthorizationError</Code>
+    <Message>User: arn:aws:iam::123456789012:user/x is not authorized to perform: SNS:Publish &amp; more</Message>
+  </Error>
+  <RequestId>d74b8436-ae13-5ab4-a9ff-ce54dfea72a0</RequestId>
+</ErrorResponse>"#;
+        let msg = sns_api_error(body).expect("api error").to_string();
+        assert!(
+            msg.starts_with("sns api error: code=AuthorizationError"),
+            "{msg}"
+        );
+        assert!(msg.contains("SNS:Publish & more"), "{msg}");
+        assert!(sns_api_error("<html>bad gateway</html>").is_none());
+    }
+
+    #[test]
+    fn debug_and_preview_redact_credentials() {
+        let cfg = config().with_session_token("session-token");
+        let cfg_dbg = format!("{cfg:?}");
+        assert!(!cfg_dbg.contains("sns-secret-key"), "{cfg_dbg}");
+        assert!(!cfg_dbg.contains("session-token"), "{cfg_dbg}");
+        let sink = SnsSink::new(cfg).expect("build sink");
+        let sink_dbg = format!("{sink:?}");
+        assert!(!sink_dbg.contains("sns-secret-key"), "{sink_dbg}");
+        assert!(!sink_dbg.contains("session-token"), "{sink_dbg}");
+        assert!(
+            sink_dbg.contains("sns.us-east-1.amazonaws.com"),
+            "{sink_dbg}"
+        );
+
+        let preview = sink
+            .preview(&Event::new("kind", Severity::Info, "t"))
+            .expect("preview");
+        assert_eq!(preview["TopicArn"], ARN);
+        assert!(!preview.to_string().contains("secret"), "{preview}");
+    }
+}
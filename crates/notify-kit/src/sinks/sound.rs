@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::io::Write;
 #[cfg(not(feature = "sound-command"))]
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -11,23 +12,36 @@ use crate::sinks::{BoxFuture, Sink};
 #[cfg(not(feature = "sound-command"))]
 static WARNED_SOUND_COMMAND_DISABLED: AtomicBool = AtomicBool::new(false);
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct SoundConfig {
     pub command_argv: Option<Vec<String>>,
+    /// Per-severity commands (e.g. an alarm for `Error`, a chime for `Success`); a matching entry
+    /// takes precedence over `command_argv`. Severities without an entry fall back to
+    /// `command_argv`, then to the terminal bell.
+    pub command_by_severity: HashMap<Severity, Vec<String>>,
 }
 
 #[derive(Debug)]
 pub struct SoundSink {
     command_argv: Option<Vec<String>>,
+    command_by_severity: HashMap<Severity, Vec<String>>,
 }
 
 impl SoundSink {
     pub fn new(config: SoundConfig) -> Self {
         Self {
             command_argv: config.command_argv,
+            command_by_severity: config.command_by_severity,
         }
     }
 
+    fn command_for(&self, severity: Severity) -> Option<&[String]> {
+        self.command_by_severity
+            .get(&severity)
+            .map(Vec::as_slice)
+            .or(self.command_argv.as_deref())
+    }
+
     fn bell_count(severity: Severity) -> usize {
         match severity {
             Severity::Error => 2,
@@ -87,7 +101,7 @@ impl Sink for SoundSink {
 
     fn send<'a>(&'a self, event: &'a Event) -> BoxFuture<'a, crate::Result<()>> {
         Box::pin(async move {
-            if let Some(_argv) = self.command_argv.as_deref() {
+            if let Some(_argv) = self.command_for(event.severity) {
                 #[cfg(feature = "sound-command")]
                 {
                     Self::send_command(_argv).await?;
@@ -99,7 +113,7 @@ impl Sink for SoundSink {
                     if !WARNED_SOUND_COMMAND_DISABLED.swap(true, Ordering::Relaxed) {
                         tracing::warn!(
                             sink = "sound",
-                            "sound command_argv configured but feature \"sound-command\" is disabled; falling back to terminal bell"
+                            "sound command configured but feature \"sound-command\" is disabled; falling back to terminal bell"
                         );
                     }
                     Self::send_terminal_bell(event)?;
@@ -115,9 +129,44 @@ impl Sink for SoundSink {
 
 #[cfg(test)]
 mod tests {
-    #[cfg(feature = "sound-command")]
     use super::*;
 
+    #[test]
+    fn command_for_prefers_severity_specific_argv() {
+        let argv = |cmd: &str| vec![cmd.to_string()];
+        let sink = SoundSink::new(SoundConfig {
+            command_argv: Some(argv("default")),
+            command_by_severity: HashMap::from([
+                (Severity::Error, argv("alarm")),
+                (Severity::Success, argv("chime")),
+            ]),
+        });
+        assert_eq!(sink.command_for(Severity::Error), Some(&argv("alarm")[..]));
+        assert_eq!(
+            sink.command_for(Severity::Success),
+            Some(&argv("chime")[..])
+        );
+        assert_eq!(
+            sink.command_for(Severity::Warning),
+            Some(&argv("default")[..])
+        );
+        assert_eq!(sink.command_for(Severity::Info), Some(&argv("default")[..]));
+    }
+
+    #[test]
+    fn command_for_falls_back_to_bell_when_unset() {
+        let sink = SoundSink::new(SoundConfig {
+            command_by_severity: HashMap::from([(Severity::Error, vec!["alarm".to_string()])]),
+            ..SoundConfig::default()
+        });
+        assert!(sink.command_for(Severity::Error).is_some());
+        assert_eq!(sink.command_for(Severity::Info), None);
+        assert_eq!(
+            SoundSink::new(SoundConfig::default()).command_for(Severity::Error),
+            None
+        );
+    }
+
     #[cfg(feature = "sound-command")]
     #[test]
     fn send_command_rejects_empty_argv() {
@@ -1,85 +1,190 @@
 use std::borrow::Cow;
 
-use crate::Event;
+use crate::render::{EventFormatter, MaxChars, TagRenderStyle, TruncationStyle};
+use crate::{Attachment, Event, Severity};
+
+/// Unit in which text limits are measured.
+///
+/// Targets disagree on how they count message length: most count Unicode scalar values, some
+/// count UTF-8 bytes (storage/column limits) and some count UTF-16 code units (JS-based APIs).
+/// Truncation never splits a character in any unit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum TruncateUnit {
+    #[default]
+    Chars,
+    Bytes,
+    Utf16,
+}
+
+impl TruncateUnit {
+    fn char_len(self, ch: char) -> usize {
+        match self {
+            Self::Chars => 1,
+            Self::Bytes => ch.len_utf8(),
+            Self::Utf16 => ch.len_utf16(),
+        }
+    }
+}
 
 #[derive(Debug, Clone, Copy)]
 pub(crate) struct TextLimits {
-    pub max_chars: usize,
+    pub max_chars: MaxChars,
     pub max_title_chars: usize,
     pub max_body_chars: usize,
+    pub max_url_chars: usize,
     pub max_tags: usize,
     pub max_tag_key_chars: usize,
     pub max_tag_value_chars: usize,
+    /// Unit for all `max_*` limits above.
+    pub unit: TruncateUnit,
+    /// Join parts with `" | "` and flatten newlines inside parts (SMS, chat topics, log lines).
+    pub single_line: bool,
+    /// Re-format tag values that are decimal numbers (e.g. `0.30000000000000004`) with this many
+    /// fraction digits (at most [`MAX_FLOAT_PRECISION`]); `None` keeps values as-is.
+    pub float_precision: Option<usize>,
+    /// Print `Event::url` on its own line after the body; sinks that surface the URL natively
+    /// (link, button, payload field) turn this off.
+    pub include_url: bool,
+    /// Applies to the overall cap and every per-part cap.
+    pub truncation: TruncationStyle,
+    /// Render tags as one compact JSON object line instead of `key=value` lines.
+    pub tags_as_json: bool,
+    /// Layout of the tag lines when `tags_as_json` is off.
+    pub tag_style: TagRenderStyle,
+    /// Print an `attachments: a.log, b.png` line listing `Event::attachments` after the URL;
+    /// sinks that upload the files turn this off.
+    pub include_attachments: bool,
+    /// Start the title with a severity emoji (`❌ `, `⚠️ `, `ℹ️ `, `✅ `); the prefix counts
+    /// toward `max_chars` and is dropped rather than cut when it doesn't fit.
+    pub severity_prefix: bool,
 }
 
 impl Default for TextLimits {
     fn default() -> Self {
         Self {
-            max_chars: 16 * 1024,
+            max_chars: MaxChars::Limited(16 * 1024),
             max_title_chars: 256,
             max_body_chars: 4 * 1024,
+            max_url_chars: 2048,
             max_tags: 32,
             max_tag_key_chars: 64,
             max_tag_value_chars: 256,
+            unit: TruncateUnit::Chars,
+            single_line: false,
+            float_precision: None,
+            include_url: true,
+            truncation: TruncationStyle::Ellipsis,
+            tags_as_json: false,
+            tag_style: TagRenderStyle::KeyEqualsValue,
+            include_attachments: true,
+            severity_prefix: false,
         }
     }
 }
 
 impl TextLimits {
-    pub(crate) fn new(max_chars: usize) -> Self {
+    /// `max_chars` of `0` (or [`MaxChars::Unlimited`]) lifts the overall cap and the body cap.
+    pub(crate) fn new(max_chars: impl Into<MaxChars>) -> Self {
+        let max_chars = max_chars.into();
+        let defaults = Self::default();
         Self {
             max_chars,
-            ..Self::default()
+            max_body_chars: match max_chars {
+                MaxChars::Limited(_) => defaults.max_body_chars,
+                MaxChars::Unlimited => usize::MAX,
+            },
+            ..defaults
         }
     }
+
+    #[must_use]
+    pub(crate) fn with_unit(mut self, unit: TruncateUnit) -> Self {
+        self.unit = unit;
+        self
+    }
+
+    #[must_use]
+    pub(crate) fn without_url(mut self) -> Self {
+        self.include_url = false;
+        self
+    }
+
+    #[must_use]
+    pub(crate) fn without_attachment_note(mut self) -> Self {
+        self.include_attachments = false;
+        self
+    }
+
+    #[must_use]
+    pub(crate) fn with_severity_prefix(mut self, severity_prefix: bool) -> Self {
+        self.severity_prefix = severity_prefix;
+        self
+    }
+}
+
+fn severity_emoji_prefix(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "❌ ",
+        Severity::Warning => "⚠️ ",
+        Severity::Info => "ℹ️ ",
+        Severity::Success => "✅ ",
+    }
 }
 
 struct LimitedChars {
     max: usize,
+    unit: TruncateUnit,
+    style: TruncationStyle,
     out: String,
     out_chars: usize,
     truncated: bool,
 }
 
-fn byte_index_after_n_chars(input: &str, max_chars: usize) -> usize {
-    if max_chars == 0 {
+fn byte_index_after_n_units(input: &str, max_units: usize, unit: TruncateUnit) -> usize {
+    if max_units == 0 {
         return 0;
     }
     if input.is_ascii() {
-        return input.len().min(max_chars);
+        return input.len().min(max_units);
     }
     let mut count = 0usize;
     for (idx, ch) in input.char_indices() {
-        count += 1;
-        if count == max_chars {
+        count += unit.char_len(ch);
+        if count > max_units {
+            return idx;
+        }
+        if count == max_units {
             return idx + ch.len_utf8();
         }
     }
     input.len()
 }
 
-fn take_prefix_chars(input: &str, max_chars: usize) -> (&str, usize, bool) {
-    if max_chars == 0 {
+fn take_prefix_units(input: &str, max_units: usize, unit: TruncateUnit) -> (&str, usize, bool) {
+    if max_units == 0 {
         return ("", 0, !input.is_empty());
     }
     if input.is_ascii() {
-        let end = input.len().min(max_chars);
-        return (&input[..end], end, input.len() > max_chars);
+        let end = input.len().min(max_units);
+        return (&input[..end], end, input.len() > max_units);
     }
     let mut count = 0usize;
-    for (idx, _) in input.char_indices() {
-        if count == max_chars {
+    for (idx, ch) in input.char_indices() {
+        let len = unit.char_len(ch);
+        if count + len > max_units {
             return (&input[..idx], count, true);
         }
-        count += 1;
+        count += len;
     }
     (input, count, false)
 }
 
 impl LimitedChars {
-    fn new(max: usize) -> Self {
+    fn new(max: usize, unit: TruncateUnit, style: TruncationStyle) -> Self {
         Self {
             max,
+            unit,
+            style,
             out: String::with_capacity(max.min(256)),
             out_chars: 0,
             truncated: false,
@@ -98,18 +203,6 @@ impl LimitedChars {
         self.max.saturating_sub(self.out_chars)
     }
 
-    fn push_char(&mut self, ch: char) {
-        if self.truncated || self.max == 0 {
-            return;
-        }
-        if self.out_chars >= self.max {
-            self.truncated = true;
-            return;
-        }
-        self.out.push(ch);
-        self.out_chars += 1;
-    }
-
     fn push_str(&mut self, s: &str) {
         if self.truncated || self.max == 0 {
             return;
@@ -119,16 +212,16 @@ impl LimitedChars {
             self.truncated = true;
             return;
         }
-        let (prefix, chars_taken, was_truncated) = take_prefix_chars(s, remaining);
+        let (prefix, chars_taken, was_truncated) = take_prefix_units(s, remaining, self.unit);
         self.out.push_str(prefix);
         self.out_chars += chars_taken;
         self.truncated = was_truncated;
     }
 
     fn finish(mut self) -> String {
-        if self.truncated && self.max > 3 {
+        if self.truncated && self.style == TruncationStyle::Ellipsis && self.max > 3 {
             let keep = self.max - 3;
-            let keep_end = byte_index_after_n_chars(&self.out, keep);
+            let keep_end = byte_index_after_n_units(&self.out, keep, self.unit);
             self.out.truncate(keep_end);
             self.out.push_str("...");
         }
@@ -136,18 +229,96 @@ impl LimitedChars {
     }
 }
 
+/// Parses tag values that are plain decimals; integers, ids and versions (`1.2.3`) are rejected.
+fn parse_plain_decimal(value: &str) -> Option<f64> {
+    let looks_decimal = value.contains('.')
+        && value
+            .bytes()
+            .all(|b| b.is_ascii_digit() || matches!(b, b'.' | b'-' | b'+'));
+    if !looks_decimal {
+        return None;
+    }
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|number| number.is_finite())
+}
+
+/// Largest useful `float_precision`: an `f64` has at most 17 significant decimal digits, and
+/// larger values would only pad every float tag with zeros.
+pub(crate) const MAX_FLOAT_PRECISION: usize = 17;
+
+fn format_float_tag(value: &str, precision: Option<usize>) -> Cow<'_, str> {
+    let Some(precision) = precision else {
+        return Cow::Borrowed(value);
+    };
+    let precision = precision.min(MAX_FLOAT_PRECISION);
+    match parse_plain_decimal(value) {
+        Some(number) => Cow::Owned(format!("{number:.precision$}")),
+        None => Cow::Borrowed(value),
+    }
+}
+
+/// Structured counterpart of `float_precision`: a plain decimal tag value becomes a JSON number
+/// rounded to `precision` fraction digits; anything else stays a string.
+pub(crate) fn tag_value_json(value: &str, precision: Option<usize>) -> serde_json::Value {
+    let rounded = precision.and_then(|precision| {
+        let precision = precision.min(MAX_FLOAT_PRECISION);
+        let number = parse_plain_decimal(value)?;
+        format!("{number:.precision$}").parse::<f64>().ok()
+    });
+    rounded.and_then(serde_json::Number::from_f64).map_or_else(
+        || serde_json::Value::String(value.to_string()),
+        serde_json::Value::Number,
+    )
+}
+
+fn flatten_newlines(input: &str, single_line: bool) -> Cow<'_, str> {
+    if !single_line || !input.contains(['\n', '\r']) {
+        return Cow::Borrowed(input);
+    }
+    let mut out = String::with_capacity(input.len());
+    for (idx, line) in input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .enumerate()
+    {
+        if idx > 0 {
+            out.push(' ');
+        }
+        out.push_str(line.trim());
+    }
+    Cow::Owned(out)
+}
+
 fn format_event_text_parts_limited(
     event: &Event,
     limits: TextLimits,
     include_title: bool,
 ) -> String {
-    let mut out = LimitedChars::new(limits.max_chars);
+    let separator = if limits.single_line { " | " } else { "\n" };
+    let mut out = LimitedChars::new(limits.max_chars.limit(), limits.unit, limits.truncation);
     if out.is_full() {
         return out.finish();
     }
 
     if include_title {
-        let title = truncate_chars_cow(&event.title, limits.max_title_chars);
+        if limits.severity_prefix && !event.title.trim().is_empty() {
+            let prefix = severity_emoji_prefix(event.severity);
+            let prefix_units: usize = prefix.chars().map(|ch| limits.unit.char_len(ch)).sum();
+            // `⚠️` / `ℹ️` are two scalars: only add the prefix when it survives the final `...`
+            // with room for at least one title character, so it is never cut in half.
+            if prefix_units + 3 < out.remaining_chars() {
+                out.push_str(prefix);
+            }
+        }
+        let title = flatten_newlines(&event.title, limits.single_line);
+        let title = truncate_units_cow(
+            &title,
+            limits.max_title_chars,
+            limits.unit,
+            limits.truncation,
+        );
         out.push_str(title.as_ref());
         if out.is_full() {
             return out.finish();
@@ -158,16 +329,18 @@ fn format_event_text_parts_limited(
         let body = body.trim();
         if !body.is_empty() {
             if !out.is_empty() {
-                if out.remaining_chars() <= 1 {
+                if out.remaining_chars() <= separator.len() {
                     out.truncated = true;
                     return out.finish();
                 }
-                out.push_char('\n');
+                out.push_str(separator);
             }
             if out.is_full() {
                 return out.finish();
             }
-            let body = truncate_chars_cow(body, limits.max_body_chars);
+            let body = flatten_newlines(body, limits.single_line);
+            let body =
+                truncate_units_cow(&body, limits.max_body_chars, limits.unit, limits.truncation);
             out.push_str(body.as_ref());
             if out.is_full() {
                 return out.finish();
@@ -175,40 +348,234 @@ fn format_event_text_parts_limited(
         }
     }
 
-    for (idx, (k, v)) in event.tags.iter().enumerate() {
+    let url = event.url.as_deref().map(str::trim).unwrap_or("");
+    if limits.include_url && !url.is_empty() {
+        if !out.is_empty() {
+            if out.remaining_chars() <= separator.len() {
+                out.truncated = true;
+                return out.finish();
+            }
+            out.push_str(separator);
+        }
+        if out.is_full() {
+            return out.finish();
+        }
+        let url = flatten_newlines(url, true);
+        let url = truncate_units_cow(&url, limits.max_url_chars, limits.unit, limits.truncation);
+        out.push_str(url.as_ref());
+        if out.is_full() {
+            return out.finish();
+        }
+    }
+
+    if limits.include_attachments && !event.attachments.is_empty() {
+        if !out.is_empty() {
+            if out.remaining_chars() <= separator.len() {
+                out.truncated = true;
+                return out.finish();
+            }
+            out.push_str(separator);
+        }
+        if out.is_full() {
+            return out.finish();
+        }
+        let note = attachment_note(event);
+        let note = flatten_newlines(&note, true);
+        // Capped like the URL line.
+        let note = truncate_units_cow(&note, limits.max_url_chars, limits.unit, limits.truncation);
+        out.push_str(note.as_ref());
+        if out.is_full() {
+            return out.finish();
+        }
+    }
+
+    if limits.tags_as_json {
+        if limits.max_tags == 0 || event.tags.is_empty() || out.is_full() {
+            return out.finish();
+        }
+        let separator_len = if out.is_empty() { 0 } else { separator.len() };
+        let budget = out.remaining_chars().saturating_sub(separator_len);
+        let Some(line) = tags_json_line(event, limits, budget) else {
+            out.truncated = true;
+            return out.finish();
+        };
+        if separator_len > 0 {
+            out.push_str(separator);
+        }
+        out.push_str(&line);
+        return out.finish();
+    }
+
+    let style = match limits.tag_style {
+        TagRenderStyle::MarkdownTable if limits.single_line => TagRenderStyle::KeyEqualsValue,
+        style => style,
+    };
+    let table = style == TagRenderStyle::MarkdownTable;
+    let (lead, middle, tail) = match style {
+        TagRenderStyle::BulletColon => ("• ", ": ", ""),
+        TagRenderStyle::MarkdownTable => ("| ", " | ", " |"),
+        TagRenderStyle::KeyEqualsValue => ("", "=", ""),
+    };
+    for (idx, (k, v)) in event.tags_in_order().enumerate() {
         if idx >= limits.max_tags || out.is_full() {
             break;
         }
         if !out.is_empty() {
-            if out.remaining_chars() <= 1 {
+            if out.remaining_chars() <= separator.len() {
                 out.truncated = true;
                 break;
             }
-            out.push_char('\n');
+            out.push_str(separator);
         }
         if out.is_full() {
             break;
         }
-        let key = truncate_chars_cow(k, limits.max_tag_key_chars);
+        if table && idx == 0 {
+            out.push_str("| key | value |\n| --- | --- |\n");
+            if out.is_full() {
+                break;
+            }
+        }
+        out.push_str(lead);
+        let key = flatten_newlines(k, limits.single_line || table);
+        let key = escape_table_cell(key, table);
+        let key = truncate_units_cow(
+            &key,
+            limits.max_tag_key_chars,
+            limits.unit,
+            limits.truncation,
+        );
         out.push_str(key.as_ref());
         if out.is_full() {
             break;
         }
-        out.push_char('=');
+        out.push_str(middle);
         if out.is_full() {
             break;
         }
-        let value = truncate_chars_cow(v, limits.max_tag_value_chars);
+        let value = format_float_tag(v, limits.float_precision);
+        let value = flatten_newlines(&value, limits.single_line || table);
+        let value = escape_table_cell(value, table);
+        let value = truncate_units_cow(
+            &value,
+            limits.max_tag_value_chars,
+            limits.unit,
+            limits.truncation,
+        );
         out.push_str(value.as_ref());
+        out.push_str(tail);
     }
 
     out.finish()
 }
 
+/// Escapes `|` so a tag cannot break out of its markdown table cell.
+fn escape_table_cell(cell: Cow<'_, str>, table: bool) -> Cow<'_, str> {
+    if !table || !cell.contains('|') {
+        return cell;
+    }
+    Cow::Owned(cell.replace('|', "\\|"))
+}
+
+/// Tags as a compact JSON object holding as many tags (in render order) as fit in `budget` units, so
+/// the overall cap never cuts the object into invalid JSON. `None` if not even one tag fits.
+fn tags_json_line(event: &Event, limits: TextLimits, budget: usize) -> Option<String> {
+    let mut tags = serde_json::Map::new();
+    let mut line = None;
+    for (key, value) in event.tags_in_order().take(limits.max_tags) {
+        let key = truncate_units_cow(
+            key,
+            limits.max_tag_key_chars,
+            limits.unit,
+            limits.truncation,
+        );
+        let value = truncate_units_cow(
+            value,
+            limits.max_tag_value_chars,
+            limits.unit,
+            limits.truncation,
+        );
+        tags.insert(
+            key.into_owned(),
+            tag_value_json(&value, limits.float_precision),
+        );
+        let candidate = serde_json::Value::Object(tags.clone()).to_string();
+        let units: usize = candidate.chars().map(|ch| limits.unit.char_len(ch)).sum();
+        if units > budget {
+            break;
+        }
+        line = Some(candidate);
+    }
+    line
+}
+
+/// `attachments: a.log, b.png` for `Event::attachments` (blank names are skipped).
+pub(crate) fn attachment_note(event: &Event) -> String {
+    let names: Vec<&str> = event
+        .attachments
+        .iter()
+        .map(Attachment::display_name)
+        .filter(|name| !name.is_empty())
+        .collect();
+    format!("attachments: {}", names.join(", "))
+}
+
+/// `Event::url` if it can be rendered as a native link: an `http(s)` URL without whitespace.
+///
+/// Sinks fall back to the plain-text URL line for anything else.
+pub(crate) fn event_link_url(event: &Event) -> Option<&str> {
+    let url = event.url.as_deref()?.trim();
+    let scheme_ok = ["https://", "http://"].iter().any(|scheme| {
+        url.get(..scheme.len())
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case(scheme))
+    });
+    (scheme_ok && url.len() <= 2048 && !url.chars().any(|ch| ch.is_whitespace() || ch.is_control()))
+        .then_some(url)
+}
+
 pub(crate) fn format_event_text_limited(event: &Event, limits: TextLimits) -> String {
     format_event_text_parts_limited(event, limits, true)
 }
 
+/// Renders the message text through `formatter` when the sink config has one, cutting its output
+/// to `limits.max_chars` (in `limits.unit`); otherwise uses the built-in layout.
+pub(crate) fn format_event_text_with(
+    formatter: Option<&dyn EventFormatter>,
+    event: &Event,
+    limits: TextLimits,
+) -> String {
+    let Some(formatter) = formatter else {
+        return format_event_text_limited(event, limits);
+    };
+    let max_chars = match limits.max_chars {
+        MaxChars::Limited(max_chars) => max_chars,
+        MaxChars::Unlimited => 0,
+    };
+    let text = formatter.format(event, max_chars);
+    truncate_units_cow(
+        &text,
+        limits.max_chars.limit(),
+        limits.unit,
+        limits.truncation,
+    )
+    .into_owned()
+}
+
+/// Like [`format_event_text_limited`], but never returns a blank message: when everything renders
+/// to nothing, falls back to the title, then the kind.
+pub(crate) fn format_event_text_non_empty(event: &Event, limits: TextLimits) -> String {
+    let text = format_event_text_limited(event, limits);
+    if !text.trim().is_empty() {
+        return text;
+    }
+    let fallback = [event.title.as_str(), event.kind.as_str()]
+        .into_iter()
+        .map(str::trim)
+        .find(|value| !value.is_empty())
+        .unwrap_or("notification");
+    truncate_chars(fallback, limits.max_title_chars)
+}
+
 pub(crate) fn format_event_body_and_tags_limited(event: &Event, limits: TextLimits) -> String {
     format_event_text_parts_limited(event, limits, false)
 }
@@ -269,6 +636,32 @@ fn truncate_chars_cow(input: &str, max_chars: usize) -> Cow<'_, str> {
     Cow::Borrowed(&input[..end])
 }
 
+fn truncate_units_cow(
+    input: &str,
+    max_units: usize,
+    unit: TruncateUnit,
+    style: TruncationStyle,
+) -> Cow<'_, str> {
+    if style == TruncationStyle::Hard {
+        return Cow::Borrowed(take_prefix_units(input, max_units, unit).0);
+    }
+    if unit == TruncateUnit::Chars || input.is_ascii() {
+        return truncate_chars_cow(input, max_units);
+    }
+    let (prefix, _, truncated) = take_prefix_units(input, max_units, unit);
+    if !truncated {
+        return Cow::Borrowed(input);
+    }
+    if max_units > 3 {
+        let keep_end = byte_index_after_n_units(prefix, max_units - 3, unit);
+        let mut out = String::with_capacity(keep_end + 3);
+        out.push_str(&input[..keep_end]);
+        out.push_str("...");
+        return Cow::Owned(out);
+    }
+    Cow::Borrowed(prefix)
+}
+
 pub(crate) fn truncate_chars(input: &str, max_chars: usize) -> String {
     truncate_chars_cow(input, max_chars).into_owned()
 }
@@ -278,6 +671,10 @@ mod tests {
     use super::*;
     use crate::Severity;
 
+    fn truncate_units(input: &str, max_units: usize, unit: TruncateUnit) -> String {
+        truncate_units_cow(input, max_units, unit, TruncationStyle::Ellipsis).into_owned()
+    }
+
     #[test]
     fn truncate_chars_is_utf8_safe() {
         let input = "a😀b";
@@ -312,6 +709,92 @@ mod tests {
         assert!(matches!(out, std::borrow::Cow::Borrowed("abc")));
     }
 
+    #[test]
+    fn truncate_units_bytes_never_splits_utf8() {
+        let input = "a你好b";
+        assert_eq!(truncate_units(input, 8, TruncateUnit::Bytes), "a你好b");
+        assert_eq!(truncate_units(input, 7, TruncateUnit::Bytes), "a你...");
+        assert_eq!(truncate_units(input, 3, TruncateUnit::Bytes), "a");
+        assert_eq!(truncate_units(input, 4, TruncateUnit::Bytes), "a...");
+        for max in 0..10 {
+            let out = truncate_units(input, max, TruncateUnit::Bytes);
+            assert!(out.len() <= max, "{max}: {out}");
+        }
+    }
+
+    #[test]
+    fn truncate_units_utf16_counts_surrogate_pairs() {
+        let input = "a😀b😀";
+        assert_eq!(truncate_units(input, 6, TruncateUnit::Utf16), "a😀b😀");
+        assert_eq!(truncate_units(input, 2, TruncateUnit::Utf16), "a");
+        assert_eq!(truncate_units(input, 3, TruncateUnit::Utf16), "a😀");
+        assert_eq!(truncate_units(input, 5, TruncateUnit::Utf16), "a...");
+        for max in 0..8 {
+            let out = truncate_units(input, max, TruncateUnit::Utf16);
+            assert!(out.encode_utf16().count() <= max, "{max}: {out}");
+        }
+    }
+
+    #[test]
+    fn truncate_units_chars_matches_truncate_chars() {
+        let input = "a你😀bcdef";
+        for max in 0..10 {
+            assert_eq!(
+                truncate_units(input, max, TruncateUnit::Chars),
+                truncate_chars(input, max)
+            );
+        }
+    }
+
+    #[test]
+    fn hard_truncation_keeps_exactly_n_units() {
+        let hard = |input, max, unit| {
+            truncate_units_cow(input, max, unit, TruncationStyle::Hard).into_owned()
+        };
+        assert_eq!(hard("abcdefghij", 6, TruncateUnit::Chars), "abcdef");
+        assert_eq!(hard("你好世界你好", 4, TruncateUnit::Chars), "你好世界");
+        assert_eq!(hard("a你好b", 7, TruncateUnit::Bytes), "a你好");
+        assert_eq!(hard("a你好b", 6, TruncateUnit::Bytes), "a你");
+        assert_eq!(hard("a😀b😀", 3, TruncateUnit::Utf16), "a😀");
+        assert_eq!(hard("short", 10, TruncateUnit::Chars), "short");
+
+        let event = Event::new("k", Severity::Info, "x".repeat(100));
+        for max in 1..40 {
+            let mut limits = TextLimits::new(max);
+            limits.truncation = TruncationStyle::Hard;
+            let out = format_event_text_limited(&event, limits);
+            assert_eq!(out.chars().count(), max, "{max}: {out}");
+            assert!(!out.ends_with("..."), "{max}: {out}");
+        }
+    }
+
+    #[test]
+    fn format_event_text_limited_respects_unit() {
+        let event = Event::new("k", Severity::Info, "标题")
+            .with_body("正文内容很长很长")
+            .with_tag("标签", "值😀");
+
+        for unit in [
+            TruncateUnit::Chars,
+            TruncateUnit::Bytes,
+            TruncateUnit::Utf16,
+        ] {
+            for max in 1..48 {
+                let out = format_event_text_limited(&event, TextLimits::new(max).with_unit(unit));
+                let len = match unit {
+                    TruncateUnit::Chars => out.chars().count(),
+                    TruncateUnit::Bytes => out.len(),
+                    TruncateUnit::Utf16 => out.encode_utf16().count(),
+                };
+                assert!(len <= max, "{unit:?} {max}: {out}");
+            }
+        }
+
+        let out =
+            format_event_text_limited(&event, TextLimits::new(10).with_unit(TruncateUnit::Bytes));
+        assert_eq!(out, "标题\n...");
+    }
+
     #[test]
     fn format_event_text_limited_caps_tags_and_length() {
         let mut event = Event::new("k", Severity::Info, "title").with_body("body");
@@ -320,7 +803,7 @@ mod tests {
         }
 
         let limits = TextLimits {
-            max_chars: 20,
+            max_chars: MaxChars::Limited(20),
             max_tags: 2,
             ..TextLimits::default()
         };
@@ -339,7 +822,7 @@ mod tests {
         let out = format_event_text_limited(
             &event,
             TextLimits {
-                max_chars: 8,
+                max_chars: MaxChars::Limited(8),
                 ..TextLimits::default()
             },
         );
@@ -357,20 +840,292 @@ mod tests {
         let out = format_event_text_limited(
             &event,
             TextLimits {
-                max_chars: 0,
+                max_chars: MaxChars::Limited(0),
                 ..TextLimits::default()
             },
         );
         assert!(out.is_empty(), "{out}");
     }
 
+    #[test]
+    fn unlimited_budget_keeps_long_body_but_caps_title() {
+        let title = "t".repeat(300);
+        let body = "b".repeat(20 * 1024);
+        let event = Event::new("k", Severity::Info, title).with_body(body.clone());
+
+        let out = format_event_text_limited(&event, TextLimits::new(0));
+        assert_eq!(out, format!("{}...\n{body}", "t".repeat(253)));
+        assert_eq!(
+            format_event_text_limited(&event, TextLimits::new(MaxChars::Unlimited)),
+            out
+        );
+    }
+
+    #[test]
+    fn attachment_filenames_are_listed_after_url() {
+        let event = Event::new("k", Severity::Info, "title")
+            .with_body("body")
+            .with_url("https://example.com/run/1")
+            .with_tag("k", "v")
+            .with_attachment(Attachment::new("build.log", "text/plain", b"ok".to_vec()))
+            .with_attachment(Attachment::new("shot.png", "image/png", vec![0x89, b'P']));
+
+        assert_eq!(
+            format_event_text_limited(&event, TextLimits::default()),
+            "title\nbody\nhttps://example.com/run/1\nattachments: build.log, shot.png\nk=v"
+        );
+        assert_eq!(
+            format_event_text_limited(&event, TextLimits::default().without_attachment_note()),
+            "title\nbody\nhttps://example.com/run/1\nk=v"
+        );
+        let single_line = TextLimits {
+            single_line: true,
+            ..TextLimits::default()
+        };
+        assert_eq!(
+            format_event_text_limited(&event, single_line.without_url()),
+            "title | body | attachments: build.log, shot.png | k=v"
+        );
+    }
+
+    #[test]
+    fn url_is_printed_on_its_own_line_after_body() {
+        let event = Event::new("k", Severity::Info, "title")
+            .with_body("body")
+            .with_url("https://example.com/run/1")
+            .with_tag("k", "v");
+
+        assert_eq!(
+            format_event_text_limited(&event, TextLimits::default()),
+            "title\nbody\nhttps://example.com/run/1\nk=v"
+        );
+        assert_eq!(
+            format_event_text_limited(&event, TextLimits::default().without_url()),
+            "title\nbody\nk=v"
+        );
+        assert_eq!(
+            format_event_body_and_tags_limited(&event.clone().with_body(""), TextLimits::default()),
+            "https://example.com/run/1\nk=v"
+        );
+    }
+
+    #[test]
+    fn float_precision_formats_decimal_tag_values() {
+        let event = Event::new("k", Severity::Info, "t")
+            .with_tag("duration_s", (0.1f64 + 0.2).to_string())
+            .with_tag("exit", "3")
+            .with_tag("version", "1.2.3");
+        let limits = TextLimits {
+            float_precision: Some(2),
+            ..TextLimits::default()
+        };
+
+        assert_eq!(
+            format_event_text_limited(&event, limits),
+            "t\nduration_s=0.30\nexit=3\nversion=1.2.3"
+        );
+        assert_eq!(
+            format_event_text_limited(&event, TextLimits::default()),
+            "t\nduration_s=0.30000000000000004\nexit=3\nversion=1.2.3"
+        );
+    }
+
+    #[test]
+    fn float_precision_is_capped() {
+        let event = Event::new("k", Severity::Info, "t").with_tag("ratio", "0.5");
+        let limits = TextLimits {
+            float_precision: Some(usize::MAX),
+            ..TextLimits::default()
+        };
+
+        assert_eq!(
+            format_event_text_limited(&event, limits),
+            format!("t\nratio=0.{:0<17}", 5)
+        );
+        assert_eq!(
+            tag_value_json("0.5", Some(usize::MAX)),
+            serde_json::json!(0.5)
+        );
+    }
+
+    #[test]
+    fn ordered_tags_render_in_insertion_order() {
+        let sorted = Event::new("k", Severity::Info, "t")
+            .with_tag("status", "failed")
+            .with_tag("duration", "12s");
+        assert_eq!(
+            format_event_text_limited(&sorted, TextLimits::default()),
+            "t\nduration=12s\nstatus=failed"
+        );
+
+        let ordered = Event::new("k", Severity::Info, "t")
+            .with_ordered_tags([("status", "failed"), ("duration", "12s")])
+            .with_tag("branch", "main");
+        assert_eq!(
+            format_event_text_limited(&ordered, TextLimits::default()),
+            "t\nstatus=failed\nduration=12s\nbranch=main"
+        );
+        // `max_tags` keeps the first tags in render order.
+        let limits = TextLimits {
+            max_tags: 1,
+            ..TextLimits::default()
+        };
+        assert_eq!(
+            format_event_text_limited(&ordered, limits),
+            "t\nstatus=failed"
+        );
+    }
+
+    #[test]
+    fn tag_styles_render_each_layout() {
+        let event = Event::new("k", Severity::Info, "title")
+            .with_tag("host", "a|b")
+            .with_tag("msg", "line 1\nline 2");
+        let render = |tag_style| {
+            format_event_text_limited(
+                &event,
+                TextLimits {
+                    tag_style,
+                    ..TextLimits::default()
+                },
+            )
+        };
+
+        assert_eq!(
+            render(TagRenderStyle::KeyEqualsValue),
+            "title\nhost=a|b\nmsg=line 1\nline 2"
+        );
+        assert_eq!(
+            render(TagRenderStyle::BulletColon),
+            "title\n• host: a|b\n• msg: line 1\nline 2"
+        );
+        assert_eq!(
+            render(TagRenderStyle::MarkdownTable),
+            "title\n| key | value |\n| --- | --- |\n| host | a\\|b |\n| msg | line 1 line 2 |"
+        );
+
+        // Tables need line breaks: single-line output falls back to `key=value`.
+        let single_line = TextLimits {
+            tag_style: TagRenderStyle::MarkdownTable,
+            single_line: true,
+            ..TextLimits::default()
+        };
+        assert_eq!(
+            format_event_text_limited(&event, single_line),
+            "title | host=a|b | msg=line 1 line 2"
+        );
+    }
+
+    #[test]
+    fn tag_styles_respect_limits() {
+        let event = Event::new("k", Severity::Info, "t")
+            .with_tag("alpha", "0123456789")
+            .with_tag("beta", "v")
+            .with_tag("gamma", "v");
+        for tag_style in [
+            TagRenderStyle::KeyEqualsValue,
+            TagRenderStyle::BulletColon,
+            TagRenderStyle::MarkdownTable,
+        ] {
+            let limits = TextLimits {
+                tag_style,
+                max_tags: 2,
+                max_tag_key_chars: 4,
+                max_tag_value_chars: 5,
+                ..TextLimits::default()
+            };
+            let out = format_event_text_limited(&event, limits);
+            assert!(out.contains("a..."), "{tag_style:?}: {out}");
+            assert!(out.contains("01..."), "{tag_style:?}: {out}");
+            assert!(out.contains("beta"), "{tag_style:?}: {out}");
+            assert!(!out.contains("gamma"), "{tag_style:?}: {out}");
+
+            for unit in [TruncateUnit::Chars, TruncateUnit::Bytes] {
+                for max in 1..64 {
+                    let limits = TextLimits {
+                        tag_style,
+                        ..TextLimits::new(max).with_unit(unit)
+                    };
+                    let out = format_event_text_limited(&event, limits);
+                    let used: usize = out.chars().map(|ch| unit.char_len(ch)).sum();
+                    assert!(used <= max, "{tag_style:?} {unit:?} max={max}: {out:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn severity_prefix_is_opt_in() {
+        let event = Event::new("k", Severity::Error, "build failed").with_body("body");
+        assert_eq!(
+            format_event_text_limited(&event, TextLimits::default()),
+            "build failed\nbody"
+        );
+
+        let limits = TextLimits::default().with_severity_prefix(true);
+        assert_eq!(
+            format_event_text_limited(&event, limits),
+            "❌ build failed\nbody"
+        );
+        let warning = Event::new("k", Severity::Warning, "disk");
+        assert_eq!(format_event_text_limited(&warning, limits), "⚠️ disk");
+        let info = Event::new("k", Severity::Info, "t");
+        assert_eq!(format_event_text_limited(&info, limits), "ℹ️ t");
+        let success = Event::new("k", Severity::Success, "t");
+        assert_eq!(format_event_text_limited(&success, limits), "✅ t");
+        // No prefix on an empty title or when only the body is rendered.
+        let empty = Event::new("k", Severity::Error, " ").with_body("body");
+        assert!(!format_event_text_limited(&empty, limits).contains('❌'));
+        assert_eq!(format_event_body_and_tags_limited(&event, limits), "body");
+    }
+
+    #[test]
+    fn severity_prefix_counts_toward_max_chars() {
+        let event = Event::new("k", Severity::Warning, "disk almost full").with_body("body");
+        for unit in [
+            TruncateUnit::Chars,
+            TruncateUnit::Bytes,
+            TruncateUnit::Utf16,
+        ] {
+            for max in 1..40 {
+                let limits = TextLimits::new(max)
+                    .with_unit(unit)
+                    .with_severity_prefix(true);
+                let out = format_event_text_limited(&event, limits);
+                let used: usize = out.chars().map(|ch| unit.char_len(ch)).sum();
+                assert!(used <= max, "{unit:?} max={max}: {out:?}");
+                // The prefix is either whole or absent, never split between its scalars.
+                assert!(
+                    out.starts_with("⚠️ ") || !out.contains('⚠'),
+                    "{unit:?} max={max}: {out:?}"
+                );
+            }
+        }
+
+        // "⚠️ " is 7 bytes: with 10 there is no room left for "d..." so the prefix is dropped.
+        let bytes = |max| {
+            format_event_text_limited(
+                &event,
+                TextLimits::new(max)
+                    .with_unit(TruncateUnit::Bytes)
+                    .with_severity_prefix(true),
+            )
+        };
+        assert_eq!(bytes(10), "disk al...");
+        assert_eq!(bytes(11), "⚠️ d...");
+        assert_eq!(
+            format_event_text_limited(&event, TextLimits::new(12).with_severity_prefix(true)),
+            "⚠️ disk a..."
+        );
+    }
+
     #[test]
     fn format_event_text_limited_no_trailing_newline_when_body_cannot_fit() {
         let event = Event::new("k", Severity::Info, "a").with_body("body");
         let out = format_event_text_limited(
             &event,
             TextLimits {
-                max_chars: 2,
+                max_chars: MaxChars::Limited(2),
                 ..TextLimits::default()
             },
         );
@@ -383,7 +1138,7 @@ mod tests {
         let out = format_event_text_limited(
             &event,
             TextLimits {
-                max_chars: 2,
+                max_chars: MaxChars::Limited(2),
                 ..TextLimits::default()
             },
         );
@@ -0,0 +1,534 @@
+use std::fmt::Write as _;
+use std::time::Duration;
+
+use crate::sinks::http::{
+    build_http_client, max_response_body_bytes, parse_and_validate_https_url,
+    read_text_body_limited, redact_url, select_http_client, send_reqwest,
+};
+use crate::sinks::text::{TextLimits, TruncateUnit, format_event_text_limited, truncate_chars};
+use crate::sinks::{BoxFuture, Sink};
+use crate::{ErrorKind, Event};
+
+const TWILIO_ALLOWED_HOSTS: [&str; 1] = ["api.twilio.com"];
+
+/// Twilio rejects message bodies longer than this (counted in UTF-16 code units).
+const TWILIO_MAX_CHARS: usize = 1600;
+
+#[non_exhaustive]
+#[derive(Clone)]
+pub struct TwilioSmsConfig {
+    /// Account SID (`AC` followed by 32 hex digits).
+    pub account_sid: String,
+    pub auth_token: String,
+    /// Sender number in E.164 form, e.g. `+15005550006`.
+    pub from: String,
+    /// Recipients in E.164 form; each gets its own message.
+    pub to: Vec<String>,
+    pub timeout: Duration,
+    /// Message length limit; `0` means Twilio's own limit of 1600 characters.
+    pub max_chars: usize,
+    pub enforce_public_ip: bool,
+}
+
+impl std::fmt::Debug for TwilioSmsConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TwilioSmsConfig")
+            .field("account_sid", &self.account_sid)
+            .field("auth_token", &"<redacted>")
+            .field("from", &self.from)
+            .field("to", &self.to)
+            .field("timeout", &self.timeout)
+            .field("max_chars", &self.max_chars)
+            .field("enforce_public_ip", &self.enforce_public_ip)
+            .finish()
+    }
+}
+
+impl TwilioSmsConfig {
+    pub fn new(
+        account_sid: impl Into<String>,
+        auth_token: impl Into<String>,
+        from: impl Into<String>,
+        to: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        Self {
+            account_sid: account_sid.into(),
+            auth_token: auth_token.into(),
+            from: from.into(),
+            to: to.into_iter().map(Into::into).collect(),
+            timeout: Duration::from_secs(5),
+            max_chars: TWILIO_MAX_CHARS,
+            enforce_public_ip: true,
+        }
+    }
+
+    #[must_use]
+    pub fn with_recipient(mut self, to: impl Into<String>) -> Self {
+        self.to.push(to.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    #[must_use]
+    pub fn with_max_chars(mut self, max_chars: usize) -> Self {
+        self.max_chars = max_chars;
+        self
+    }
+
+    #[must_use]
+    pub fn with_public_ip_check(mut self, enforce_public_ip: bool) -> Self {
+        self.enforce_public_ip = enforce_public_ip;
+        self
+    }
+}
+
+/// Sends the event as an SMS to each configured recipient via Twilio's Messages API.
+///
+/// Recipients are messaged concurrently; the send fails if any recipient fails, with one line
+/// per failed recipient.
+pub struct TwilioSmsSink {
+    api_url: reqwest::Url,
+    account_sid: String,
+    auth_token: String,
+    from: String,
+    to: Vec<String>,
+    client: reqwest::Client,
+    timeout: Duration,
+    max_chars: usize,
+    enforce_public_ip: bool,
+}
+
+impl std::fmt::Debug for TwilioSmsSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TwilioSmsSink")
+            .field("api_url", &redact_url(&self.api_url))
+            .field("account_sid", &self.account_sid)
+            .field("auth_token", &"<redacted>")
+            .field("from", &self.from)
+            .field("to", &self.to)
+            .field("max_chars", &self.max_chars)
+            .field("enforce_public_ip", &self.enforce_public_ip)
+            .finish_non_exhaustive()
+    }
+}
+
+impl TwilioSmsSink {
+    pub fn new(config: TwilioSmsConfig) -> crate::Result<Self> {
+        let account_sid = config.account_sid.trim();
+        if !is_account_sid(account_sid) {
+            return Err(crate::Error::invalid_config(anyhow::anyhow!(
+                "twilio account_sid must be `AC` followed by 32 hex digits"
+            )));
+        }
+        let auth_token = config.auth_token.trim();
+        if auth_token.is_empty() {
+            return Err(crate::Error::invalid_config(anyhow::anyhow!(
+                "twilio auth_token must not be empty"
+            )));
+        }
+        let from = config.from.trim();
+        if !is_e164(from) {
+            return Err(crate::Error::invalid_config(anyhow::anyhow!(
+                "twilio from number must be in E.164 form (e.g. +15005550006)"
+            )));
+        }
+        if config.to.is_empty() {
+            return Err(crate::Error::invalid_config(anyhow::anyhow!(
+                "twilio needs at least one recipient"
+            )));
+        }
+        let mut to = Vec::with_capacity(config.to.len());
+        for number in &config.to {
+            let number = number.trim();
+            if !is_e164(number) {
+                return Err(crate::Error::invalid_config(anyhow::anyhow!(
+                    "twilio recipient `{}` must be in E.164 form (e.g. +15005550006)",
+                    truncate_chars(number, 32)
+                )));
+            }
+            if !to.iter().any(|existing| existing == number) {
+                to.push(number.to_string());
+            }
+        }
+        let max_chars = match config.max_chars {
+            0 => TWILIO_MAX_CHARS,
+            max_chars => max_chars.min(TWILIO_MAX_CHARS),
+        };
+
+        let api_url = parse_api_url(&format!(
+            "https://api.twilio.com/2010-04-01/Accounts/{account_sid}/Messages.json"
+        ))?;
+        let client = build_http_client(config.timeout)?;
+        Ok(Self {
+            api_url,
+            account_sid: account_sid.to_string(),
+            auth_token: auth_token.to_string(),
+            from: from.to_string(),
+            to,
+            client,
+            timeout: config.timeout,
+            max_chars,
+            enforce_public_ip: config.enforce_public_ip,
+        })
+    }
+
+    fn build_body(&self, event: &Event) -> String {
+        let mut limits = TextLimits::new(self.max_chars).with_unit(TruncateUnit::Utf16);
+        limits.single_line = true;
+        format_event_text_limited(event, limits)
+    }
+
+    fn build_form<'a>(&'a self, to: &'a str, body: &'a str) -> [(&'static str, &'a str); 3] {
+        [("To", to), ("From", self.from.as_str()), ("Body", body)]
+    }
+
+    fn build_request(
+        &self,
+        client: &reqwest::Client,
+        to: &str,
+        body: &str,
+    ) -> reqwest::RequestBuilder {
+        client
+            .post(self.api_url.as_str())
+            .basic_auth(&self.account_sid, Some(&self.auth_token))
+            .form(&self.build_form(to, body))
+    }
+
+    async fn send_one(&self, client: &reqwest::Client, to: &str, body: &str) -> crate::Result<()> {
+        let resp = send_reqwest(self.build_request(client, to, body), "twilio").await?;
+
+        let status = resp.status();
+        if status.is_success() {
+            return Ok(());
+        }
+        let body = match read_text_body_limited(resp, max_response_body_bytes()).await {
+            Ok(body) => body,
+            Err(err) => {
+                return Err(crate::Error::http_status(
+                    status,
+                    anyhow::anyhow!(
+                        "twilio http error: {status} (failed to read response body: {err})"
+                    ),
+                ));
+            }
+        };
+        if let Ok(json) = serde_json::from_str::<serde_json::Value>(&body) {
+            if json.get("code").is_some() || json.get("message").is_some() {
+                return Err(crate::Error::http_status(status, twilio_api_error(&json)));
+            }
+        }
+        let summary = truncate_chars(body.trim(), 200);
+        if summary.is_empty() {
+            return Err(crate::Error::http_status(
+                status,
+                anyhow::anyhow!("twilio http error: {status} (response body omitted)"),
+            ));
+        }
+        Err(crate::Error::http_status(
+            status,
+            anyhow::anyhow!("twilio http error: {status}, response={summary}"),
+        ))
+    }
+}
+
+fn parse_api_url(url: &str) -> crate::Result<reqwest::Url> {
+    let api_url = parse_and_validate_https_url(url, &TWILIO_ALLOWED_HOSTS)?;
+    if !api_url.path().starts_with("/2010-04-01/Accounts/") {
+        return Err(crate::Error::ssrf(anyhow::anyhow!(
+            "url path is not allowed"
+        )));
+    }
+    Ok(api_url)
+}
+
+fn is_account_sid(sid: &str) -> bool {
+    sid.len() == 34 && sid.starts_with("AC") && sid.bytes().skip(2).all(|b| b.is_ascii_hexdigit())
+}
+
+/// `+` followed by 8 to 15 digits, the first of which is not `0`.
+fn is_e164(number: &str) -> bool {
+    let Some(digits) = number.strip_prefix('+') else {
+        return false;
+    };
+    (8..=15).contains(&digits.len())
+        && !digits.starts_with('0')
+        && digits.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// `"twilio api error: ..."` from a `{"code": ..., "message": ...}` response.
+fn twilio_api_error(body: &serde_json::Value) -> anyhow::Error {
+    let code = body["code"].as_i64().unwrap_or(-1);
+    let message = truncate_chars(body["message"].as_str().unwrap_or("").trim(), 200);
+    if message.is_empty() {
+        return anyhow::anyhow!("twilio api error: code={code} (no message)");
+    }
+    anyhow::anyhow!("twilio api error: code={code}, message={message}")
+}
+
+/// One line per failed recipient; keeps the failures' kind when they all agree.
+fn recipient_failures_error(failures: Vec<(&str, crate::Error)>, total: usize) -> crate::Error {
+    let mut kinds = failures.iter().map(|(_, err)| err.kind());
+    let first = kinds.next().unwrap_or(ErrorKind::Other);
+    let kind = if kinds.all(|kind| kind == first) {
+        first
+    } else {
+        ErrorKind::Other
+    };
+    let mut msg = format!(
+        "twilio sms failed for {} of {total} recipients:",
+        failures.len()
+    );
+    for (to, err) in failures {
+        if write!(&mut msg, "\n- {to}: {err:#}").is_err() {
+            return anyhow::anyhow!("failed to format twilio error").into();
+        }
+    }
+    crate::Error::with_kind(kind, anyhow::anyhow!(msg))
+}
+
+impl Sink for TwilioSmsSink {
+    fn name(&self) -> &'static str {
+        "twilio"
+    }
+
+    fn send<'a>(&'a self, event: &'a Event) -> BoxFuture<'a, crate::Result<()>> {
+        Box::pin(async move {
+            let client = select_http_client(
+                &self.client,
+                self.timeout,
+                &self.api_url,
+                self.enforce_public_ip,
+            )
+            .await?;
+
+            let body = self.build_body(event);
+            let results = futures_util::future::join_all(
+                self.to.iter().map(|to| self.send_one(&client, to, &body)),
+            )
+            .await;
+
+            let failures: Vec<(&str, crate::Error)> = self
+                .to
+                .iter()
+                .zip(results)
+                .filter_map(|(to, result)| result.err().map(|err| (to.as_str(), err)))
+                .collect();
+            if failures.is_empty() {
+                return Ok(());
+            }
+            Err(recipient_failures_error(failures, self.to.len()))
+        })
+    }
+
+    /// The form fields for the first recipient, plus the full recipient list.
+    fn preview(&self, event: &Event) -> Option<serde_json::Value> {
+        let body = self.build_body(event);
+        let first = self.to.first()?;
+        let mut fields: serde_json::Map<String, serde_json::Value> = self
+            .build_form(first, &body)
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), serde_json::json!(value)))
+            .collect();
+        fields.insert("recipients".to_string(), serde_json::json!(self.to));
+        Some(serde_json::Value::Object(fields))
+    }
+
+    fn diagnostic_target(&self) -> Option<String> {
+        Some(format!(
+            "{} ({} recipients)",
+            redact_url(&self.api_url),
+            self.to.len()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use base64::Engine as _;
+
+    use super::*;
+    use crate::Severity;
+
+    const SID: &str = "AC0123456789abcdef0123456789abcdef";
+
+    fn config() -> TwilioSmsConfig {
+        TwilioSmsConfig::new(
+            SID,
+            "twilio-secret",
+            "+15005550006",
+            ["+14155550100", "+447700900123"],
+        )
+    }
+
+    #[test]
+    fn validates_numbers_and_account_sid() {
+        for number in ["+14155550100", "+447700900123", "+8613800138000"] {
+            assert!(is_e164(number), "{number}");
+        }
+        for number in [
+            "14155550100",
+            "+1 415 555 0100",
+            "+0123456789",
+            "+1234567",
+            "+1234567890123456",
+            "+1415555010a",
+            "",
+        ] {
+            assert!(!is_e164(number), "{number}");
+        }
+
+        let mut cases = Vec::new();
+        let mut cfg = config();
+        cfg.account_sid = "AC123".to_string();
+        cases.push((cfg, "account_sid"));
+        let mut cfg = config();
+        cfg.account_sid = "ACzz23456789abcdef0123456789abcdef".to_string();
+        cases.push((cfg, "account_sid"));
+        let mut cfg = config();
+        cfg.auth_token = " ".to_string();
+        cases.push((cfg, "auth_token"));
+        let mut cfg = config();
+        cfg.from = "5005550006".to_string();
+        cases.push((cfg, "from"));
+        let mut cfg = config();
+        cfg.to = Vec::new();
+        cases.push((cfg, "at least one recipient"));
+        cases.push((config().with_recipient("0044 7700"), "`0044 7700`"));
+
+        for (cfg, needle) in cases {
+            let err = TwilioSmsSink::new(cfg).expect_err("expected invalid config");
+            assert!(err.to_string().contains(needle), "{needle}: {err:#}");
+            assert_eq!(err.kind(), ErrorKind::InvalidConfig);
+        }
+    }
+
+    #[test]
+    fn builds_messages_url_and_dedups_recipients() {
+        let sink = TwilioSmsSink::new(config().with_recipient(" +14155550100 ")).expect("build");
+        assert_eq!(
+            sink.api_url.as_str(),
+            format!("https://api.twilio.com/2010-04-01/Accounts/{SID}/Messages.json")
+        );
+        assert_eq!(sink.to, ["+14155550100", "+447700900123"]);
+
+        for url in [
+            "https://api.twilio.com.evil.example/2010-04-01/Accounts/x/Messages.json",
+            "https://evil.example/2010-04-01/Accounts/x/Messages.json",
+            "http://api.twilio.com/2010-04-01/Accounts/x/Messages.json",
+            "https://api.twilio.com/v1/Other",
+        ] {
+            assert!(parse_api_url(url).is_err(), "{url}");
+        }
+    }
+
+    #[test]
+    fn payload_is_compact_form_with_basic_auth() {
+        let sink = TwilioSmsSink::new(config()).expect("build sink");
+        let event = Event::new("kind", Severity::Error, "db down")
+            .with_body("primary unreachable\nfailover started")
+            .with_tag("region", "eu");
+        let body = sink.build_body(&event);
+        assert_eq!(
+            body,
+            "db down | primary unreachable failover started | region=eu"
+        );
+
+        let req = sink
+            .build_request(&sink.client, "+14155550100", &body)
+            .build()
+            .expect("build request");
+        let expected =
+            base64::engine::general_purpose::STANDARD.encode(format!("{SID}:twilio-secret"));
+        let auth = req
+            .headers()
+            .get(reqwest::header::AUTHORIZATION)
+            .expect("authorization header");
+        assert_eq!(auth.to_str().expect("ascii"), format!("Basic {expected}"));
+        let form = req
+            .body()
+            .and_then(|body| body.as_bytes())
+            .map(|body| String::from_utf8_lossy(body).into_owned())
+            .expect("buffered body");
+        assert!(
+            form.starts_with("To=%2B14155550100&From=%2B15005550006&Body=db+down+%7C+"),
+            "{form}"
+        );
+    }
+
+    #[test]
+    fn body_is_capped_at_twilio_limit() {
+        let event = Event::new("kind", Severity::Info, "t").with_body("😀".repeat(2000));
+        for max_chars in [0, 5000] {
+            let sink = TwilioSmsSink::new(config().with_max_chars(max_chars)).expect("build");
+            let body = sink.build_body(&event);
+            assert!(
+                body.encode_utf16().count() <= TWILIO_MAX_CHARS,
+                "{max_chars}"
+            );
+            assert!(body.ends_with("..."));
+        }
+    }
+
+    #[test]
+    fn aggregates_recipient_failures() {
+        let failures = vec![
+            (
+                "+14155550100",
+                crate::Error::http_status(
+                    reqwest::StatusCode::BAD_REQUEST,
+                    twilio_api_error(&serde_json::json!({
+                        "code": 21211,
+                        "message": "Invalid 'To' Phone Number"
+                    })),
+                ),
+            ),
+            (
+                "+447700900123",
+                crate::Error::http_status(
+                    reqwest::StatusCode::BAD_REQUEST,
+                    anyhow::anyhow!("twilio http error: 400 Bad Request"),
+                ),
+            ),
+        ];
+        let err = recipient_failures_error(failures, 3);
+        assert_eq!(err.kind(), ErrorKind::HttpStatus(400));
+        let msg = err.to_string();
+        assert!(
+            msg.starts_with("twilio sms failed for 2 of 3 recipients:"),
+            "{msg}"
+        );
+        assert!(
+            msg.contains("- +14155550100: twilio api error: code=21211, message=Invalid 'To'"),
+            "{msg}"
+        );
+
+        let mixed = vec![
+            ("+1", crate::Error::timeout(anyhow::anyhow!("timed out"))),
+            ("+2", anyhow::anyhow!("other").into()),
+        ];
+        assert_eq!(recipient_failures_error(mixed, 2).kind(), ErrorKind::Other);
+    }
+
+    #[test]
+    fn debug_and_preview_redact_auth_token() {
+        let cfg = config();
+        let cfg_dbg = format!("{cfg:?}");
+        assert!(!cfg_dbg.contains("twilio-secret"), "{cfg_dbg}");
+        let sink = TwilioSmsSink::new(cfg).expect("build sink");
+        let sink_dbg = format!("{sink:?}");
+        assert!(!sink_dbg.contains("twilio-secret"), "{sink_dbg}");
+        assert!(sink_dbg.contains("api.twilio.com"), "{sink_dbg}");
+
+        let preview = sink
+            .preview(&Event::new("kind", Severity::Info, "t"))
+            .expect("preview");
+        assert!(!preview.to_string().contains("twilio-secret"), "{preview}");
+        assert_eq!(preview["To"], "+14155550100");
+        assert_eq!(preview["recipients"].as_array().map(Vec::len), Some(2));
+    }
+}
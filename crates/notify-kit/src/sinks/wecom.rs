@@ -1,12 +1,14 @@
+use std::sync::Arc;
 use std::time::Duration;
 
 use crate::Event;
+use crate::render::EventFormatter;
 use crate::sinks::http::{
-    DEFAULT_MAX_RESPONSE_BODY_BYTES, build_http_client, parse_and_validate_https_url,
+    build_http_client, max_response_body_bytes, parse_and_validate_https_url,
     read_json_body_limited, read_text_body_limited, redact_url, redact_url_str, select_http_client,
     send_reqwest, validate_url_path_prefix,
 };
-use crate::sinks::text::{TextLimits, format_event_text_limited, truncate_chars};
+use crate::sinks::text::{TextLimits, TruncateUnit, format_event_text_with, truncate_chars};
 use crate::sinks::{BoxFuture, Sink};
 
 const WECOM_ALLOWED_HOSTS: [&str; 1] = ["qyapi.weixin.qq.com"];
@@ -16,7 +18,11 @@ const WECOM_ALLOWED_HOSTS: [&str; 1] = ["qyapi.weixin.qq.com"];
 pub struct WeComWebhookConfig {
     pub webhook_url: String,
     pub timeout: Duration,
+    /// Message length limit, measured in UTF-8 bytes (WeCom caps `text.content` at 2048 bytes);
+    /// `0` means unlimited.
     pub max_chars: usize,
+    /// Custom message layout; `None` uses the built-in one ([`crate::DefaultFormatter`]).
+    pub formatter: Option<Arc<dyn EventFormatter>>,
     pub enforce_public_ip: bool,
 }
 
@@ -26,6 +32,7 @@ impl std::fmt::Debug for WeComWebhookConfig {
             .field("webhook_url", &redact_url_str(&self.webhook_url))
             .field("timeout", &self.timeout)
             .field("max_chars", &self.max_chars)
+            .field("custom_formatter", &self.formatter.is_some())
             .field("enforce_public_ip", &self.enforce_public_ip)
             .finish()
     }
@@ -37,6 +44,7 @@ impl WeComWebhookConfig {
             webhook_url: webhook_url.into(),
             timeout: Duration::from_secs(2),
             max_chars: 2000,
+            formatter: None,
             enforce_public_ip: true,
         }
     }
@@ -53,6 +61,12 @@ impl WeComWebhookConfig {
         self
     }
 
+    #[must_use]
+    pub fn with_formatter(mut self, formatter: Arc<dyn EventFormatter>) -> Self {
+        self.formatter = Some(formatter);
+        self
+    }
+
     #[must_use]
     pub fn with_public_ip_check(mut self, enforce_public_ip: bool) -> Self {
         self.enforce_public_ip = enforce_public_ip;
@@ -65,6 +79,7 @@ pub struct WeComWebhookSink {
     client: reqwest::Client,
     timeout: Duration,
     max_chars: usize,
+    formatter: Option<Arc<dyn EventFormatter>>,
     enforce_public_ip: bool,
 }
 
@@ -73,6 +88,7 @@ impl std::fmt::Debug for WeComWebhookSink {
         f.debug_struct("WeComWebhookSink")
             .field("webhook_url", &redact_url(&self.webhook_url))
             .field("max_chars", &self.max_chars)
+            .field("custom_formatter", &self.formatter.is_some())
             .finish_non_exhaustive()
     }
 }
@@ -87,12 +103,21 @@ impl WeComWebhookSink {
             client,
             timeout: config.timeout,
             max_chars: config.max_chars,
+            formatter: config.formatter,
             enforce_public_ip: config.enforce_public_ip,
         })
     }
 
-    fn build_payload(event: &Event, max_chars: usize) -> serde_json::Value {
-        let text = format_event_text_limited(event, TextLimits::new(max_chars));
+    fn build_payload(
+        event: &Event,
+        max_chars: usize,
+        formatter: Option<&dyn EventFormatter>,
+    ) -> serde_json::Value {
+        let text = format_event_text_with(
+            formatter,
+            event,
+            TextLimits::new(max_chars).with_unit(TruncateUnit::Bytes),
+        );
         serde_json::json!({
             "msgtype": "text",
             "text": { "content": text },
@@ -114,7 +139,7 @@ impl Sink for WeComWebhookSink {
                 self.enforce_public_ip,
             )
             .await?;
-            let payload = Self::build_payload(event, self.max_chars);
+            let payload = Self::build_payload(event, self.max_chars, self.formatter.as_deref());
 
             let resp = send_reqwest(
                 client.post(self.webhook_url.as_str()).json(&payload),
@@ -124,30 +149,33 @@ impl Sink for WeComWebhookSink {
 
             let status = resp.status();
             if !status.is_success() {
-                let body = match read_text_body_limited(resp, DEFAULT_MAX_RESPONSE_BODY_BYTES).await
-                {
+                let body = match read_text_body_limited(resp, max_response_body_bytes()).await {
                     Ok(body) => body,
                     Err(err) => {
-                        return Err(anyhow::anyhow!(
-                            "wecom webhook http error: {status} (failed to read response body: {err})"
-                        )
-                        .into());
+                        return Err(crate::Error::http_status(
+                            status,
+                            anyhow::anyhow!(
+                                "wecom webhook http error: {status} (failed to read response body: {err})"
+                            ),
+                        ));
                     }
                 };
                 let summary = truncate_chars(body.trim(), 200);
                 if summary.is_empty() {
-                    return Err(anyhow::anyhow!(
-                        "wecom webhook http error: {status} (response body omitted)"
-                    )
-                    .into());
+                    return Err(crate::Error::http_status(
+                        status,
+                        anyhow::anyhow!(
+                            "wecom webhook http error: {status} (response body omitted)"
+                        ),
+                    ));
                 }
-                return Err(anyhow::anyhow!(
-                    "wecom webhook http error: {status}, response={summary}"
-                )
-                .into());
+                return Err(crate::Error::http_status(
+                    status,
+                    anyhow::anyhow!("wecom webhook http error: {status}, response={summary}"),
+                ));
             }
 
-            let body = read_json_body_limited(resp, DEFAULT_MAX_RESPONSE_BODY_BYTES).await?;
+            let body = read_json_body_limited(resp, max_response_body_bytes()).await?;
             let errcode = body["errcode"].as_i64().unwrap_or(-1);
             if errcode == 0 {
                 return Ok(());
@@ -159,6 +187,18 @@ impl Sink for WeComWebhookSink {
             )
         })
     }
+
+    fn preview(&self, event: &Event) -> Option<serde_json::Value> {
+        Some(Self::build_payload(
+            event,
+            self.max_chars,
+            self.formatter.as_deref(),
+        ))
+    }
+
+    fn diagnostic_target(&self) -> Option<String> {
+        Some(redact_url(&self.webhook_url))
+    }
 }
 
 #[cfg(test)]
@@ -172,7 +212,7 @@ mod tests {
             .with_body("ok")
             .with_tag("thread_id", "t1");
 
-        let payload = WeComWebhookSink::build_payload(&event, 2000);
+        let payload = WeComWebhookSink::build_payload(&event, 2000, None);
         assert_eq!(payload["msgtype"].as_str().unwrap_or(""), "text");
         let text = payload["text"]["content"].as_str().unwrap_or("");
         assert!(text.contains("done"));
@@ -180,6 +220,16 @@ mod tests {
         assert!(text.contains("thread_id=t1"));
     }
 
+    #[test]
+    fn truncates_payload_by_utf8_bytes() {
+        let event = Event::new("turn_completed", Severity::Success, "完成".repeat(1000));
+
+        let payload = WeComWebhookSink::build_payload(&event, 2000, None);
+        let text = payload["text"]["content"].as_str().unwrap_or("");
+        assert!(text.len() <= 2000, "{}", text.len());
+        assert!(text.ends_with("..."), "{text}");
+    }
+
     #[test]
     fn rejects_non_https_webhook_url() {
         let cfg = WeComWebhookConfig::new(
@@ -0,0 +1,442 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::Event;
+use crate::render::EventFormatter;
+use crate::sinks::http::{
+    build_http_client, max_response_body_bytes, parse_and_validate_https_url_basic,
+    read_json_body_limited, read_text_body_limited, redact_url, redact_url_str, select_http_client,
+    send_reqwest,
+};
+use crate::sinks::text::{TextLimits, format_event_text_with, truncate_chars};
+use crate::sinks::{BoxFuture, Sink};
+
+/// Zulip rejects topics longer than this.
+const ZULIP_MAX_TOPIC_CHARS: usize = 60;
+
+#[non_exhaustive]
+#[derive(Clone)]
+pub struct ZulipConfig {
+    /// Zulip server, e.g. `https://chat.example.com` (may include a path prefix).
+    pub base_url: String,
+    pub bot_email: String,
+    pub api_key: String,
+    /// Stream (channel) name or numeric ID.
+    pub stream: String,
+    pub topic: String,
+    pub timeout: Duration,
+    /// Message length limit; `0` means unlimited (see [`crate::MaxChars`]).
+    pub max_chars: usize,
+    /// Custom message layout; `None` uses the built-in one ([`crate::DefaultFormatter`]).
+    pub formatter: Option<Arc<dyn EventFormatter>>,
+    pub enforce_public_ip: bool,
+}
+
+impl std::fmt::Debug for ZulipConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ZulipConfig")
+            .field("base_url", &redact_url_str(&self.base_url))
+            .field("bot_email", &self.bot_email)
+            .field("api_key", &"<redacted>")
+            .field("stream", &self.stream)
+            .field("topic", &self.topic)
+            .field("timeout", &self.timeout)
+            .field("max_chars", &self.max_chars)
+            .field("custom_formatter", &self.formatter.is_some())
+            .field("enforce_public_ip", &self.enforce_public_ip)
+            .finish()
+    }
+}
+
+impl ZulipConfig {
+    pub fn new(
+        base_url: impl Into<String>,
+        bot_email: impl Into<String>,
+        api_key: impl Into<String>,
+        stream: impl Into<String>,
+        topic: impl Into<String>,
+    ) -> Self {
+        Self {
+            base_url: base_url.into(),
+            bot_email: bot_email.into(),
+            api_key: api_key.into(),
+            stream: stream.into(),
+            topic: topic.into(),
+            timeout: Duration::from_secs(2),
+            max_chars: 10000,
+            formatter: None,
+            enforce_public_ip: true,
+        }
+    }
+
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    #[must_use]
+    pub fn with_max_chars(mut self, max_chars: usize) -> Self {
+        self.max_chars = max_chars;
+        self
+    }
+
+    #[must_use]
+    pub fn with_formatter(mut self, formatter: Arc<dyn EventFormatter>) -> Self {
+        self.formatter = Some(formatter);
+        self
+    }
+
+    #[must_use]
+    pub fn with_public_ip_check(mut self, enforce_public_ip: bool) -> Self {
+        self.enforce_public_ip = enforce_public_ip;
+        self
+    }
+}
+
+pub struct ZulipSink {
+    api_url: reqwest::Url,
+    bot_email: String,
+    api_key: String,
+    stream: String,
+    topic: String,
+    client: reqwest::Client,
+    timeout: Duration,
+    max_chars: usize,
+    formatter: Option<Arc<dyn EventFormatter>>,
+    enforce_public_ip: bool,
+}
+
+impl std::fmt::Debug for ZulipSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ZulipSink")
+            .field("api_url", &redact_url(&self.api_url))
+            .field("bot_email", &self.bot_email)
+            .field("api_key", &"<redacted>")
+            .field("stream", &self.stream)
+            .field("topic", &self.topic)
+            .field("max_chars", &self.max_chars)
+            .field("custom_formatter", &self.formatter.is_some())
+            .field("enforce_public_ip", &self.enforce_public_ip)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ZulipSink {
+    pub fn new(config: ZulipConfig) -> crate::Result<Self> {
+        let bot_email = config.bot_email.trim();
+        if bot_email.is_empty() || !bot_email.contains('@') {
+            return Err(anyhow::anyhow!("zulip bot_email must be an email address").into());
+        }
+        let api_key = config.api_key.trim();
+        if api_key.is_empty() {
+            return Err(anyhow::anyhow!("zulip api_key must not be empty").into());
+        }
+        let stream = config.stream.trim();
+        if stream.is_empty() {
+            return Err(anyhow::anyhow!("zulip stream must not be empty").into());
+        }
+        let topic = config.topic.trim();
+        if topic.is_empty() {
+            return Err(anyhow::anyhow!("zulip topic must not be empty").into());
+        }
+
+        let mut api_url = parse_and_validate_https_url_basic(config.base_url.trim())?;
+        if api_url.query().is_some() || api_url.fragment().is_some() {
+            return Err(anyhow::anyhow!("zulip base_url must not contain a query").into());
+        }
+        api_url
+            .path_segments_mut()
+            .map_err(|_| anyhow::anyhow!("invalid zulip base_url"))?
+            .pop_if_empty()
+            .extend(["api", "v1", "messages"]);
+        let client = build_http_client(config.timeout)?;
+
+        Ok(Self {
+            api_url,
+            bot_email: bot_email.to_string(),
+            api_key: api_key.to_string(),
+            stream: stream.to_string(),
+            topic: truncate_chars(topic, ZULIP_MAX_TOPIC_CHARS),
+            client,
+            timeout: config.timeout,
+            max_chars: config.max_chars,
+            formatter: config.formatter,
+            enforce_public_ip: config.enforce_public_ip,
+        })
+    }
+
+    fn build_form(
+        event: &Event,
+        stream: &str,
+        topic: &str,
+        max_chars: usize,
+        formatter: Option<&dyn EventFormatter>,
+    ) -> Vec<(&'static str, String)> {
+        let content = format_event_text_with(formatter, event, TextLimits::new(max_chars));
+        vec![
+            ("type", "stream".to_string()),
+            ("to", stream.to_string()),
+            ("topic", topic.to_string()),
+            ("content", content),
+        ]
+    }
+
+    fn build_request(&self, client: &reqwest::Client, event: &Event) -> reqwest::RequestBuilder {
+        let form = Self::build_form(
+            event,
+            &self.stream,
+            &self.topic,
+            self.max_chars,
+            self.formatter.as_deref(),
+        );
+        client
+            .post(self.api_url.as_str())
+            .basic_auth(&self.bot_email, Some(&self.api_key))
+            .form(&form)
+    }
+}
+
+/// `"zulip api error: ..."` from a `{"result": "error", "msg": ...}` response.
+fn zulip_api_error(status: reqwest::StatusCode, body: &serde_json::Value) -> crate::Error {
+    let msg = truncate_chars(body["msg"].as_str().unwrap_or("").trim(), 200);
+    let code = body["code"].as_str().unwrap_or("");
+    match (msg.is_empty(), code.is_empty()) {
+        (true, _) => anyhow::anyhow!("zulip api error: {status} (no msg)").into(),
+        (false, true) => anyhow::anyhow!("zulip api error: {status}, msg={msg}").into(),
+        (false, false) => {
+            anyhow::anyhow!("zulip api error: {status}, code={code}, msg={msg}").into()
+        }
+    }
+}
+
+impl Sink for ZulipSink {
+    fn name(&self) -> &'static str {
+        "zulip"
+    }
+
+    fn send<'a>(&'a self, event: &'a Event) -> BoxFuture<'a, crate::Result<()>> {
+        Box::pin(async move {
+            let client = select_http_client(
+                &self.client,
+                self.timeout,
+                &self.api_url,
+                self.enforce_public_ip,
+            )
+            .await?;
+
+            let resp = send_reqwest(self.build_request(&client, event), "zulip").await?;
+
+            let status = resp.status();
+            if !status.is_success() {
+                let body = match read_text_body_limited(resp, max_response_body_bytes()).await {
+                    Ok(body) => body,
+                    Err(err) => {
+                        return Err(crate::Error::http_status(
+                            status,
+                            anyhow::anyhow!(
+                                "zulip http error: {status} (failed to read response body: {err})"
+                            ),
+                        ));
+                    }
+                };
+                if let Ok(json) = serde_json::from_str::<serde_json::Value>(&body) {
+                    if json["result"].as_str() == Some("error") {
+                        return Err(zulip_api_error(status, &json));
+                    }
+                }
+                let summary = truncate_chars(body.trim(), 200);
+                if summary.is_empty() {
+                    return Err(crate::Error::http_status(
+                        status,
+                        anyhow::anyhow!("zulip http error: {status} (response body omitted)"),
+                    ));
+                }
+                return Err(crate::Error::http_status(
+                    status,
+                    anyhow::anyhow!("zulip http error: {status}, response={summary}"),
+                ));
+            }
+
+            let body = read_json_body_limited(resp, max_response_body_bytes()).await?;
+            if body["result"].as_str() == Some("success") {
+                return Ok(());
+            }
+            Err(zulip_api_error(status, &body))
+        })
+    }
+
+    /// The form fields as a JSON object.
+    fn preview(&self, event: &Event) -> Option<serde_json::Value> {
+        let form = Self::build_form(
+            event,
+            &self.stream,
+            &self.topic,
+            self.max_chars,
+            self.formatter.as_deref(),
+        );
+        let fields = form
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), serde_json::Value::String(value)))
+            .collect();
+        Some(serde_json::Value::Object(fields))
+    }
+
+    fn diagnostic_target(&self) -> Option<String> {
+        Some(format!(
+            "{} (stream={}, topic={})",
+            redact_url(&self.api_url),
+            self.stream,
+            self.topic
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use base64::Engine as _;
+
+    use super::*;
+    use crate::Severity;
+
+    fn config() -> ZulipConfig {
+        ZulipConfig::new(
+            "https://chat.example.com",
+            "notify-bot@chat.example.com",
+            "zulip-secret-key",
+            "alerts",
+            "ci",
+        )
+    }
+
+    #[test]
+    fn builds_messages_api_url() {
+        let sink = ZulipSink::new(config()).expect("build sink");
+        assert_eq!(
+            sink.api_url.as_str(),
+            "https://chat.example.com/api/v1/messages"
+        );
+
+        let mut cfg = config();
+        cfg.base_url = "https://example.com/zulip/".to_string();
+        let sink = ZulipSink::new(cfg).expect("build sink");
+        assert_eq!(
+            sink.api_url.as_str(),
+            "https://example.com/zulip/api/v1/messages"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_config() {
+        let mut cases = Vec::new();
+        let mut cfg = config();
+        cfg.base_url = "http://chat.example.com".to_string();
+        cases.push((cfg, "https"));
+        let mut cfg = config();
+        cfg.base_url = "https://chat.example.com/?x=1".to_string();
+        cases.push((cfg, "query"));
+        let mut cfg = config();
+        cfg.bot_email = "bot".to_string();
+        cases.push((cfg, "bot_email"));
+        let mut cfg = config();
+        cfg.api_key = " ".to_string();
+        cases.push((cfg, "api_key"));
+        let mut cfg = config();
+        cfg.stream = String::new();
+        cases.push((cfg, "stream"));
+        let mut cfg = config();
+        cfg.topic = String::new();
+        cases.push((cfg, "topic"));
+
+        for (cfg, needle) in cases {
+            let err = ZulipSink::new(cfg).expect_err("expected invalid config");
+            assert!(err.to_string().contains(needle), "{needle}: {err:#}");
+        }
+    }
+
+    #[test]
+    fn builds_expected_form() {
+        let event = Event::new("turn_completed", Severity::Success, "done")
+            .with_body("ok")
+            .with_tag("thread_id", "t1");
+
+        let form = ZulipSink::build_form(&event, "alerts", "ci", 10000, None);
+        assert_eq!(form[0], ("type", "stream".to_string()));
+        assert_eq!(form[1], ("to", "alerts".to_string()));
+        assert_eq!(form[2], ("topic", "ci".to_string()));
+        assert_eq!(form[3].0, "content");
+        assert!(form[3].1.contains("done"));
+        assert!(form[3].1.contains("ok"));
+        assert!(form[3].1.contains("thread_id=t1"));
+    }
+
+    #[test]
+    fn request_is_form_encoded_with_basic_auth() {
+        let sink = ZulipSink::new(config()).expect("build sink");
+        let event = Event::new("kind", Severity::Info, "hello world");
+        let req = sink
+            .build_request(&sink.client, &event)
+            .build()
+            .expect("build request");
+
+        let expected = base64::engine::general_purpose::STANDARD
+            .encode("notify-bot@chat.example.com:zulip-secret-key");
+        let auth = req
+            .headers()
+            .get(reqwest::header::AUTHORIZATION)
+            .expect("authorization header");
+        assert_eq!(auth.to_str().expect("ascii"), format!("Basic {expected}"));
+        assert!(auth.is_sensitive());
+        assert_eq!(
+            req.headers()
+                .get(reqwest::header::CONTENT_TYPE)
+                .and_then(|v| v.to_str().ok()),
+            Some("application/x-www-form-urlencoded")
+        );
+
+        let body = req
+            .body()
+            .and_then(|body| body.as_bytes())
+            .expect("buffered body");
+        let body = std::str::from_utf8(body).expect("utf-8 body");
+        assert!(
+            body.starts_with("type=stream&to=alerts&topic=ci&content="),
+            "{body}"
+        );
+        assert!(body.contains("hello+world"), "{body}");
+    }
+
+    #[test]
+    fn long_topics_are_truncated() {
+        let mut cfg = config();
+        cfg.topic = "t".repeat(100);
+        let sink = ZulipSink::new(cfg).expect("build sink");
+        assert_eq!(sink.topic.chars().count(), ZULIP_MAX_TOPIC_CHARS);
+    }
+
+    #[test]
+    fn api_errors_include_code_and_msg() {
+        let body = serde_json::json!({
+            "result": "error",
+            "msg": "Stream 'nope' does not exist",
+            "code": "STREAM_DOES_NOT_EXIST"
+        });
+        let err = zulip_api_error(reqwest::StatusCode::BAD_REQUEST, &body);
+        let msg = err.to_string();
+        assert!(msg.contains("code=STREAM_DOES_NOT_EXIST"), "{msg}");
+        assert!(msg.contains("does not exist"), "{msg}");
+    }
+
+    #[test]
+    fn debug_redacts_api_key() {
+        let cfg = config();
+        let cfg_dbg = format!("{cfg:?}");
+        assert!(!cfg_dbg.contains("zulip-secret-key"), "{cfg_dbg}");
+        assert!(cfg_dbg.contains("<redacted>"), "{cfg_dbg}");
+
+        let sink = ZulipSink::new(cfg).expect("build sink");
+        let sink_dbg = format!("{sink:?}");
+        assert!(!sink_dbg.contains("zulip-secret-key"), "{sink_dbg}");
+        assert!(sink_dbg.contains("chat.example.com"), "{sink_dbg}");
+    }
+}